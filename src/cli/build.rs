@@ -0,0 +1,82 @@
+use colored::*;
+use std::path::Path;
+
+use crate::{file, project::Manifest};
+
+pub fn build_command(arguments: Vec<String>) -> Result<(), ()> {
+    if let Some(argument) = arguments.first() {
+        let opt_str = argument.as_str();
+        if opt_str == "-h" || opt_str == "--help" {
+            println!("{}", build_help());
+            return Ok(());
+        }
+
+        eprintln!("{}", build_help());
+        eprintln!("{}: invalid option: `{}`", "error".on_red(), argument);
+        return Err(());
+    }
+
+    let root = Path::new(".");
+    let manifest = Manifest::load(root)?;
+    let files = file::find_thp_files(&manifest.src_dir(root))?;
+
+    if files.is_empty() {
+        println!(
+            "{}: No THP files found in `{}`",
+            "warning".yellow(),
+            manifest.build.src
+        );
+        return Ok(());
+    }
+
+    let mut failed_files = Vec::new();
+    for path in files.iter() {
+        let output_path = manifest.output_path(root, path);
+        match file::compile_to(path, &output_path) {
+            Ok(_) => println!("Compiled {}", path.display()),
+            Err(_) => {
+                eprintln!("{}: Failed to compile {}", "error".on_red(), path.display());
+                failed_files.push(path);
+            }
+        }
+    }
+
+    let compiled_files = files.len() - failed_files.len();
+    if failed_files.is_empty() {
+        println!(
+            "\n{} {} file(s) into `{}`",
+            "Built".green(),
+            compiled_files,
+            manifest.build.out
+        );
+        return Ok(());
+    }
+
+    eprintln!(
+        "\n{}: {} file(s) compiled, {} failed:",
+        "error".on_red(),
+        compiled_files,
+        failed_files.len()
+    );
+    for path in failed_files {
+        eprintln!("  {}", path.display());
+    }
+
+    Err(())
+}
+
+fn build_help() -> String {
+    format!(
+        r#"Build the project.
+
+Compiles all the THP files in the source directory, and writes
+the PHP output to the output directory, keeping the same
+directory structure. Both are configured in `thp.toml`.
+
+Usage:
+
+  `thp build`       Build the project
+  `thp build -h`    Print this message & exit
+        "#,
+    )
+}
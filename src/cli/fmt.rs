@@ -0,0 +1,88 @@
+use colored::*;
+use std::path::Path;
+
+use crate::{
+    file,
+    project::{Manifest, MANIFEST_FILE},
+};
+
+use super::format::format_file;
+
+pub fn fmt_command(arguments: Vec<String>) -> Result<(), ()> {
+    let mut check = false;
+    for argument in arguments {
+        match argument.as_str() {
+            "--check" => check = true,
+            "-h" | "--help" => {
+                println!("{}", fmt_help());
+                return Ok(());
+            }
+            _ => {
+                eprintln!("{}", fmt_help());
+                eprintln!("{}: invalid option: `{}`", "error".on_red(), argument);
+                return Err(());
+            }
+        }
+    }
+
+    // Inside a project only the source directory is formatted
+    let root = Path::new(".");
+    let files = if root.join(MANIFEST_FILE).is_file() {
+        let manifest = Manifest::load(root)?;
+        file::find_thp_files(&manifest.src_dir(root))?
+    } else {
+        file::find_thp_files(root)?
+    };
+
+    let mut changed_files = 0;
+    let mut failed_files = 0;
+    for path in files.iter() {
+        match format_file(path, check) {
+            Ok(true) => {
+                changed_files += 1;
+                if check {
+                    println!("{} is not formatted", path.display());
+                } else {
+                    println!("Formatted {}", path.display());
+                }
+            }
+            Ok(false) => {}
+            Err(_) => failed_files += 1,
+        }
+    }
+
+    if failed_files > 0 {
+        eprintln!(
+            "{}: {} file(s) could not be formatted",
+            "error".on_red(),
+            failed_files
+        );
+        return Err(());
+    }
+
+    if check && changed_files > 0 {
+        eprintln!(
+            "{}: {} of {} file(s) are not formatted",
+            "error".on_red(),
+            changed_files,
+            files.len()
+        );
+        return Err(());
+    }
+
+    Ok(())
+}
+
+fn fmt_help() -> String {
+    format!(
+        r#"Format all the THP files in the project, in place.
+If there is no `thp.toml`, formats the current directory.
+
+Usage:
+
+  `thp fmt`            Format all files
+  `thp fmt --check`    Exit with an error if any file is not formatted
+  `thp fmt -h`         Print this message & exit
+        "#,
+    )
+}
@@ -0,0 +1,98 @@
+use colored::*;
+use std::{fs, path::Path};
+
+use crate::{error_handling::PrintableError, file, formatter};
+
+pub fn format_command(arguments: Vec<String>) -> Result<(), ()> {
+    let mut check = false;
+    let mut files = Vec::new();
+    for argument in arguments {
+        match argument.as_str() {
+            "--check" => check = true,
+            "-h" | "--help" => {
+                println!("{}", format_help());
+                return Ok(());
+            }
+            opt if opt.starts_with('-') => {
+                eprintln!("{}", format_help());
+                eprintln!("{}: invalid option: `{}`", "error".on_red(), opt);
+                return Err(());
+            }
+            _ => files.push(argument),
+        }
+    }
+
+    if files.is_empty() {
+        eprintln!("{}", format_help());
+        eprintln!("{}: {}", "error".on_red(), "No file specified");
+        return Err(());
+    }
+    if files.len() > 1 {
+        eprintln!("{}", format_help());
+        eprintln!(
+            "{}: {}",
+            "error".on_red(),
+            "Only a single file can be formatted at a time"
+        );
+        return Err(());
+    }
+
+    let file = Path::new(&files[0]);
+    let changed = format_file(file, check)?;
+
+    if check && changed {
+        eprintln!("{}: {} is not formatted", "error".on_red(), file.display());
+        return Err(());
+    }
+
+    Ok(())
+}
+
+/// Formats the file at `path` in place. If `check` is true, the file
+/// is not written.
+///
+/// Returns whether the formatted code is different from the file contents.
+pub fn format_file(path: &Path, check: bool) -> Result<bool, ()> {
+    let contents = file::read_file(path)?;
+
+    let formatted = match formatter::format_code(&contents) {
+        Ok(code) => code,
+        Err(errors) => {
+            eprintln!(
+                "{}: {} has errors and cannot be formatted",
+                "error".on_red(),
+                path.display()
+            );
+            errors.print_ariadne_in(&contents, &path.display().to_string());
+            return Err(());
+        }
+    };
+
+    if formatted == contents {
+        return Ok(false);
+    }
+
+    if !check {
+        if let Err(error) = fs::write(path, formatted) {
+            eprintln!("{}: Error writing {}", "error".on_red(), path.display());
+            eprintln!("{}", error);
+            return Err(());
+        }
+    }
+
+    Ok(true)
+}
+
+fn format_help() -> String {
+    format!(
+        r#"Format a single file in place.
+
+Usage:
+
+  `thp format {0}`            Format {0}
+  `thp format --check {0}`    Exit with an error if {0} is not formatted
+  `thp format -h`                Print this message & exit
+        "#,
+        "_file_".green()
+    )
+}
@@ -0,0 +1,96 @@
+use colored::*;
+use std::{fs, path::Path};
+
+use crate::project::{Manifest, MANIFEST_FILE};
+
+/// Code of the file created in a new project
+const INDEX_FILE_CONTENTS: &str = "print(\"Hello, world!\")\n";
+
+pub fn init_command(arguments: Vec<String>) -> Result<(), ()> {
+    if let Some(argument) = arguments.first() {
+        let opt_str = argument.as_str();
+        if opt_str == "-h" || opt_str == "--help" {
+            println!("{}", init_help());
+            return Ok(());
+        }
+
+        eprintln!("{}", init_help());
+        eprintln!("{}: invalid option: `{}`", "error".on_red(), argument);
+        return Err(());
+    }
+
+    let root = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(error) => {
+            eprintln!("{}: Error reading the current directory", "error".on_red());
+            eprintln!("{}", error);
+            return Err(());
+        }
+    };
+
+    let manifest_path = root.join(MANIFEST_FILE);
+    if manifest_path.exists() {
+        eprintln!(
+            "{}: {} already exists in this directory",
+            "error".on_red(),
+            MANIFEST_FILE
+        );
+        return Err(());
+    }
+
+    let name = match root.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => String::from("project"),
+    };
+    let manifest = Manifest::new(name);
+
+    write(&manifest_path, &manifest.to_toml())?;
+    println!("Created {}", MANIFEST_FILE);
+
+    // Only scaffold the sources if the directory doesn't exist already
+    let src_dir = manifest.src_dir(&root);
+    if !src_dir.exists() {
+        if let Err(error) = fs::create_dir_all(&src_dir) {
+            eprintln!(
+                "{}: Error creating directory {}",
+                "error".on_red(),
+                manifest.build.src
+            );
+            eprintln!("{}", error);
+            return Err(());
+        }
+
+        write(&src_dir.join("index.thp"), INDEX_FILE_CONTENTS)?;
+        println!("Created {}/index.thp", manifest.build.src);
+    }
+
+    println!("Project `{}` initialized", manifest.project.name);
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ()> {
+    match fs::write(path, contents) {
+        Ok(_) => Ok(()),
+        Err(error) => {
+            eprintln!("{}: Error writing {}", "error".on_red(), path.display());
+            eprintln!("{}", error);
+            Err(())
+        }
+    }
+}
+
+fn init_help() -> String {
+    format!(
+        r#"Initialize a new project in the current directory.
+
+Creates a `{0}` manifest, and a `src` directory with a
+sample file if it doesn't exist.
+
+Usage:
+
+  `thp init`       Initialize the project
+  `thp init -h`    Print this message & exit
+        "#,
+        MANIFEST_FILE
+    )
+}
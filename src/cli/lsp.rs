@@ -0,0 +1,41 @@
+use colored::*;
+
+use crate::lsp;
+
+pub fn lsp_command(arguments: Vec<String>) -> Result<(), ()> {
+    if let Some(argument) = arguments.first() {
+        let opt_str = argument.as_str();
+        if opt_str == "-h" || opt_str == "--help" {
+            println!("{}", lsp_help());
+            return Ok(());
+        }
+
+        eprintln!("{}", lsp_help());
+        eprintln!("{}: invalid option: `{}`", "error".on_red(), argument);
+        return Err(());
+    }
+
+    // stdout is used by the protocol, so errors go to stderr
+    match lsp::run() {
+        Ok(_) => Ok(()),
+        Err(reason) => {
+            eprintln!("{}: {}", "error".on_red(), reason);
+            Err(())
+        }
+    }
+}
+
+fn lsp_help() -> String {
+    format!(
+        r#"Start the language server.
+
+The server communicates with the editor through stdin & stdout,
+using the Language Server Protocol.
+
+Usage:
+
+  `thp lsp`       Start the language server
+  `thp lsp -h`    Print this message & exit
+        "#,
+    )
+}
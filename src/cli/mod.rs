@@ -1,9 +1,15 @@
+mod build;
 mod compile;
 mod empty;
+mod fmt;
+mod format;
 mod help;
+mod init;
+mod lsp;
 mod repl;
 mod tokenize;
 mod types;
+mod watch;
 
 use types::CommandType;
 
@@ -25,6 +31,7 @@ Commands
   fmt       Formats all files in the project
   watch, w  Starts compilation of the project in watch mode
   tokenize  Tokenize code from STDIN and output tokens as JSON to STDOUT
+  lsp       Starts the language server, using STDIN & STDOUT
 
   help, h   Print this message & exit
 
@@ -70,6 +77,7 @@ fn parse_args() -> Result<(CommandType, Vec<String>), String> {
             "build" => CommandType::Build,
             "fmt" => CommandType::Fmt,
             "tokenize" => CommandType::Tokenize,
+            "lsp" => CommandType::Lsp,
             "watch" | "w" => CommandType::Watch,
             "help" | "h" => CommandType::Help,
             _ => return Err(format!("Unknown command `{}`", command)),
@@ -12,8 +12,8 @@ use std::io::{self, BufRead};
 #[derive(Serialize)]
 enum TokenizeResult {
     Ok(Vec<Token>),
-    MixedErr(Vec<Token>, MistiError),
-    Err(MistiError),
+    MixedErr(Vec<Token>, Vec<MistiError>),
+    Err(Vec<MistiError>),
 }
 
 pub fn tokenize_command(arguments: Vec<String>) -> Result<(), ()> {
@@ -83,8 +83,8 @@ pub fn tokenize_command(arguments: Vec<String>) -> Result<(), ()> {
             return Ok(());
         }
         (Ok(t), _) => t,
-        (Err(misti_error), _) => {
-            let output_value = TokenizeResult::Err(misti_error);
+        (Err(misti_errors), _) => {
+            let output_value = TokenizeResult::Err(misti_errors);
             let json = serde_json::to_string(&output_value).unwrap();
             println!("{}", json);
             return Ok(());
@@ -103,8 +103,8 @@ pub fn tokenize_command(arguments: Vec<String>) -> Result<(), ()> {
             return Ok(());
         }
         (Ok(a), _) => a,
-        (Err(misti_error), _) => {
-            let output_value = TokenizeResult::MixedErr(tokens, misti_error);
+        (Err(misti_errors), _) => {
+            let output_value = TokenizeResult::MixedErr(tokens, misti_errors);
             let json = serde_json::to_string(&output_value).unwrap();
             println!("{}", json);
             return Ok(());
@@ -113,7 +113,7 @@ pub fn tokenize_command(arguments: Vec<String>) -> Result<(), ()> {
 
     let result = match semantic::check_semantics(&ast) {
         Ok(()) => TokenizeResult::Ok(tokens),
-        Err(error) => TokenizeResult::MixedErr(tokens, error),
+        Err(errors) => TokenizeResult::MixedErr(tokens, errors),
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -131,9 +131,9 @@ Level 0: Perform only lexical analysis
 Level 1: Performs syntactic analysis
 Level 2: Performs semantic analysis
 
-If lexical analysis fails, a lexical error is returned.
-If syntax analysis fails, tokens from lexical analysis and a syntax error is returned.
-If semantic analysis fails, tokens from lexical analysis and a syntax error is returned.
+If lexical analysis fails, all the lexical errors are returned.
+If syntax analysis fails, tokens from lexical analysis and all the syntax errors are returned.
+If semantic analysis fails, tokens from lexical analysis and all the semantic errors are returned.
 If the process succeedes, only tokens are returned.
 
 Usage:
@@ -9,6 +9,7 @@ pub enum CommandType {
     Watch,
     Help,
     Tokenize,
+    Lsp,
     None,
 }
 
@@ -17,13 +18,15 @@ impl CommandType {
         match self {
             CommandType::Help => super::help::help_command(options),
             CommandType::Compile => super::compile::compile_command(options),
+            CommandType::Format => super::format::format_command(options),
+            CommandType::Fmt => super::fmt::fmt_command(options),
             CommandType::Repl => super::repl::repl_command(options),
+            CommandType::Init => super::init::init_command(options),
+            CommandType::Build => super::build::build_command(options),
+            CommandType::Watch => super::watch::watch_command(options),
             CommandType::None => super::empty::empty_command(options),
             CommandType::Tokenize => super::tokenize::tokenize_command(options),
-            _ => {
-                eprintln!("Not implemented yet! {:?} {:?}", self, options);
-                Err(())
-            }
+            CommandType::Lsp => super::lsp::lsp_command(options),
         }
     }
 }
@@ -0,0 +1,171 @@
+use colored::*;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{file, project::Manifest};
+
+/// Time between each check of the source files
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Last modification time of every THP file in the source directory
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+pub fn watch_command(arguments: Vec<String>) -> Result<(), ()> {
+    if let Some(argument) = arguments.first() {
+        let opt_str = argument.as_str();
+        if opt_str == "-h" || opt_str == "--help" {
+            println!("{}", watch_help());
+            return Ok(());
+        }
+
+        eprintln!("{}", watch_help());
+        eprintln!("{}: invalid option: `{}`", "error".on_red(), argument);
+        return Err(());
+    }
+
+    let root = Path::new(".");
+    let manifest = Manifest::load(root)?;
+    let src_dir = manifest.src_dir(root);
+
+    println!(
+        "Watching `{}` for changes. Press Ctrl+C to stop.",
+        manifest.build.src
+    );
+
+    // The first time every file is compiled
+    let mut previous = Snapshot::new();
+    loop {
+        // If the source directory cannot be read (e.g. it's being moved)
+        // the error is printed, and it's tried again later
+        if let Ok(current) = take_snapshot(&src_dir) {
+            let changed = changed_files(&previous, &current);
+            if !changed.is_empty() {
+                compile_files(&manifest, root, &changed);
+            }
+            previous = current;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Compiles every file in `files`, printing the result of each one.
+/// Errors don't stop the compilation of the rest of the files
+fn compile_files(manifest: &Manifest, root: &Path, files: &[PathBuf]) {
+    let mut failed_files = 0;
+    for path in files.iter() {
+        let output_path = manifest.output_path(root, path);
+        match file::compile_to(path, &output_path) {
+            Ok(_) => println!("Compiled {}", path.display()),
+            Err(_) => {
+                eprintln!("{}: Failed to compile {}", "error".on_red(), path.display());
+                failed_files += 1;
+            }
+        }
+    }
+
+    if failed_files == 0 {
+        println!("{} Waiting for changes...\n", "Done.".green());
+    } else {
+        eprintln!(
+            "{} file(s) failed. Waiting for changes...\n",
+            failed_files.to_string().red()
+        );
+    }
+}
+
+/// Reads the modification time of all the THP files in `src_dir`.
+/// Files whose metadata cannot be read are left out.
+fn take_snapshot(src_dir: &Path) -> Result<Snapshot, ()> {
+    let files = file::find_thp_files(src_dir)?;
+
+    let snapshot = files
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect();
+
+    Ok(snapshot)
+}
+
+/// Returns the files that were created or modified between the
+/// `previous` and `current` snapshots, sorted
+fn changed_files(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(*modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    changed.sort();
+    changed
+}
+
+fn watch_help() -> String {
+    format!(
+        r#"Compile the project in watch mode.
+
+Compiles all the THP files of the project, and then compiles
+them again every time they change, until stopped.
+
+Usage:
+
+  `thp watch`       Start watching the project
+  `thp watch -h`    Print this message & exit
+        "#,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn should_detect_new_and_modified_files() {
+        let previous = Snapshot::from([
+            (PathBuf::from("src/a.thp"), time(10)),
+            (PathBuf::from("src/b.thp"), time(10)),
+            (PathBuf::from("src/deleted.thp"), time(10)),
+        ]);
+        let current = Snapshot::from([
+            (PathBuf::from("src/a.thp"), time(10)),
+            (PathBuf::from("src/b.thp"), time(20)),
+            (PathBuf::from("src/c.thp"), time(20)),
+        ]);
+
+        assert_eq!(
+            vec![PathBuf::from("src/b.thp"), PathBuf::from("src/c.thp")],
+            changed_files(&previous, &current)
+        );
+    }
+
+    #[test]
+    fn should_detect_all_files_on_first_snapshot() {
+        let current = Snapshot::from([
+            (PathBuf::from("src/b.thp"), time(10)),
+            (PathBuf::from("src/a.thp"), time(10)),
+        ]);
+
+        assert_eq!(
+            vec![PathBuf::from("src/a.thp"), PathBuf::from("src/b.thp")],
+            changed_files(&Snapshot::new(), &current)
+        );
+    }
+
+    #[test]
+    fn should_not_detect_unchanged_files() {
+        let snapshot = Snapshot::from([(PathBuf::from("src/a.thp"), time(10))]);
+        assert!(changed_files(&snapshot, &snapshot).is_empty());
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{
+    codegen::Transpilable,
+    php_ast::{
+        PClassDefinition, PConstructor, PConstructorParameter, PEnumDefinition,
+        PInterfaceDefinition, PMethodSignature, PProperty,
+    },
+};
+
+use super::statement::transpile_block;
+
+impl Transpilable for PClassDefinition<'_> {
+    /// The properties, the constructor and each method
+    /// are separated by a blank line
+    fn transpile(&self) -> String {
+        let mut sections = Vec::<String>::new();
+        if !self.properties.is_empty() {
+            let properties: Vec<_> = self
+                .properties
+                .iter()
+                .map(|p| format!("{};", p.transpile()))
+                .collect();
+            sections.push(properties.join("\n"));
+        }
+        if let Some(constructor) = &self.constructor {
+            sections.push(constructor.transpile());
+        }
+        for method in self.methods.iter() {
+            sections.push(format!("public {}", method.transpile()));
+        }
+
+        let modifier = match self.modifier {
+            Some(modifier) => format!("{} ", modifier),
+            None => String::new(),
+        };
+        let parent = match self.parent {
+            Some(parent) => format!(" extends {}", parent),
+            None => String::new(),
+        };
+
+        let interfaces = match self.interfaces.is_empty() {
+            true => String::new(),
+            false => {
+                let names: Vec<_> = self.interfaces.iter().map(|i| i.as_str()).collect();
+                format!(" implements {}", names.join(", "))
+            }
+        };
+
+        let mut result = format!(
+            "{}class {}{}{}\n{{\n",
+            modifier, self.name, parent, interfaces
+        );
+        for line in sections.join("\n\n").lines() {
+            if !line.is_empty() {
+                result.push_str("    ");
+                result.push_str(line);
+            }
+            result.push('\n');
+        }
+        result.push('}');
+
+        result
+    }
+}
+
+impl Transpilable for PEnumDefinition<'_> {
+    fn transpile(&self) -> String {
+        match self {
+            PEnumDefinition::Backed(name, cases) => {
+                let mut result = format!("enum {}: string\n{{\n", name);
+                for case in cases {
+                    result.push_str(&format!("    case {} = \"{}\";\n", case, case));
+                }
+                result.push('}');
+                result
+            }
+            PEnumDefinition::Sealed(parent, variants) => {
+                let mut classes = vec![parent.transpile()];
+                classes.extend(variants.iter().map(|v| v.transpile()));
+                classes.join("\n")
+            }
+        }
+    }
+}
+
+/// Each method is a public signature, without a body
+impl Transpilable for PInterfaceDefinition<'_> {
+    fn transpile(&self) -> String {
+        let mut result = format!("interface {}\n{{\n", self.name);
+        for method in self.methods.iter() {
+            result.push_str(&format!("    public {};\n", method.transpile()));
+        }
+        result.push('}');
+
+        result
+    }
+}
+
+impl Transpilable for PMethodSignature<'_> {
+    fn transpile(&self) -> String {
+        let params: Vec<_> = self.parameters.iter().map(|p| p.transpile()).collect();
+        let return_type = match &self.return_type {
+            Some(t) => format!(": {}", t),
+            None => String::from(""),
+        };
+
+        format!(
+            "function {}({}){}",
+            self.name,
+            params.join(", "),
+            return_type
+        )
+    }
+}
+
+impl Transpilable for PProperty<'_> {
+    fn transpile(&self) -> String {
+        // Readonly properties must have a type
+        let datatype = match (&self.datatype, self.is_readonly) {
+            (Some(t), _) => format!("{} ", t),
+            (None, true) => String::from("mixed "),
+            (None, false) => String::from(""),
+        };
+        let readonly = if self.is_readonly { "readonly " } else { "" };
+
+        format!("public {}{}${}", readonly, datatype, self.name)
+    }
+}
+
+impl Transpilable for PConstructor<'_> {
+    fn transpile(&self) -> String {
+        let params: Vec<_> = self.parameters.iter().map(|p| p.transpile()).collect();
+
+        format!(
+            "public function __construct({})\n{}",
+            params.join(", "),
+            transpile_block(&self.body)
+        )
+    }
+}
+
+impl Transpilable for PConstructorParameter<'_> {
+    fn transpile(&self) -> String {
+        match self {
+            PConstructorParameter::Parameter(p) => p.transpile(),
+            PConstructorParameter::Promoted(p) => p.transpile(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codegen::Transpilable,
+        php_ast::{
+            PAssignment, PClassDefinition, PConstructor, PConstructorParameter, PEnumDefinition,
+            PExpresssion, PFunctionDefinition, PInterfaceDefinition, PMethodSignature, PParameter,
+            PPrimary, PProperty, PStatement,
+        },
+    };
+
+    #[test]
+    fn should_transpile_empty_class() {
+        let ast = PClassDefinition {
+            name: String::from("Empty"),
+            modifier: None,
+            parent: None,
+            interfaces: vec![],
+            properties: vec![],
+            constructor: None,
+            methods: vec![],
+        };
+
+        assert_eq!("class Empty\n{\n}", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_properties() {
+        let (x, y, z) = (String::from("x"), String::from("y"), String::from("z"));
+        let property = |name, datatype: Option<&str>, is_readonly| PProperty {
+            name,
+            datatype: datatype.map(String::from),
+            is_readonly,
+        };
+
+        assert_eq!(
+            "public readonly int $x",
+            property(&x, Some("int"), true).transpile()
+        );
+        assert_eq!(
+            "public string $y",
+            property(&y, Some("string"), false).transpile()
+        );
+        assert_eq!(
+            "public readonly mixed $z",
+            property(&z, None, true).transpile()
+        );
+        assert_eq!("public $z", property(&z, None, false).transpile());
+    }
+
+    #[test]
+    fn should_transpile_class() {
+        let (x, y, moves, zero, sum, op) = (
+            String::from("x"),
+            String::from("y"),
+            String::from("moves"),
+            String::from("0"),
+            String::from("sum"),
+            String::from("="),
+        );
+        let ast = PClassDefinition {
+            name: String::from("Point"),
+            modifier: None,
+            parent: None,
+            interfaces: vec![],
+            properties: vec![PProperty {
+                name: &moves,
+                datatype: Some(String::from("int")),
+                is_readonly: false,
+            }],
+            constructor: Some(PConstructor {
+                parameters: vec![
+                    PConstructorParameter::Promoted(PProperty {
+                        name: &x,
+                        datatype: Some(String::from("int")),
+                        is_readonly: true,
+                    }),
+                    PConstructorParameter::Parameter(PParameter {
+                        name: &y,
+                        datatype: None,
+                    }),
+                ],
+                body: vec![PStatement::ExpressionStatement(PExpresssion::Reassignment(
+                    PAssignment {
+                        target: Box::new(PExpresssion::PropertyAccess(
+                            Box::new(PExpresssion::Primary(PPrimary::This)),
+                            &moves,
+                            "->",
+                        )),
+                        operator: &op,
+                        value: Box::new(PExpresssion::Primary(PPrimary::IntegerLiteral(&zero))),
+                    },
+                ))],
+            }),
+            methods: vec![PFunctionDefinition {
+                name: &sum,
+                parameters: vec![],
+                return_type: Some(String::from("int")),
+                body: vec![PStatement::Return(Some(PExpresssion::Primary(
+                    PPrimary::IntegerLiteral(&zero),
+                )))],
+            }],
+        };
+
+        let expected = "class Point\n{\n    public int $moves;\n\n    public function __construct(public readonly int $x, $y)\n    {\n        $this->moves = 0;\n    }\n\n    public function sum(): int\n    {\n        return 0;\n    }\n}";
+        assert_eq!(expected, ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_backed_enum() {
+        let (suit, hearts, spades) = (
+            String::from("Suit"),
+            String::from("Hearts"),
+            String::from("Spades"),
+        );
+        let ast = PEnumDefinition::Backed(&suit, vec![&hearts, &spades]);
+
+        let expected =
+            "enum Suit: string\n{\n    case Hearts = \"Hearts\";\n    case Spades = \"Spades\";\n}";
+        assert_eq!(expected, ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_sealed_enum() {
+        let (shape, radius) = (String::from("Shape"), String::from("radius"));
+        let parent = PClassDefinition {
+            name: shape.clone(),
+            modifier: Some("abstract"),
+            parent: None,
+            interfaces: vec![],
+            properties: vec![],
+            constructor: None,
+            methods: vec![],
+        };
+        let circle = PClassDefinition {
+            name: String::from("Shape_Circle"),
+            modifier: Some("final"),
+            parent: Some(&shape),
+            interfaces: vec![],
+            properties: vec![],
+            constructor: Some(PConstructor {
+                parameters: vec![PConstructorParameter::Promoted(PProperty {
+                    name: &radius,
+                    datatype: Some(String::from("float")),
+                    is_readonly: true,
+                })],
+                body: vec![],
+            }),
+            methods: vec![],
+        };
+        let ast = PEnumDefinition::Sealed(parent, vec![circle]);
+
+        let expected = "abstract class Shape\n{\n}\nfinal class Shape_Circle extends Shape\n{\n    public function __construct(public readonly float $radius)\n    {\n    }\n}";
+        assert_eq!(expected, ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_implemented_interfaces() {
+        let (shape, marker) = (String::from("Shape"), String::from("Marker"));
+        let ast = PClassDefinition {
+            name: String::from("Square"),
+            modifier: None,
+            parent: None,
+            interfaces: vec![&shape, &marker],
+            properties: vec![],
+            constructor: None,
+            methods: vec![],
+        };
+
+        assert_eq!(
+            "class Square implements Shape, Marker\n{\n}",
+            ast.transpile()
+        );
+    }
+
+    #[test]
+    fn should_transpile_interface() {
+        let (shape, area, scale, factor) = (
+            String::from("Shape"),
+            String::from("area"),
+            String::from("scale"),
+            String::from("factor"),
+        );
+        let ast = PInterfaceDefinition {
+            name: &shape,
+            methods: vec![
+                PMethodSignature {
+                    name: &area,
+                    parameters: vec![],
+                    return_type: Some(String::from("float")),
+                },
+                PMethodSignature {
+                    name: &scale,
+                    parameters: vec![PParameter {
+                        name: &factor,
+                        datatype: Some(String::from("float")),
+                    }],
+                    return_type: Some(String::from("void")),
+                },
+            ],
+        };
+
+        let expected = "interface Shape\n{\n    public function area(): float;\n    public function scale(float $factor): void;\n}";
+        assert_eq!(expected, ast.transpile());
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{codegen::Transpilable, php_ast::PSimpleAssignment};
+use crate::{
+    codegen::Transpilable,
+    php_ast::{PAssignment, PSimpleAssignment},
+};
 
 impl Transpilable for PSimpleAssignment<'_> {
     fn transpile(&self) -> String {
@@ -9,6 +12,17 @@ impl Transpilable for PSimpleAssignment<'_> {
     }
 }
 
+impl Transpilable for PAssignment<'_> {
+    fn transpile(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.target.transpile(),
+            self.operator,
+            self.value.transpile()
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
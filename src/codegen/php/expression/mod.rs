@@ -1,7 +1,11 @@
-use crate::{codegen::Transpilable, php_ast::PExpresssion};
+use crate::{
+    codegen::Transpilable,
+    php_ast::{PExpresssion, PMatch},
+};
 use PExpresssion::*;
 
 mod assignment;
+mod precedence;
 mod primary_expression;
 
 impl Transpilable for PExpresssion<'_> {
@@ -9,12 +13,408 @@ impl Transpilable for PExpresssion<'_> {
         match self {
             Primary(p) => p.transpile(),
             Assignment(a) => a.transpile(),
+            Reassignment(a) => a.transpile(),
             FunctionCall(f) => f.transpile(),
             BinaryOp(left, right, op) => {
-                let left_str = left.transpile();
-                let right_str = right.transpile();
+                // The THP AST has no parenthesis, its structure
+                // determines which ones the PHP code needs
+                let (left_precedence, right_precedence) = precedence::operand_precedences(op);
+                let left_str = transpile_operand(left, left_precedence);
+                let right_str = transpile_operand(right, right_precedence);
                 format!("{} {} {}", left_str, op, right_str)
             }
+            UnaryOp(op, expr) => {
+                // `- -$a` must not become the decrement operator `--$a`
+                let is_repeated_sign = matches!(&**expr, UnaryOp(inner_op, _) if *inner_op == *op);
+                let expr_str = if is_repeated_sign {
+                    format!("({})", expr.transpile())
+                } else {
+                    transpile_operand(expr, precedence::UNARY)
+                };
+
+                format!("{}{}", op, expr_str)
+            }
+            Cast(datatype, expr) => {
+                format!(
+                    "({}) {}",
+                    datatype,
+                    transpile_operand(expr, precedence::UNARY)
+                )
+            }
+            Array(items) => {
+                let items: Vec<_> = items.iter().map(|i| i.transpile()).collect();
+                format!("[{}]", items.join(", "))
+            }
+            ArrayAccess(array, index) => {
+                format!(
+                    "{}[{}]",
+                    transpile_operand(array, precedence::PRIMARY),
+                    index.transpile()
+                )
+            }
+            PropertyAccess(object, property, op) => {
+                format!(
+                    "{}{}{}",
+                    transpile_operand(object, precedence::PRIMARY),
+                    op,
+                    property
+                )
+            }
+            MethodCall(object, call, op) => {
+                format!(
+                    "{}{}{}",
+                    transpile_operand(object, precedence::PRIMARY),
+                    op,
+                    call.transpile()
+                )
+            }
+            New(class, arguments) => {
+                let arguments: Vec<_> = arguments.iter().map(|a| a.transpile()).collect();
+                format!("new {}({})", class, arguments.join(", "))
+            }
+            InstanceOf(value, class) => {
+                format!(
+                    "{} instanceof {}",
+                    transpile_operand(value, precedence::PRIMARY),
+                    class
+                )
+            }
+            ClassConstant(class, constant) => format!("{}::{}", class, constant),
+            Match(m) => m.transpile(),
+            Call(function, arguments) => {
+                // `$object->property(1)` would call a method instead
+                let function_str = match &**function {
+                    PropertyAccess(_, _, _) => format!("({})", function.transpile()),
+                    _ => transpile_operand(function, precedence::PRIMARY),
+                };
+                let arguments: Vec<_> = arguments.iter().map(|a| a.transpile()).collect();
+                format!("{}({})", function_str, arguments.join(", "))
+            }
+            FunctionReference(name) => format!("{}(...)", name),
+            ArrowFunction(f) => f.transpile(),
+            Closure(c) => c.transpile(),
+        }
+    }
+}
+
+impl Transpilable for PMatch<'_> {
+    fn transpile(&self) -> String {
+        let mut result = format!("match ({}) {{\n", self.subject.transpile());
+        for arm in self.arms.iter() {
+            let conditions = match arm.conditions.is_empty() {
+                true => String::from("default"),
+                false => {
+                    let conditions: Vec<_> = arm.conditions.iter().map(|c| c.transpile()).collect();
+                    conditions.join(", ")
+                }
+            };
+
+            // The value may be a nested match, indent all its lines
+            let arm_str = format!("{} => {},", conditions, arm.value.transpile());
+            for line in arm_str.lines() {
+                result.push_str("    ");
+                result.push_str(line);
+                result.push('\n');
+            }
+        }
+        result.push('}');
+
+        result
+    }
+}
+
+/// Transpiles an operand, surrounding it with parenthesis if
+/// it binds less tightly than `min_precedence`
+fn transpile_operand(expression: &PExpresssion, min_precedence: u8) -> String {
+    if precedence::precedence(expression) < min_precedence {
+        format!("({})", expression.transpile())
+    } else {
+        expression.transpile()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codegen::Transpilable,
+        php_ast::{PExpresssion, PPrimary},
+    };
+
+    fn var(name: &str) -> PExpresssion<'_> {
+        PExpresssion::Primary(PPrimary::Variable(name))
+    }
+
+    fn int(value: &String) -> PExpresssion<'_> {
+        PExpresssion::Primary(PPrimary::IntegerLiteral(value))
+    }
+
+    #[test]
+    fn should_transpile_arrays() {
+        let (one, two) = (String::from("1"), String::from("2"));
+        assert_eq!("[]", PExpresssion::Array(vec![]).transpile());
+        assert_eq!(
+            "[1, 2]",
+            PExpresssion::Array(vec![int(&one), int(&two)]).transpile()
+        );
+    }
+
+    #[test]
+    fn should_transpile_array_access() {
+        let (items, zero) = (String::from("items"), String::from("0"));
+        let ast = PExpresssion::ArrayAccess(Box::new(var(&items)), Box::new(int(&zero)));
+        assert_eq!("$items[0]", ast.transpile());
+
+        let (matrix, one) = (String::from("matrix"), String::from("1"));
+        let inner = PExpresssion::ArrayAccess(Box::new(var(&matrix)), Box::new(int(&zero)));
+        let ast = PExpresssion::ArrayAccess(Box::new(inner), Box::new(int(&one)));
+        assert_eq!("$matrix[0][1]", ast.transpile());
+    }
+
+    #[test]
+    fn should_parenthesize_array_access_of_operators() {
+        let (a, b, zero) = (String::from("a"), String::from("b"), String::from("0"));
+        let plus = String::from("+");
+        let sum = PExpresssion::BinaryOp(Box::new(var(&a)), Box::new(var(&b)), &plus);
+        let ast = PExpresssion::ArrayAccess(Box::new(sum), Box::new(int(&zero)));
+
+        assert_eq!("($a + $b)[0]", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_unary_operators() {
+        let (not, minus, done) = (String::from("!"), String::from("-"), String::from("done"));
+        assert_eq!(
+            "!$done",
+            PExpresssion::UnaryOp(&not, Box::new(var(&done))).transpile()
+        );
+        assert_eq!(
+            "-$done",
+            PExpresssion::UnaryOp(&minus, Box::new(var(&done))).transpile()
+        );
+    }
+
+    #[test]
+    fn should_parenthesize_unary_operands() {
+        let (minus, plus, a, b) = (
+            String::from("-"),
+            String::from("+"),
+            String::from("a"),
+            String::from("b"),
+        );
+        let sum = PExpresssion::BinaryOp(Box::new(var(&a)), Box::new(var(&b)), &plus);
+        let ast = PExpresssion::UnaryOp(&minus, Box::new(sum));
+        assert_eq!("-($a + $b)", ast.transpile());
+
+        let zero = String::from("0");
+        let access = PExpresssion::ArrayAccess(Box::new(var(&a)), Box::new(int(&zero)));
+        let ast = PExpresssion::UnaryOp(&minus, Box::new(access));
+        assert_eq!("-$a[0]", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_casts() {
+        let (plus, a, b) = (String::from("+"), String::from("a"), String::from("b"));
+        let ast = PExpresssion::Cast("float", Box::new(var(&a)));
+        assert_eq!("(float) $a", ast.transpile());
+
+        let sum = PExpresssion::BinaryOp(Box::new(var(&a)), Box::new(var(&b)), &plus);
+        let ast = PExpresssion::Cast("float", Box::new(sum));
+        assert_eq!("(float) ($a + $b)", ast.transpile());
+    }
+
+    #[test]
+    fn should_not_generate_decrement_operator() {
+        let (minus, not, a) = (String::from("-"), String::from("!"), String::from("a"));
+        let inner = PExpresssion::UnaryOp(&minus, Box::new(var(&a)));
+        let ast = PExpresssion::UnaryOp(&minus, Box::new(inner));
+        assert_eq!("-(-$a)", ast.transpile());
+
+        let inner = PExpresssion::UnaryOp(&not, Box::new(var(&a)));
+        let ast = PExpresssion::UnaryOp(&not, Box::new(inner));
+        assert_eq!("!(!$a)", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_property_access() {
+        let (user, name) = (String::from("user"), String::from("name"));
+        let ast = PExpresssion::PropertyAccess(Box::new(var(&user)), &name, "?->");
+        assert_eq!("$user?->name", ast.transpile());
+
+        let (a, b) = (String::from("a"), String::from("b"));
+        let fallback = binary(var(&a), var(&b), "??");
+        let ast = PExpresssion::PropertyAccess(Box::new(fallback), &name, "->");
+        assert_eq!("($a ?? $b)->name", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_instanceof() {
+        let (value, not) = (String::from("value"), String::from("!"));
+        let ast = PExpresssion::InstanceOf(Box::new(var(&value)), String::from("Person"));
+        assert_eq!("$value instanceof Person", ast.transpile());
+
+        // `!` binds less tightly than `instanceof`
+        let ast = PExpresssion::UnaryOp(&not, Box::new(ast));
+        assert_eq!("!$value instanceof Person", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_enum_values() {
+        let (suit, hearts, radius) = (
+            String::from("Suit"),
+            String::from("Hearts"),
+            String::from("1.5"),
+        );
+        let ast = PExpresssion::ClassConstant(&suit, &hearts);
+        assert_eq!("Suit::Hearts", ast.transpile());
+
+        let radius = PExpresssion::Primary(PPrimary::FloatingLiteral(&radius));
+        let ast = PExpresssion::New(String::from("Shape_Circle"), vec![radius]);
+        assert_eq!("new Shape_Circle(1.5)", ast.transpile());
+
+        // `new` can't be accessed without parenthesis
+        let ast = PExpresssion::New(String::from("Shape_Empty"), vec![]);
+        let ast = PExpresssion::PropertyAccess(Box::new(ast), &hearts, "->");
+        assert_eq!("(new Shape_Empty())->Hearts", ast.transpile());
+    }
+
+    #[test]
+    fn should_group_null_coalescing_to_the_right() {
+        let (a, b, c) = (String::from("a"), String::from("b"), String::from("c"));
+
+        let ast = binary(var(&a), binary(var(&b), var(&c), "??"), "??");
+        assert_eq!("$a ?? $b ?? $c", ast.transpile());
+        let ast = binary(binary(var(&a), var(&b), "??"), var(&c), "??");
+        assert_eq!("($a ?? $b) ?? $c", ast.transpile());
+
+        let ast = binary(var(&a), binary(var(&b), var(&c), "=="), "??");
+        assert_eq!("$a ?? $b == $c", ast.transpile());
+    }
+
+    fn binary<'a>(
+        left: PExpresssion<'a>,
+        right: PExpresssion<'a>,
+        op: &'a str,
+    ) -> PExpresssion<'a> {
+        PExpresssion::BinaryOp(Box::new(left), Box::new(right), op)
+    }
+
+    #[test]
+    fn should_keep_grouping_of_binary_operators() {
+        let (a, b, c) = (String::from("a"), String::from("b"), String::from("c"));
+
+        // (a + b) * c
+        let ast = binary(binary(var(&a), var(&b), "+"), var(&c), "*");
+        assert_eq!("($a + $b) * $c", ast.transpile());
+
+        // a + b * c
+        let ast = binary(var(&a), binary(var(&b), var(&c), "*"), "+");
+        assert_eq!("$a + $b * $c", ast.transpile());
+
+        // a * b + c
+        let ast = binary(binary(var(&a), var(&b), "*"), var(&c), "+");
+        assert_eq!("$a * $b + $c", ast.transpile());
+    }
+
+    #[test]
+    fn should_respect_associativity() {
+        let (a, b, c) = (String::from("a"), String::from("b"), String::from("c"));
+
+        // Left associative
+        let ast = binary(binary(var(&a), var(&b), "-"), var(&c), "-");
+        assert_eq!("$a - $b - $c", ast.transpile());
+        let ast = binary(var(&a), binary(var(&b), var(&c), "-"), "-");
+        assert_eq!("$a - ($b - $c)", ast.transpile());
+
+        // Right associative
+        let ast = binary(var(&a), binary(var(&b), var(&c), "**"), "**");
+        assert_eq!("$a ** $b ** $c", ast.transpile());
+        let ast = binary(binary(var(&a), var(&b), "**"), var(&c), "**");
+        assert_eq!("($a ** $b) ** $c", ast.transpile());
+
+        // Non associative
+        let ast = binary(binary(var(&a), var(&b), "=="), var(&c), "==");
+        assert_eq!("($a == $b) == $c", ast.transpile());
+        let ast = binary(binary(var(&a), var(&b), "<"), var(&c), ">=");
+        assert_eq!("($a < $b) >= $c", ast.transpile());
+    }
+
+    #[test]
+    fn should_group_concatenation_below_arithmetic() {
+        let (a, b, c) = (String::from("a"), String::from("b"), String::from("c"));
+
+        // In PHP 8 `$a . $b + $c` is `$a . ($b + $c)`
+        let ast = binary(binary(var(&a), var(&b), "."), var(&c), "+");
+        assert_eq!("($a . $b) + $c", ast.transpile());
+        let ast = binary(var(&a), binary(var(&b), var(&c), "+"), ".");
+        assert_eq!("$a . $b + $c", ast.transpile());
+    }
+
+    #[test]
+    fn should_parenthesize_unary_operands_of_binary_operators() {
+        let (minus, a, b) = (String::from("-"), String::from("a"), String::from("b"));
+
+        let negative = PExpresssion::UnaryOp(&minus, Box::new(var(&a)));
+        let ast = binary(negative, var(&b), "*");
+        assert_eq!("-$a * $b", ast.transpile());
+
+        // `-$a ** $b` is `-($a ** $b)`
+        let negative = PExpresssion::UnaryOp(&minus, Box::new(var(&a)));
+        let ast = binary(negative, var(&b), "**");
+        assert_eq!("(-$a) ** $b", ast.transpile());
+    }
+
+    /// Operators that THP generates, grouped from highest to lowest
+    /// precedence, as listed in the PHP manual
+    const PRECEDENCE_GROUPS: [&[&str]; 7] = [
+        &["*", "/", "%"],
+        &["+", "-"],
+        &["."],
+        &["<", "<=", ">", ">="],
+        &["==", "!="],
+        &["&&"],
+        &["||"],
+    ];
+
+    fn group_of(op: &str) -> usize {
+        PRECEDENCE_GROUPS
+            .iter()
+            .position(|group| group.contains(&op))
+            .unwrap()
+    }
+
+    #[test]
+    fn should_parenthesize_every_operator_pair() {
+        let (a, b, c) = (String::from("a"), String::from("b"), String::from("c"));
+        let operators: Vec<&str> = PRECEDENCE_GROUPS
+            .iter()
+            .flat_map(|g| g.iter())
+            .copied()
+            .collect();
+        let is_non_associative = |op: &str| group_of(op) == 3 || group_of(op) == 4;
+
+        for outer in &operators {
+            for inner in &operators {
+                let inner_binds_less = group_of(inner) > group_of(outer);
+                let same_group = group_of(inner) == group_of(outer);
+
+                // (a inner b) outer c
+                let ast = binary(binary(var(&a), var(&b), inner), var(&c), outer);
+                let expected = if inner_binds_less || (same_group && is_non_associative(outer)) {
+                    format!("($a {} $b) {} $c", inner, outer)
+                } else {
+                    format!("$a {} $b {} $c", inner, outer)
+                };
+                assert_eq!(expected, ast.transpile(), "({}) {}", inner, outer);
+
+                // a outer (b inner c)
+                let ast = binary(var(&a), binary(var(&b), var(&c), inner), outer);
+                let expected = if inner_binds_less || same_group {
+                    format!("$a {} ($b {} $c)", outer, inner)
+                } else {
+                    format!("$a {} $b {} $c", outer, inner)
+                };
+                assert_eq!(expected, ast.transpile(), "{} ({})", outer, inner);
+            }
         }
     }
 }
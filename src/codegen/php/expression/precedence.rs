@@ -0,0 +1,94 @@
+//! Precedence of PHP expressions, used to decide where parenthesis are needed.
+//!
+//! See https://www.php.net/manual/en/language.operators.precedence.php
+
+use crate::php_ast::PExpresssion;
+
+/// Assignment has the lowest precedence of the expressions THP generates
+pub const ASSIGNMENT: u8 = 0;
+/// Prefix operators: `!`, `-`, `+`. `instanceof` binds more tightly
+/// than them, but less than `**`, so it gets the same precedence and
+/// its operands are required to be primary expressions
+pub const UNARY: u8 = 13;
+/// Literals, variables, function calls, array creation, array access,
+/// property access and class constants
+pub const PRIMARY: u8 = 15;
+
+/// Returns the precedence of a binary operator. A higher value binds tighter.
+pub fn binary_precedence(operator: &str) -> u8 {
+    match operator {
+        "**" => 14,
+        "*" | "/" | "%" => 12,
+        "+" | "-" => 11,
+        "<<" | ">>" => 10,
+        "." => 9,
+        "<" | "<=" | ">" | ">=" => 8,
+        "==" | "!=" | "===" | "!==" | "<>" | "<=>" => 7,
+        "&" => 6,
+        "^" => 5,
+        "|" => 4,
+        "&&" => 3,
+        "||" => 2,
+        "??" => 1,
+        _ => unreachable!("Compiler bug: unknown PHP binary operator `{}`", operator),
+    }
+}
+
+/// How operators of the same precedence are grouped
+#[derive(Debug, PartialEq)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a ** b ** c` is `a ** (b ** c)`
+    Right,
+    /// `a == b == c` is a syntax error
+    None,
+}
+
+/// Returns the associativity of a binary operator
+pub fn binary_associativity(operator: &str) -> Associativity {
+    match operator {
+        "**" | "??" => Associativity::Right,
+        "<" | "<=" | ">" | ">=" | "==" | "!=" | "===" | "!==" | "<>" | "<=>" => Associativity::None,
+        _ => Associativity::Left,
+    }
+}
+
+/// Returns the minimum precedence the left & right operands of
+/// a binary operator must have to not be surrounded by parenthesis
+pub fn operand_precedences(operator: &str) -> (u8, u8) {
+    let precedence = binary_precedence(operator);
+    match binary_associativity(operator) {
+        Associativity::Left => (precedence, precedence + 1),
+        Associativity::Right => (precedence + 1, precedence),
+        Associativity::None => (precedence + 1, precedence + 1),
+    }
+}
+
+/// Returns the precedence of an expression, determined by its outermost operator
+pub fn precedence(expression: &PExpresssion) -> u8 {
+    match expression {
+        PExpresssion::Primary(_)
+        | PExpresssion::FunctionCall(_)
+        | PExpresssion::Array(_)
+        | PExpresssion::ArrayAccess(_, _)
+        | PExpresssion::PropertyAccess(_, _, _)
+        | PExpresssion::MethodCall(_, _, _)
+        | PExpresssion::ClassConstant(_, _)
+        | PExpresssion::Call(_, _)
+        | PExpresssion::FunctionReference(_) => PRIMARY,
+        // A match or a `new` can be the operand of an operator, but they can't
+        // be accessed without parenthesis, e.g. `(new Point(1, 2))->x`
+        PExpresssion::UnaryOp(_, _)
+        | PExpresssion::Cast(_, _)
+        | PExpresssion::InstanceOf(_, _)
+        | PExpresssion::Match(_)
+        | PExpresssion::New(_, _) => UNARY,
+        PExpresssion::BinaryOp(_, _, operator) => binary_precedence(operator),
+        // The body of an arrow function extends as far as possible
+        PExpresssion::Assignment(_)
+        | PExpresssion::Reassignment(_)
+        | PExpresssion::ArrowFunction(_)
+        | PExpresssion::Closure(_) => ASSIGNMENT,
+    }
+}
@@ -7,6 +7,7 @@ impl Transpilable for PPrimary<'_> {
             PPrimary::FloatingLiteral(value) => value.to_string(),
             PPrimary::StringLiteral(value) => format!("\"{}\"", value),
             PPrimary::Variable(name) => format!("${}", name),
+            PPrimary::This => String::from("$this"),
             // PPrimary::Symbol(name) => format!("{}", name),
             PPrimary::BoolLiteral(bool) => {
                 if *bool {
@@ -15,6 +16,7 @@ impl Transpilable for PPrimary<'_> {
                     String::from("false")
                 }
             }
+            PPrimary::NullLiteral => String::from("null"),
         }
     }
 }
@@ -68,6 +70,11 @@ mod tests {
         assert_eq!("322.644", output)
     }
 
+    #[test]
+    fn should_transpile_null() {
+        assert_eq!("null", PPrimary::NullLiteral.transpile())
+    }
+
     #[test]
     fn should_transpile_variable() {
         let input = String::from("name");
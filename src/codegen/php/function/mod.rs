@@ -1,4 +1,11 @@
-use crate::{codegen::Transpilable, php_ast::PFunctionCall};
+use crate::{
+    codegen::Transpilable,
+    php_ast::{
+        PArrowFunction, PClosure, PFunctionCall, PFunctionDefinition, PParameter, PTypeHint,
+    },
+};
+
+use super::statement::transpile_block;
 
 impl Transpilable for PFunctionCall<'_> {
     fn transpile(&self) -> String {
@@ -7,3 +14,123 @@ impl Transpilable for PFunctionCall<'_> {
         format!("{}({})", self.function_name, args.join(", "))
     }
 }
+
+impl Transpilable for PFunctionDefinition<'_> {
+    fn transpile(&self) -> String {
+        let params: Vec<_> = self.parameters.iter().map(|p| p.transpile()).collect();
+
+        format!(
+            "function {}({}){}\n{}",
+            self.name,
+            params.join(", "),
+            return_type_hint(&self.return_type),
+            transpile_block(&self.body)
+        )
+    }
+}
+
+impl Transpilable for PArrowFunction<'_> {
+    fn transpile(&self) -> String {
+        let params: Vec<_> = self.parameters.iter().map(|p| p.transpile()).collect();
+        format!(
+            "fn({}){} => {}",
+            params.join(", "),
+            return_type_hint(&self.return_type),
+            self.value.transpile()
+        )
+    }
+}
+
+impl Transpilable for PClosure<'_> {
+    fn transpile(&self) -> String {
+        let params: Vec<_> = self.parameters.iter().map(|p| p.transpile()).collect();
+        let uses = match self.uses.is_empty() {
+            true => String::from(""),
+            false => {
+                let uses: Vec<_> = self
+                    .uses
+                    .iter()
+                    .map(|u| match u.by_reference {
+                        true => format!("&${}", u.name),
+                        false => format!("${}", u.name),
+                    })
+                    .collect();
+                format!(" use ({})", uses.join(", "))
+            }
+        };
+
+        format!(
+            "function ({}){}{} {}",
+            params.join(", "),
+            uses,
+            return_type_hint(&self.return_type),
+            transpile_block(&self.body)
+        )
+    }
+}
+
+fn return_type_hint(return_type: &PTypeHint) -> String {
+    match return_type {
+        Some(t) => format!(": {}", t),
+        None => String::from(""),
+    }
+}
+
+impl Transpilable for PParameter<'_> {
+    fn transpile(&self) -> String {
+        match &self.datatype {
+            Some(t) => format!("{} ${}", t, self.name),
+            None => format!("${}", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codegen::Transpilable,
+        php_ast::{PExpresssion, PFunctionDefinition, PParameter, PPrimary, PStatement},
+    };
+
+    #[test]
+    fn should_transpile_empty_function() {
+        let name = String::from("main");
+        let ast = PFunctionDefinition {
+            name: &name,
+            parameters: vec![],
+            return_type: Some(String::from("void")),
+            body: vec![],
+        };
+
+        assert_eq!("function main(): void\n{\n}", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_function_with_params_and_body() {
+        let name = String::from("add");
+        let a = String::from("a");
+        let b = String::from("b");
+        let ast = PFunctionDefinition {
+            name: &name,
+            parameters: vec![
+                PParameter {
+                    name: &a,
+                    datatype: Some(String::from("int")),
+                },
+                PParameter {
+                    name: &b,
+                    datatype: None,
+                },
+            ],
+            return_type: Some(String::from("int")),
+            body: vec![PStatement::ExpressionStatement(PExpresssion::Primary(
+                PPrimary::Variable(&a),
+            ))],
+        };
+
+        assert_eq!(
+            "function add(int $a, $b): int\n{\n    $a;\n}",
+            ast.transpile()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod class;
 mod expression;
 mod function;
 pub mod statement;
@@ -1,4 +1,7 @@
-use crate::{codegen::Transpilable, php_ast::PStatement};
+use crate::{
+    codegen::Transpilable,
+    php_ast::{PForEach, PIf, PStatement, PWhile},
+};
 
 impl Transpilable for PStatement<'_> {
     fn transpile(&self) -> String {
@@ -7,6 +10,226 @@ impl Transpilable for PStatement<'_> {
                 let expr_str = expr.transpile();
                 format!("{};", expr_str)
             }
+            PStatement::FunctionDefinition(f) => f.transpile(),
+            PStatement::ClassDefinition(c) => c.transpile(),
+            PStatement::EnumDefinition(e) => e.transpile(),
+            PStatement::InterfaceDefinition(i) => i.transpile(),
+            PStatement::If(i) => i.transpile(),
+            PStatement::ForEach(f) => f.transpile(),
+            PStatement::While(w) => w.transpile(),
+            PStatement::Return(Some(value)) => format!("return {};", value.transpile()),
+            PStatement::Return(None) => String::from("return;"),
+            PStatement::Global(names) => {
+                let variables: Vec<_> = names.iter().map(|name| format!("${}", name)).collect();
+                format!("global {};", variables.join(", "))
+            }
+        }
+    }
+}
+
+impl Transpilable for PIf<'_> {
+    fn transpile(&self) -> String {
+        let mut result = format!(
+            "if ({}) {}",
+            self.condition.transpile(),
+            transpile_block(&self.body)
+        );
+
+        for else_if in self.else_ifs.iter() {
+            result.push_str(&format!(
+                " elseif ({}) {}",
+                else_if.condition.transpile(),
+                transpile_block(&else_if.body)
+            ));
+        }
+
+        if let Some(else_body) = &self.else_body {
+            result.push_str(&format!(" else {}", transpile_block(else_body)));
+        }
+
+        result
+    }
+}
+
+impl Transpilable for PForEach<'_> {
+    fn transpile(&self) -> String {
+        let variables = match self.key {
+            Some(key) => format!("${} => ${}", key, self.value),
+            None => format!("${}", self.value),
+        };
+
+        format!(
+            "foreach ({} as {}) {}",
+            self.collection.transpile(),
+            variables,
+            transpile_block(&self.body)
+        )
+    }
+}
+
+impl Transpilable for PWhile<'_> {
+    fn transpile(&self) -> String {
+        format!(
+            "while ({}) {}",
+            self.condition.transpile(),
+            transpile_block(&self.body)
+        )
+    }
+}
+
+/// Transpiles a list of statements surrounded by braces,
+/// with each statement indented one level
+pub fn transpile_block(statements: &[PStatement]) -> String {
+    let mut result = String::from("{\n");
+    for statement in statements {
+        for line in statement.transpile().lines() {
+            if !line.is_empty() {
+                result.push_str("    ");
+                result.push_str(line);
+            }
+            result.push('\n');
         }
     }
+    result.push('}');
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codegen::Transpilable,
+        php_ast::{
+            PElseIf, PExpresssion, PForEach, PFunctionDefinition, PIf, PPrimary, PStatement, PWhile,
+        },
+    };
+
+    use super::transpile_block;
+
+    fn variable(name: &str) -> PExpresssion<'_> {
+        PExpresssion::Primary(PPrimary::Variable(name))
+    }
+
+    fn statement(name: &str) -> PStatement<'_> {
+        PStatement::ExpressionStatement(variable(name))
+    }
+
+    #[test]
+    fn should_transpile_empty_block() {
+        assert_eq!("{\n}", transpile_block(&[]));
+    }
+
+    #[test]
+    fn should_indent_nested_blocks() {
+        let name = String::from("inner");
+        let value = String::from("322");
+        let inner = PStatement::FunctionDefinition(PFunctionDefinition {
+            name: &name,
+            parameters: vec![],
+            return_type: None,
+            body: vec![PStatement::ExpressionStatement(PExpresssion::Primary(
+                PPrimary::IntegerLiteral(&value),
+            ))],
+        });
+
+        assert_eq!(
+            "{\n    function inner()\n    {\n        322;\n    }\n}",
+            transpile_block(&[inner])
+        );
+    }
+
+    #[test]
+    fn should_transpile_if() {
+        let (a, b) = (String::from("a"), String::from("b"));
+        let ast = PIf {
+            condition: variable(&a),
+            body: vec![statement(&b)],
+            else_ifs: vec![],
+            else_body: None,
+        };
+
+        assert_eq!("if ($a) {\n    $b;\n}", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_if_elseif_else() {
+        let (a, b, c) = (String::from("a"), String::from("b"), String::from("c"));
+        let ast = PIf {
+            condition: variable(&a),
+            body: vec![statement(&a)],
+            else_ifs: vec![
+                PElseIf {
+                    condition: variable(&b),
+                    body: vec![statement(&b)],
+                },
+                PElseIf {
+                    condition: variable(&c),
+                    body: vec![],
+                },
+            ],
+            else_body: Some(vec![statement(&c)]),
+        };
+
+        assert_eq!(
+            "if ($a) {\n    $a;\n} elseif ($b) {\n    $b;\n} elseif ($c) {\n} else {\n    $c;\n}",
+            ast.transpile()
+        );
+    }
+
+    #[test]
+    fn should_transpile_foreach() {
+        let (items, key, value) = (
+            String::from("items"),
+            String::from("key"),
+            String::from("value"),
+        );
+        let ast = PForEach {
+            collection: variable(&items),
+            key: None,
+            value: &value,
+            body: vec![statement(&value)],
+        };
+        assert_eq!(
+            "foreach ($items as $value) {\n    $value;\n}",
+            ast.transpile()
+        );
+
+        let ast = PForEach {
+            collection: variable(&items),
+            key: Some(&key),
+            value: &value,
+            body: vec![],
+        };
+        assert_eq!("foreach ($items as $key => $value) {\n}", ast.transpile());
+    }
+
+    #[test]
+    fn should_transpile_while() {
+        let (a, b) = (String::from("a"), String::from("b"));
+        let ast = PWhile {
+            condition: variable(&a),
+            body: vec![statement(&b)],
+        };
+
+        assert_eq!("while ($a) {\n    $b;\n}", ast.transpile());
+    }
+
+    #[test]
+    fn should_indent_nested_statements() {
+        let (a, b) = (String::from("a"), String::from("b"));
+        let ast = PWhile {
+            condition: variable(&a),
+            body: vec![PStatement::If(PIf {
+                condition: variable(&b),
+                body: vec![statement(&b)],
+                else_ifs: vec![],
+                else_body: Some(vec![statement(&a)]),
+            })],
+        };
+
+        assert_eq!(
+            "while ($a) {\n    if ($b) {\n        $b;\n    } else {\n        $a;\n    }\n}",
+            ast.transpile()
+        );
+    }
 }
@@ -26,6 +26,19 @@ pub const SEMANTIC_DUPLICATED_REFERENCE: u32 = 22;
 pub const SEMANTIC_MISMATCHED_ARGUMENT_COUNT: u32 = 23;
 pub const SYNTAX_INVALID_ARRAY_ACCESS: u32 = 24;
 pub const SEMANTIC_IMMUTABLE_VARIABLE: u32 = 25;
+pub const SYNTAX_INVALID_RETURN: u32 = 26;
+pub const SEMANTIC_MISSING_RETURN: u32 = 27;
+pub const SEMANTIC_INVALID_RETURN: u32 = 28;
+pub const SYNTAX_INVALID_DATATYPE: u32 = 29;
+pub const SEMANTIC_CANNOT_INFER_TYPE: u32 = 30;
+pub const SEMANTIC_NO_MATCHING_OVERLOAD: u32 = 31;
+pub const SYNTAX_INVALID_MATCH: u32 = 32;
+pub const SEMANTIC_NON_EXHAUSTIVE_MATCH: u32 = 33;
+pub const SYNTAX_INVALID_CLASS_DECLARATION: u32 = 34;
+pub const SYNTAX_INVALID_ENUM_DECLARATION: u32 = 35;
+pub const SYNTAX_INVALID_INTERFACE_DECLARATION: u32 = 36;
+pub const SEMANTIC_UNIMPLEMENTED_INTERFACE: u32 = 37;
+pub const SYNTAX_INVALID_MEMBER_ACCESS: u32 = 38;
 
 /// Reads the error codes from the error code list
 pub fn error_code_to_string() -> String {
@@ -10,7 +10,12 @@ pub mod error_messages;
 
 pub trait PrintableError {
     fn get_error_str(&self, chars: &Vec<char>) -> String;
-    fn print_ariadne(&self, source: &String);
+    /// Prints the error for a snippet of code that is not in a file, e.g. in the REPL
+    fn print_ariadne(&self, source: &String) {
+        self.print_ariadne_in(source, "sample.thp");
+    }
+    /// Prints the error, pointing to `file_name`
+    fn print_ariadne_in(&self, source: &String, file_name: &str);
 }
 
 #[derive(Serialize, Debug)]
@@ -46,13 +51,13 @@ impl PrintableError for ErrorContainer {
         panic!("REMOVED: manually generating an error message")
     }
 
-    fn print_ariadne(&self, source: &String) {
+    fn print_ariadne_in(&self, source: &String, file_name: &str) {
         let mut report: ariadne::ReportBuilder<'_, (&str, Range<usize>)> =
-            Report::build(ReportKind::Error, "sample.thp", self.error_offset);
+            Report::build(ReportKind::Error, file_name, self.error_offset);
 
         for label in self.labels.iter() {
-            let l = Label::new(("sample.thp", label.start..label.end))
-                .with_message(label.message.clone());
+            let l =
+                Label::new((file_name, label.start..label.end)).with_message(label.message.clone());
             report = report.with_label(l)
         }
 
@@ -67,7 +72,32 @@ impl PrintableError for ErrorContainer {
         report
             .with_code(self.error_code)
             .finish()
-            .eprint(("sample.thp", Source::from(source)))
+            .eprint((file_name, Source::from(source)))
             .unwrap()
     }
 }
+
+/// Every stage of the compiler reports all the errors it finds,
+/// so they are printed one after another
+impl PrintableError for Vec<ErrorContainer> {
+    fn get_error_str(&self, chars: &Vec<char>) -> String {
+        self.iter()
+            .map(|error| error.get_error_str(chars))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn print_ariadne_in(&self, source: &String, file_name: &str) {
+        for error in self.iter() {
+            error.print_ariadne_in(source, file_name);
+        }
+    }
+}
+
+/// Allows using `?` to bubble up a single error
+/// where a list of errors is expected
+impl From<ErrorContainer> for Vec<ErrorContainer> {
+    fn from(error: ErrorContainer) -> Self {
+        vec![error]
+    }
+}
@@ -32,15 +32,15 @@ impl PrintableError for SemanticError {
         )
     }
 
-    fn print_ariadne(&self, source: &String) {
-        let report = Report::build(ReportKind::Error, "sample.thp", self.error_start)
+    fn print_ariadne_in(&self, source: &String, file_name: &str) {
+        let report = Report::build(ReportKind::Error, file_name, self.error_start)
             .with_label(
-                Label::new(("sample.thp", self.error_start..self.error_end))
+                Label::new((file_name, self.error_start..self.error_end))
                     .with_message(self.reason.clone())
                     .with_color(Color::Red),
             )
             .finish();
 
-        report.eprint(("sample.thp", Source::from(source))).unwrap();
+        report.eprint((file_name, Source::from(source))).unwrap();
     }
 }
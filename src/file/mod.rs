@@ -1,19 +1,46 @@
 use colored::*;
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::codegen::Transpilable;
 use crate::php_ast::transformers::PHPTransformable;
 use crate::{error_handling::PrintableError, lexic, syntax};
 
 pub fn compile_file(input: &String) -> Result<(), ()> {
-    let input_path = Path::new(input);
+    let contents = read_file(Path::new(input))?;
 
+    let out_code = match compile(&contents, input) {
+        Ok(out_code) => out_code,
+        Err(_) => {
+            return Err(());
+        }
+    };
+
+    let mut output_path = Path::new(input)
+        .canonicalize()
+        .expect("Invalid input path: Cannot be canonicalized");
+    output_path.set_extension("php");
+
+    match fs::write(output_path, out_code) {
+        Ok(_) => Ok(()),
+        Err(error) => {
+            eprintln!("{}: Error writing output file", "error".on_red());
+            eprintln!("{}", error);
+            Err(())
+        }
+    }
+}
+
+/// Reads the file at `input_path` as UTF-8, printing an error if it fails
+pub fn read_file(input_path: &Path) -> Result<String, ()> {
     if !input_path.is_file() {
         eprintln!(
             "{}: {} {}",
             "error".on_red(),
             "Input path is not a valid file:".red(),
-            input
+            input_path.display()
         );
         return Err(());
     }
@@ -27,46 +54,98 @@ pub fn compile_file(input: &String) -> Result<(), ()> {
         }
     };
 
-    let contents = match String::from_utf8(bytes) {
-        Ok(str) => str,
+    match String::from_utf8(bytes) {
+        Ok(str) => Ok(str),
         Err(error) => {
             eprintln!("{}: Input file contains invalid UTF-8", "error".on_red());
             eprintln!("{}", error);
-            return Err(());
+            Err(())
         }
-    };
+    }
+}
 
-    let out_code = match compile(&contents) {
-        Ok(out_code) => out_code,
-        Err(_) => {
+/// Returns the paths of all the THP files inside `dir` and its subdirectories,
+/// sorted. Hidden files and directories are ignored.
+pub fn find_thp_files(dir: &Path) -> Result<Vec<PathBuf>, ()> {
+    let mut files = Vec::new();
+    find_thp_files_impl(dir, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn find_thp_files_impl(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), ()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!(
+                "{}: Error reading directory {}",
+                "error".on_red(),
+                dir.display()
+            );
+            eprintln!("{}", error);
             return Err(());
         }
     };
 
-    let mut output_path = Path::new(input)
-        .canonicalize()
-        .expect("Invalid input path: Cannot be canonicalized");
-    output_path.set_extension("php");
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if is_hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            find_thp_files_impl(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "thp") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Compiles the THP file at `input_path` and writes the PHP code
+/// to `output_path`, creating its parent directories if needed
+pub fn compile_to(input_path: &Path, output_path: &Path) -> Result<(), ()> {
+    let contents = read_file(input_path)?;
+    let out_code = compile(&contents, &input_path.display().to_string())?;
+
+    if let Some(parent) = output_path.parent() {
+        if let Err(error) = fs::create_dir_all(parent) {
+            eprintln!(
+                "{}: Error creating directory {}",
+                "error".on_red(),
+                parent.display()
+            );
+            eprintln!("{}", error);
+            return Err(());
+        }
+    }
 
     match fs::write(output_path, out_code) {
         Ok(_) => Ok(()),
         Err(error) => {
-            eprintln!("{}: Error writing output file", "error".on_red());
+            eprintln!(
+                "{}: Error writing {}",
+                "error".on_red(),
+                output_path.display()
+            );
             eprintln!("{}", error);
             Err(())
         }
     }
 }
 
-/// Full pipeline from THP source code to PHP output
-fn compile(input: &String) -> Result<String, ()> {
+/// Full pipeline from THP source code to PHP output.
+/// Prints the errors found, if any, as coming from `file_name`
+pub fn compile(input: &String, file_name: &str) -> Result<String, ()> {
     //
     // Lexical analysis
     //
     let tokens = match lexic::get_tokens(input) {
         Ok(t) => t,
-        Err(error) => {
-            error.print_ariadne(input);
+        Err(errors) => {
+            errors.print_ariadne_in(input, file_name);
             return Err(());
         }
     };
@@ -76,8 +155,8 @@ fn compile(input: &String) -> Result<String, ()> {
     //
     let ast = match syntax::build_ast(&tokens) {
         Ok(ast) => ast,
-        Err(error) => {
-            error.print_ariadne(input);
+        Err(errors) => {
+            errors.print_ariadne_in(input, file_name);
             return Err(());
         }
     };
@@ -88,8 +167,8 @@ fn compile(input: &String) -> Result<String, ()> {
     let res1 = crate::semantic::check_semantics(&ast);
     match res1 {
         Ok(_) => {}
-        Err(error) => {
-            error.print_ariadne(input);
+        Err(errors) => {
+            errors.print_ariadne_in(input, file_name);
             return Err(());
         }
     }
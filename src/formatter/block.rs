@@ -0,0 +1,336 @@
+use crate::syntax::ast::{
+    class::{ClassBody, ClassMember},
+    enum_declaration::{EnumDeclaration, EnumVariant},
+    interface::{FunctionSignature, InterfaceDeclaration},
+    var_binding::VariableBinding,
+    Block, BlockMember, Expression, FunctionDeclaration, ModuleAST, ModuleMembers, Positionable,
+    Statement,
+};
+
+use super::{comment_code, Formattable, Formatter};
+
+/// A statement or expression, at the top level of a module or inside a block,
+/// a property or method inside the body of a class, a variant of an enum,
+/// or a method of an interface
+enum Member<'a, 'b> {
+    Stmt(&'b Statement<'a>),
+    Expr(&'b Expression<'a>),
+    Field(&'b VariableBinding<'a>),
+    Method(&'b FunctionDeclaration<'a>),
+    Variant(&'b EnumVariant<'a>),
+    Signature(&'b FunctionSignature<'a>),
+}
+
+impl Member<'_, '_> {
+    /// Returns the start and end position of this member.
+    ///
+    /// The start position may be after the leading keyword of a statement
+    /// (e.g. `val`, `fun`), it's only used to place comments and blank lines.
+    fn get_position(&self) -> (usize, usize) {
+        match self {
+            Member::Stmt(Statement::Binding(b)) => binding_position(b),
+            Member::Field(b) => binding_position(b),
+            Member::Stmt(Statement::FnDecl(f)) => function_position(f),
+            Member::Method(f) => function_position(f),
+            Member::Stmt(Statement::ClassDecl(c)) => c.get_position(),
+            Member::Stmt(Statement::EnumDecl(e)) => e.get_position(),
+            Member::Variant(v) => v.get_position(),
+            Member::Stmt(Statement::InterfaceDecl(i)) => i.get_position(),
+            Member::Signature(s) => s.get_position(),
+            Member::Stmt(Statement::Conditional(c)) => {
+                let (start, _) = c.if_member.condition.get_position();
+                let last_block = match (&c.else_block, c.else_if_members.last()) {
+                    (Some(else_block), _) => else_block,
+                    (None, Some(else_if_member)) => &else_if_member.body,
+                    (None, None) => &c.if_member.body,
+                };
+                let (_, end) = last_block.get_position();
+                (start, end + 1)
+            }
+            Member::Stmt(Statement::ForLoop(l)) => (l.loop_start, l.loop_end + 1),
+            Member::Stmt(Statement::WhileLoop(l)) => (l.loop_start, l.loop_end + 1),
+            Member::Stmt(Statement::Assignment(a)) => {
+                let start = match &a.object {
+                    Some(object) => object.get_position().0,
+                    None => a.identifier.position,
+                };
+                let (_, end) = a.expression.get_position();
+                (start, end)
+            }
+            Member::Stmt(Statement::Return(r)) => r.get_position(),
+            Member::Expr(e) => e.get_position(),
+        }
+    }
+
+    fn format(&self, f: &mut Formatter) -> String {
+        match self {
+            Member::Stmt(s) => s.format(f),
+            Member::Expr(e) => e.format(f),
+            Member::Field(b) => b.format(f),
+            Member::Method(m) => m.format(f),
+            Member::Variant(v) => v.format(f),
+            Member::Signature(s) => s.format(f),
+        }
+    }
+}
+
+fn binding_position(binding: &VariableBinding) -> (usize, usize) {
+    let start = match &binding.datatype {
+        Some(datatype) => datatype.name.position,
+        None => binding.identifier.position,
+    };
+    let (_, end) = binding.expression.get_position();
+    (start, end)
+}
+
+fn function_position(function: &FunctionDeclaration) -> (usize, usize) {
+    let (_, end) = function.block.get_position();
+    (function.identifier.position, end + 1)
+}
+
+/// Formats a list of members, one per line, at the current indentation level.
+///
+/// The comments that appear before `end` are placed between the members,
+/// and consecutive blank lines are collapsed into a single one.
+fn format_members(f: &mut Formatter, members: Vec<Member>, end: usize) -> Vec<String> {
+    let mut lines = Vec::<String>::new();
+    // Where the last line pushed ends in the source code
+    let mut last_end: Option<usize> = None;
+
+    for member in members {
+        let (member_start, member_end) = member.get_position();
+
+        while let Some(comment) = f.take_comment_before(member_start) {
+            let (code, comment_end) = comment_code(comment);
+            push_line(
+                f,
+                &mut lines,
+                &mut last_end,
+                comment.position,
+                comment_end,
+                code,
+            );
+        }
+
+        let code = member.format(f);
+
+        // Comments inside the member that weren't placed inside a block
+        // (e.g. between the arguments of a function call) go before it
+        while let Some(comment) = f.take_comment_before(member_end) {
+            let (code, comment_end) = comment_code(comment);
+            push_line(
+                f,
+                &mut lines,
+                &mut last_end,
+                comment.position,
+                comment_end,
+                code,
+            );
+        }
+
+        push_line(f, &mut lines, &mut last_end, member_start, member_end, code);
+
+        if let Some(comment) = f.take_trailing_comment(member_end) {
+            let (code, comment_end) = comment_code(comment);
+            if let Some(last_line) = lines.last_mut() {
+                last_line.push(' ');
+                last_line.push_str(&code);
+            }
+            last_end = Some(comment_end);
+        }
+    }
+
+    // Comments after the last member
+    while let Some(comment) = f.take_comment_before(end) {
+        let (code, comment_end) = comment_code(comment);
+        push_line(
+            f,
+            &mut lines,
+            &mut last_end,
+            comment.position,
+            comment_end,
+            code,
+        );
+    }
+
+    lines
+}
+
+/// Pushes an indented line, preceded by a blank line if there was
+/// at least one blank line before it in the source code
+fn push_line(
+    f: &Formatter,
+    lines: &mut Vec<String>,
+    last_end: &mut Option<usize>,
+    start: usize,
+    end: usize,
+    code: String,
+) {
+    if let Some(last_end) = *last_end {
+        if f.count_new_lines(last_end, start) > 1 {
+            lines.push(String::from(""));
+        }
+    }
+
+    lines.push(format!("{}{}", f.indent(), code));
+    *last_end = Some(end);
+}
+
+impl Formattable for ModuleAST<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let members = self
+            .productions
+            .iter()
+            .map(|member| match member {
+                ModuleMembers::Stmt(s) => Member::Stmt(s),
+                ModuleMembers::Expr(e) => Member::Expr(e),
+            })
+            .collect();
+        // Include every comment until the end of the file
+        let end = f.chars.len() + 1;
+        let lines = format_members(f, members, end);
+
+        if lines.is_empty() {
+            return String::from("");
+        }
+
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+impl Formattable for Block<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let members = self
+            .members
+            .iter()
+            .map(|member| match member {
+                BlockMember::Stmt(s) => Member::Stmt(s),
+                BlockMember::Expr(e) => Member::Expr(e),
+            })
+            .collect();
+
+        f.indentation += 1;
+        let lines = format_members(f, members, self.end);
+        f.indentation -= 1;
+
+        if lines.is_empty() {
+            return String::from("{}");
+        }
+
+        format!("{{\n{}\n{}}}", lines.join("\n"), f.indent())
+    }
+}
+
+impl Formattable for ClassBody<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let members = self
+            .members
+            .iter()
+            .map(|member| match member {
+                ClassMember::Field(b) => Member::Field(b),
+                ClassMember::Method(m) => Member::Method(m),
+            })
+            .collect();
+
+        f.indentation += 1;
+        let lines = format_members(f, members, self.end);
+        f.indentation -= 1;
+
+        if lines.is_empty() {
+            return String::from("{}");
+        }
+
+        format!("{{\n{}\n{}}}", lines.join("\n"), f.indent())
+    }
+}
+
+/// Each variant goes in its own line
+impl Formattable for EnumDeclaration<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let members = self.variants.iter().map(Member::Variant).collect();
+
+        f.indentation += 1;
+        let lines = format_members(f, members, self.end);
+        f.indentation -= 1;
+
+        let declaration = format!("enum {}", self.identifier.value);
+        if lines.is_empty() {
+            return format!("{} {{}}", declaration);
+        }
+
+        format!("{} {{\n{}\n{}}}", declaration, lines.join("\n"), f.indent())
+    }
+}
+
+/// Each method goes in its own line
+impl Formattable for InterfaceDeclaration<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let members = self.methods.iter().map(Member::Signature).collect();
+
+        f.indentation += 1;
+        let lines = format_members(f, members, self.end);
+        f.indentation -= 1;
+
+        let declaration = format!("interface {}", self.identifier.value);
+        if lines.is_empty() {
+            return format!("{} {{}}", declaration);
+        }
+
+        format!("{} {{\n{}\n{}}}", declaration, lines.join("\n"), f.indent())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formatter::test_utils::format;
+
+    #[test]
+    fn should_indent_blocks() {
+        let input = "fun f() {\n  val a = 1\n  if a == 1 {\n      print(a)\n  }\n}";
+        let expected = "fun f() {\n    val a = 1\n    if a == 1 {\n        print(a)\n    }\n}\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_remove_blank_lines_at_block_edges() {
+        let input = "fun f() {\n\n\n    val a = 1\n\n\n\n    print(a)\n\n}";
+        let expected = "fun f() {\n    val a = 1\n\n    print(a)\n}\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_keep_comments_inside_blocks() {
+        let input = "fun f() { // start\n    // leading\n    print(1) // trailing\n    // end\n}";
+        let expected =
+            "fun f() {\n    // start\n    // leading\n    print(1) // trailing\n    // end\n}\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_keep_comments_in_empty_blocks() {
+        let input = "fun f() {\n// TODO\n}";
+        let expected = "fun f() {\n    // TODO\n}\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_format_class_bodies() {
+        let input = "class Counter(var Int count) {\n  val Int step = 1\n\n\n  fun increment() {\n  self.count += self.step // step\n  }\n}";
+        let expected = "class Counter(var Int count) {\n    Int step = 1\n\n    fun increment() {\n        self.count += self.step // step\n    }\n}\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_format_enums() {
+        let input = "enum Shape {\n  Circle(Float radius) // round\n\n\n  Rect(Float width,Float height)\n  Empty\n}\nenum Never {\n}\nval s = Shape::Empty";
+        let expected = "enum Shape {\n    Circle(Float radius) // round\n\n    Rect(Float width, Float height)\n    Empty\n}\nenum Never {}\nval s = Shape::Empty\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_format_interfaces() {
+        let input = "interface Shape {\n  fun area()->Float // area\n\n\n  fun scale(Float factor)\n}\ninterface Marker {\n}\nclass Square(val Float side):Shape,Marker {\n}";
+        let expected = "interface Shape {\n    fun area() -> Float // area\n\n    fun scale(Float factor)\n}\ninterface Marker {}\nclass Square(val Float side) : Shape, Marker {}\n";
+        assert_eq!(format(input), expected);
+    }
+}
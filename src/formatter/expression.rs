@@ -0,0 +1,356 @@
+use crate::syntax::ast::{
+    functions::{FunctionCall, Lambda},
+    match_expression::Match,
+    Array, ArrayAccess, Expression, Positionable,
+};
+
+use super::{comment_code, format_list, params_span, Formattable, Formatter};
+
+impl Formattable for Expression<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        match self {
+            Expression::Int(t) => t.value.clone(),
+            Expression::Float(t) => t.value.clone(),
+            // The token stores the string as written, without the quotes
+            Expression::String(t) => format!("\"{}\"", t.value),
+            Expression::Boolean(t) => t.value.clone(),
+            Expression::Null(t) => t.value.clone(),
+            Expression::Identifier(t) => t.value.clone(),
+            Expression::EnumVariant(enum_name, variant, _) => {
+                format!("{}::{}", enum_name.value, variant.value)
+            }
+            Expression::FunctionCall(f_call) => f_call.format(f),
+            Expression::UnaryOperator(operator, expression) => {
+                // Always group nested operators, so `-(-a)` isn't written as `--a`
+                format!(
+                    "{}{}",
+                    operator.value,
+                    format_operand(expression, PRIMARY, f)
+                )
+            }
+            Expression::BinaryOperator(left_expr, right_expr, operator) => {
+                if is_access_operator(&operator.value) {
+                    // The right side is the name of the member, or a method call
+                    let left = format_operand(left_expr, PRIMARY, f);
+                    return format!("{}{}{}", left, operator.value, right_expr.format(f));
+                }
+
+                let (left_precedence, right_precedence) = operand_precedences(&operator.value);
+                let left = format_operand(left_expr, left_precedence, f);
+                let right = format_operand(right_expr, right_precedence, f);
+                format!("{} {} {}", left, operator.value, right)
+            }
+            Expression::Array(array) => array.format(f),
+            Expression::ArrayAcccess(array_access) => array_access.format(f),
+            Expression::TypeTest(type_test) => format!(
+                "{} is {}",
+                format_operand(&type_test.expression, TERM, f),
+                type_test.datatype.format(f)
+            ),
+            Expression::Match(match_expression) => match_expression.format(f),
+            Expression::Lambda(lambda) => lambda.format(f),
+        }
+    }
+}
+
+/// Precedence of THP expressions, mirroring the levels of the parser.
+/// A higher value binds tighter.
+///
+/// Matches and lambdas end in a block, so they are always grouped
+/// when they are operands.
+const GROUPED: u8 = 0;
+const TERM: u8 = 7;
+const UNARY: u8 = 9;
+/// Literals, identifiers, function calls, array accesses and member accesses
+const PRIMARY: u8 = 10;
+
+/// Returns the precedence of a binary operator
+fn binary_precedence(operator: &str) -> u8 {
+    match operator {
+        "??" => 1,
+        "||" => 2,
+        "&&" => 3,
+        "==" | "!=" => 4,
+        ">" | ">=" | "<" | "<=" => 5,
+        "-" | "+" | "++" => TERM,
+        "/" | "*" | "%" => 8,
+        "." | "?." | "!." => PRIMARY,
+        _ => unreachable!("Compiler bug: unknown THP binary operator `{}`", operator),
+    }
+}
+
+/// Returns the minimum precedence the left & right operands of
+/// a binary operator must have to not be surrounded by parenthesis.
+///
+/// `??` groups to the right, all other operators group to the left.
+fn operand_precedences(operator: &str) -> (u8, u8) {
+    let precedence = binary_precedence(operator);
+    match operator {
+        "??" => (precedence + 1, precedence),
+        _ => (precedence, precedence + 1),
+    }
+}
+
+/// Returns the precedence of an expression, determined by its outermost operator
+fn precedence(expression: &Expression) -> u8 {
+    match expression {
+        Expression::BinaryOperator(_, _, operator) => binary_precedence(&operator.value),
+        Expression::UnaryOperator(_, _) => UNARY,
+        // `is` binds tighter than comparisons, and less than terms
+        Expression::TypeTest(_) => TERM - 1,
+        Expression::Match(_) | Expression::Lambda(_) => GROUPED,
+        Expression::Int(_)
+        | Expression::Float(_)
+        | Expression::String(_)
+        | Expression::Boolean(_)
+        | Expression::Null(_)
+        | Expression::Identifier(_)
+        | Expression::EnumVariant(_, _, _)
+        | Expression::FunctionCall(_)
+        | Expression::Array(_)
+        | Expression::ArrayAcccess(_) => PRIMARY,
+    }
+}
+
+/// Formats an expression that is the operand of an operator,
+/// a function call or an array access.
+///
+/// The expression is grouped with parenthesis only if it binds less
+/// tightly than `min_precedence`, so formatting never changes how
+/// an expression is parsed.
+fn format_operand(expression: &Expression, min_precedence: u8, f: &mut Formatter) -> String {
+    if precedence(expression) < min_precedence {
+        format!("({})", expression.format(f))
+    } else {
+        expression.format(f)
+    }
+}
+
+/// Operators that are written without spaces around them
+fn is_access_operator(operator: &String) -> bool {
+    operator == "." || operator == "?." || operator == "!."
+}
+
+impl Formattable for FunctionCall<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let function = format_operand(&self.function, PRIMARY, f);
+        let arguments = format_list(
+            f,
+            &self.arguments.arguments,
+            "(",
+            ")",
+            (
+                self.arguments.paren_open_pos,
+                self.arguments.paren_close_pos - 1,
+            ),
+            function.len(),
+        );
+
+        format!("{}{}", function, arguments)
+    }
+}
+
+impl Formattable for Lambda<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let return_type = match &self.return_type {
+            Some(t) => format!(" -> {}", t.format(f)),
+            None => String::from(""),
+        };
+        // The parameters list is followed by the return type and the opening brace
+        let extra_width = "fn".len() + return_type.len() + 2;
+        let params = format_list(
+            f,
+            &self.params_list.parameters,
+            "(",
+            ")",
+            params_span(&self.params_list),
+            extra_width,
+        );
+
+        format!("fn{}{} {}", params, return_type, self.block.format(f))
+    }
+}
+
+/// Each arm goes in its own line, at the indentation of the match.
+/// The comments between the arms stay in their own lines
+impl Formattable for Match<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let mut result = format!("match {}", self.subject.format(f));
+
+        for arm in self.arms.iter() {
+            let (arm_start, _) = arm.patterns[0].get_position();
+            push_comments_before(f, &mut result, arm_start);
+
+            let patterns: Vec<_> = arm.patterns.iter().map(|p| p.format(f)).collect();
+            let guard = match &arm.guard {
+                Some(guard) => format!(" if {}", guard.format(f)),
+                None => String::from(""),
+            };
+            result.push_str(&format!(
+                "\n{}case {}{} {}",
+                f.indent(),
+                patterns.join(", "),
+                guard,
+                arm.body.format(f)
+            ));
+        }
+
+        if let Some(else_block) = &self.else_block {
+            push_comments_before(f, &mut result, else_block.start);
+            result.push_str(&format!("\n{}else {}", f.indent(), else_block.format(f)));
+        }
+
+        result
+    }
+}
+
+/// Pushes the comments before `position` to `result`, each in its own line
+fn push_comments_before(f: &mut Formatter, result: &mut String, position: usize) {
+    while let Some(comment) = f.take_comment_before(position) {
+        result.push_str(&format!("\n{}{}", f.indent(), comment_code(comment).0));
+    }
+}
+
+impl Formattable for Array<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        format_list(f, &self.exps, "[", "]", (self.start, self.end), 0)
+    }
+}
+
+impl Formattable for ArrayAccess<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        format!(
+            "{}[{}]",
+            format_operand(&self.left_expr, PRIMARY, f),
+            self.idx_expr.format(f)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formatter::test_utils::format;
+
+    #[test]
+    fn should_format_literals() {
+        assert_eq!(format("322"), "322\n");
+        assert_eq!(format("0x1f"), "0x1f\n");
+        assert_eq!(format("3.1415"), "3.1415\n");
+        assert_eq!(
+            format("\"hello\\n \\\"world\\\"\""),
+            "\"hello\\n \\\"world\\\"\"\n"
+        );
+        assert_eq!(format("true"), "true\n");
+        assert_eq!(format("name"), "name\n");
+    }
+
+    #[test]
+    fn should_add_spaces_around_binary_operators() {
+        assert_eq!(format("a+b"), "a + b\n");
+        assert_eq!(format("a   ==b"), "a == b\n");
+        assert_eq!(format("a ++\"b\""), "a ++ \"b\"\n");
+    }
+
+    #[test]
+    fn should_not_add_spaces_to_unary_operators() {
+        assert_eq!(format("- 10"), "-10\n");
+        assert_eq!(format("! value"), "!value\n");
+        assert_eq!(format("2 * - 4"), "2 * -4\n");
+    }
+
+    #[test]
+    fn should_group_nested_operations() {
+        assert_eq!(format("(a * b) + c"), "a * b + c\n");
+        assert_eq!(format("a * (b + c)"), "a * (b + c)\n");
+        assert_eq!(format("-(a + b)"), "-(a + b)\n");
+        assert_eq!(format("(-a) + b"), "-a + b\n");
+        assert_eq!(format("-(-a)"), "-(-a)\n");
+        assert_eq!(format("(a is Int) == b"), "a is Int == b\n");
+        assert_eq!(format("(a + b) is Int"), "a + b is Int\n");
+        assert_eq!(format("(a < b) is Bool"), "(a < b) is Bool\n");
+    }
+
+    #[test]
+    fn should_not_group_left_associative_chains() {
+        assert_eq!(format("a + b + total"), "a + b + total\n");
+        assert_eq!(format("(a + b) + total"), "a + b + total\n");
+        assert_eq!(format("a - (b - c)"), "a - (b - c)\n");
+        assert_eq!(format("!true && false || true"), "!true && false || true\n");
+        assert_eq!(format("a ?? (b ?? c)"), "a ?? b ?? c\n");
+        assert_eq!(format("(a ?? b) ?? c"), "(a ?? b) ?? c\n");
+    }
+
+    #[test]
+    fn should_not_group_member_accesses() {
+        assert_eq!(format("self.hits + 1"), "self.hits + 1\n");
+        assert_eq!(format("b.items[0]"), "b.items[0]\n");
+        assert_eq!(format("b.get_items()[1]"), "b.get_items()[1]\n");
+        assert_eq!(format("-b.items[0] + first"), "-b.items[0] + first\n");
+        assert_eq!(format("(-a).b"), "(-a).b\n");
+    }
+
+    #[test]
+    fn should_format_function_calls() {
+        assert_eq!(format("print( \"hello\" )"), "print(\"hello\")\n");
+        assert_eq!(format("add(1,2 ,3)"), "add(1, 2, 3)\n");
+        assert_eq!(format("add(1,2,)"), "add(1, 2)\n");
+        assert_eq!(format("f()"), "f()\n");
+    }
+
+    #[test]
+    fn should_split_long_argument_lists() {
+        let input = "print(\"a very long string argument\", \"another very long string argument\", 322, 644)";
+        let expected = "print(\n    \"a very long string argument\",\n    \"another very long string argument\",\n    322,\n    644,\n)\n";
+        assert_eq!(format(input), expected);
+        assert_eq!(format(expected), expected);
+    }
+
+    #[test]
+    fn should_format_arrays() {
+        assert_eq!(format("[1,2,  3]"), "[1, 2, 3]\n");
+        assert_eq!(format("[1,2,]"), "[1, 2]\n");
+        assert_eq!(format("[ ]"), "[]\n");
+        assert_eq!(format("numbers[ 0 ]"), "numbers[0]\n");
+    }
+
+    #[test]
+    fn should_format_match() {
+        assert_eq!(
+            format("val a = match b\n  case 1,2 {\"x\"}\n  case 3 if c { \"y\" }\n  else {}"),
+            "val a = match b\ncase 1, 2 {\n    \"x\"\n}\ncase 3 if c {\n    \"y\"\n}\nelse {}\n"
+        );
+    }
+
+    #[test]
+    fn should_format_lambdas() {
+        assert_eq!(
+            format("val f=fn(Int x)->Int{x+1}"),
+            "val f = fn(Int x) -> Int {\n    x + 1\n}\n"
+        );
+        assert_eq!(format("val f = fn( ) { }"), "val f = fn() {}\n");
+        assert_eq!(
+            format("apply( fn(Int x)->Int{x} , 2)(3)"),
+            "apply(\n    fn(Int x) -> Int {\n        x\n    },\n    2,\n)(3)\n"
+        );
+    }
+
+    #[test]
+    fn should_keep_comments_next_to_their_list_items() {
+        assert_eq!(
+            format("print(\n    1, // one\n    2\n)"),
+            "print(\n    1, // one\n    2,\n)\n"
+        );
+        assert_eq!(
+            format("val xs = [\n    1,\n    // two\n    2,\n]"),
+            "val xs = [\n    1,\n    // two\n    2,\n]\n"
+        );
+    }
+
+    #[test]
+    fn should_keep_comments_between_match_arms() {
+        assert_eq!(
+            format("val x = match a\n// before case\ncase 1 { 2 }\n// after\nelse { 3 }"),
+            "val x = match a\n// before case\ncase 1 {\n    2\n}\n// after\nelse {\n    3\n}\n"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{
+    error_handling::MistiError,
+    lexic::{
+        self,
+        token::{Token, TokenType},
+    },
+    syntax::{
+        self,
+        ast::{ParamsList, Positionable},
+    },
+};
+
+mod block;
+mod expression;
+mod statement;
+#[cfg(test)]
+mod test_utils;
+
+/// Width of a single indentation level
+const INDENT_WIDTH: usize = 4;
+/// Lists (arguments, parameters, arrays) longer than
+/// this are split into multiple lines
+const MAX_LINE_WIDTH: usize = 80;
+
+/// Trait that the AST and its nodes implement to be formatted back into THP code
+pub trait Formattable {
+    /// Returns the canonical THP code of this node.
+    ///
+    /// The output doesn't have leading indentation, and every
+    /// line after the first one is indented according to `f`
+    fn format(&self, f: &mut Formatter) -> String;
+}
+
+/// Holds the state needed while formatting a file
+pub struct Formatter<'a> {
+    /// The source code, used to detect blank lines and trailing comments
+    chars: Vec<char>,
+    /// All the comments of the source code, in order
+    comments: Vec<&'a Token>,
+    /// Index of the first comment that hasn't been emitted yet
+    next_comment: usize,
+    /// Current indentation level
+    indentation: usize,
+}
+
+impl<'a> Formatter<'a> {
+    pub fn new(input: &String, tokens: &'a Vec<Token>) -> Formatter<'a> {
+        let comments = tokens
+            .iter()
+            .filter(|t| {
+                t.token_type == TokenType::Comment || t.token_type == TokenType::MultilineComment
+            })
+            .collect();
+
+        Formatter {
+            chars: input.chars().collect(),
+            comments,
+            next_comment: 0,
+            indentation: 0,
+        }
+    }
+
+    /// Returns the whitespace of the current indentation level
+    fn indent(&self) -> String {
+        " ".repeat(self.indentation * INDENT_WIDTH)
+    }
+
+    /// Whether a line of `width` characters fits at the current indentation level
+    fn fits(&self, width: usize) -> bool {
+        self.indentation * INDENT_WIDTH + width <= MAX_LINE_WIDTH
+    }
+
+    /// Returns the next comment that hasn't been emitted, if it starts before `position`
+    fn take_comment_before(&mut self, position: usize) -> Option<&'a Token> {
+        match self.comments.get(self.next_comment) {
+            Some(comment) if comment.position < position => {
+                self.next_comment += 1;
+                Some(comment)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the next comment that hasn't been emitted, if it is in the
+    /// same line as `position` (e.g. `val x = 10  // comment`)
+    fn take_trailing_comment(&mut self, position: usize) -> Option<&'a Token> {
+        match self.comments.get(self.next_comment) {
+            Some(comment)
+                if comment.position >= position
+                    && self.count_new_lines(position, comment.position) == 0 =>
+            {
+                self.next_comment += 1;
+                Some(comment)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a comment that hasn't been emitted starts between `start` and `end`
+    fn has_comment_between(&self, start: usize, end: usize) -> bool {
+        self.comments[self.next_comment..]
+            .iter()
+            .any(|comment| comment.position >= start && comment.position < end)
+    }
+
+    /// Counts the new lines in the source code between `start` and `end`
+    fn count_new_lines(&self, start: usize, end: usize) -> usize {
+        let end = end.min(self.chars.len());
+        if start >= end {
+            return 0;
+        }
+
+        self.chars[start..end]
+            .iter()
+            .filter(|c| **c == '\n')
+            .count()
+    }
+}
+
+/// Returns the source code of a comment, and the position where it ends
+fn comment_code(comment: &Token) -> (String, usize) {
+    match comment.token_type {
+        // The value of a single line comment already contains the `//`
+        TokenType::Comment => {
+            let end = comment.position + comment.value.chars().count();
+            (comment.value.trim_end().to_string(), end)
+        }
+        TokenType::MultilineComment => {
+            let end = comment.position + comment.value.chars().count() + 4;
+            (format!("/*{}*/", comment.value), end)
+        }
+        _ => unreachable!("Illegal state: Tried to format a token that is not a comment"),
+    }
+}
+
+/// Formats a list of items delimited by `open` and `close`, separated by commas.
+/// `span` is the position of `open` and `close` in the source code.
+///
+/// If the list fits in a single line (considering `extra_width` characters
+/// that go before or after it) it's returned as is. Otherwise every item is
+/// placed in its own line, with a trailing comma.
+///
+/// A list with comments is always split, and each comment
+/// stays before or after the item it's next to.
+fn format_list<T: Formattable + Positionable>(
+    f: &mut Formatter,
+    items: &[T],
+    open: &str,
+    close: &str,
+    span: (usize, usize),
+    extra_width: usize,
+) -> String {
+    let (open_position, close_position) = span;
+    let has_comments = f.has_comment_between(open_position, close_position);
+    let first_comment = f.next_comment;
+
+    let flat_items: Vec<String> = items.iter().map(|item| item.format(f)).collect();
+    let flat = format!("{}{}{}", open, flat_items.join(", "), close);
+
+    let is_multiline = flat_items.iter().any(|item| item.contains('\n'));
+    if items.is_empty() || (!has_comments && !is_multiline && f.fits(extra_width + flat.len())) {
+        return flat;
+    }
+
+    // The items are formatted again, so their comments too
+    f.next_comment = first_comment;
+    f.indentation += 1;
+    let indent = f.indent();
+    let mut lines = Vec::<String>::new();
+    for item in items {
+        let (item_start, item_end) = item.get_position();
+        while let Some(comment) = f.take_comment_before(item_start) {
+            lines.push(format!("{}{}\n", indent, comment_code(comment).0));
+        }
+
+        let mut line = format!("{}{},", indent, item.format(f));
+        if f.has_comment_between(item_end, close_position) {
+            if let Some(comment) = f.take_trailing_comment(item_end) {
+                line.push_str(&format!(" {}", comment_code(comment).0));
+            }
+        }
+        lines.push(format!("{}\n", line));
+    }
+    // Comments after the last item
+    while let Some(comment) = f.take_comment_before(close_position) {
+        lines.push(format!("{}{}\n", indent, comment_code(comment).0));
+    }
+    f.indentation -= 1;
+
+    format!("{}\n{}{}{}", open, lines.join(""), f.indent(), close)
+}
+
+/// Returns the position of the parentheses of a parameters list
+fn params_span(params_list: &ParamsList) -> (usize, usize) {
+    // The end of the list is after its closing paren
+    (params_list.start, params_list.end - 1)
+}
+
+/// Formats THP source code.
+///
+/// Returns the errors found if the code cannot be tokenized or parsed.
+/// Semantic errors don't prevent formatting.
+pub fn format_code(input: &String) -> Result<String, Vec<MistiError>> {
+    let tokens = lexic::get_tokens(input)?;
+    let ast = syntax::build_ast(&tokens)?;
+
+    let mut formatter = Formatter::new(input, &tokens);
+    Ok(ast.format(&mut formatter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::format;
+
+    #[test]
+    fn should_format_empty_file() {
+        assert_eq!(format(""), "");
+        assert_eq!(format("\n\n\n"), "");
+    }
+
+    #[test]
+    fn should_end_files_with_a_single_new_line() {
+        assert_eq!(format("val x = 10"), "val x = 10\n");
+        assert_eq!(format("val x = 10\n\n\n"), "val x = 10\n");
+    }
+
+    #[test]
+    fn should_normalize_blank_lines() {
+        let input = "\n\nval a = 1\nval b = 2\n\n\n\nval c = 3\n";
+        let expected = "val a = 1\nval b = 2\n\nval c = 3\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_keep_comments() {
+        let input = "// header\n\n\nval a = 1   // trailing  \n/* multi\nline */\nprint(a)\n// end";
+        let expected = "// header\n\nval a = 1 // trailing\n/* multi\nline */\nprint(a)\n// end\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_return_syntax_errors() {
+        let result = format_code(&String::from("val x\nval y"));
+        match result {
+            Err(errors) => assert_eq!(errors.len(), 2),
+            Ok(code) => panic!("Expected an error, got {}", code),
+        }
+    }
+
+    #[test]
+    fn should_format_generic_datatypes() {
+        assert_eq!(format("Array[ Int ] xs = [1]"), "Array[Int] xs = [1]\n");
+        assert_eq!(
+            format("fun f(Map[String,Int] m) -> Array[Int,] {\n    [1]\n}"),
+            "fun f(Map[String, Int] m) -> Array[Int] {\n    [1]\n}\n"
+        );
+        assert_eq!(
+            format("fun first[T,U,](Array[T] items, U x) -> T {\n    items[0]\n}"),
+            "fun first[T, U](Array[T] items, U x) -> T {\n    items[0]\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_format_union_datatypes() {
+        assert_eq!(
+            format("Int|String  | ?Bool x = 1"),
+            "Int | String | ?Bool x = 1\n"
+        );
+        assert_eq!(
+            format("fun f(Array[Int|Float] xs) -> Int  |  Null {\n    null\n}"),
+            "fun f(Array[Int | Float] xs) -> Int | Null {\n    null\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_be_idempotent() {
+        let input = "// comment\nfun   add(Int a,Int b)->Int{\n  val sum=a+b\n\n\n  if sum>10{print(\"big\")}else{print(\"small\")}\n}\n\nvar   Int counter=0\nwhile counter<10 {counter+=1}\nfor  i ,v in [1,2,3]{\n  // inside\n  print(v)\n}\n";
+        let first = format(input);
+        let second = format(&first);
+        assert_eq!(first, second);
+    }
+}
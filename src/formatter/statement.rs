@@ -0,0 +1,348 @@
+use crate::syntax::ast::{
+    class::ClassDeclaration,
+    datatype::Datatype,
+    enum_declaration::EnumVariant,
+    interface::FunctionSignature,
+    loops::{ForLoop, WhileLoop},
+    var_binding::VariableBinding,
+    Assignment, Conditional, FunctionDeclaration, Parameter, Return, Statement,
+};
+
+use super::{format_list, params_span, Formattable, Formatter};
+
+impl Formattable for Statement<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        match self {
+            Statement::Binding(b) => b.format(f),
+            Statement::FnDecl(fun) => fun.format(f),
+            Statement::ClassDecl(c) => c.format(f),
+            Statement::EnumDecl(e) => e.format(f),
+            Statement::InterfaceDecl(i) => i.format(f),
+            Statement::Conditional(c) => c.format(f),
+            Statement::ForLoop(l) => l.format(f),
+            Statement::WhileLoop(l) => l.format(f),
+            Statement::Assignment(a) => a.format(f),
+            Statement::Return(r) => r.format(f),
+        }
+    }
+}
+
+impl Formattable for VariableBinding<'_> {
+    /// The `val` keyword is omitted when the datatype is present
+    fn format(&self, f: &mut Formatter) -> String {
+        let binding = match (self.is_mutable, &self.datatype) {
+            (true, Some(datatype)) => {
+                format!("var {} {}", datatype.format(f), self.identifier.value)
+            }
+            (true, None) => format!("var {}", self.identifier.value),
+            (false, Some(datatype)) => format!("{} {}", datatype.format(f), self.identifier.value),
+            (false, None) => format!("val {}", self.identifier.value),
+        };
+
+        format!("{} = {}", binding, self.expression.format(f))
+    }
+}
+
+impl Formattable for FunctionDeclaration<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let return_type = match &self.return_type {
+            Some(t) => format!(" -> {}", t.format(f)),
+            None => String::from(""),
+        };
+        let type_params = match self.type_params.is_empty() {
+            true => String::from(""),
+            false => {
+                let names: Vec<&str> = self.type_params.iter().map(|t| t.value.as_str()).collect();
+                format!("[{}]", names.join(", "))
+            }
+        };
+        let declaration = format!("fun {}{}", self.identifier.value, type_params);
+        // The parameters list is followed by the return type and the opening brace
+        let extra_width = declaration.len() + return_type.len() + 2;
+        let params = format_list(
+            f,
+            &self.params_list.parameters,
+            "(",
+            ")",
+            params_span(&self.params_list),
+            extra_width,
+        );
+
+        format!(
+            "{}{}{} {}",
+            declaration,
+            params,
+            return_type,
+            self.block.format(f)
+        )
+    }
+}
+
+/// The methods of interfaces are written like functions, without a body
+impl Formattable for FunctionSignature<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let return_type = match &self.return_type {
+            Some(t) => format!(" -> {}", t.format(f)),
+            None => String::from(""),
+        };
+        let type_params = match self.type_params.is_empty() {
+            true => String::from(""),
+            false => {
+                let names: Vec<&str> = self.type_params.iter().map(|t| t.value.as_str()).collect();
+                format!("[{}]", names.join(", "))
+            }
+        };
+        let declaration = format!("fun {}{}", self.identifier.value, type_params);
+        // The parameters list is followed by the return type
+        let extra_width = declaration.len() + return_type.len();
+        let params = format_list(
+            f,
+            &self.params_list.parameters,
+            "(",
+            ")",
+            params_span(&self.params_list),
+            extra_width,
+        );
+
+        format!("{}{}{}", declaration, params, return_type)
+    }
+}
+
+impl Formattable for ClassDeclaration<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let mut result = format!("class {}", self.identifier.value);
+
+        if let Some(constructor) = &self.constructor {
+            // The parameters list may be followed by the opening brace
+            let extra_width = result.len() + 2;
+            let params = format_list(
+                f,
+                &constructor.parameters,
+                "(",
+                ")",
+                params_span(constructor),
+                extra_width,
+            );
+            result.push_str(&params);
+        }
+
+        if !self.interfaces.is_empty() {
+            let names: Vec<&str> = self.interfaces.iter().map(|i| i.value.as_str()).collect();
+            result.push_str(&format!(" : {}", names.join(", ")));
+        }
+
+        if let Some(body) = &self.body {
+            result.push(' ');
+            result.push_str(&body.format(f));
+        }
+
+        result
+    }
+}
+
+impl Formattable for EnumVariant<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        match &self.payload {
+            Some(payload) => {
+                let extra_width = self.identifier.value.len();
+                let fields = format_list(
+                    f,
+                    &payload.parameters,
+                    "(",
+                    ")",
+                    params_span(payload),
+                    extra_width,
+                );
+                format!("{}{}", self.identifier.value, fields)
+            }
+            None => self.identifier.value.clone(),
+        }
+    }
+}
+
+impl Formattable for Parameter<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let parameter = format!("{} {}", self.datatype.format(f), self.identifier.value);
+
+        match self.modifier {
+            Some(modifier) => format!("{} {}", modifier.value, parameter),
+            None => parameter,
+        }
+    }
+}
+
+impl Formattable for Datatype<'_> {
+    /// Datatypes are always written in a single line
+    fn format(&self, _: &mut Formatter) -> String {
+        format_datatype(self)
+    }
+}
+
+fn format_datatype(datatype: &Datatype) -> String {
+    let nullable = if datatype.nullable.is_some() { "?" } else { "" };
+    let mut result = if let Some(return_type) = &datatype.return_type {
+        let params: Vec<String> = datatype.type_params.iter().map(format_datatype).collect();
+        format!(
+            "{}({}) -> {}",
+            nullable,
+            params.join(", "),
+            format_datatype(return_type)
+        )
+    } else if datatype.type_params.is_empty() {
+        format!("{}{}", nullable, datatype.name.value)
+    } else {
+        let params: Vec<String> = datatype.type_params.iter().map(format_datatype).collect();
+        format!("{}{}[{}]", nullable, datatype.name.value, params.join(", "))
+    };
+
+    for alternative in datatype.alternatives.iter() {
+        result.push_str(" | ");
+        result.push_str(&format_datatype(alternative));
+    }
+    result
+}
+
+impl Formattable for Conditional<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let mut result = format!(
+            "if {} {}",
+            self.if_member.condition.format(f),
+            self.if_member.body.format(f)
+        );
+
+        for else_if_member in self.else_if_members.iter() {
+            result.push_str(&format!(
+                " else if {} {}",
+                else_if_member.condition.format(f),
+                else_if_member.body.format(f)
+            ));
+        }
+
+        if let Some(else_block) = &self.else_block {
+            result.push_str(&format!(" else {}", else_block.format(f)));
+        }
+
+        result
+    }
+}
+
+impl Formattable for ForLoop<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let variables = match self.key {
+            Some(key) => format!("{}, {}", key.value, self.value.value),
+            None => self.value.value.clone(),
+        };
+
+        format!(
+            "for {} in {} {}",
+            variables,
+            self.collection.format(f),
+            self.body.format(f)
+        )
+    }
+}
+
+impl Formattable for WhileLoop<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        format!("while {} {}", self.condition.format(f), self.body.format(f))
+    }
+}
+
+impl Formattable for Assignment<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        let target = match &self.object {
+            Some(object) => format!("{}.{}", object.format(f), self.identifier.value),
+            None => self.identifier.value.clone(),
+        };
+
+        format!(
+            "{} {} {}",
+            target,
+            self.operator.value,
+            self.expression.format(f)
+        )
+    }
+}
+
+impl Formattable for Return<'_> {
+    fn format(&self, f: &mut Formatter) -> String {
+        match &self.value {
+            Some(value) => format!("return {}", value.format(f)),
+            None => String::from("return"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formatter::test_utils::format;
+
+    #[test]
+    fn should_format_bindings() {
+        assert_eq!(format("val   x=10"), "val x = 10\n");
+        assert_eq!(format("var x  =  10"), "var x = 10\n");
+        assert_eq!(format("val Int x = 10"), "Int x = 10\n");
+        assert_eq!(format("Int x = 10"), "Int x = 10\n");
+        assert_eq!(format("var Int x = 10"), "var Int x = 10\n");
+        assert_eq!(
+            format("?( Int,String )->  Int f = null"),
+            "?(Int, String) -> Int f = null\n"
+        );
+    }
+
+    #[test]
+    fn should_format_assignments() {
+        assert_eq!(format("x=10"), "x = 10\n");
+        assert_eq!(format("x+=  1"), "x += 1\n");
+    }
+
+    #[test]
+    fn should_format_function_declarations() {
+        assert_eq!(format("fun   main(){}"), "fun main() {}\n");
+        assert_eq!(
+            format("fun add(Int a,Int b,)->Int{a+b}"),
+            "fun add(Int a, Int b) -> Int {\n    a + b\n}\n"
+        );
+    }
+
+    #[test]
+    fn should_split_long_parameter_lists() {
+        let input = "fun register_user(String first_name, String last_name, String email_address) -> Bool {}";
+        let expected = "fun register_user(\n    String first_name,\n    String last_name,\n    String email_address,\n) -> Bool {}\n";
+        assert_eq!(format(input), expected);
+        assert_eq!(format(expected), expected);
+    }
+
+    #[test]
+    fn should_format_conditionals() {
+        let input = "if a{print(a)}else if b {print(b)}\nelse{print(c)}";
+        let expected =
+            "if a {\n    print(a)\n} else if b {\n    print(b)\n} else {\n    print(c)\n}\n";
+        assert_eq!(format(input), expected);
+    }
+
+    #[test]
+    fn should_format_returns() {
+        assert_eq!(
+            format("fun f()->Int{return   1+2}"),
+            "fun f() -> Int {\n    return 1 + 2\n}\n"
+        );
+        assert_eq!(format("fun f(){\nreturn\n}"), "fun f() {\n    return\n}\n");
+    }
+
+    #[test]
+    fn should_format_loops() {
+        assert_eq!(
+            format("for  v in [1,2]{print(v)}"),
+            "for v in [1, 2] {\n    print(v)\n}\n"
+        );
+        assert_eq!(
+            format("for k ,v in values {print(v)}"),
+            "for k, v in values {\n    print(v)\n}\n"
+        );
+        assert_eq!(
+            format("while  x<10{x+=1}"),
+            "while x < 10 {\n    x += 1\n}\n"
+        );
+    }
+}
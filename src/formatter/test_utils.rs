@@ -0,0 +1,8 @@
+//! Helpers shared by the tests of the formatter
+
+use super::format_code;
+
+/// Formats the THP program `input`, which must be free of lexic & syntax errors
+pub fn format(input: &str) -> String {
+    format_code(&String::from(input)).unwrap()
+}
@@ -39,10 +39,14 @@ pub enum LexResult {
     Err(ErrorContainer),
 }
 
-/// Scans and returns all the tokens in the input String
-pub fn get_tokens(input: &String) -> Result<Vec<Token>, MistiError> {
+/// Scans and returns all the tokens in the input String.
+///
+/// If errors are found, the scanner skips the rest of the offending line
+/// and continues, so that every lexical error in the input is returned.
+pub fn get_tokens(input: &String) -> Result<Vec<Token>, Vec<MistiError>> {
     let chars: Vec<char> = input.chars().into_iter().collect();
     let mut results = Vec::new();
+    let mut errors = Vec::new();
     let mut current_pos: usize = 0;
     let mut indentation_stack = vec![0];
     // Used to emit INDENT & DEDENT tokens
@@ -88,11 +92,20 @@ pub fn get_tokens(input: &String) -> Result<Vec<Token>, MistiError> {
                 current_pos = next_pos;
             }
             LexResult::Err(error_info) => {
-                return Err(error_info);
+                // Resume scanning at the next line
+                let error_pos = current_pos.max(error_info.error_offset);
+                current_pos = skip_to_new_line(&chars, error_pos).max(current_pos + 1);
+                at_new_line = false;
+
+                errors.push(error_info);
             }
         }
     }
 
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
     // emit DEDENT tokens for each entry left in the indentation_stack,
     // except the first one (which is 0)
     for _ in 0..indentation_stack.len() - 1 {
@@ -219,6 +232,16 @@ fn handle_indentation(
     }
 }
 
+/// Returns the position of the next new line at or after `pos`,
+/// or the length of the input if there are no more new lines
+fn skip_to_new_line(input: &Chars, pos: usize) -> usize {
+    let mut current_pos = pos;
+    while has_input(input, current_pos) && input[current_pos] != '\n' {
+        current_pos += 1;
+    }
+    current_pos
+}
+
 /// Returns the char at `pos`
 fn peek(input: &Chars, pos: usize) -> char {
     let result = input.get(pos).unwrap_or(&'\0');
@@ -501,3 +524,29 @@ mod indentation_tests {
         assert!(tokens.is_err());
     }
 }
+
+#[cfg(test)]
+mod error_recovery_tests {
+    use super::*;
+
+    #[test]
+    fn should_report_every_error() {
+        let input = String::from("val a = 0x\nval b = \"hello\nval c = 0b");
+        let errors = get_tokens(&input).unwrap_err();
+
+        assert_eq!(3, errors.len());
+        assert_eq!(10, errors[0].error_offset);
+        assert_eq!(25, errors[1].error_offset);
+        assert_eq!(34, errors[2].error_offset);
+    }
+
+    #[test]
+    fn should_continue_after_invalid_char() {
+        let input = String::from("val a = `\nval b = `");
+        let errors = get_tokens(&input).unwrap_err();
+
+        assert_eq!(2, errors.len());
+        assert_eq!(8, errors[0].error_offset);
+        assert_eq!(18, errors[1].error_offset);
+    }
+}
@@ -7,6 +7,7 @@ fn str_is_keyword(s: &String) -> Option<TokenType> {
         "val" => Some(TokenType::VAL),
         "var" => Some(TokenType::VAR),
         "fun" => Some(TokenType::FUN),
+        "fn" => Some(TokenType::FN),
         "if" => Some(TokenType::IF),
         "else" => Some(TokenType::ELSE),
         "for" => Some(TokenType::FOR),
@@ -14,6 +15,10 @@ fn str_is_keyword(s: &String) -> Option<TokenType> {
         "while" => Some(TokenType::WHILE),
         "match" => Some(TokenType::MATCH),
         "case" => Some(TokenType::CASE),
+        "return" => Some(TokenType::RETURN),
+        "class" => Some(TokenType::CLASS),
+        "enum" => Some(TokenType::ENUM),
+        "interface" => Some(TokenType::INTERFACE),
         _ => None,
     }
 }
@@ -170,4 +175,64 @@ mod tests {
             panic!()
         }
     }
+
+    #[test]
+    fn should_scan_return_keyword() {
+        let input = str_to_vec("return");
+        if let LexResult::Some(token, next) = scan(*input.get(0).unwrap(), &input, 0) {
+            assert_eq!(6, next);
+            assert_eq!(TokenType::RETURN, token.token_type);
+            assert_eq!("return", token.value);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn should_scan_class_keyword() {
+        let input = str_to_vec("class");
+        if let LexResult::Some(token, next) = scan(*input.get(0).unwrap(), &input, 0) {
+            assert_eq!(5, next);
+            assert_eq!(TokenType::CLASS, token.token_type);
+            assert_eq!("class", token.value);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn should_scan_enum_keyword() {
+        let input = str_to_vec("enum");
+        if let LexResult::Some(token, next) = scan(*input.get(0).unwrap(), &input, 0) {
+            assert_eq!(4, next);
+            assert_eq!(TokenType::ENUM, token.token_type);
+            assert_eq!("enum", token.value);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn should_scan_interface_keyword() {
+        let input = str_to_vec("interface");
+        if let LexResult::Some(token, next) = scan(*input.get(0).unwrap(), &input, 0) {
+            assert_eq!(9, next);
+            assert_eq!(TokenType::INTERFACE, token.token_type);
+            assert_eq!("interface", token.value);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn should_scan_fn_keyword() {
+        let input = str_to_vec("fn");
+        if let LexResult::Some(token, next) = scan(*input.get(0).unwrap(), &input, 0) {
+            assert_eq!(2, next);
+            assert_eq!(TokenType::FN, token.token_type);
+            assert_eq!("fn", token.value);
+        } else {
+            panic!()
+        }
+    }
 }
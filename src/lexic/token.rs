@@ -31,6 +31,11 @@ pub enum TokenType {
     WHILE,
     MATCH,
     CASE,
+    RETURN,
+    CLASS,
+    ENUM,
+    INTERFACE,
+    FN,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
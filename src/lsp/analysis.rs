@@ -0,0 +1,354 @@
+//! Runs the compiler stages on a document, and converts
+//! their results to LSP values
+
+use serde_json::{json, Value};
+
+use crate::{
+    error_handling::MistiError,
+    lexic,
+    semantic::{self, symbol_table::SymbolTable},
+    syntax,
+};
+
+/// Runs the lexer, parser & semantic analysis on `text`.
+///
+/// Returns the errors found, and the symbol table with the
+/// types of the identifiers. If the code cannot be tokenized
+/// or parsed, the symbol table is empty.
+pub fn analyze(text: &String) -> (Vec<MistiError>, SymbolTable) {
+    let mut symbol_table = SymbolTable::new();
+    semantic::std::populate(&mut symbol_table);
+
+    let tokens = match lexic::get_tokens(text) {
+        Ok(tokens) => tokens,
+        Err(errors) => return (errors, symbol_table),
+    };
+
+    let ast = match syntax::build_ast(&tokens) {
+        Ok(ast) => ast,
+        Err(errors) => return (errors, symbol_table),
+    };
+
+    let errors = match semantic::check_semantics_with(&ast, &mut symbol_table) {
+        Ok(_) => vec![],
+        Err(errors) => errors,
+    };
+
+    (errors, symbol_table)
+}
+
+/// Converts the errors of the compiler to LSP diagnostics
+pub fn diagnostics(text: &String, errors: &[MistiError]) -> Vec<Value> {
+    errors
+        .iter()
+        .map(|error| {
+            let (start, end) = match error.labels.first() {
+                Some(label) => (label.start, label.end),
+                None => (error.error_offset, error.error_offset + 1),
+            };
+
+            let mut messages: Vec<String> =
+                error.labels.iter().map(|l| l.message.clone()).collect();
+            if let Some(help) = &error.help {
+                messages.push(format!("help: {}", help));
+            }
+            if let Some(note) = &error.note {
+                messages.push(format!("note: {}", note));
+            }
+
+            json!({
+                "range": range(text, start, end),
+                // 1 is error
+                "severity": 1,
+                "code": error.error_code,
+                "source": "thp",
+                "message": messages.join("\n"),
+            })
+        })
+        .collect()
+}
+
+/// Returns the hover for the identifier at `offset`, if it has a type
+pub fn hover(text: &String, offset: usize) -> Option<Value> {
+    let (_, symbol_table) = analyze(text);
+    let symbol = symbol_table.symbol_at(offset)?;
+    let (start, end) = symbol.span_at(offset)?;
+
+    let signature = match symbol.datatype {
+        semantic::types::Type::Function(_, _) | semantic::types::Type::Polymorphic(_, _) => {
+            format!("fun {}{}", symbol.name, symbol.datatype)
+        }
+        _ => format!("{} {}", symbol.datatype, symbol.name),
+    };
+
+    Some(json!({
+        "contents": {
+            "kind": "markdown",
+            "value": format!("```thp\n{}\n```", signature),
+        },
+        "range": range(text, start, end),
+    }))
+}
+
+/// Returns the location where the symbol at `offset` is declared.
+/// Symbols of the stdlib don't have a location
+pub fn definition(text: &String, uri: &str, offset: usize) -> Option<Value> {
+    let (_, symbol_table) = analyze(text);
+    let (start, end) = symbol_table.symbol_at(offset)?.declaration?;
+
+    Some(location(text, uri, start, end))
+}
+
+/// Returns the locations where the symbol at `offset` is used,
+/// and optionally where it's declared
+pub fn references(
+    text: &String,
+    uri: &str,
+    offset: usize,
+    include_declaration: bool,
+) -> Vec<Value> {
+    let (_, symbol_table) = analyze(text);
+    let Some(symbol) = symbol_table.symbol_at(offset) else {
+        return vec![];
+    };
+
+    let declaration = match symbol.declaration {
+        Some(span) if include_declaration => Some(span),
+        _ => None,
+    };
+
+    declaration
+        .iter()
+        .chain(symbol.references.iter())
+        .map(|(start, end)| location(text, uri, *start, *end))
+        .collect()
+}
+
+fn location(text: &String, uri: &str, start: usize, end: usize) -> Value {
+    json!({
+        "uri": uri,
+        "range": range(text, start, end),
+    })
+}
+
+/// Converts a range of char offsets to an LSP range
+fn range(text: &String, start: usize, end: usize) -> Value {
+    let (start_line, start_character) = offset_to_position(text, start);
+    let (end_line, end_character) = offset_to_position(text, end);
+
+    json!({
+        "start": { "line": start_line, "character": start_character },
+        "end": { "line": end_line, "character": end_character },
+    })
+}
+
+/// Converts a char offset to a line & character, both starting at 0.
+///
+/// LSP counts characters in UTF-16 code units.
+pub fn offset_to_position(text: &String, offset: usize) -> (usize, usize) {
+    let mut line = 0;
+    let mut character = 0;
+    for c in text.chars().take(offset) {
+        if c == '\n' {
+            line += 1;
+            character = 0;
+        } else {
+            character += c.len_utf16();
+        }
+    }
+
+    (line, character)
+}
+
+/// Converts a line & character to a char offset.
+/// Positions past the end of a line point to the end of the line.
+pub fn position_to_offset(text: &String, line: usize, character: usize) -> usize {
+    let mut current_line = 0;
+    let mut current_character = 0;
+    for (offset, c) in text.chars().enumerate() {
+        if current_line == line && (current_character >= character || c == '\n') {
+            return offset;
+        }
+
+        if c == '\n' {
+            current_line += 1;
+            current_character = 0;
+        } else {
+            current_character += c.len_utf16();
+        }
+    }
+
+    text.chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_offsets_to_positions() {
+        let text = String::from("val a = 1\nval ñ = \"😀\"\n");
+
+        assert_eq!((0, 0), offset_to_position(&text, 0));
+        assert_eq!((0, 4), offset_to_position(&text, 4));
+        assert_eq!((1, 0), offset_to_position(&text, 10));
+        assert_eq!((1, 4), offset_to_position(&text, 14));
+        // The emoji takes 2 UTF-16 code units
+        assert_eq!((1, 11), offset_to_position(&text, 20));
+    }
+
+    #[test]
+    fn should_convert_positions_to_offsets() {
+        let text = String::from("val a = 1\nval ñ = \"😀\"\n");
+
+        assert_eq!(0, position_to_offset(&text, 0, 0));
+        assert_eq!(14, position_to_offset(&text, 1, 4));
+        assert_eq!(20, position_to_offset(&text, 1, 11));
+        // Past the end of the line
+        assert_eq!(9, position_to_offset(&text, 0, 50));
+        // Past the end of the file
+        assert_eq!(22, position_to_offset(&text, 5, 0));
+    }
+
+    #[test]
+    fn should_report_diagnostics_of_every_stage() {
+        let text = String::from("val x = \"unclosed");
+        let (errors, _) = analyze(&text);
+        assert_eq!(1, diagnostics(&text, &errors).len());
+
+        let text = String::from("val x\nval y");
+        let (errors, _) = analyze(&text);
+        assert_eq!(2, diagnostics(&text, &errors).len());
+
+        let text = String::from("val x = y\nval z = w");
+        let (errors, _) = analyze(&text);
+        let diagnostics = diagnostics(&text, &errors);
+        assert_eq!(2, diagnostics.len());
+        assert_eq!(
+            json!({
+                "start": { "line": 1, "character": 8 },
+                "end": { "line": 1, "character": 9 },
+            }),
+            diagnostics[1]["range"]
+        );
+    }
+
+    #[test]
+    fn should_not_report_diagnostics_of_valid_code() {
+        let text = String::from("val x = 10\nprint(\"hello\")");
+        let (errors, _) = analyze(&text);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn should_hover_variables() {
+        let text = String::from("val name = \"John\"\nfor n in [1, 2] {\n    print(name)\n}");
+
+        let hover_value = |offset| hover(&text, offset).unwrap()["contents"]["value"].clone();
+        assert_eq!(json!("```thp\nString name\n```"), hover_value(5));
+        // Loop variable
+        assert_eq!(json!("```thp\nInt n\n```"), hover_value(22));
+        // Usage of a variable inside a block
+        assert_eq!(json!("```thp\nString name\n```"), hover_value(46));
+    }
+
+    #[test]
+    fn should_hover_functions() {
+        let text = String::from("fun greet() {}\nprint(\"hi\")");
+
+        let hover_value = |offset| hover(&text, offset).unwrap()["contents"]["value"].clone();
+        assert_eq!(json!("```thp\nfun greet() -> Unit\n```"), hover_value(5));
+        assert_eq!(
+            json!("```thp\nfun print(String) -> Void\n```"),
+            hover_value(16)
+        );
+    }
+
+    #[test]
+    fn should_hover_function_signatures() {
+        let text = String::from("fun add(Int a, Int b) -> Int {\n    a + b\n}");
+
+        let hover_value = |offset| hover(&text, offset).unwrap()["contents"]["value"].clone();
+        assert_eq!(
+            json!("```thp\nfun add(Int, Int) -> Int\n```"),
+            hover_value(4)
+        );
+    }
+
+    #[test]
+    fn should_hover_generic_functions() {
+        let text = String::from("fun first[T](Array[T] items) -> T {\n    items[0]\n}");
+
+        let hover_value = |offset| hover(&text, offset).unwrap()["contents"]["value"].clone();
+        assert_eq!(
+            json!("```thp\nfun first[T](Array[T]) -> T\n```"),
+            hover_value(4)
+        );
+        assert_eq!(json!("```thp\nArray[T] items\n```"), hover_value(22));
+    }
+
+    #[test]
+    fn should_hover_parameters() {
+        let text = String::from("fun f(Int count) {\n    count\n}");
+
+        let hover_value = |offset| hover(&text, offset).unwrap()["contents"]["value"].clone();
+        assert_eq!(json!("```thp\nInt count\n```"), hover_value(10));
+        assert_eq!(json!("```thp\nInt count\n```"), hover_value(25));
+    }
+
+    #[test]
+    fn should_find_definitions() {
+        let text = String::from("val x = 10\nval y = x\nprint(\"a\")");
+
+        let expected = json!({
+            "uri": "file:///a.thp",
+            "range": {
+                "start": { "line": 0, "character": 4 },
+                "end": { "line": 0, "character": 5 },
+            },
+        });
+        assert_eq!(
+            Some(expected.clone()),
+            definition(&text, "file:///a.thp", 19)
+        );
+        assert_eq!(Some(expected), definition(&text, "file:///a.thp", 4));
+        // Symbols of the stdlib
+        assert_eq!(None, definition(&text, "file:///a.thp", 21));
+    }
+
+    #[test]
+    fn should_find_references() {
+        let text = String::from("var x = 10\nx = x\nprint(\"a\")");
+
+        let lines = |locations: Vec<Value>| -> Vec<Value> {
+            locations
+                .iter()
+                .map(|l| l["range"]["start"].clone())
+                .collect()
+        };
+
+        assert_eq!(
+            vec![
+                json!({ "line": 1, "character": 0 }),
+                json!({ "line": 1, "character": 4 }),
+            ],
+            lines(references(&text, "file:///a.thp", 4, false))
+        );
+        assert_eq!(
+            vec![
+                json!({ "line": 0, "character": 4 }),
+                json!({ "line": 1, "character": 0 }),
+                json!({ "line": 1, "character": 4 }),
+            ],
+            lines(references(&text, "file:///a.thp", 15, true))
+        );
+        assert!(references(&text, "file:///a.thp", 8, true).is_empty());
+    }
+
+    #[test]
+    fn should_not_hover_non_identifiers() {
+        let text = String::from("val x = 10");
+        assert_eq!(None, hover(&text, 0));
+        assert_eq!(None, hover(&text, 9));
+    }
+}
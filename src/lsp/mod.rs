@@ -0,0 +1,337 @@
+//! A language server that communicates through stdin & stdout.
+//!
+//! Supports publishing diagnostics when a document is opened
+//! or changed, hovering identifiers to see their type, and
+//! going to the definition & references of a symbol.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufReader};
+
+mod analysis;
+mod transport;
+
+/// JSON-RPC error code of a request with an unknown method
+const METHOD_NOT_FOUND: i32 = -32601;
+
+/// State of the language server
+pub struct Server {
+    /// Contents of the open documents, by their uri
+    documents: HashMap<String, String>,
+    /// Whether the client sent a `shutdown` request
+    shutdown_requested: bool,
+}
+
+impl Server {
+    pub fn new() -> Server {
+        Server {
+            documents: HashMap::new(),
+            shutdown_requested: false,
+        }
+    }
+
+    /// Handles a message from the client, and returns the messages
+    /// to send back: responses and notifications
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or("");
+        let params = &message["params"];
+
+        // Requests have an id, notifications don't
+        let Some(id) = message.get("id") else {
+            return self.handle_notification(method, params);
+        };
+
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    // The full document is sent on every change
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "referencesProvider": true,
+                },
+                "serverInfo": {
+                    "name": "thp",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Value::Null
+            }
+            "textDocument/hover" => self.hover(params),
+            "textDocument/definition" => self.definition(params),
+            "textDocument/references" => self.references(params),
+            _ => {
+                return vec![json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {
+                        "code": METHOD_NOT_FOUND,
+                        "message": format!("Method not found: {}", method),
+                    },
+                })]
+            }
+        };
+
+        vec![json!({ "jsonrpc": "2.0", "id": id, "result": result })]
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or("");
+                self.documents.insert(uri.clone(), text.to_string());
+                vec![self.publish_diagnostics(&uri)]
+            }
+            "textDocument/didChange" => {
+                // With full sync the last change has the whole document
+                let changes = params["contentChanges"].as_array();
+                let Some(text) = changes
+                    .and_then(|c| c.last())
+                    .and_then(|c| c["text"].as_str())
+                else {
+                    return vec![];
+                };
+
+                self.documents.insert(uri.clone(), text.to_string());
+                vec![self.publish_diagnostics(&uri)]
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                // Clear the diagnostics of the closed document
+                vec![diagnostics_notification(&uri, vec![])]
+            }
+            // Other notifications, like `initialized`, are ignored
+            _ => vec![],
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: &String) -> Value {
+        let text = &self.documents[uri];
+        let (errors, _) = analysis::analyze(text);
+        diagnostics_notification(uri, analysis::diagnostics(text, &errors))
+    }
+
+    /// Returns the text of the document and the offset of the position
+    /// of a `TextDocumentPositionParams`, if the document is open
+    fn document_position(&self, params: &Value) -> Option<(&String, usize)> {
+        let uri = params["textDocument"]["uri"].as_str()?;
+        let text = self.documents.get(uri)?;
+
+        let line = params["position"]["line"].as_u64().unwrap_or(0) as usize;
+        let character = params["position"]["character"].as_u64().unwrap_or(0) as usize;
+        let offset = analysis::position_to_offset(text, line, character);
+
+        Some((text, offset))
+    }
+
+    fn hover(&self, params: &Value) -> Value {
+        let Some((text, offset)) = self.document_position(params) else {
+            return Value::Null;
+        };
+
+        analysis::hover(text, offset).unwrap_or(Value::Null)
+    }
+
+    fn definition(&self, params: &Value) -> Value {
+        let Some((text, offset)) = self.document_position(params) else {
+            return Value::Null;
+        };
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+
+        analysis::definition(text, uri, offset).unwrap_or(Value::Null)
+    }
+
+    fn references(&self, params: &Value) -> Value {
+        let Some((text, offset)) = self.document_position(params) else {
+            return Value::Null;
+        };
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+        let include_declaration = params["context"]["includeDeclaration"]
+            .as_bool()
+            .unwrap_or(false);
+
+        Value::Array(analysis::references(text, uri, offset, include_declaration))
+    }
+}
+
+fn diagnostics_notification(uri: &String, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+            "uri": uri,
+            "diagnostics": diagnostics,
+        },
+    })
+}
+
+/// Runs the language server until the client sends the `exit` notification
+pub fn run() -> Result<(), String> {
+    let mut reader = BufReader::new(io::stdin().lock());
+    let mut writer = io::stdout().lock();
+    let mut server = Server::new();
+
+    while let Some(message) = transport::read_message(&mut reader)? {
+        if message["method"] == "exit" {
+            break;
+        }
+
+        for response in server.handle(&message) {
+            transport::write_message(&mut writer, &response)?;
+        }
+    }
+
+    if server.shutdown_requested {
+        Ok(())
+    } else {
+        Err(String::from("The client exited without a shutdown request"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(server: &mut Server, text: &str) -> Vec<Value> {
+        server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": {
+                    "uri": "file:///main.thp",
+                    "languageId": "thp",
+                    "version": 1,
+                    "text": text,
+                },
+            },
+        }))
+    }
+
+    #[test]
+    fn should_respond_to_initialize() {
+        let mut server = Server::new();
+        let responses = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {},
+        }));
+
+        assert_eq!(1, responses.len());
+        assert_eq!(json!(1), responses[0]["id"]);
+        assert_eq!(
+            json!(true),
+            responses[0]["result"]["capabilities"]["hoverProvider"]
+        );
+    }
+
+    #[test]
+    fn should_publish_diagnostics_on_open_and_change() {
+        let mut server = Server::new();
+
+        let messages = open(&mut server, "val x = y");
+        assert_eq!(1, messages.len());
+        assert_eq!(
+            json!("textDocument/publishDiagnostics"),
+            messages[0]["method"]
+        );
+        assert_eq!(
+            1,
+            messages[0]["params"]["diagnostics"]
+                .as_array()
+                .unwrap()
+                .len()
+        );
+
+        let messages = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": "file:///main.thp", "version": 2 },
+                "contentChanges": [{ "text": "val x = 10" }],
+            },
+        }));
+        assert_eq!(1, messages.len());
+        assert_eq!(json!([]), messages[0]["params"]["diagnostics"]);
+    }
+
+    #[test]
+    fn should_respond_to_hover() {
+        let mut server = Server::new();
+        open(&mut server, "val count = 10\nprint(count)");
+
+        let hover_at = |server: &mut Server, line, character| {
+            server.handle(&json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/hover",
+                "params": {
+                    "textDocument": { "uri": "file:///main.thp" },
+                    "position": { "line": line, "character": character },
+                },
+            }))
+        };
+
+        let responses = hover_at(&mut server, 0, 6);
+        assert_eq!(
+            json!("```thp\nInt count\n```"),
+            responses[0]["result"]["contents"]["value"]
+        );
+
+        // Hovering whitespace
+        let responses = hover_at(&mut server, 0, 11);
+        assert_eq!(Value::Null, responses[0]["result"]);
+    }
+
+    #[test]
+    fn should_respond_to_definition_and_references() {
+        let mut server = Server::new();
+        open(&mut server, "val count = 10\nprint(count)");
+
+        let responses = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "textDocument/definition",
+            "params": {
+                "textDocument": { "uri": "file:///main.thp" },
+                "position": { "line": 1, "character": 8 },
+            },
+        }));
+        assert_eq!(
+            json!({ "line": 0, "character": 4 }),
+            responses[0]["result"]["range"]["start"]
+        );
+
+        let responses = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 5,
+            "method": "textDocument/references",
+            "params": {
+                "textDocument": { "uri": "file:///main.thp" },
+                "position": { "line": 0, "character": 4 },
+                "context": { "includeDeclaration": true },
+            },
+        }));
+        assert_eq!(2, responses[0]["result"].as_array().unwrap().len());
+    }
+
+    #[test]
+    fn should_respond_with_error_to_unknown_requests() {
+        let mut server = Server::new();
+        let responses = server.handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "textDocument/formatting",
+            "params": {},
+        }));
+
+        assert_eq!(json!(METHOD_NOT_FOUND), responses[0]["error"]["code"]);
+    }
+}
@@ -0,0 +1,101 @@
+//! Reads and writes JSON-RPC messages with the headers
+//! used by the Language Server Protocol
+
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+/// Reads the next message. Returns `Ok(None)` when the input ends.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, String> {
+    let mut content_length = None;
+
+    // Headers end with an empty line
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) => return Ok(None),
+            Ok(_) => {}
+            Err(error) => return Err(error.to_string()),
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        // Other headers (Content-Type) are ignored
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            match value.trim().parse::<usize>() {
+                Ok(length) => content_length = Some(length),
+                Err(_) => return Err(format!("Invalid Content-Length header: {}", line)),
+            }
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        return Err(String::from("Missing Content-Length header"));
+    };
+
+    let mut content = vec![0; content_length];
+    if let Err(error) = reader.read_exact(&mut content) {
+        return Err(error.to_string());
+    }
+
+    match serde_json::from_slice(&content) {
+        Ok(message) => Ok(Some(message)),
+        Err(error) => Err(format!("Invalid JSON message: {}", error)),
+    }
+}
+
+/// Writes `message` with its headers
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), String> {
+    let content = message.to_string();
+    let result = write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )
+    .and_then(|_| writer.flush());
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Cursor;
+
+    #[test]
+    fn should_read_messages() {
+        let input = "Content-Length: 17\r\n\r\n{\"method\":\"exit\"}Content-Length: 2\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{}";
+        let mut reader = Cursor::new(input);
+
+        assert_eq!(
+            Some(json!({"method": "exit"})),
+            read_message(&mut reader).unwrap()
+        );
+        assert_eq!(Some(json!({})), read_message(&mut reader).unwrap());
+        assert_eq!(None, read_message(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn should_fail_without_content_length() {
+        let mut reader = Cursor::new("Content-Type: text\r\n\r\n{}");
+        assert!(read_message(&mut reader).is_err());
+    }
+
+    #[test]
+    fn should_write_messages() {
+        let mut output = Vec::new();
+        write_message(&mut output, &json!({"id": 1})).unwrap();
+
+        assert_eq!(
+            "Content-Length: 8\r\n\r\n{\"id\":1}",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
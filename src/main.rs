@@ -15,6 +15,12 @@ mod semantic;
 mod php_ast;
 // Transforms an AST to JS
 mod codegen;
+// Formats THP code
+mod formatter;
+// Handles the project manifest
+mod project;
+// Language server
+mod lsp;
 
 mod error_handling;
 
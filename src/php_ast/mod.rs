@@ -5,6 +5,10 @@
 /// THP
 pub mod transformers;
 
+/// The type hint of a parameter, a property or a return value, e.g. `?int`.
+/// None if the THP type has no PHP equivalent, then it's emitted without a hint
+pub type PTypeHint = Option<String>;
+
 /// A single PHP source code file
 pub struct PFile<'a> {
     pub statements: Vec<PStatement<'a>>,
@@ -13,6 +17,17 @@ pub struct PFile<'a> {
 /// A PHP statement
 pub enum PStatement<'a> {
     ExpressionStatement(PExpressionStatement<'a>),
+    FunctionDefinition(PFunctionDefinition<'a>),
+    ClassDefinition(PClassDefinition<'a>),
+    EnumDefinition(PEnumDefinition<'a>),
+    InterfaceDefinition(PInterfaceDefinition<'a>),
+    If(PIf<'a>),
+    ForEach(PForEach<'a>),
+    While(PWhile<'a>),
+    /// A return statement, with an optional value
+    Return(Option<PExpresssion<'a>>),
+    /// Makes global variables visible inside a function, e.g. `global $rate;`
+    Global(Vec<String>),
 }
 
 /// A statement composed of a single expression,
@@ -26,13 +41,282 @@ pub enum PStatement<'a> {
 /// ```
 pub type PExpressionStatement<'a> = PExpresssion<'a>;
 
+/// A function definition
+///
+/// ## Examples
+///
+/// ```php
+/// function add(int $a, int $b): int
+/// {
+///     return $a + $b;
+/// }
+/// ```
+pub struct PFunctionDefinition<'a> {
+    pub name: &'a String,
+    pub parameters: Vec<PParameter<'a>>,
+    pub return_type: PTypeHint,
+    pub body: Vec<PStatement<'a>>,
+}
+
+/// A parameter of a function definition
+pub struct PParameter<'a> {
+    pub name: &'a str,
+    pub datatype: PTypeHint,
+}
+
+/// A class definition. Its members are public
+///
+/// ## Examples
+///
+/// ```php
+/// class Point
+/// {
+///     public int $moves;
+///
+///     public function __construct(public readonly int $x, public int $y)
+///     {
+///         $this->moves = 0;
+///     }
+///
+///     public function sum(): int
+///     {
+///         return $this->x + $this->y;
+///     }
+/// }
+/// ```
+pub struct PClassDefinition<'a> {
+    pub name: String,
+    /// `abstract` or `final`
+    pub modifier: Option<&'a str>,
+    /// The class this class extends
+    pub parent: Option<&'a String>,
+    /// The interfaces this class implements
+    pub interfaces: Vec<&'a String>,
+    /// The properties declared in the body of the class
+    pub properties: Vec<PProperty<'a>>,
+    /// None if there are no parameters nor properties to initialize
+    pub constructor: Option<PConstructor<'a>>,
+    pub methods: Vec<PFunctionDefinition<'a>>,
+}
+
+/// The definition of a THP enum
+pub enum PEnumDefinition<'a> {
+    /// An enum without payloads is a backed enum: its name
+    /// and its cases, whose values are their names
+    ///
+    /// ```php
+    /// enum Suit: string
+    /// {
+    ///     case Hearts = "Hearts";
+    /// }
+    /// ```
+    Backed(&'a String, Vec<&'a String>),
+    /// An enum with payloads is an abstract class, and a final class that
+    /// extends it for each variant, with the payload as its properties
+    ///
+    /// ```php
+    /// abstract class Shape
+    /// {
+    /// }
+    /// final class Shape_Circle extends Shape
+    /// {
+    ///     public function __construct(public readonly float $radius)
+    ///     {
+    ///     }
+    /// }
+    /// ```
+    Sealed(PClassDefinition<'a>, Vec<PClassDefinition<'a>>),
+}
+
+/// An interface definition, the signatures of its methods
+///
+/// ## Examples
+///
+/// ```php
+/// interface Shape
+/// {
+///     public function area(): float;
+/// }
+/// ```
+pub struct PInterfaceDefinition<'a> {
+    pub name: &'a String,
+    pub methods: Vec<PMethodSignature<'a>>,
+}
+
+/// A method of an interface, without a body
+pub struct PMethodSignature<'a> {
+    pub name: &'a String,
+    pub parameters: Vec<PParameter<'a>>,
+    pub return_type: PTypeHint,
+}
+
+/// A property of a class
+pub struct PProperty<'a> {
+    pub name: &'a String,
+    pub datatype: PTypeHint,
+    /// Readonly properties can only be initialized once, in the constructor
+    pub is_readonly: bool,
+}
+
+/// The `__construct` method of a class
+pub struct PConstructor<'a> {
+    pub parameters: Vec<PConstructorParameter<'a>>,
+    /// Initializes the properties declared in the body of the class
+    pub body: Vec<PStatement<'a>>,
+}
+
+pub enum PConstructorParameter<'a> {
+    Parameter(PParameter<'a>),
+    /// A parameter that also declares a property, e.g. `public int $x`
+    Promoted(PProperty<'a>),
+}
+
+/// An if statement, with its elseif and else clauses
+///
+/// ## Examples
+///
+/// ```php
+/// if ($a) {
+///     // ...
+/// } elseif ($b) {
+///     // ...
+/// } else {
+///     // ...
+/// }
+/// ```
+pub struct PIf<'a> {
+    pub condition: PExpresssion<'a>,
+    pub body: Vec<PStatement<'a>>,
+    pub else_ifs: Vec<PElseIf<'a>>,
+    pub else_body: Option<Vec<PStatement<'a>>>,
+}
+
+pub struct PElseIf<'a> {
+    pub condition: PExpresssion<'a>,
+    pub body: Vec<PStatement<'a>>,
+}
+
+/// A foreach loop
+///
+/// ## Examples
+///
+/// ```php
+/// foreach ($collection as $key => $value) {
+///     // ...
+/// }
+/// ```
+pub struct PForEach<'a> {
+    pub collection: PExpresssion<'a>,
+    pub key: Option<&'a String>,
+    pub value: &'a String,
+    pub body: Vec<PStatement<'a>>,
+}
+
+/// A while loop
+pub struct PWhile<'a> {
+    pub condition: PExpresssion<'a>,
+    pub body: Vec<PStatement<'a>>,
+}
+
 /// A generic PHP expression
 pub enum PExpresssion<'a> {
     FunctionCall(PFunctionCall<'a>),
     Primary(PPrimary<'a>),
     /// This comes from a THP binding
     Assignment(PSimpleAssignment<'a>),
-    BinaryOp(Box<PExpresssion<'a>>, Box<PExpresssion<'a>>, &'a String),
+    /// This comes from a THP assignment
+    Reassignment(PAssignment<'a>),
+    /// The left & right operands, and a PHP operator, e.g. `$a . $b`
+    BinaryOp(Box<PExpresssion<'a>>, Box<PExpresssion<'a>>, &'a str),
+    /// A prefix operator and its operand, e.g. `!$a` or `-$a`
+    UnaryOp(&'a String, Box<PExpresssion<'a>>),
+    /// A type and the value converted to it, e.g. `(float) $a`
+    Cast(&'a str, Box<PExpresssion<'a>>),
+    /// An array creation expression, e.g. `[1, 2, 3]`
+    Array(Vec<PExpresssion<'a>>),
+    /// A subscript expression: the array and the index, e.g. `$a[0]`
+    ArrayAccess(Box<PExpresssion<'a>>, Box<PExpresssion<'a>>),
+    /// The object, the property and the access operator (`->` or `?->`),
+    /// e.g. `$user?->name`
+    PropertyAccess(Box<PExpresssion<'a>>, &'a String, &'a str),
+    /// The object, the method & its arguments, and the access
+    /// operator (`->` or `?->`), e.g. `$user->greet("hi")`
+    MethodCall(Box<PExpresssion<'a>>, PFunctionCall<'a>, &'a str),
+    /// The creation of an instance of a class: the class
+    /// and the arguments, e.g. `new Point(1, 2)`
+    New(String, Vec<PExpresssion<'a>>),
+    /// The value and the class, e.g. `$value instanceof Person`
+    InstanceOf(Box<PExpresssion<'a>>, String),
+    /// The class and the constant, e.g. the enum case `Suit::Hearts`
+    ClassConstant(&'a String, &'a String),
+    Match(PMatch<'a>),
+    /// A call to a function stored in a value: the function and the
+    /// arguments, e.g. `$callback(1)` or `(fn($x) => $x)(1)`
+    Call(Box<PExpresssion<'a>>, Vec<PExpresssion<'a>>),
+    /// A named function used as a value, with the
+    /// first-class callable syntax, e.g. `add(...)`
+    FunctionReference(&'a String),
+    ArrowFunction(PArrowFunction<'a>),
+    Closure(PClosure<'a>),
+}
+
+/// An anonymous function that returns a single expression.
+/// It captures the variables it uses by value on its own
+///
+/// ## Examples
+///
+/// ```php
+/// fn(int $x): int => $x + $offset
+/// ```
+pub struct PArrowFunction<'a> {
+    pub parameters: Vec<PParameter<'a>>,
+    pub return_type: PTypeHint,
+    pub value: Box<PExpresssion<'a>>,
+}
+
+/// An anonymous function with a body, and the variables it captures
+///
+/// ## Examples
+///
+/// ```php
+/// function (int $x) use ($offset, &$total): void {
+///     $total += $x + $offset;
+/// }
+/// ```
+pub struct PClosure<'a> {
+    pub parameters: Vec<PParameter<'a>>,
+    pub uses: Vec<PClosureUse>,
+    pub return_type: PTypeHint,
+    pub body: Vec<PStatement<'a>>,
+}
+
+/// A variable captured by a closure
+pub struct PClosureUse {
+    pub name: String,
+    /// Captured with `&`, so that changes are seen outside the closure
+    pub by_reference: bool,
+}
+
+/// A match expression
+///
+/// ## Examples
+///
+/// ```php
+/// match ($value) {
+///     1, 2 => "small",
+///     default => "big",
+/// }
+/// ```
+pub struct PMatch<'a> {
+    pub subject: Box<PExpresssion<'a>>,
+    pub arms: Vec<PMatchArm<'a>>,
+}
+
+pub struct PMatchArm<'a> {
+    /// The values compared to the subject.
+    /// Empty for the `default` arm
+    pub conditions: Vec<PExpresssion<'a>>,
+    pub value: PExpresssion<'a>,
 }
 
 pub struct PSimpleAssignment<'a> {
@@ -40,11 +324,19 @@ pub struct PSimpleAssignment<'a> {
     pub assignment: Box<PExpresssion<'a>>,
 }
 
-/// A function call as an expression
+/// An assignment to a variable or a property, with `=` or
+/// a compound operator, e.g. `$this->count += 1`
+pub struct PAssignment<'a> {
+    /// A variable or a property access
+    pub target: Box<PExpresssion<'a>>,
+    pub operator: &'a str,
+    pub value: Box<PExpresssion<'a>>,
+}
+
+/// A call to a named function or a method
 pub struct PFunctionCall<'a> {
-    /// Arbitrary expressions that compute into
-    /// a function not supported
-    pub function_name: &'a String,
+    /// Functions stored in values are called with `PExpresssion::Call`
+    pub function_name: &'a str,
     pub arguments: Vec<PExpresssion<'a>>,
 }
 
@@ -54,12 +346,15 @@ pub enum PPrimary<'a> {
     FloatingLiteral(&'a String),
     StringLiteral(&'a String),
     BoolLiteral(bool),
+    NullLiteral,
     /// https://phplang.org/spec/19-grammar.html#grammar-variable
     ///
     /// Supports only variable -> callable-variable -> simple-variable -> variable-name
     ///
     /// This is a $variable
-    Variable(&'a String),
+    Variable(&'a str),
+    /// `$this`, the instance of a class inside its methods
+    This,
     // This is a symbol, e.g. a function name
     // Symbol(&'a String),
 }
@@ -0,0 +1,21 @@
+use crate::{
+    php_ast::PStatement,
+    syntax::ast::{Block, BlockMember},
+};
+
+use super::PHPTransformable;
+
+/// Transforms a THP block into the list of PHP statements of its body
+impl<'a> PHPTransformable<'a> for Block<'_> {
+    type Item = Vec<PStatement<'a>>;
+
+    fn into_php_ast(&'a self) -> Vec<PStatement<'a>> {
+        self.members
+            .iter()
+            .map(|member| match member {
+                BlockMember::Stmt(stmt) => stmt.into_php_ast(),
+                BlockMember::Expr(expr) => PStatement::ExpressionStatement(expr.into_php_ast()),
+            })
+            .collect()
+    }
+}
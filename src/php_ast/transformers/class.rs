@@ -0,0 +1,150 @@
+use crate::{
+    lexic::token::TokenType,
+    php_ast::{
+        PAssignment, PClassDefinition, PConstructor, PConstructorParameter, PExpresssion,
+        PParameter, PPrimary, PProperty, PStatement,
+    },
+    syntax::ast::class::{ClassDeclaration, ClassMember},
+};
+
+use super::{types::datatype_hint, PHPTransformable};
+
+impl<'a> PHPTransformable<'a> for ClassDeclaration<'a> {
+    type Item = PClassDefinition<'a>;
+
+    fn into_php_ast(&'a self) -> Self::Item {
+        let mut properties = Vec::new();
+        let mut initializers = Vec::new();
+        let mut methods = Vec::new();
+
+        for member in self.members() {
+            match member {
+                ClassMember::Field(field) => {
+                    properties.push(PProperty {
+                        name: &field.identifier.value,
+                        datatype: field.datatype.as_ref().and_then(datatype_hint),
+                        is_readonly: !field.is_mutable,
+                    });
+
+                    // Fields are initialized in the constructor, since
+                    // their values may use the parameters of the class
+                    let property = PExpresssion::PropertyAccess(
+                        Box::new(PExpresssion::Primary(PPrimary::This)),
+                        &field.identifier.value,
+                        "->",
+                    );
+                    initializers.push(PStatement::ExpressionStatement(PExpresssion::Reassignment(
+                        PAssignment {
+                            target: Box::new(property),
+                            operator: "=",
+                            value: Box::new(field.expression.into_php_ast()),
+                        },
+                    )));
+                }
+                ClassMember::Method(method) => methods.push(method.into_php_ast()),
+            }
+        }
+
+        // The parameters with `val` or `var` are promoted to properties
+        let parameters: Vec<_> = match &self.constructor {
+            Some(constructor) => constructor
+                .parameters
+                .iter()
+                .map(|parameter| {
+                    let name = &parameter.identifier.value;
+                    let datatype = datatype_hint(&parameter.datatype);
+                    match parameter.modifier {
+                        Some(modifier) => PConstructorParameter::Promoted(PProperty {
+                            name,
+                            datatype,
+                            is_readonly: modifier.token_type == TokenType::VAL,
+                        }),
+                        None => PConstructorParameter::Parameter(PParameter { name, datatype }),
+                    }
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        let constructor = if parameters.is_empty() && initializers.is_empty() {
+            None
+        } else {
+            Some(PConstructor {
+                parameters,
+                body: initializers,
+            })
+        };
+
+        PClassDefinition {
+            name: self.identifier.value.clone(),
+            modifier: None,
+            parent: None,
+            interfaces: self.interfaces.iter().map(|i| &i.value).collect(),
+            properties,
+            constructor,
+            methods,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexic::get_tokens,
+        php_ast::{transformers::PHPTransformable, PConstructorParameter, PStatement},
+        syntax::{
+            ast::{ModuleMembers, Statement},
+            build_ast,
+        },
+    };
+
+    #[test]
+    fn should_transform_class() {
+        let tokens = get_tokens(&String::from(
+            "class Point(val Int x, var Int y, Int z) {\n    var Int moves = z\n    val label = \"p\"\n\n    fun sum() -> Int {\n        self.x + self.y\n    }\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::ClassDecl(class)) = &ast.productions[0] else {
+            panic!("Expected a class declaration")
+        };
+
+        let class = class.into_php_ast();
+        assert_eq!("Point", class.name);
+
+        assert_eq!(2, class.properties.len());
+        assert_eq!(Some(String::from("int")), class.properties[0].datatype);
+        assert!(!class.properties[0].is_readonly);
+        assert_eq!(None, class.properties[1].datatype);
+        assert!(class.properties[1].is_readonly);
+
+        let constructor = class.constructor.unwrap();
+        match constructor.parameters.as_slice() {
+            [PConstructorParameter::Promoted(x), PConstructorParameter::Promoted(y), PConstructorParameter::Parameter(z)] =>
+            {
+                assert!(x.is_readonly);
+                assert!(!y.is_readonly);
+                assert_eq!("z", z.name);
+            }
+            _ => panic!("Expected 2 promoted parameters and a parameter"),
+        }
+        assert_eq!(2, constructor.body.len());
+        assert!(matches!(
+            constructor.body[0],
+            PStatement::ExpressionStatement(_)
+        ));
+
+        assert_eq!(1, class.methods.len());
+    }
+
+    #[test]
+    fn should_not_generate_empty_constructors() {
+        let tokens = get_tokens(&String::from("class Empty")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::ClassDecl(class)) = &ast.productions[0] else {
+            panic!("Expected a class declaration")
+        };
+
+        assert!(class.into_php_ast().constructor.is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    php_ast::{PClassDefinition, PConstructor, PConstructorParameter, PEnumDefinition, PProperty},
+    syntax::ast::enum_declaration::EnumDeclaration,
+};
+
+use super::{types::datatype_hint, PHPTransformable};
+
+impl<'a> PHPTransformable<'a> for EnumDeclaration<'a> {
+    type Item = PEnumDefinition<'a>;
+
+    fn into_php_ast(&'a self) -> Self::Item {
+        let enum_name = &self.identifier.value;
+        if !self.has_payloads() {
+            let cases = self.variants.iter().map(|v| &v.identifier.value).collect();
+            return PEnumDefinition::Backed(enum_name, cases);
+        }
+
+        let parent = PClassDefinition {
+            name: enum_name.clone(),
+            modifier: Some("abstract"),
+            parent: None,
+            interfaces: vec![],
+            properties: vec![],
+            constructor: None,
+            methods: vec![],
+        };
+
+        // The fields of the variants are immutable
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let parameters: Vec<_> = variant
+                    .payload
+                    .iter()
+                    .flat_map(|payload| payload.parameters.iter())
+                    .map(|field| {
+                        PConstructorParameter::Promoted(PProperty {
+                            name: &field.identifier.value,
+                            datatype: datatype_hint(&field.datatype),
+                            is_readonly: true,
+                        })
+                    })
+                    .collect();
+                let constructor = match parameters.is_empty() {
+                    true => None,
+                    false => Some(PConstructor {
+                        parameters,
+                        body: vec![],
+                    }),
+                };
+
+                PClassDefinition {
+                    name: variant_class_name(enum_name, &variant.identifier.value),
+                    modifier: Some("final"),
+                    parent: Some(enum_name),
+                    interfaces: vec![],
+                    properties: vec![],
+                    constructor,
+                    methods: vec![],
+                }
+            })
+            .collect();
+
+        PEnumDefinition::Sealed(parent, variants)
+    }
+}
+
+/// Returns the name of the PHP class of a variant with payload, e.g. `Shape_Circle`
+pub fn variant_class_name(enum_name: &str, variant: &str) -> String {
+    format!("{}_{}", enum_name, variant)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexic::get_tokens,
+        php_ast::{transformers::PHPTransformable, PEnumDefinition},
+        syntax::{
+            ast::{ModuleMembers, Statement},
+            build_ast,
+        },
+    };
+
+    #[test]
+    fn should_transform_enum_without_payloads() {
+        let tokens = get_tokens(&String::from("enum Suit {\n    Hearts\n    Spades\n}")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::EnumDecl(enum_declaration)) = &ast.productions[0] else {
+            panic!("Expected an enum declaration")
+        };
+
+        match enum_declaration.into_php_ast() {
+            PEnumDefinition::Backed(name, cases) => {
+                assert_eq!("Suit", name);
+                assert_eq!(vec!["Hearts", "Spades"], cases);
+            }
+            _ => panic!("Expected a backed enum"),
+        }
+    }
+
+    #[test]
+    fn should_transform_enum_with_payloads() {
+        let tokens = get_tokens(&String::from(
+            "enum Shape {\n    Circle(Float radius)\n    Empty\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::EnumDecl(enum_declaration)) = &ast.productions[0] else {
+            panic!("Expected an enum declaration")
+        };
+
+        match enum_declaration.into_php_ast() {
+            PEnumDefinition::Sealed(parent, variants) => {
+                assert_eq!("Shape", parent.name);
+                assert_eq!(Some("abstract"), parent.modifier);
+
+                assert_eq!(2, variants.len());
+                assert_eq!("Shape_Circle", variants[0].name);
+                assert_eq!(Some("final"), variants[0].modifier);
+                assert_eq!("Shape", variants[0].parent.unwrap());
+                assert_eq!(
+                    1,
+                    variants[0].constructor.as_ref().unwrap().parameters.len()
+                );
+                assert!(variants[1].constructor.is_none());
+            }
+            _ => panic!("Expected a sealed enum"),
+        }
+    }
+}
@@ -1,11 +1,18 @@
 use crate::{
-    php_ast::{PExpresssion, PPrimary},
-    syntax::ast::Expression,
+    lexic::token::TokenType,
+    php_ast::{
+        PArrowFunction, PAssignment, PClosure, PClosureUse, PExpresssion, PFunctionCall, PMatch,
+        PMatchArm, PParameter, PPrimary, PStatement,
+    },
+    semantic::types::expression::is_member_access,
+    syntax::ast::{
+        functions::FunctionCall, match_expression::Match, Block, BlockMember, Expression, TypeTest,
+    },
 };
 
 // TODO: next rewrite the test to use the output of Transpilable?
 
-use super::PHPTransformable;
+use super::{enum_declaration::variant_class_name, PHPTransformable};
 
 /// Transforms a THP expression into a PHP expression
 impl<'a> PHPTransformable<'a> for Expression<'_> {
@@ -27,37 +34,408 @@ impl<'a> PHPTransformable<'a> for Expression<'_> {
                 PExpresssion::Primary(expr)
             }
             Expression::FunctionCall(f) => {
-                let fn_call_expr = f.into_php_ast();
-
-                PExpresssion::FunctionCall(fn_call_expr)
+                // Calling a class, or a variant with a payload, creates an instance
+                match &*f.function {
+                    Expression::Identifier(i) if i.token_type == TokenType::Datatype => {
+                        PExpresssion::New(i.value.clone(), arguments_into_php(f))
+                    }
+                    Expression::EnumVariant(enum_name, variant, _) => PExpresssion::New(
+                        variant_class_name(&enum_name.value, &variant.value),
+                        arguments_into_php(f),
+                    ),
+                    Expression::Identifier(i) if i.is_function.get() => {
+                        PExpresssion::FunctionCall(f.into_php_ast())
+                    }
+                    // Variables, lambdas and other values that are functions
+                    function => {
+                        PExpresssion::Call(Box::new(function.into_php_ast()), arguments_into_php(f))
+                    }
+                }
+            }
+            // The variants without a payload of enums with payloads are classes too
+            Expression::EnumVariant(enum_name, variant, is_class) if is_class.get() => {
+                PExpresssion::New(variant_class_name(&enum_name.value, &variant.value), vec![])
+            }
+            Expression::EnumVariant(enum_name, variant, _) => {
+                PExpresssion::ClassConstant(&enum_name.value, &variant.value)
+            }
+            // Inside methods the instance is `self`
+            Expression::Identifier(i) if i.value == "self" => PExpresssion::Primary(PPrimary::This),
+            Expression::Identifier(i) if i.is_function.get() => {
+                PExpresssion::FunctionReference(&i.token.value)
             }
-            Expression::Identifier(i) => PExpresssion::Primary(PPrimary::Variable(&i.value)),
+            Expression::Identifier(i) => PExpresssion::Primary(PPrimary::Variable(&i.token.value)),
             Expression::Boolean(b) => {
                 PExpresssion::Primary(PPrimary::BoolLiteral(b.value == "true"))
             }
-            Expression::UnaryOperator(_, _) => unimplemented!("transform unary op into php"),
-            Expression::BinaryOperator(left_expr, right_expr, op) => {
-                // For now assume that any THP operator directly maps to a PHP operator...
+            Expression::Null(_) => PExpresssion::Primary(PPrimary::NullLiteral),
+            Expression::UnaryOperator(op, expr) => {
+                PExpresssion::UnaryOp(&op.value, Box::new(expr.into_php_ast()))
+            }
+            Expression::BinaryOperator(left_expr, right_expr, op)
+                if is_member_access(&op.value) =>
+            {
+                let object = Box::new(left_expr.into_php_ast());
+                let php_op = match op.value.as_str() {
+                    "?." => "?->",
+                    _ => "->",
+                };
 
+                // The semantic check only allows identifiers and method calls as members
+                match &**right_expr {
+                    Expression::Identifier(property) => {
+                        PExpresssion::PropertyAccess(object, &property.value, php_op)
+                    }
+                    Expression::FunctionCall(method) => {
+                        PExpresssion::MethodCall(object, method.into_php_ast(), php_op)
+                    }
+                    _ => unreachable!("Compiler bug: member access to a non identifier"),
+                }
+            }
+            Expression::BinaryOperator(left_expr, right_expr, op) => {
                 let left_value = left_expr.into_php_ast();
                 let right_value = right_expr.into_php_ast();
 
-                PExpresssion::BinaryOp(Box::new(left_value), Box::new(right_value), &op.value)
+                // PHP loosely considers `0`, `""`, `false` and `[]` equal to
+                // null, so comparisons with null are strict
+                let is_null = |expression: &Expression| matches!(expression, Expression::Null(_));
+                let compares_null = is_null(left_expr) || is_null(right_expr);
+
+                // Besides concatenation, THP operators map directly to PHP operators
+                let php_op = match op.value.as_str() {
+                    "++" => ".",
+                    "==" if compares_null => "===",
+                    "!=" if compares_null => "!==",
+                    other => other,
+                };
+
+                PExpresssion::BinaryOp(Box::new(left_value), Box::new(right_value), php_op)
             }
-            Expression::Array(_) => unimplemented!("transform array into php"),
-            Expression::ArrayAcccess(_) => unimplemented!("transform array access into php"),
+            Expression::Array(array) => {
+                let elements = array.exps.iter().map(|element| match array.to_float.get() {
+                    true => float_into_php(element),
+                    false => element.into_php_ast(),
+                });
+                PExpresssion::Array(elements.collect())
+            }
+            Expression::ArrayAcccess(access) => PExpresssion::ArrayAccess(
+                Box::new(access.left_expr.into_php_ast()),
+                Box::new(access.idx_expr.into_php_ast()),
+            ),
+            Expression::TypeTest(type_test) => type_test_into_php(type_test),
+            Expression::Match(match_expression) => match_into_php(match_expression),
+            Expression::Lambda(lambda) => lambda.into_php_ast(),
+        }
+    }
+}
+
+/// Transforms an expression that is an Int or a Float into a PHP float.
+/// A Float literal doesn't need a conversion
+pub fn float_into_php<'a>(expression: &'a Expression<'_>) -> PExpresssion<'a> {
+    match expression {
+        Expression::Float(_) => expression.into_php_ast(),
+        _ => PExpresssion::Cast("float", Box::new(expression.into_php_ast())),
+    }
+}
+
+fn arguments_into_php<'a>(call: &'a FunctionCall<'_>) -> Vec<PExpresssion<'a>> {
+    call.arguments
+        .arguments
+        .iter()
+        .map(|a| a.into_php_ast())
+        .collect()
+}
+
+/// The parameter of the function that a type test or a match becomes
+/// to evaluate its subject once. It starts with an uppercase letter,
+/// which THP variables can't, so it never hides a variable of the user
+const SUBJECT: &str = "Subject";
+
+/// Transforms a type test into `is_*` function calls and `instanceof`.
+///
+/// A union is tested with a check for each of its members, joined with
+/// `||`. If the tested expression isn't a variable, the checks are the body
+/// of a function called with it, so that it's evaluated once:
+/// `(fn($Subject) => is_int($Subject) || is_string($Subject))(f())`
+fn type_test_into_php<'a>(type_test: &'a TypeTest<'_>) -> PExpresssion<'a> {
+    let checks = type_checks(type_test, || type_test.expression.into_php_ast());
+    // A single check isn't joined with `||`
+    let is_single_check = !matches!(checks, PExpresssion::BinaryOp(_, _, "||"));
+    if is_single_check || matches!(*type_test.expression, Expression::Identifier(_)) {
+        return checks;
+    }
+
+    let subject = || PExpresssion::Primary(PPrimary::Variable(SUBJECT));
+    let function = PExpresssion::ArrowFunction(PArrowFunction {
+        parameters: vec![PParameter {
+            name: SUBJECT,
+            datatype: None,
+        }],
+        return_type: None,
+        value: Box::new(type_checks(type_test, subject)),
+    });
+    PExpresssion::Call(
+        Box::new(function),
+        vec![type_test.expression.into_php_ast()],
+    )
+}
+
+/// Returns the checks of a type test joined with `||`,
+/// testing the value returned by `value`
+fn type_checks<'a>(
+    type_test: &'a TypeTest<'_>,
+    value: impl Fn() -> PExpresssion<'a>,
+) -> PExpresssion<'a> {
+    let mut datatypes = vec![&type_test.datatype];
+    datatypes.extend(type_test.datatype.alternatives.iter());
+
+    let mut checks: Vec<PExpresssion<'a>> = Vec::new();
+    let mut functions: Vec<&'a str> = Vec::new();
+    let mut add_function_check = |function: &'a str, checks: &mut Vec<PExpresssion<'a>>| {
+        if !functions.contains(&function) {
+            functions.push(function);
+            checks.push(PExpresssion::FunctionCall(PFunctionCall {
+                function_name: function,
+                arguments: vec![value()],
+            }));
+        }
+    };
+
+    for datatype in datatypes {
+        if datatype.nullable.is_some() {
+            add_function_check("is_null", &mut checks);
         }
+
+        let function = match datatype.name.value.as_str() {
+            "Int" => "is_int",
+            "Float" => "is_float",
+            "String" => "is_string",
+            "Bool" => "is_bool",
+            "Null" => "is_null",
+            "Array" => "is_array",
+            _ => {
+                checks.push(PExpresssion::InstanceOf(
+                    Box::new(value()),
+                    datatype.name.value.clone(),
+                ));
+                continue;
+            }
+        };
+        add_function_check(function, &mut checks);
+    }
+
+    let mut checks = checks.into_iter();
+    let first_check = checks.next().expect("A datatype has at least one member");
+    checks.fold(first_check, |left, right| {
+        PExpresssion::BinaryOp(Box::new(left), Box::new(right), "||")
+    })
+}
+
+/// Transforms a match into PHP's match.
+///
+/// PHP compares the subject to the conditions of each arm with `===`.
+/// Guards and variants of enums with payloads can't be written that way,
+/// so if there are any, the match is over `true` and each condition
+/// compares the subject explicitly.
+///
+/// Those variants are compared with `instanceof`, and the fields of a
+/// payload are bound with a destructuring assignment, which is always truthy:
+/// `$s instanceof Shape_Circle && ([$radius] = [$s->radius])`
+///
+/// PHP would assign the bindings in the scope of the match, and evaluate
+/// the subject in each comparison. So a match that binds fields, or whose
+/// subject isn't a variable, is the body of a function called with the
+/// subject: `(fn($Subject) => match (true) {...})(f())`
+fn match_into_php<'a>(match_expression: &'a Match<'_>) -> PExpresssion<'a> {
+    let subject = &match_expression.subject;
+    let patterns = || {
+        match_expression
+            .arms
+            .iter()
+            .flat_map(|arm| arm.patterns.iter())
+    };
+    let has_guards = match_expression.arms.iter().any(|arm| arm.guard.is_some());
+    let has_variants = patterns().any(|pattern| match pattern {
+        Expression::FunctionCall(_) => true,
+        Expression::EnumVariant(_, _, is_class) => is_class.get(),
+        _ => false,
+    });
+    let has_bindings = patterns().any(|pattern| match pattern {
+        Expression::FunctionCall(call) => !call.arguments.arguments.is_empty(),
+        _ => false,
+    });
+
+    if !has_guards && !has_variants {
+        let arms = match_arms_into_php(match_expression, |pattern| pattern.into_php_ast());
+        return PExpresssion::Match(PMatch {
+            subject: Box::new(subject.into_php_ast()),
+            arms,
+        });
+    }
+
+    let is_function = has_bindings || !matches!(**subject, Expression::Identifier(_));
+    let subject_value = || match is_function {
+        true => PExpresssion::Primary(PPrimary::Variable(SUBJECT)),
+        false => subject.into_php_ast(),
+    };
+    let compare_to_subject = |pattern: &'a Expression<'_>| {
+        let (enum_name, variant, arguments) = match pattern {
+            Expression::FunctionCall(call) => match &*call.function {
+                Expression::EnumVariant(enum_name, variant, _) => {
+                    (enum_name, variant, &call.arguments.arguments[..])
+                }
+                _ => unreachable!("Compiler bug: a function call as a pattern of a match"),
+            },
+            Expression::EnumVariant(enum_name, variant, is_class) if is_class.get() => {
+                (enum_name, variant, &[][..])
+            }
+            _ => {
+                return PExpresssion::BinaryOp(
+                    Box::new(subject_value()),
+                    Box::new(pattern.into_php_ast()),
+                    "===",
+                )
+            }
+        };
+
+        let class_name = variant_class_name(&enum_name.value, &variant.value);
+        let instance_of = PExpresssion::InstanceOf(Box::new(subject_value()), class_name);
+        if arguments.is_empty() {
+            return instance_of;
+        }
+
+        // The parser only allows identifiers as bindings
+        let (bindings, fields) = arguments
+            .iter()
+            .map(|argument| {
+                let Expression::Identifier(binding) = argument else {
+                    unreachable!("Compiler bug: a binding of a pattern is not an identifier")
+                };
+                // The bindings have the names of the fields
+                let field =
+                    PExpresssion::PropertyAccess(Box::new(subject_value()), &binding.value, "->");
+                let binding = PExpresssion::Primary(PPrimary::Variable(&binding.value));
+                (binding, field)
+            })
+            .unzip();
+        let destructuring = PExpresssion::Reassignment(PAssignment {
+            target: Box::new(PExpresssion::Array(bindings)),
+            operator: "=",
+            value: Box::new(PExpresssion::Array(fields)),
+        });
+        PExpresssion::BinaryOp(Box::new(instance_of), Box::new(destructuring), "&&")
+    };
+
+    let php_match = PExpresssion::Match(PMatch {
+        subject: Box::new(PExpresssion::Primary(PPrimary::BoolLiteral(true))),
+        arms: match_arms_into_php(match_expression, compare_to_subject),
+    });
+    if !is_function {
+        return php_match;
+    }
+
+    // Arrow functions capture by value, mutable variables need a closure
+    let parameters = vec![PParameter {
+        name: SUBJECT,
+        datatype: None,
+    }];
+    let captures = match_expression.captures.borrow();
+    let function = match captures.iter().all(|capture| !capture.is_mutable) {
+        true => PExpresssion::ArrowFunction(PArrowFunction {
+            parameters,
+            return_type: None,
+            value: Box::new(php_match),
+        }),
+        false => PExpresssion::Closure(PClosure {
+            parameters,
+            uses: captures
+                .iter()
+                .map(|capture| PClosureUse {
+                    name: capture.name.clone(),
+                    by_reference: capture.is_mutable,
+                })
+                .collect(),
+            return_type: None,
+            body: vec![PStatement::Return(Some(php_match))],
+        }),
+    };
+    PExpresssion::Call(Box::new(function), vec![subject.into_php_ast()])
+}
+
+/// Transforms the arms of a match, with `condition` transforming each pattern.
+/// A guard is joined to the conditions of its arm with `&&`
+fn match_arms_into_php<'a>(
+    match_expression: &'a Match<'_>,
+    condition: impl Fn(&'a Expression<'_>) -> PExpresssion<'a>,
+) -> Vec<PMatchArm<'a>> {
+    let mut arms = Vec::new();
+    for arm in match_expression.arms.iter() {
+        let conditions = match &arm.guard {
+            None => arm.patterns.iter().map(&condition).collect(),
+            Some(guard) => {
+                let comparisons = arm
+                    .patterns
+                    .iter()
+                    .map(&condition)
+                    .reduce(|left, right| {
+                        PExpresssion::BinaryOp(Box::new(left), Box::new(right), "||")
+                    })
+                    .expect("A match arm has at least one pattern");
+                let guard = Box::new(guard.into_php_ast());
+                vec![PExpresssion::BinaryOp(Box::new(comparisons), guard, "&&")]
+            }
+        };
+
+        arms.push(PMatchArm {
+            conditions,
+            value: arm_value_into_php(&arm.body, match_expression.to_float.get()),
+        });
+    }
+
+    if let Some(else_block) = &match_expression.else_block {
+        arms.push(PMatchArm {
+            conditions: vec![],
+            value: arm_value_into_php(else_block, match_expression.to_float.get()),
+        });
+    }
+
+    arms
+}
+
+/// Transforms the block of a match arm into its value, converted to
+/// a float if `to_float`. An empty block is null
+fn arm_value_into_php<'a>(block: &'a Block<'_>, to_float: bool) -> PExpresssion<'a> {
+    match block.members.as_slice() {
+        [] => PExpresssion::Primary(PPrimary::NullLiteral),
+        [BlockMember::Expr(value)] if to_float => float_into_php(value),
+        [BlockMember::Expr(value)] => value.into_php_ast(),
+        _ => unreachable!(
+            "Compiler bug: the parser only allows an expression in the arms of a match"
+        ),
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+
     use crate::{
-        lexic::token::Token,
+        codegen::Transpilable,
+        lexic::{get_tokens, token::Token},
         php_ast::{transformers::PHPTransformable, PExpresssion, PPrimary},
-        syntax::ast::Expression,
+        syntax::{
+            ast::{functions::Capture, Array, ArrayAccess, Expression, Identifier},
+            parseable::Parseable,
+        },
     };
 
+    /// Parses `input` as an expression, and transpiles it into PHP
+    fn transpile(input: &str) -> String {
+        let tokens = get_tokens(&String::from(input)).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+        expression.into_php_ast().transpile()
+    }
+
     #[test]
     fn should_transform_string() {
         let t = Token::new_string("Hello".into(), 0);
@@ -99,4 +477,234 @@ mod tests {
             _ => panic!("Expected a Float literal"),
         }
     }
+
+    #[test]
+    fn should_transform_unary_operator() {
+        let op = Token::new_operator("!".into(), 0);
+        let t = Token::new_identifier("done".into(), 1);
+        let input =
+            Expression::UnaryOperator(&op, Box::new(Expression::Identifier(Identifier::new(&t))));
+        let output = input.into_php_ast();
+
+        match output {
+            PExpresssion::UnaryOp(op, expr) => {
+                assert_eq!("!", op);
+                assert!(matches!(
+                    *expr,
+                    PExpresssion::Primary(PPrimary::Variable(_))
+                ));
+            }
+            _ => panic!("Expected a unary operator"),
+        }
+    }
+
+    #[test]
+    fn should_transform_array_and_array_access() {
+        let t1 = Token::new_int("1".into(), 1);
+        let t2 = Token::new_int("2".into(), 4);
+        let idx = Token::new_int("0".into(), 7);
+        let array = Expression::Array(Array {
+            exps: vec![Expression::Int(&t1), Expression::Int(&t2)],
+            start: 0,
+            end: 5,
+            to_float: Cell::new(false),
+        });
+        let input = Expression::ArrayAcccess(ArrayAccess {
+            left_expr: Box::new(array),
+            idx_expr: Box::new(Expression::Int(&idx)),
+            end_pos: 8,
+        });
+        let output = input.into_php_ast();
+
+        match output {
+            PExpresssion::ArrayAccess(array, index) => {
+                match *array {
+                    PExpresssion::Array(items) => assert_eq!(2, items.len()),
+                    _ => panic!("Expected an array"),
+                }
+                assert!(matches!(
+                    *index,
+                    PExpresssion::Primary(PPrimary::IntegerLiteral(_))
+                ));
+            }
+            _ => panic!("Expected an array access"),
+        }
+    }
+
+    #[test]
+    fn should_transform_concatenation() {
+        let a = Token::new_identifier("a".into(), 0);
+        let op = Token::new_operator("++".into(), 2);
+        let b = Token::new_identifier("b".into(), 5);
+        let input = Expression::BinaryOperator(
+            Box::new(Expression::Identifier(Identifier::new(&a))),
+            Box::new(Expression::Identifier(Identifier::new(&b))),
+            &op,
+        );
+
+        match input.into_php_ast() {
+            PExpresssion::BinaryOp(_, _, op) => assert_eq!(".", op),
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_compare_with_null_strictly() {
+        assert_eq!("$n === null", transpile("n == null"));
+        assert_eq!("$n !== null", transpile("n != null"));
+        assert_eq!("null === $n", transpile("null == n"));
+        assert_eq!("$a == $b", transpile("a == b"));
+        assert_eq!("$a != 0", transpile("a != 0"));
+    }
+
+    #[test]
+    fn should_transform_null_safe_access() {
+        let user = Token::new_identifier("user".into(), 0);
+        let op = Token::new_operator("?.".into(), 4);
+        let name = Token::new_identifier("name".into(), 6);
+        let input = Expression::BinaryOperator(
+            Box::new(Expression::Identifier(Identifier::new(&user))),
+            Box::new(Expression::Identifier(Identifier::new(&name))),
+            &op,
+        );
+
+        match input.into_php_ast() {
+            PExpresssion::PropertyAccess(object, property, op) => {
+                assert!(matches!(
+                    *object,
+                    PExpresssion::Primary(PPrimary::Variable(_))
+                ));
+                assert_eq!("name", property);
+                assert_eq!("?->", op);
+            }
+            _ => panic!("Expected a property access"),
+        }
+    }
+
+    #[test]
+    fn should_transform_type_tests() {
+        assert_eq!("is_int($value)", transpile("value is Int"));
+        assert_eq!("$value instanceof Person", transpile("value is Person"));
+        assert_eq!(
+            "is_null($value) || is_string($value) || is_array($value)",
+            transpile("value is ?String | Array[Int] | Null")
+        );
+        // The expression is evaluated once
+        assert_eq!("is_int($next())", transpile("next() is Int"));
+        assert_eq!(
+            "(fn($Subject) => is_int($Subject) || is_float($Subject))($next())",
+            transpile("next() is Int | Float")
+        );
+    }
+
+    #[test]
+    fn should_transform_logic_operators() {
+        assert_eq!("$a && $b || !$c", transpile("a && b || !c"));
+        assert_eq!("$a && ($b || $c)", transpile("a && (b || c)"));
+        assert_eq!("$a == 1 && $b < 2", transpile("a == 1 && b < 2"));
+        assert_eq!("!($a && $b)", transpile("!(a && b)"));
+    }
+
+    #[test]
+    fn should_transform_match() {
+        assert_eq!(
+            "match ($a) {\n    1, 2 => \"small\",\n    default => \"big\",\n}",
+            transpile("match a\ncase 1, 2 { \"small\" }\nelse { \"big\" }")
+        );
+        assert_eq!(
+            "match ($a) {\n    true => 1,\n    false => null,\n}",
+            transpile("match a\ncase true { 1 }\ncase false {}")
+        );
+    }
+
+    #[test]
+    fn should_transform_match_with_guards() {
+        assert_eq!(
+            "match (true) {\n    ($a === 1 || $a === 2) && $b => \"guarded\",\n    $a === 3 => \"three\",\n    default => \"other\",\n}",
+            transpile("match a\ncase 1, 2 if b { \"guarded\" }\ncase 3 { \"three\" }\nelse { \"other\" }")
+        );
+    }
+
+    #[test]
+    fn should_transform_enum_variants() {
+        assert_eq!("Suit::Hearts", transpile("Suit::Hearts"));
+        assert_eq!("new Shape_Rect(1, 2.5)", transpile("Shape::Rect(1, 2.5)"));
+        assert_eq!(
+            "match ($s) {\n    Suit::Hearts => 1,\n    default => 2,\n}",
+            transpile("match s\ncase Suit::Hearts { 1 }\nelse { 2 }")
+        );
+    }
+
+    #[test]
+    fn should_destructure_variants_in_match() {
+        assert_eq!(
+            "(fn($Subject) => match (true) {\n    $Subject instanceof Shape_Rect && ([$height, $width] = [$Subject->height, $Subject->width]) && $width > 1 => $width,\n    $Subject instanceof Shape_Circle && ([$radius] = [$Subject->radius]) => 0,\n    default => 1,\n})($s)",
+            transpile("match s\ncase Shape::Rect(height, width) if width > 1 { width }\ncase Shape::Circle(radius) { 0 }\nelse { 1 }")
+        );
+        // Without bindings, a variable is compared directly
+        assert_eq!(
+            "match (true) {\n    $s instanceof Shape_Rect => 0,\n    default => 1,\n}",
+            transpile("match s\ncase Shape::Rect() { 0 }\nelse { 1 }")
+        );
+    }
+
+    #[test]
+    fn should_evaluate_the_subject_of_match_once() {
+        assert_eq!(
+            "(fn($Subject) => match (true) {\n    $Subject === 1 && $b => \"one\",\n    default => \"other\",\n})($next())",
+            transpile("match next()\ncase 1 if b { \"one\" }\nelse { \"other\" }")
+        );
+    }
+
+    #[test]
+    fn should_capture_mutable_variables_in_match_by_reference() {
+        let tokens = get_tokens(&String::from(
+            "match s\ncase Opt::Some(value) { fn() { total = total + value } }\nelse { fn() {} }",
+        ))
+        .unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+        let Expression::Match(match_expression) = &expression else {
+            panic!("Expected a match")
+        };
+        // Set by the semantic analysis
+        match_expression.captures.borrow_mut().push(Capture {
+            name: "total".into(),
+            is_mutable: true,
+        });
+
+        let output = expression.into_php_ast().transpile();
+        assert!(output.starts_with("(function ($Subject) use (&$total) {\n    return match (true) {\n        $Subject instanceof Opt_Some && ([$value] = [$Subject->value]) => "));
+        assert!(output.ends_with("})($s)"));
+    }
+
+    #[test]
+    fn should_transform_functions_as_values() {
+        let tokens = get_tokens(&String::from("apply(add, f)")).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+        let Expression::FunctionCall(call) = &expression else {
+            panic!("Expected a function call")
+        };
+        // Set by the semantic analysis
+        let Expression::Identifier(apply) = &*call.function else {
+            panic!("Expected an identifier")
+        };
+        apply.is_function.set(true);
+        let Expression::Identifier(add) = &call.arguments.arguments[0] else {
+            panic!("Expected an identifier")
+        };
+        add.is_function.set(true);
+
+        assert_eq!("apply(add(...), $f)", expression.into_php_ast().transpile());
+    }
+
+    #[test]
+    fn should_transform_calls_to_values() {
+        assert_eq!("$f(1, 2)", transpile("f(1, 2)"));
+        assert_eq!("$f(1)(2)", transpile("f(1)(2)"));
+        assert_eq!("$callbacks[0]($x)", transpile("callbacks[0](x)"));
+        assert_eq!(
+            "(fn(int $x): int => $x)(1)",
+            transpile("(fn(Int x) -> Int { x })(1)")
+        );
+    }
 }
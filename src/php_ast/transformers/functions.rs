@@ -1,19 +1,29 @@
 use crate::{
-    php_ast::PFunctionCall,
-    syntax::ast::{functions::FunctionCall, Expression},
+    php_ast::{
+        PArrowFunction, PClosure, PClosureUse, PExpresssion, PFunctionCall, PFunctionDefinition,
+        PParameter, PSimpleAssignment, PStatement,
+    },
+    semantic::types::{global::UNIT, Type},
+    syntax::ast::{
+        datatype::Datatype,
+        functions::{FunctionCall, Lambda},
+        Block, BlockMember, Expression, FunctionDeclaration, ParamsList, Statement,
+    },
 };
 
-use super::PHPTransformable;
+use super::{
+    types::{datatype_hint, return_type_hint},
+    PHPTransformable,
+};
 
 impl<'a> PHPTransformable<'a> for FunctionCall<'a> {
     type Item = PFunctionCall<'a>;
 
     fn into_php_ast(&'a self) -> Self::Item {
-        let function_expr = match *self.function {
-            Expression::Identifier(i) => &i.value,
-            _ => panic!(
-                "Cannot use an arbitrary expression as a function, only identifiers (for now)"
-            ),
+        // Other callees are transformed into a PExpresssion::Call
+        let function_expr = match &*self.function {
+            Expression::Identifier(i) => &i.token.value,
+            _ => unreachable!("Compiler bug: only functions and methods are called by name"),
         };
 
         let expressions: Vec<_> = self
@@ -29,3 +39,343 @@ impl<'a> PHPTransformable<'a> for FunctionCall<'a> {
         }
     }
 }
+
+impl<'a> PHPTransformable<'a> for FunctionDeclaration<'a> {
+    type Item = PFunctionDefinition<'a>;
+
+    fn into_php_ast(&'a self) -> Self::Item {
+        let parameters = parameters_into_php(&self.params_list);
+        let return_type = return_type(&self.return_type);
+
+        let mut body = self.block.into_php_ast();
+        if !return_type.is_unit() {
+            return_tail_expressions(&self.block, &mut body);
+        }
+
+        // A top level function captures the variables of the module
+        let captures = self.captures.borrow();
+        if !captures.is_empty() {
+            let names = captures
+                .iter()
+                .map(|capture| capture.name.clone())
+                .collect();
+            body.insert(0, PStatement::Global(names));
+        }
+
+        PFunctionDefinition {
+            name: &self.identifier.value,
+            parameters,
+            return_type: return_type_hint(&self.return_type),
+            body,
+        }
+    }
+}
+
+impl<'a> PHPTransformable<'a> for Lambda<'a> {
+    type Item = PExpresssion<'a>;
+
+    fn into_php_ast(&'a self) -> Self::Item {
+        let parameters = parameters_into_php(&self.params_list);
+        let return_type = return_type(&self.return_type);
+
+        // Arrow functions capture by value, mutable variables need a closure
+        let captures = self.captures.borrow();
+        let is_by_value = captures.iter().all(|capture| !capture.is_mutable);
+        if let ([BlockMember::Expr(value)], false, true) = (
+            self.block.members.as_slice(),
+            return_type.is_unit(),
+            is_by_value,
+        ) {
+            return PExpresssion::ArrowFunction(PArrowFunction {
+                parameters,
+                return_type: return_type_hint(&self.return_type),
+                value: Box::new(value.into_php_ast()),
+            });
+        }
+
+        let mut body = self.block.into_php_ast();
+        if !return_type.is_unit() {
+            return_tail_expressions(&self.block, &mut body);
+        }
+
+        let uses = captures
+            .iter()
+            .map(|capture| PClosureUse {
+                name: capture.name.clone(),
+                by_reference: capture.is_mutable,
+            })
+            .collect();
+
+        PExpresssion::Closure(PClosure {
+            parameters,
+            uses,
+            return_type: return_type_hint(&self.return_type),
+            body,
+        })
+    }
+}
+
+/// Transforms a function declared inside another function into
+/// a closure assigned to a variable with the name of the function,
+/// e.g. `$inner = function () use (&$inner) {...};`
+pub fn nested_function_into_php<'a>(function: &'a FunctionDeclaration) -> PExpresssion<'a> {
+    let parameters = parameters_into_php(&function.params_list);
+    let return_type = return_type(&function.return_type);
+
+    let mut body = function.block.into_php_ast();
+    if !return_type.is_unit() {
+        return_tail_expressions(&function.block, &mut body);
+    }
+
+    // A recursive function captures its own variable by reference,
+    // it's assigned after the closure is created
+    let uses = function
+        .captures
+        .borrow()
+        .iter()
+        .map(|capture| PClosureUse {
+            name: capture.name.clone(),
+            by_reference: capture.is_mutable || capture.name == function.identifier.value,
+        })
+        .collect();
+
+    let closure = PExpresssion::Closure(PClosure {
+        parameters,
+        uses,
+        return_type: return_type_hint(&function.return_type),
+        body,
+    });
+    PExpresssion::Assignment(PSimpleAssignment {
+        variable: &function.identifier.value,
+        assignment: Box::new(closure),
+    })
+}
+
+fn parameters_into_php<'a>(params_list: &'a ParamsList) -> Vec<PParameter<'a>> {
+    params_list
+        .parameters
+        .iter()
+        .map(|p| PParameter {
+            name: &p.identifier.value,
+            datatype: datatype_hint(&p.datatype),
+        })
+        .collect()
+}
+
+/// A function without a return type returns Unit
+fn return_type(return_datatype: &Option<Datatype>) -> Type {
+    match return_datatype {
+        Some(t) => Type::from(t),
+        None => Type::Value(UNIT.into()),
+    }
+}
+
+/// Turns the expression at the end of a function body into a PHP `return`.
+///
+/// If the body ends with an if/else, the expressions at the end
+/// of each of its branches are returned instead.
+fn return_tail_expressions(block: &Block, body: &mut Vec<PStatement>) {
+    match block.members.last() {
+        Some(BlockMember::Expr(_)) => {
+            // THP expressions are always transformed into expression statements
+            if let Some(PStatement::ExpressionStatement(value)) = body.pop() {
+                body.push(PStatement::Return(Some(value)));
+            }
+        }
+        Some(BlockMember::Stmt(Statement::Conditional(conditional))) => {
+            // Without an else the value of the conditional is not returned
+            let (Some(else_block), Some(PStatement::If(php_if))) =
+                (&conditional.else_block, body.last_mut())
+            else {
+                return;
+            };
+
+            return_tail_expressions(&conditional.if_member.body, &mut php_if.body);
+            for (member, php_else_if) in conditional
+                .else_if_members
+                .iter()
+                .zip(php_if.else_ifs.iter_mut())
+            {
+                return_tail_expressions(&member.body, &mut php_else_if.body);
+            }
+            if let Some(php_else_body) = &mut php_if.else_body {
+                return_tail_expressions(else_block, php_else_body);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codegen::Transpilable,
+        lexic::get_tokens,
+        php_ast::{transformers::PHPTransformable, PExpresssion, PStatement},
+        semantic::check_semantics,
+        syntax::{
+            ast::{
+                functions::{Capture, Lambda},
+                ModuleMembers, Statement,
+            },
+            build_ast,
+            parseable::Parseable,
+        },
+    };
+
+    #[test]
+    fn should_transform_function_declaration() {
+        let tokens = get_tokens(&String::from(
+            "fun add(Int a, Person b) -> Int {\n    a\n    b\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::FnDecl(fun)) = &ast.productions[0] else {
+            panic!("Expected a function declaration")
+        };
+
+        let output = fun.into_php_ast();
+        assert_eq!("add", output.name);
+        assert_eq!(2, output.parameters.len());
+        assert_eq!("a", output.parameters[0].name);
+        assert_eq!(Some(String::from("int")), output.parameters[0].datatype);
+        assert_eq!("b", output.parameters[1].name);
+        assert_eq!(None, output.parameters[1].datatype);
+        assert_eq!(Some(String::from("int")), output.return_type);
+        assert_eq!(2, output.body.len());
+        assert!(matches!(output.body[0], PStatement::ExpressionStatement(_)));
+    }
+
+    #[test]
+    fn should_transform_function_without_return_type() {
+        let tokens = get_tokens(&String::from("fun main() {}")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::FnDecl(fun)) = &ast.productions[0] else {
+            panic!("Expected a function declaration")
+        };
+
+        let output = fun.into_php_ast();
+        assert_eq!(Some(String::from("void")), output.return_type);
+        assert!(output.body.is_empty());
+    }
+
+    #[test]
+    fn should_return_tail_expressions() {
+        let tokens = get_tokens(&String::from(
+            "fun f(Bool a) -> Int {\n    if a {\n        1\n    } else {\n        print(\"b\")\n        2\n    }\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::FnDecl(fun)) = &ast.productions[0] else {
+            panic!("Expected a function declaration")
+        };
+
+        let output = fun.into_php_ast();
+        let PStatement::If(php_if) = &output.body[0] else {
+            panic!("Expected an if statement")
+        };
+        assert!(matches!(php_if.body[0], PStatement::Return(Some(_))));
+        let else_body = php_if.else_body.as_ref().unwrap();
+        assert!(matches!(else_body[0], PStatement::ExpressionStatement(_)));
+        assert!(matches!(else_body[1], PStatement::Return(Some(_))));
+    }
+
+    #[test]
+    fn should_not_return_tail_expressions_of_unit_functions() {
+        let tokens = get_tokens(&String::from("fun main() {\n    print(\"a\")\n}")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::FnDecl(fun)) = &ast.productions[0] else {
+            panic!("Expected a function declaration")
+        };
+
+        let output = fun.into_php_ast();
+        assert!(matches!(output.body[0], PStatement::ExpressionStatement(_)));
+    }
+
+    #[test]
+    fn should_declare_module_variables_as_global() {
+        let tokens = get_tokens(&String::from(
+            "val rate = 3\nfun charge(Int amount) -> Int {\n    amount * rate\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        check_semantics(&ast).unwrap();
+
+        let ModuleMembers::Stmt(statement) = &ast.productions[1] else {
+            panic!("Expected a function declaration")
+        };
+        assert_eq!(
+            "function charge(int $amount): int\n{\n    global $rate;\n    return $amount * $rate;\n}",
+            statement.into_php_ast().transpile()
+        );
+    }
+
+    #[test]
+    fn should_transform_nested_functions_into_closures() {
+        let tokens = get_tokens(&String::from(
+            "fun outer(Int a) -> Int {\n    fun inner(Int n) -> Int {\n        if n <= 0 {\n            return a\n        }\n        inner(n - 1)\n    }\n    inner(2)\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        check_semantics(&ast).unwrap();
+
+        let ModuleMembers::Stmt(statement) = &ast.productions[0] else {
+            panic!("Expected a function declaration")
+        };
+        let expected = "function outer(int $a): int\n{\n    $inner = function (int $n) use ($a, &$inner): int {\n        if ($n <= 0) {\n            return $a;\n        }\n        return $inner($n - 1);\n    };\n    return $inner(2);\n}";
+        assert_eq!(expected, statement.into_php_ast().transpile());
+    }
+
+    #[test]
+    fn should_transform_lambdas_into_arrow_functions() {
+        let tokens = get_tokens(&String::from("fn(Int x) -> Int { x + offset }")).unwrap();
+        let (lambda, _) = Lambda::try_parse(&tokens, 0).unwrap();
+        lambda.captures.borrow_mut().push(Capture {
+            name: "offset".into(),
+            is_mutable: false,
+        });
+
+        let output = lambda.into_php_ast();
+        assert!(matches!(output, PExpresssion::ArrowFunction(_)));
+        assert_eq!("fn(int $x): int => $x + $offset", output.transpile());
+    }
+
+    #[test]
+    fn should_transform_lambdas_into_closures() {
+        let transpile = |input: &str, captures: Vec<Capture>| {
+            let tokens = get_tokens(&String::from(input)).unwrap();
+            let (lambda, _) = Lambda::try_parse(&tokens, 0).unwrap();
+            *lambda.captures.borrow_mut() = captures;
+            lambda.into_php_ast().transpile()
+        };
+
+        // Lambdas with many statements
+        let captures = vec![Capture {
+            name: "offset".into(),
+            is_mutable: false,
+        }];
+        assert_eq!(
+            "function (int $x) use ($offset): int {\n    $y = $x + $offset;\n    return $y;\n}",
+            transpile(
+                "fn(Int x) -> Int {\n    val y = x + offset\n    y\n}",
+                captures
+            )
+        );
+
+        // Mutable variables are captured by reference
+        let captures = vec![Capture {
+            name: "total".into(),
+            is_mutable: true,
+        }];
+        assert_eq!(
+            "function (int $x) use (&$total): int {\n    return $total + $x;\n}",
+            transpile("fn(Int x) -> Int { total + x }", captures)
+        );
+
+        // Lambdas without return type
+        assert_eq!(
+            "function (): void {\n    $a = 1;\n}",
+            transpile("fn() {\n    val a = 1\n}", vec![])
+        );
+    }
+}
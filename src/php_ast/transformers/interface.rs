@@ -0,0 +1,78 @@
+use crate::{
+    php_ast::{PInterfaceDefinition, PMethodSignature, PParameter},
+    syntax::ast::interface::{FunctionSignature, InterfaceDeclaration},
+};
+
+use super::{
+    types::{datatype_hint, return_type_hint},
+    PHPTransformable,
+};
+
+impl<'a> PHPTransformable<'a> for InterfaceDeclaration<'a> {
+    type Item = PInterfaceDefinition<'a>;
+
+    fn into_php_ast(&'a self) -> Self::Item {
+        PInterfaceDefinition {
+            name: &self.identifier.value,
+            methods: self.methods.iter().map(|m| m.into_php_ast()).collect(),
+        }
+    }
+}
+
+impl<'a> PHPTransformable<'a> for FunctionSignature<'a> {
+    type Item = PMethodSignature<'a>;
+
+    fn into_php_ast(&'a self) -> Self::Item {
+        let parameters = self
+            .params_list
+            .parameters
+            .iter()
+            .map(|p| PParameter {
+                name: &p.identifier.value,
+                datatype: datatype_hint(&p.datatype),
+            })
+            .collect();
+
+        // A method without a return type returns Unit,
+        // the same as in the classes that implement it
+        PMethodSignature {
+            name: &self.identifier.value,
+            parameters,
+            return_type: return_type_hint(&self.return_type),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexic::get_tokens,
+        php_ast::transformers::PHPTransformable,
+        syntax::{
+            ast::{ModuleMembers, Statement},
+            build_ast,
+        },
+    };
+
+    #[test]
+    fn should_transform_interface() {
+        let tokens = get_tokens(&String::from(
+            "interface Shape {\n    fun area() -> Float\n    fun scale(Float factor)\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(Statement::InterfaceDecl(interface)) = &ast.productions[0] else {
+            panic!("Expected an interface declaration")
+        };
+
+        let interface = interface.into_php_ast();
+        assert_eq!("Shape", interface.name);
+        assert_eq!(2, interface.methods.len());
+        assert_eq!(
+            Some(String::from("float")),
+            interface.methods[0].return_type
+        );
+        assert_eq!("factor", interface.methods[1].parameters[0].name);
+        assert_eq!(Some(String::from("void")), interface.methods[1].return_type);
+    }
+}
@@ -1,7 +1,12 @@
+pub mod block;
+pub mod class;
+pub mod enum_declaration;
 pub mod expression;
 pub mod functions;
+pub mod interface;
 pub mod module_ast;
 pub mod statement;
+pub mod types;
 
 /// Implemented by AST nodes that can be transformed to PHP
 pub trait PHPTransformable<'a> {
@@ -1,9 +1,12 @@
 use crate::{
-    php_ast::{PExpresssion, PSimpleAssignment, PStatement},
+    php_ast::{
+        PAssignment, PElseIf, PExpresssion, PForEach, PIf, PPrimary, PSimpleAssignment, PStatement,
+        PWhile,
+    },
     syntax::ast::Statement,
 };
 
-use super::PHPTransformable;
+use super::{expression::float_into_php, functions::nested_function_into_php, PHPTransformable};
 
 /// Transforms a THP expression into a PHP expression
 impl<'a> PHPTransformable<'a> for Statement<'_> {
@@ -12,26 +15,109 @@ impl<'a> PHPTransformable<'a> for Statement<'_> {
     fn into_php_ast(&'a self) -> PStatement<'a> {
         match self {
             Statement::Binding(b) => {
-                let binding_expr = b.expression.into_php_ast();
+                let binding_expr = match b.to_float.get() {
+                    true => float_into_php(&b.expression),
+                    false => b.expression.into_php_ast(),
+                };
 
                 PStatement::ExpressionStatement(PExpresssion::Assignment(PSimpleAssignment {
                     variable: &b.identifier.value,
                     assignment: Box::new(binding_expr),
                 }))
             }
-            _ => todo!("transformation for statement: {:?}", self),
+            Statement::FnDecl(f) if f.is_nested.get() => {
+                PStatement::ExpressionStatement(nested_function_into_php(f))
+            }
+            Statement::FnDecl(f) => PStatement::FunctionDefinition(f.into_php_ast()),
+            Statement::ClassDecl(c) => PStatement::ClassDefinition(c.into_php_ast()),
+            Statement::EnumDecl(e) => PStatement::EnumDefinition(e.into_php_ast()),
+            Statement::InterfaceDecl(i) => PStatement::InterfaceDefinition(i.into_php_ast()),
+            Statement::Assignment(a) => {
+                // Properties are assigned through their object, e.g. `$this->count`
+                let target = match &a.object {
+                    Some(object) => PExpresssion::PropertyAccess(
+                        Box::new(object.into_php_ast()),
+                        &a.identifier.value,
+                        "->",
+                    ),
+                    None => PExpresssion::Primary(PPrimary::Variable(&a.identifier.value)),
+                };
+                let value = match a.to_float.get() {
+                    true => float_into_php(&a.expression),
+                    false => a.expression.into_php_ast(),
+                };
+
+                PStatement::ExpressionStatement(PExpresssion::Reassignment(PAssignment {
+                    target: Box::new(target),
+                    operator: &a.operator.value,
+                    value: Box::new(value),
+                }))
+            }
+            Statement::Conditional(c) => {
+                let else_ifs = c
+                    .else_if_members
+                    .iter()
+                    .map(|member| PElseIf {
+                        condition: member.condition.into_php_ast(),
+                        body: member.body.into_php_ast(),
+                    })
+                    .collect();
+
+                PStatement::If(PIf {
+                    condition: c.if_member.condition.into_php_ast(),
+                    body: c.if_member.body.into_php_ast(),
+                    else_ifs,
+                    else_body: c.else_block.as_ref().map(|b| b.into_php_ast()),
+                })
+            }
+            Statement::ForLoop(l) => PStatement::ForEach(PForEach {
+                collection: l.collection.into_php_ast(),
+                key: l.key.map(|k| &k.value),
+                value: &l.value.value,
+                body: l.body.into_php_ast(),
+            }),
+            Statement::WhileLoop(l) => PStatement::While(PWhile {
+                condition: l.condition.into_php_ast(),
+                body: l.body.into_php_ast(),
+            }),
+            Statement::Return(r) => PStatement::Return(r.value.as_ref().map(|v| v.into_php_ast())),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
+
     use crate::{
-        lexic::token::{Token, TokenType},
+        codegen::Transpilable,
+        lexic::{
+            get_tokens,
+            token::{Token, TokenType},
+        },
         php_ast::{transformers::PHPTransformable, PExpresssion, PPrimary, PStatement},
-        syntax::ast::{var_binding::VariableBinding, Expression, Statement},
+        semantic::check_semantics,
+        syntax::{
+            ast::{var_binding::VariableBinding, Expression, ModuleMembers, Statement},
+            build_ast,
+        },
     };
 
+    /// Checks the semantics of the statements of `input`, and transpiles each one
+    fn transpile_statements(input: &str) -> Vec<String> {
+        let tokens = get_tokens(&String::from(input)).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        check_semantics(&ast).unwrap();
+
+        ast.productions
+            .iter()
+            .map(|production| match production {
+                ModuleMembers::Stmt(stmt) => stmt.into_php_ast().transpile(),
+                ModuleMembers::Expr(_) => panic!("Expected a statement"),
+            })
+            .collect()
+    }
+
     #[test]
     fn should_transform_binding() {
         let identifier_token = Token {
@@ -47,6 +133,7 @@ mod tests {
             identifier: &identifier_token,
             expression,
             is_mutable: false,
+            to_float: Cell::new(false),
         });
         let output = binding.into_php_ast();
 
@@ -64,4 +151,126 @@ mod tests {
             _ => panic!("Expected an ExpressionStatement"),
         }
     }
+
+    #[test]
+    fn should_convert_ints_assigned_to_floats() {
+        let output = transpile_statements(
+            "val Float x = 1\nvar Float y = 2.5\ny = 3\nval Array[Float] xs = [1, 2]\nval ys = [1, 2.5]\nval Int z = 4",
+        );
+        let expected = vec![
+            "$x = (float) 1;",
+            "$y = 2.5;",
+            "$y = (float) 3;",
+            "$xs = [(float) 1, (float) 2];",
+            "$ys = [(float) 1, 2.5];",
+            "$z = 4;",
+        ];
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn should_convert_ints_assigned_to_unions_and_nested_arrays_of_floats() {
+        let output = transpile_statements(
+            "val Float | String u = 1\nval Int | Float v = 1\nval Array[Float | String] us = [1]\nval Array[Array[Float]] xs = [[1], [2]]",
+        );
+        let expected = vec![
+            "$u = (float) 1;",
+            "$v = 1;",
+            "$us = [(float) 1];",
+            "$xs = [[(float) 1], [(float) 2]];",
+        ];
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn should_match_ints_converted_to_floats() {
+        let output = transpile_statements(
+            "val Float x = 1\nval r = match x\ncase 1.0 { \"one\" }\nelse { \"other\" }\nval y = match r\ncase \"one\" { 1 }\nelse { 2.5 }",
+        );
+        let expected = vec![
+            "$x = (float) 1;",
+            "$r = match ($x) {\n    1.0 => \"one\",\n    default => \"other\",\n};",
+            "$y = match ($r) {\n    \"one\" => (float) 1,\n    default => 2.5,\n};",
+        ];
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn should_capture_variables_in_assigned_lambdas() {
+        let lambda = "fn(Int x) -> Int {\n    val y = x * 2\n    y + base\n}";
+        let output = transpile_statements(&format!(
+            "val base = 1\nvar g = fn(Int x) -> Int {{ x }}\ng = {}\nval fs = [{}]",
+            lambda, lambda
+        ));
+        assert!(output[2].starts_with("$g = function (int $x) use ($base): int {"));
+        assert!(output[3].starts_with("$fs = [function (int $x) use ($base): int {"));
+    }
+
+    #[test]
+    fn should_create_variants_without_payload_of_enums_with_payloads() {
+        let output = transpile_statements(
+            "enum Opt {\n    Some(Int value)\n    None\n}\nval o = Opt::None\nval n = match o\ncase Opt::None { 0 }\nelse { 1 }",
+        );
+        assert_eq!("$o = new Opt_None();", output[1]);
+        assert_eq!(
+            "$n = match (true) {\n    $o instanceof Opt_None => 0,\n    default => 1,\n};",
+            output[2]
+        );
+    }
+
+    #[test]
+    fn should_transform_conditional() {
+        let tokens = get_tokens(&String::from(
+            "if a {\n    b\n} else if c {\n    d\n} else if e {\n} else {\n    f\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(stmt) = &ast.productions[0] else {
+            panic!("Expected a statement")
+        };
+
+        match stmt.into_php_ast() {
+            PStatement::If(p_if) => {
+                assert_eq!(1, p_if.body.len());
+                assert_eq!(2, p_if.else_ifs.len());
+                assert_eq!(0, p_if.else_ifs[1].body.len());
+                assert_eq!(1, p_if.else_body.unwrap().len());
+            }
+            _ => panic!("Expected an If"),
+        }
+    }
+
+    #[test]
+    fn should_transform_for_loop() {
+        let tokens = get_tokens(&String::from("for key, value in items {\n    value\n}")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(stmt) = &ast.productions[0] else {
+            panic!("Expected a statement")
+        };
+
+        match stmt.into_php_ast() {
+            PStatement::ForEach(foreach) => {
+                assert_eq!(Some(&String::from("key")), foreach.key);
+                assert_eq!("value", foreach.value);
+                assert_eq!(1, foreach.body.len());
+            }
+            _ => panic!("Expected a ForEach"),
+        }
+    }
+
+    #[test]
+    fn should_transform_while_loop() {
+        let tokens = get_tokens(&String::from("while running {\n    step\n}")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let ModuleMembers::Stmt(stmt) = &ast.productions[0] else {
+            panic!("Expected a statement")
+        };
+
+        match stmt.into_php_ast() {
+            PStatement::While(p_while) => {
+                assert_eq!(1, p_while.body.len());
+            }
+            _ => panic!("Expected a While"),
+        }
+    }
 }
@@ -0,0 +1,210 @@
+use crate::{
+    php_ast::PTypeHint,
+    semantic::types::{global::UNIT, Type},
+    syntax::ast::datatype::Datatype,
+};
+
+/// Returns the PHP type hint of a datatype written in the code, if PHP has
+/// an equivalent. The classes, enums and interfaces declared in the code,
+/// found by the semantic analysis, are hinted with their name
+pub fn datatype_hint(datatype: &Datatype) -> PTypeHint {
+    let mut declared_types = Vec::new();
+    collect_declared_types(datatype, &mut declared_types);
+    php_type_hint(&Type::from(datatype), &declared_types)
+}
+
+/// Returns the PHP type hint of the return type of a function.
+/// A function without a return type returns Unit
+pub fn return_type_hint(return_datatype: &Option<Datatype>) -> PTypeHint {
+    match return_datatype {
+        Some(datatype) => datatype_hint(datatype),
+        None => php_type_hint(&Type::Value(UNIT.into()), &[]),
+    }
+}
+
+fn collect_declared_types(datatype: &Datatype, declared_types: &mut Vec<String>) {
+    if datatype.is_declared.get() {
+        declared_types.push(datatype.name.value.clone());
+    }
+
+    let inner_datatypes = datatype
+        .type_params
+        .iter()
+        .chain(datatype.return_type.as_deref())
+        .chain(datatype.alternatives.iter());
+    for inner_datatype in inner_datatypes {
+        collect_declared_types(inner_datatype, declared_types);
+    }
+}
+
+/// Returns the PHP type hint of a THP datatype, if PHP has an equivalent.
+///
+/// Types without an equivalent are emitted without a hint.
+/// `declared_types` are the names of classes, enums and interfaces,
+/// which are used as hints.
+/// PHP arrays are not generic, so the type parameters are dropped.
+/// Unions become PHP 8 union types if all their members have a hint.
+pub fn php_type_hint(datatype: &Type, declared_types: &[String]) -> PTypeHint {
+    let name = match datatype {
+        Type::Value(name) if declared_types.contains(name) => return Some(name.clone()),
+        Type::Value(name) => name.as_str(),
+        Type::Generic(name, _) => name.as_str(),
+        Type::Enum(name, _) | Type::Interface(name, _) => return Some(name.clone()),
+        Type::Function(_, _) => return None,
+        Type::Polymorphic(_, _) => return None,
+        Type::Overloaded(_) => return None,
+        Type::Unknown => return None,
+        // PHP doesn't allow `?int|string`
+        Type::Nullable(t) if matches!(**t, Type::Union(_)) => {
+            return php_type_hint(t, declared_types).map(|hint| format!("{}|null", hint))
+        }
+        Type::Nullable(t) => {
+            return match php_type_hint(t, declared_types) {
+                Some(hint) if hint != "void" => Some(format!("?{}", hint)),
+                _ => None,
+            }
+        }
+        Type::Union(members) => return php_union_type_hint(members, declared_types),
+    };
+
+    let hint = match name {
+        "Int" => "int",
+        "Float" => "float",
+        "String" => "string",
+        "Bool" => "bool",
+        "Unit" | "Void" => "void",
+        "Array" => "array",
+        _ => return None,
+    };
+
+    Some(String::from(hint))
+}
+
+/// Returns the PHP union type of the members of a union, e.g. `int|string`
+fn php_union_type_hint(members: &[Type], declared_types: &[String]) -> PTypeHint {
+    let mut hints: Vec<String> = Vec::new();
+    for member in members {
+        match php_type_hint(member, declared_types) {
+            Some(hint) if hint == "void" => return None,
+            // e.g. `Array[Int] | Array[String]` is a single PHP type
+            Some(hint) if hints.contains(&hint) => {}
+            Some(hint) => hints.push(hint),
+            None => return None,
+        }
+    }
+
+    match hints.len() {
+        1 => hints.pop(),
+        _ => Some(hints.join("|")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexic::get_tokens, syntax::parseable::Parseable};
+
+    fn value(name: &str) -> Type {
+        Type::Value(name.into())
+    }
+
+    #[test]
+    fn should_map_primitive_types() {
+        assert_eq!(Some(String::from("int")), php_type_hint(&value("Int"), &[]));
+        assert_eq!(
+            Some(String::from("float")),
+            php_type_hint(&value("Float"), &[])
+        );
+        assert_eq!(
+            Some(String::from("string")),
+            php_type_hint(&value("String"), &[])
+        );
+        assert_eq!(
+            Some(String::from("bool")),
+            php_type_hint(&value("Bool"), &[])
+        );
+        assert_eq!(
+            Some(String::from("void")),
+            php_type_hint(&value("Unit"), &[])
+        );
+    }
+
+    #[test]
+    fn should_map_generic_arrays() {
+        let array = Type::Generic("Array".into(), vec![value("Int")]);
+        assert_eq!(Some(String::from("array")), php_type_hint(&array, &[]));
+    }
+
+    #[test]
+    fn should_map_nullable_types() {
+        let nullable_string = Type::nullable(value("String"));
+        assert_eq!(
+            Some(String::from("?string")),
+            php_type_hint(&nullable_string, &[])
+        );
+        assert_eq!(None, php_type_hint(&Type::nullable(value("Person")), &[]));
+    }
+
+    #[test]
+    fn should_map_union_types() {
+        let int_or_string = Type::union(vec![value("Int"), value("String")]);
+        assert_eq!(
+            Some(String::from("int|string")),
+            php_type_hint(&int_or_string, &[])
+        );
+        assert_eq!(
+            Some(String::from("int|string|null")),
+            php_type_hint(&Type::nullable(int_or_string), &[])
+        );
+
+        let arrays = Type::union(vec![
+            Type::Generic("Array".into(), vec![value("Int")]),
+            Type::Generic("Array".into(), vec![value("String")]),
+        ]);
+        assert_eq!(Some(String::from("array")), php_type_hint(&arrays, &[]));
+
+        let with_unknown = Type::union(vec![value("Int"), value("Person")]);
+        assert_eq!(None, php_type_hint(&with_unknown, &[]));
+    }
+
+    #[test]
+    fn should_not_map_unknown_types() {
+        assert_eq!(None, php_type_hint(&value("Person"), &[]));
+        let map = Type::Generic("Map".into(), vec![value("String"), value("Int")]);
+        assert_eq!(None, php_type_hint(&map, &[]));
+    }
+
+    #[test]
+    fn should_map_declared_types() {
+        let declared_types = vec![String::from("Point")];
+        assert_eq!(
+            Some(String::from("Point")),
+            php_type_hint(&value("Point"), &declared_types)
+        );
+        assert_eq!(
+            Some(String::from("?Point")),
+            php_type_hint(&Type::nullable(value("Point")), &declared_types)
+        );
+        assert_eq!(
+            Some(String::from("Point|int")),
+            php_type_hint(
+                &Type::union(vec![value("Point"), value("Int")]),
+                &declared_types
+            )
+        );
+    }
+
+    #[test]
+    fn should_map_datatypes_marked_as_declared() {
+        let tokens = get_tokens(&String::from("?Point | Array[Point]")).unwrap();
+        let (datatype, _) = Datatype::try_parse(&tokens, 0).unwrap();
+        assert_eq!(None, datatype_hint(&datatype));
+
+        // Set by the semantic analysis
+        datatype.is_declared.set(true);
+        assert_eq!(
+            Some(String::from("Point|array|null")),
+            datatype_hint(&datatype)
+        );
+    }
+}
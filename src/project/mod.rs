@@ -0,0 +1,229 @@
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Name of the file that marks the root of a THP project
+pub const MANIFEST_FILE: &str = "thp.toml";
+
+/// Oldest PHP version the generated code runs on. The output uses
+/// enums and readonly properties, which were added in PHP 8.1
+const MIN_PHP_VERSION: (u32, u32) = (8, 1);
+
+/// Contents of the `thp.toml` file of a project
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Manifest {
+    pub project: ProjectConfig,
+    #[serde(default)]
+    pub build: BuildConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ProjectConfig {
+    pub name: String,
+}
+
+/// Build configuration. Every field is optional in the manifest
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct BuildConfig {
+    /// Directory with the THP sources, relative to the project root
+    pub src: String,
+    /// Directory where the PHP output is written, relative to the project root
+    pub out: String,
+    /// PHP version the output targets. Must be at least `MIN_PHP_VERSION`
+    pub php_version: String,
+}
+
+impl Default for BuildConfig {
+    fn default() -> Self {
+        BuildConfig {
+            src: String::from("src"),
+            out: String::from("out"),
+            php_version: String::from("8.2"),
+        }
+    }
+}
+
+impl Manifest {
+    /// Creates a manifest with the default build configuration
+    pub fn new(name: String) -> Manifest {
+        Manifest {
+            project: ProjectConfig { name },
+            build: BuildConfig::default(),
+        }
+    }
+
+    /// Parses the contents of a manifest
+    pub fn parse(input: &str) -> Result<Manifest, String> {
+        let manifest: Manifest = match toml::from_str(input) {
+            Ok(m) => m,
+            Err(error) => return Err(error.message().to_string()),
+        };
+
+        let php_version = &manifest.build.php_version;
+        match parse_php_version(php_version) {
+            Some(version) if version < MIN_PHP_VERSION => {
+                return Err(format!(
+                    "PHP {} is not supported. THP generates code for PHP {}.{} or later",
+                    php_version, MIN_PHP_VERSION.0, MIN_PHP_VERSION.1
+                ));
+            }
+            Some(_) => {}
+            None => {
+                return Err(format!(
+                    "Invalid PHP version `{}`. Expected a version like `8.2`",
+                    php_version
+                ));
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Returns the manifest as TOML, ready to be written to a file
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("Illegal state: The manifest cannot be serialized")
+    }
+
+    /// Reads and parses the manifest inside `root`, printing an error if it fails
+    pub fn load(root: &Path) -> Result<Manifest, ()> {
+        let manifest_path = root.join(MANIFEST_FILE);
+        if !manifest_path.is_file() {
+            eprintln!(
+                "{}: {} not found. Run `thp init` to create a new project",
+                "error".on_red(),
+                MANIFEST_FILE
+            );
+            return Err(());
+        }
+
+        let contents = match fs::read_to_string(&manifest_path) {
+            Ok(c) => c,
+            Err(error) => {
+                eprintln!("{}: Error reading {}", "error".on_red(), MANIFEST_FILE);
+                eprintln!("{}", error);
+                return Err(());
+            }
+        };
+
+        match Manifest::parse(&contents) {
+            Ok(manifest) => Ok(manifest),
+            Err(reason) => {
+                eprintln!("{}: Invalid {}", "error".on_red(), MANIFEST_FILE);
+                eprintln!("{}", reason);
+                Err(())
+            }
+        }
+    }
+
+    /// Returns the path of the source directory
+    pub fn src_dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.build.src)
+    }
+
+    /// Returns the path of the output directory
+    pub fn out_dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.build.out)
+    }
+
+    /// Returns where the PHP code of `file` is written. The directory
+    /// structure of the source directory is mirrored in the output directory.
+    ///
+    /// `file` must be inside the source directory.
+    pub fn output_path(&self, root: &Path, file: &Path) -> PathBuf {
+        let src_dir = self.src_dir(root);
+        let relative = file
+            .strip_prefix(&src_dir)
+            .expect("Illegal state: Tried to build a file outside the source directory");
+
+        let mut output = self.out_dir(root).join(relative);
+        output.set_extension("php");
+        output
+    }
+}
+
+/// Parses a version of the form `major.minor`, e.g. `8.2`
+fn parse_php_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.split_once('.')?;
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if !is_number(major) || !is_number(minor) {
+        return None;
+    }
+
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_full_manifest() {
+        let input = "[project]\nname = \"app\"\n\n[build]\nsrc = \"lib\"\nout = \"public\"\nphp_version = \"8.3\"\n";
+        let manifest = Manifest::parse(input).unwrap();
+
+        assert_eq!("app", manifest.project.name);
+        assert_eq!("lib", manifest.build.src);
+        assert_eq!("public", manifest.build.out);
+        assert_eq!("8.3", manifest.build.php_version);
+    }
+
+    #[test]
+    fn should_use_default_build_config() {
+        let manifest = Manifest::parse("[project]\nname = \"app\"\n").unwrap();
+        assert_eq!(BuildConfig::default(), manifest.build);
+
+        let manifest =
+            Manifest::parse("[project]\nname = \"app\"\n[build]\nout = \"dist\"").unwrap();
+        assert_eq!("src", manifest.build.src);
+        assert_eq!("dist", manifest.build.out);
+    }
+
+    #[test]
+    fn should_fail_on_invalid_manifest() {
+        assert!(Manifest::parse("").is_err());
+        assert!(Manifest::parse("[project]\nname = 322\n").is_err());
+        assert!(
+            Manifest::parse("[project]\nname = \"app\"\n[build]\nphp_version = \"8\"").is_err()
+        );
+        assert!(
+            Manifest::parse("[project]\nname = \"app\"\n[build]\nphp_version = \"v8.2\"").is_err()
+        );
+    }
+
+    #[test]
+    fn should_reject_php_versions_without_enums() {
+        let manifest =
+            |version| format!("[project]\nname = \"app\"\n[build]\nphp_version = \"{version}\"");
+
+        assert!(Manifest::parse(&manifest("8.1")).is_ok());
+        assert!(Manifest::parse(&manifest("9.0")).is_ok());
+        assert!(Manifest::parse(&manifest("8.0")).is_err());
+        assert!(Manifest::parse(&manifest("7.4")).is_err());
+    }
+
+    #[test]
+    fn should_serialize_and_parse_back() {
+        let manifest = Manifest::new(String::from("app"));
+        let parsed = Manifest::parse(&manifest.to_toml()).unwrap();
+        assert_eq!(manifest, parsed);
+    }
+
+    #[test]
+    fn should_mirror_source_tree_in_output_dir() {
+        let manifest = Manifest::new(String::from("app"));
+        let root = Path::new("/project");
+
+        assert_eq!(
+            PathBuf::from("/project/out/index.php"),
+            manifest.output_path(root, Path::new("/project/src/index.thp"))
+        );
+        assert_eq!(
+            PathBuf::from("/project/out/models/user.php"),
+            manifest.output_path(root, Path::new("/project/src/models/user.thp"))
+        );
+    }
+}
@@ -160,8 +160,8 @@ fn compile(input: &String, symbol_table: &mut SymbolTable) -> Option<String> {
     //
     let tokens = match lexic::get_tokens(input) {
         Ok(t) => t,
-        Err(error) => {
-            error.print_ariadne(input);
+        Err(errors) => {
+            errors.print_ariadne(input);
             return None;
         }
     };
@@ -171,8 +171,8 @@ fn compile(input: &String, symbol_table: &mut SymbolTable) -> Option<String> {
     //
     let ast = match syntax::build_ast(&tokens) {
         Ok(ast) => ast,
-        Err(error) => {
-            error.print_ariadne(input);
+        Err(errors) => {
+            errors.print_ariadne(input);
             return None;
         }
     };
@@ -183,8 +183,8 @@ fn compile(input: &String, symbol_table: &mut SymbolTable) -> Option<String> {
     let res1 = crate::semantic::check_semantics_with(&ast, symbol_table);
     match res1 {
         Ok(_) => {}
-        Err(error) => {
-            error.print_ariadne(input);
+        Err(errors) => {
+            errors.print_ariadne(input);
             return None;
         }
     }
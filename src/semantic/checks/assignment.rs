@@ -1,19 +1,28 @@
 use crate::{
     error_handling::{
         self,
-        error_messages::{SEMANTIC_IMMUTABLE_VARIABLE, SEMANTIC_INVALID_REFERENCE},
+        error_messages::{
+            SEMANTIC_IMMUTABLE_VARIABLE, SEMANTIC_INVALID_REFERENCE, SEMANTIC_MISMATCHED_TYPES,
+        },
         ErrorContainer, ErrorLabel,
     },
-    semantic::{self, impls::SemanticCheck, types::Typed},
-    syntax::ast::{Assignment, Positionable},
+    semantic::{
+        self,
+        impls::SemanticCheck,
+        symbol_table::SymbolTable,
+        types::{expression::get_member, is_assignable_expression, Typed},
+    },
+    syntax::ast::{Assignment, Expression, Positionable},
 };
 
 impl SemanticCheck for Assignment<'_> {
     fn check_semantics(
         &self,
         scope: &semantic::symbol_table::SymbolTable,
-    ) -> Result<(), error_handling::MistiError> {
-        // for now the assignment can only be to a variable
+    ) -> Result<(), Vec<error_handling::MistiError>> {
+        if let Some(object) = &self.object {
+            return check_property_assignment(self, object, scope);
+        }
 
         // get the datatype and mutability status
         let datatype = match scope.get_type_and_mut(&self.identifier.value) {
@@ -34,7 +43,7 @@ impl SemanticCheck for Assignment<'_> {
                     note: None,
                     help: None,
                 };
-                return Err(econtainer);
+                return Err(vec![econtainer]);
             }
             None => {
                 // throw error: variable does not exist
@@ -50,23 +59,27 @@ impl SemanticCheck for Assignment<'_> {
                     note: None,
                     help: None,
                 };
-                return Err(econtainer);
+                return Err(vec![econtainer]);
             }
         };
 
+        scope.reference(self.identifier);
+
         // assert the datatype is the same
+        self.expression.check_semantics(scope)?;
         let expression_type = self.expression.get_type(scope)?;
+        let resolved_datatype = datatype.resolve(scope);
 
-        if !datatype.equals(&expression_type) {
+        if !is_assignable_expression(&self.expression, &expression_type, &resolved_datatype) {
             // throw error: variable and expression have different types
             let label = ErrorLabel {
-                message: format!("This variable has type {:?}", datatype),
+                message: format!("This variable has type {}", datatype),
                 start: self.identifier.position,
                 end: self.identifier.get_end_position(),
             };
             let (expr_start, expr_end) = self.expression.get_position();
             let label2 = ErrorLabel {
-                message: format!("But this expression has type {:?}", expression_type),
+                message: format!("But this expression has type {}", expression_type),
                 start: expr_start,
                 end: expr_end,
             };
@@ -77,10 +90,71 @@ impl SemanticCheck for Assignment<'_> {
                 note: None,
                 help: None,
             };
-            return Err(econtainer);
+            return Err(vec![econtainer]);
         }
 
-        // ok
+        self.to_float
+            .set(expression_type.converts_to_float(&resolved_datatype));
         Ok(())
     }
 }
+
+/// Checks the assignment of a property of `object`, e.g. `self.count = 0`
+fn check_property_assignment(
+    assignment: &Assignment,
+    object: &Expression,
+    scope: &SymbolTable,
+) -> Result<(), Vec<error_handling::MistiError>> {
+    object.check_semantics(scope)?;
+    let property =
+        get_member(object, assignment.identifier, ".", scope).map_err(|error| vec![error])?;
+
+    // Only properties declared with `var` can be assigned
+    if !property.is_mutable {
+        let label = ErrorLabel {
+            message: String::from(
+                "This member is immutable, therefore it cannot be assigned a new value",
+            ),
+            start: assignment.identifier.position,
+            end: assignment.identifier.get_end_position(),
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_IMMUTABLE_VARIABLE,
+            error_offset: assignment.identifier.position,
+            labels: vec![label],
+            note: None,
+            help: Some(String::from(
+                "Declare the property with `var` to make it mutable",
+            )),
+        };
+        return Err(vec![econtainer]);
+    }
+
+    assignment.expression.check_semantics(scope)?;
+    let expression_type = assignment.expression.get_type(scope)?;
+
+    let property_type = property.datatype.resolve(scope);
+    if !is_assignable_expression(&assignment.expression, &expression_type, &property_type) {
+        let label = ErrorLabel {
+            message: format!("This property has type {}", property.datatype),
+            start: assignment.identifier.position,
+            end: assignment.identifier.get_end_position(),
+        };
+        let (expr_start, expr_end) = assignment.expression.get_position();
+        let label2 = ErrorLabel {
+            message: format!("But this expression has type {}", expression_type),
+            start: expr_start,
+            end: expr_end,
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_MISMATCHED_TYPES,
+            error_offset: assignment.identifier.position,
+            labels: vec![label, label2],
+            note: None,
+            help: None,
+        };
+        return Err(vec![econtainer]);
+    }
+
+    Ok(())
+}
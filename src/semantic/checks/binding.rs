@@ -2,7 +2,7 @@ use crate::{
     error_handling::{error_messages::SEMANTIC_DUPLICATED_REFERENCE, ErrorContainer, ErrorLabel},
     semantic::{
         impls::SemanticCheck,
-        types::{Type, Typed},
+        types::{is_assignable_expression, Type, Typed},
     },
     syntax::ast::{var_binding::VariableBinding, Positionable},
 };
@@ -11,7 +11,7 @@ impl SemanticCheck for VariableBinding<'_> {
     fn check_semantics(
         &self,
         scope: &crate::semantic::symbol_table::SymbolTable,
-    ) -> Result<(), crate::error_handling::MistiError> {
+    ) -> Result<(), Vec<crate::error_handling::MistiError>> {
         let binding_name = &self.identifier.value;
 
         // TODO: Define if variables can be redeclared.
@@ -29,35 +29,51 @@ impl SemanticCheck for VariableBinding<'_> {
                 note: None,
                 help: None,
             };
-            return Err(econtainer);
+            return Err(vec![econtainer]);
         }
 
-        self.expression.check_semantics(scope)?;
-
         // This gets the datatype of the assigned expression,
         // to compare it later with the declared datatype.
-        let expression_datatype = self.expression.get_type(scope)?;
+        let expression_datatype = match self.expression.check_semantics(scope) {
+            Ok(()) => self.expression.get_type(scope).map_err(|error| vec![error]),
+            Err(errors) => Err(errors),
+        };
+        let expression_datatype = match expression_datatype {
+            Ok(datatype) => datatype,
+            Err(errors) => {
+                // Register the binding anyway, with its declared type if it
+                // has one, so its usages don't report more errors
+                let datatype = match &self.datatype {
+                    Some(t) => Type::from(t),
+                    None => Type::Unknown,
+                };
+                scope.declare(self.identifier, datatype, self.is_mutable);
+
+                return Err(errors);
+            }
+        };
 
-        let datatype = match self.datatype {
-            Some(t) => Type::Value(t.value.clone()),
+        let datatype = match &self.datatype {
+            Some(t) => Type::from(t),
             // If the datatype is not defined, we use the expression datatype
             None => expression_datatype.clone(),
         };
 
         // Both the declared & actual datatypes must be the same
-        if datatype != expression_datatype {
+        let resolved_datatype = datatype.resolve(scope);
+        if !is_assignable_expression(&self.expression, &expression_datatype, &resolved_datatype) {
             // This can only happen if the binding has an annotated type,
             // so its safe to unwrap here
-            let datatype_token = self.datatype.unwrap();
+            let (datatype_start, datatype_end) = self.datatype.as_ref().unwrap().get_position();
 
             let label1 = ErrorLabel {
-                message: format!("The variable is declared as {:?} here", datatype),
-                start: datatype_token.position,
-                end: datatype_token.get_end_position(),
+                message: format!("The variable is declared as {} here", datatype),
+                start: datatype_start,
+                end: datatype_end,
             };
             let (expr_start, expr_end) = self.expression.get_position();
             let label2 = ErrorLabel {
-                message: format!("But this expression has type {:?}", expression_datatype),
+                message: format!("But this expression has type {}", expression_datatype),
                 start: expr_start,
                 end: expr_end,
             };
@@ -69,10 +85,17 @@ impl SemanticCheck for VariableBinding<'_> {
                 note: None,
                 help: None,
             };
-            return Err(econtainer);
+
+            // Register the binding with its declared type anyway,
+            // so its usages don't report more errors
+            scope.declare(self.identifier, datatype, self.is_mutable);
+
+            return Err(vec![econtainer]);
         }
 
-        scope.insert_custom(binding_name.clone(), datatype, self.is_mutable);
+        self.to_float
+            .set(expression_datatype.converts_to_float(&resolved_datatype));
+        scope.declare(self.identifier, datatype, self.is_mutable);
 
         Ok(())
     }
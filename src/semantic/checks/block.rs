@@ -5,9 +5,17 @@ use crate::{
 };
 
 impl SemanticCheck for Block<'_> {
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        // Check every member, collecting all the errors
+        let mut errors = Vec::new();
         for member in &self.members {
-            member.check_semantics(scope)?;
+            if let Err(member_errors) = member.check_semantics(scope) {
+                errors.extend(member_errors);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
         }
 
         Ok(())
@@ -18,7 +26,7 @@ impl<'a> SemanticCheck for BlockMember<'a> {
     // TODO: A block may contain a function declaration statement,
     // but (afaik) those are not allowed inside conditionals/loops
     // somehow detect those?
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
         match self {
             BlockMember::Stmt(s) => s.check_semantics(scope),
             BlockMember::Expr(e) => e.check_semantics(scope),
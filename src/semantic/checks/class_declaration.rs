@@ -0,0 +1,441 @@
+use crate::{
+    error_handling::{
+        error_messages::{
+            SEMANTIC_DUPLICATED_REFERENCE, SEMANTIC_INVALID_REFERENCE, SEMANTIC_MISMATCHED_TYPES,
+            SEMANTIC_MISSING_REFERENCE, SEMANTIC_UNIMPLEMENTED_INTERFACE,
+        },
+        ErrorContainer, ErrorLabel, MistiError,
+    },
+    lexic::token::{Token, TokenType},
+    semantic::{
+        impls::SemanticCheck,
+        symbol_table::{Member, SymbolTable},
+        types::{mark_declared_types, Type, Typed},
+    },
+    syntax::ast::{
+        class::{ClassDeclaration, ClassMember},
+        var_binding::VariableBinding,
+        Positionable,
+    },
+};
+
+use super::function_declaration::{check_function, function_type};
+
+impl SemanticCheck for ClassDeclaration<'_> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        let class_name = &self.identifier.value;
+
+        // Check that the class is not already defined
+        if scope.test(class_name)
+            || scope.get_enum(class_name).is_some()
+            || scope.get_interface(class_name).is_some()
+        {
+            let label = ErrorLabel {
+                message: format!(
+                    "A symbol with name {} was already defined at this scope",
+                    class_name,
+                ),
+                start: self.identifier.position,
+                end: self.identifier.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SEMANTIC_DUPLICATED_REFERENCE,
+                error_offset: self.identifier.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            return Err(vec![econtainer]);
+        }
+
+        let class_type = Type::Value(class_name.clone());
+        let parameters = match &self.constructor {
+            Some(constructor) => constructor.parameters.as_slice(),
+            None => &[],
+        };
+
+        // The name of the class is its constructor. It's registered before
+        // checking the members, so that they can create instances, and
+        // even if they have errors, so that its usages don't report more errors
+        let parameter_types = parameters
+            .iter()
+            .map(|parameter| Type::from(&parameter.datatype))
+            .collect();
+        let constructor_type = Type::Function(parameter_types, Box::new(class_type.clone()));
+        scope.declare_function(self.identifier, constructor_type);
+
+        let mut errors = Vec::new();
+        let mut members = Vec::new();
+
+        // The parameters declared with `val` or `var` are properties
+        for parameter in parameters.iter() {
+            if let Some(modifier) = parameter.modifier {
+                let member = Member {
+                    name: parameter.identifier.value.clone(),
+                    datatype: Type::from(&parameter.datatype),
+                    is_mutable: modifier.token_type == TokenType::VAR,
+                };
+                add_member(&mut members, member, parameter.identifier, &mut errors);
+            }
+        }
+
+        // Fields are initialized in the constructor, where its parameters are available
+        let constructor_scope = SymbolTable::new_from_parent(scope);
+        for parameter in parameters.iter() {
+            let datatype = Type::from(&parameter.datatype);
+            constructor_scope.declare(parameter.identifier, datatype, false);
+        }
+
+        for member in self.members() {
+            let (identifier, datatype, is_mutable) = match member {
+                ClassMember::Field(field) => {
+                    let datatype = match check_field(field, &constructor_scope) {
+                        Ok(datatype) => datatype,
+                        Err(field_errors) => {
+                            errors.extend(field_errors);
+                            // Register the field with its declared type anyway,
+                            // so its usages don't report more errors
+                            match &field.datatype {
+                                Some(datatype) => Type::from(datatype),
+                                None => continue,
+                            }
+                        }
+                    };
+                    (field.identifier, datatype, field.is_mutable)
+                }
+                ClassMember::Method(method) => (method.identifier, function_type(method), false),
+            };
+
+            let member = Member {
+                name: identifier.value.clone(),
+                datatype,
+                is_mutable,
+            };
+            add_member(&mut members, member, identifier, &mut errors);
+        }
+
+        // The class is registered as an implementation of its interfaces
+        // before checking its methods, so that they can use `self` as one
+        for interface in self.interfaces.iter() {
+            errors.extend(check_implementation(interface, class_name, &members, scope));
+        }
+
+        scope.declare_class(class_name.clone(), members);
+
+        // The types of the properties may be the class itself
+        for parameter in parameters.iter() {
+            mark_declared_types(&parameter.datatype, scope);
+        }
+        for member in self.members() {
+            if let ClassMember::Field(VariableBinding {
+                datatype: Some(datatype),
+                ..
+            }) = member
+            {
+                mark_declared_types(datatype, scope);
+            }
+        }
+
+        // Methods access the instance with `self`
+        let class_scope = SymbolTable::new_from_parent(scope);
+        class_scope.insert(String::from("self"), class_type);
+
+        for member in self.members() {
+            if let ClassMember::Method(method) = member {
+                if let Err(method_errors) = check_function(
+                    &method.params_list,
+                    &method.return_type,
+                    &method.block,
+                    &class_scope,
+                ) {
+                    errors.extend(method_errors);
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds a member to the members of a class, unless
+/// another member with the same name was already added
+fn add_member(
+    members: &mut Vec<Member>,
+    member: Member,
+    identifier: &Token,
+    errors: &mut Vec<MistiError>,
+) {
+    if members.iter().any(|m| m.name == member.name) {
+        let label = ErrorLabel {
+            message: format!(
+                "A member with name {} was already declared in this class",
+                member.name
+            ),
+            start: identifier.position,
+            end: identifier.get_end_position(),
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_DUPLICATED_REFERENCE,
+            error_offset: identifier.position,
+            labels: vec![label],
+            note: None,
+            help: None,
+        };
+        errors.push(econtainer);
+        return;
+    }
+
+    members.push(member);
+}
+
+/// Checks that the class `class_name`, with the members `members`,
+/// has every method of the interface `interface` with a compatible type,
+/// and registers the class as an implementation of the interface
+fn check_implementation(
+    interface: &Token,
+    class_name: &String,
+    members: &[Member],
+    scope: &SymbolTable,
+) -> Vec<MistiError> {
+    let interface_name = &interface.value;
+    let Some(methods) = scope.get_interface(interface_name) else {
+        let (error_code, message) =
+            if scope.test(interface_name) || scope.get_enum(interface_name).is_some() {
+                (
+                    SEMANTIC_INVALID_REFERENCE,
+                    format!("{} is not an interface", interface_name),
+                )
+            } else {
+                (
+                    SEMANTIC_MISSING_REFERENCE,
+                    format!("Cannot find the interface {} in this scope", interface_name),
+                )
+            };
+        return vec![interface_error(error_code, message, interface)];
+    };
+
+    scope.declare_implementation(interface_name.clone(), class_name.clone());
+
+    let mut errors = Vec::new();
+    for method in methods.iter() {
+        let Some(member) = members.iter().find(|m| m.name == method.name) else {
+            errors.push(interface_error(
+                SEMANTIC_UNIMPLEMENTED_INTERFACE,
+                format!(
+                    "The class {} is missing the method `{}` of the interface {}",
+                    class_name, method.name, interface_name
+                ),
+                interface,
+            ));
+            continue;
+        };
+
+        // PHP doesn't convert Ints to Floats in the signatures of methods
+        let member_type = member.datatype.resolve(scope);
+        if !member_type.is_subtype_of(&method.datatype.resolve(scope)) {
+            errors.push(interface_error(
+                SEMANTIC_UNIMPLEMENTED_INTERFACE,
+                format!(
+                    "The member `{}` of the class {} has type {}, but the interface {} requires {}",
+                    method.name, class_name, member.datatype, interface_name, method.datatype
+                ),
+                interface,
+            ));
+        }
+    }
+
+    errors
+}
+
+fn interface_error(error_code: u32, message: String, interface: &Token) -> MistiError {
+    let label = ErrorLabel {
+        message,
+        start: interface.position,
+        end: interface.get_end_position(),
+    };
+    ErrorContainer {
+        error_code,
+        error_offset: interface.position,
+        labels: vec![label],
+        note: None,
+        help: None,
+    }
+}
+
+/// Checks the value of a field, and returns the type of the field
+fn check_field(field: &VariableBinding, scope: &SymbolTable) -> Result<Type, Vec<MistiError>> {
+    field.expression.check_semantics(scope)?;
+    let expression_type = field.expression.get_type(scope)?;
+
+    // If the datatype is not defined, the type of the value is used
+    let Some(declared_datatype) = &field.datatype else {
+        return Ok(expression_type);
+    };
+
+    let datatype = Type::from(declared_datatype);
+    if !expression_type.is_assignable_to(&datatype.resolve(scope)) {
+        let (datatype_start, datatype_end) = declared_datatype.get_position();
+        let label1 = ErrorLabel {
+            message: format!("The property is declared as {} here", datatype),
+            start: datatype_start,
+            end: datatype_end,
+        };
+        let (expr_start, expr_end) = field.expression.get_position();
+        let label2 = ErrorLabel {
+            message: format!("But this expression has type {}", expression_type),
+            start: expr_start,
+            end: expr_end,
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_MISMATCHED_TYPES,
+            error_offset: expr_start,
+            labels: vec![label1, label2],
+            note: None,
+            help: None,
+        };
+        return Err(vec![econtainer]);
+    }
+
+    Ok(datatype)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error_handling::error_messages::{
+            SEMANTIC_DUPLICATED_REFERENCE, SEMANTIC_IMMUTABLE_VARIABLE, SEMANTIC_INVALID_REFERENCE,
+            SEMANTIC_MISMATCHED_TYPES, SEMANTIC_MISSING_REFERENCE,
+        },
+        semantic::test_utils::check,
+    };
+
+    const POINT: &str = "class Point(val Int x, var Int y) {
+    var Int moves = 0
+    val label = \"point\"
+
+    fun move(Int dy) {
+        self.y += dy
+        self.moves += 1
+    }
+
+    fun sum() -> Int {
+        self.x + self.y
+    }
+}
+";
+
+    #[test]
+    fn should_check_classes() {
+        let input = format!(
+            "{}val p = Point(1, 2)\np.move(3)\nInt total = p.sum() + p.x\nString label = p.label",
+            POINT
+        );
+        assert!(check(&input).is_ok());
+    }
+
+    #[test]
+    fn should_check_constructor_arguments() {
+        let errors = check(&format!("{}Point(1, \"2\")", POINT)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!("Expected a Int, got String", errors[0].labels[0].message);
+    }
+
+    #[test]
+    fn should_check_method_arguments() {
+        let errors = check(&format!("{}Point(1, 2).move(\"3\")", POINT)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!("Expected a Int, got String", errors[0].labels[0].message);
+    }
+
+    #[test]
+    fn should_not_assign_immutable_properties() {
+        let errors = check(&format!("{}val p = Point(1, 2)\np.x = 3", POINT)).unwrap_err();
+        assert_eq!(SEMANTIC_IMMUTABLE_VARIABLE, errors[0].error_code);
+
+        let errors = check(&format!("{}val p = Point(1, 2)\np.label = \"a\"", POINT)).unwrap_err();
+        assert_eq!(SEMANTIC_IMMUTABLE_VARIABLE, errors[0].error_code);
+
+        assert!(check(&format!(
+            "{}val p = Point(1, 2)\np.y = 3\np.moves = 0",
+            POINT
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn should_check_assigned_property_types() {
+        let errors = check(&format!("{}val p = Point(1, 2)\np.y = \"3\"", POINT)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!("This property has type Int", errors[0].labels[0].message);
+    }
+
+    #[test]
+    fn should_report_missing_members() {
+        let errors = check(&format!("{}Point(1, 2).z", POINT)).unwrap_err();
+        assert_eq!(SEMANTIC_INVALID_REFERENCE, errors[0].error_code);
+        assert_eq!(
+            "The type Point has no member `z`",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_only_access_members_through_self() {
+        let errors =
+            check("class A(val Int x) {\n    fun f() -> Int {\n        x\n    }\n}").unwrap_err();
+        assert_eq!(SEMANTIC_MISSING_REFERENCE, errors[0].error_code);
+
+        let errors = check("val a = self").unwrap_err();
+        assert_eq!(SEMANTIC_MISSING_REFERENCE, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_initialize_fields_with_constructor_parameters() {
+        assert!(check("class A(Int x) {\n    val Int double = x * 2\n}\nA(1).double").is_ok());
+
+        // Parameters without `val` or `var` are not properties
+        let errors = check("class A(Int x)\nA(1).x").unwrap_err();
+        assert_eq!(SEMANTIC_INVALID_REFERENCE, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_check_field_types() {
+        let errors = check("class A {\n    val Int x = \"a\"\n}").unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!(
+            "The property is declared as Int here",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_report_duplicated_members() {
+        let errors = check("class A(val Int x) {\n    val x = 1\n}").unwrap_err();
+        assert_eq!(SEMANTIC_DUPLICATED_REFERENCE, errors[0].error_code);
+        assert_eq!(
+            "A member with name x was already declared in this class",
+            errors[0].labels[0].message
+        );
+
+        let errors = check("class A\nclass A").unwrap_err();
+        assert_eq!(SEMANTIC_DUPLICATED_REFERENCE, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_check_methods() {
+        let errors = check("class A {\n    fun f() -> Int {\n        \"a\"\n    }\n}").unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_access_nullable_instances() {
+        assert!(check(&format!("{}?Point p = null\n?Int x = p?.x", POINT)).is_ok());
+
+        let errors = check(&format!("{}?Point p = null\np.x", POINT)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+    }
+}
@@ -1,78 +1,229 @@
+use super::return_statement::check_function_body;
 use crate::{
     error_handling::{
         error_messages::SEMANTIC_MISMATCHED_TYPES, ErrorContainer, ErrorLabel, MistiError,
     },
+    lexic::token::Token,
     semantic::{
         impls::SemanticCheck,
         symbol_table::SymbolTable,
-        types::{Type, Typed},
+        types::{global::NULL, Type, Typed},
     },
-    syntax::ast::{Conditional, Positionable},
+    syntax::ast::{Block, Conditional, Expression, Positionable},
 };
 
 impl SemanticCheck for Conditional<'_> {
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError> {
-        let bool_type = Type::Value(String::from("Bool"));
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        self.check_branches(scope, None)
+    }
+}
 
-        // Check if condition is a Bool
-        let if_condition = &self.if_member.condition;
-        let if_condition_type = if_condition.get_type(scope)?;
-        if !if_condition_type.equals(&bool_type) {
-            let (error_start, error_end) = if_condition.get_position();
-            let label = ErrorLabel {
-                message: format!(
-                    "Expected a condition of type Bool, found {:?}",
-                    if_condition_type
-                ),
-                start: error_start,
-                end: error_end,
-            };
-            let econtainer = ErrorContainer {
-                error_code: SEMANTIC_MISMATCHED_TYPES,
-                error_offset: error_start,
-                labels: vec![label],
-                note: None,
-                help: None,
-            };
-            return Err(econtainer);
-        }
+impl Conditional<'_> {
+    /// Checks the conditions and blocks of this conditional. If it's at the
+    /// end of a function, `tail_return_type` is the return type of the
+    /// function, and the expressions at the end of the blocks are checked
+    /// against it
+    pub fn check_branches(
+        &self,
+        scope: &SymbolTable,
+        tail_return_type: Option<&Type>,
+    ) -> Result<(), Vec<MistiError>> {
+        let bool_type = Type::Value(String::from("Bool"));
+        // Every condition and block is checked, even if a previous one failed
+        let mut errors = Vec::new();
+        let scopes = self.branch_scopes(scope);
 
-        // Check if block
-        let if_block = &self.if_member.body;
-        if_block.check_semantics(scope)?;
-
-        // Check all else if
-        for else_if_member in self.else_if_members.iter() {
-            let condition = &else_if_member.condition;
-            let condition_type = condition.get_type(scope)?;
-            if !condition_type.equals(&bool_type) {
-                let (error_start, error_end) = condition.get_position();
-                let label = ErrorLabel {
-                    message: format!(
-                        "Expected a condition of type Bool, found {:?}",
-                        condition_type
-                    ),
-                    start: error_start,
-                    end: error_end,
-                };
-                let econtainer = ErrorContainer {
-                    error_code: SEMANTIC_MISMATCHED_TYPES,
-                    error_offset: error_start,
-                    labels: vec![label],
-                    note: None,
-                    help: None,
-                };
-                return Err(econtainer);
+        let members = std::iter::once(&self.if_member).chain(self.else_if_members.iter());
+        for (member, (condition_scope, block_scope)) in members.zip(scopes.members.iter()) {
+            // Check if condition is a Bool
+            let condition_scope = condition_scope.as_ref().unwrap_or(scope);
+            if let Err(condition_errors) =
+                check_condition(&member.condition, &bool_type, condition_scope)
+            {
+                errors.extend(condition_errors);
             }
 
-            else_if_member.body.check_semantics(scope)?;
+            // Check the block
+            let block_scope = block_scope.as_ref().unwrap_or(scope);
+            if let Err(block_errors) = check_block(&member.body, block_scope, tail_return_type) {
+                errors.extend(block_errors);
+            }
         }
 
         // Check else
         if let Some(else_block) = &self.else_block {
-            else_block.check_semantics(scope)?;
+            let else_scope = scopes.else_block.as_ref().unwrap_or(scope);
+            if let Err(block_errors) = check_block(else_block, else_scope, tail_return_type) {
+                errors.extend(block_errors);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
         }
 
         Ok(())
     }
+
+    /// Computes the scopes of the conditions and blocks of this conditional.
+    ///
+    /// In `if x != null` x is not null inside the block, and in
+    /// `if x is Int {} else {}` x is not an Int inside the else block.
+    /// The conditions of else ifs and the else block are narrowed
+    /// by all the previous conditions being false
+    fn branch_scopes(&self, scope: &SymbolTable) -> BranchScopes {
+        let mut members = Vec::new();
+        // The scope where all the previous conditions are false
+        let mut else_scope: Option<SymbolTable> = None;
+
+        let conditions = std::iter::once(&self.if_member).chain(self.else_if_members.iter());
+        for member in conditions {
+            let condition_scope = else_scope.clone();
+            let parent_scope = condition_scope.as_ref().unwrap_or(scope);
+
+            match narrowing(&member.condition, parent_scope) {
+                Some((variable, when_true, when_false)) => {
+                    let block_scope = narrowed_scope(parent_scope, variable, &when_true);
+                    else_scope = Some(narrowed_scope(parent_scope, variable, &when_false));
+                    members.push((condition_scope, Some(block_scope)));
+                }
+                None => members.push((condition_scope.clone(), condition_scope)),
+            }
+        }
+
+        BranchScopes {
+            members,
+            else_block: else_scope,
+        }
+    }
+}
+
+/// Checks a block of a conditional, and its tail expressions if
+/// the conditional is at the end of a function
+fn check_block(
+    block: &Block,
+    scope: &SymbolTable,
+    tail_return_type: Option<&Type>,
+) -> Result<(), Vec<MistiError>> {
+    match tail_return_type {
+        Some(return_type) => check_function_body(block, return_type, scope),
+        None => block.check_semantics(scope),
+    }
+}
+
+/// The scopes where the parts of a conditional are checked.
+/// `None` means the parent scope, otherwise it's a child scope
+/// where the types of some variables are narrowed
+struct BranchScopes {
+    /// The scopes of the condition and block of the if and each else if
+    members: Vec<(Option<SymbolTable>, Option<SymbolTable>)>,
+    else_block: Option<SymbolTable>,
+}
+
+/// If `condition` tests the type of a variable, e.g. `x is Int` or
+/// `x != null`, returns the variable, its type when the condition is true
+/// and its type when the condition is false.
+///
+/// Mutable variables are not narrowed, they could be
+/// assigned a value of another type inside the block
+fn narrowing<'a>(
+    condition: &Expression<'a>,
+    scope: &SymbolTable,
+) -> Option<(&'a Token, Type, Type)> {
+    let (variable, tested_type, is_negated) = type_check(condition)?;
+    let variable_type = match scope.get_type_and_mut(&variable.value) {
+        Some((_, false)) => scope.get_type(&variable.value)?,
+        _ => return None,
+    };
+
+    let other_type = variable_type.without(&tested_type);
+    match is_negated {
+        false => Some((variable, tested_type, other_type)),
+        true => Some((variable, other_type, tested_type)),
+    }
+}
+
+/// If `condition` checks the type of a variable, returns the
+/// variable, the type checked and if the check is negated.
+/// E.g. `x == null` checks that x is `Null`, and `x != null`
+/// is the negation of that
+fn type_check<'a>(condition: &Expression<'a>) -> Option<(&'a Token, Type, bool)> {
+    match condition {
+        Expression::TypeTest(type_test) => match &*type_test.expression {
+            Expression::Identifier(variable) => {
+                Some((variable.token, Type::from(&type_test.datatype), false))
+            }
+            _ => None,
+        },
+        Expression::UnaryOperator(operator, expression) if operator.value == "!" => {
+            let (variable, tested_type, is_negated) = type_check(expression)?;
+            Some((variable, tested_type, !is_negated))
+        }
+        Expression::BinaryOperator(left_expr, right_expr, operator) => {
+            let is_negated = match operator.value.as_str() {
+                "==" => false,
+                "!=" => true,
+                _ => return None,
+            };
+
+            match (&**left_expr, &**right_expr) {
+                (Expression::Identifier(variable), Expression::Null(_))
+                | (Expression::Null(_), Expression::Identifier(variable)) => {
+                    Some((variable.token, Type::Value(String::from(NULL)), is_negated))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// If `condition` tests the type of a variable, returns a scope where
+/// the variable has the type it has when the condition is `is_true`
+pub fn narrowed_by(
+    condition: &Expression,
+    is_true: bool,
+    scope: &SymbolTable,
+) -> Option<SymbolTable> {
+    let (variable, when_true, when_false) = narrowing(condition, scope)?;
+    let datatype = if is_true { when_true } else { when_false };
+    Some(narrowed_scope(scope, variable, &datatype))
+}
+
+/// Creates a scope where `variable` has the type `datatype`
+fn narrowed_scope(scope: &SymbolTable, variable: &Token, datatype: &Type) -> SymbolTable {
+    let narrowed_scope = SymbolTable::new_from_parent(scope);
+    narrowed_scope.narrow(variable.value.clone(), datatype.clone());
+    narrowed_scope
+}
+
+/// Checks that the condition of an if/else if is a Bool
+fn check_condition(
+    condition: &Expression,
+    bool_type: &Type,
+    scope: &SymbolTable,
+) -> Result<(), Vec<MistiError>> {
+    condition.check_semantics(scope)?;
+    let condition_type = condition.get_type(scope)?;
+    if !condition_type.is_assignable_to(bool_type) {
+        let (error_start, error_end) = condition.get_position();
+        let label = ErrorLabel {
+            message: format!(
+                "Expected a condition of type Bool, found {}",
+                condition_type
+            ),
+            start: error_start,
+            end: error_end,
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_MISMATCHED_TYPES,
+            error_offset: error_start,
+            labels: vec![label],
+            note: None,
+            help: None,
+        };
+        return Err(vec![econtainer]);
+    }
+
+    Ok(())
 }
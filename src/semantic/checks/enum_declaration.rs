@@ -0,0 +1,305 @@
+use crate::{
+    error_handling::{
+        error_messages::SEMANTIC_DUPLICATED_REFERENCE, ErrorContainer, ErrorLabel, MistiError,
+    },
+    lexic::token::Token,
+    semantic::{
+        impls::SemanticCheck,
+        symbol_table::{Member, SymbolTable, Variant},
+        types::{mark_declared_types, Type},
+    },
+    syntax::ast::enum_declaration::EnumDeclaration,
+};
+
+impl SemanticCheck for EnumDeclaration<'_> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        let enum_name = &self.identifier.value;
+
+        // Check that the enum is not already defined
+        if scope.test(enum_name)
+            || scope.get_enum(enum_name).is_some()
+            || scope.get_interface(enum_name).is_some()
+        {
+            let label = ErrorLabel {
+                message: format!(
+                    "A symbol with name {} was already defined at this scope",
+                    enum_name,
+                ),
+                start: self.identifier.position,
+                end: self.identifier.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SEMANTIC_DUPLICATED_REFERENCE,
+                error_offset: self.identifier.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            return Err(vec![econtainer]);
+        }
+
+        let mut errors = Vec::new();
+        let mut variants = Vec::<Variant>::new();
+
+        for variant in self.variants.iter() {
+            let mut fields = Vec::<Member>::new();
+            let parameters = variant.payload.iter().flat_map(|p| p.parameters.iter());
+            for parameter in parameters {
+                let name = &parameter.identifier.value;
+                if fields.iter().any(|f| f.name == *name) {
+                    errors.push(duplicated_error(
+                        format!(
+                            "A field with name {} was already declared in this variant",
+                            name
+                        ),
+                        parameter.identifier,
+                    ));
+                    continue;
+                }
+
+                fields.push(Member {
+                    name: name.clone(),
+                    datatype: Type::from(&parameter.datatype),
+                    is_mutable: false,
+                });
+            }
+
+            let name = &variant.identifier.value;
+            if variants.iter().any(|v| v.name == *name) {
+                errors.push(duplicated_error(
+                    format!(
+                        "A variant with name {} was already declared in this enum",
+                        name
+                    ),
+                    variant.identifier,
+                ));
+                continue;
+            }
+
+            variants.push(Variant {
+                name: name.clone(),
+                fields,
+            });
+        }
+
+        // The enum is registered even if it has errors,
+        // so that its usages don't report more errors
+        scope.declare_enum(enum_name.clone(), variants);
+
+        // The fields may be of the enum itself
+        for variant in self.variants.iter() {
+            for parameter in variant.payload.iter().flat_map(|p| p.parameters.iter()) {
+                mark_declared_types(&parameter.datatype, scope);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+}
+
+fn duplicated_error(message: String, identifier: &Token) -> MistiError {
+    let label = ErrorLabel {
+        message,
+        start: identifier.position,
+        end: identifier.get_end_position(),
+    };
+    ErrorContainer {
+        error_code: SEMANTIC_DUPLICATED_REFERENCE,
+        error_offset: identifier.position,
+        labels: vec![label],
+        note: None,
+        help: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error_handling::error_messages::{
+            COMPILER_TODO, SEMANTIC_DUPLICATED_REFERENCE, SEMANTIC_INVALID_REFERENCE,
+            SEMANTIC_MISMATCHED_ARGUMENT_COUNT, SEMANTIC_MISMATCHED_TYPES,
+            SEMANTIC_MISSING_REFERENCE, SEMANTIC_NON_EXHAUSTIVE_MATCH,
+        },
+        semantic::test_utils::check,
+    };
+
+    const SUIT: &str = "enum Suit {\n    Hearts\n    Spades\n}\n";
+
+    const SHAPE: &str = "enum Shape {
+    Circle(Float radius)
+    Rect(Float width, Float height)
+    Empty
+}
+";
+
+    #[test]
+    fn should_check_enums() {
+        let input = format!(
+            "{}{}Suit s = Suit::Hearts\nval c = Shape::Circle(1.5)\nShape r = Shape::Rect(1, 2.0)\nval e = Shape::Empty",
+            SUIT, SHAPE
+        );
+        assert!(check(&input).is_ok());
+    }
+
+    #[test]
+    fn should_check_variant_arguments() {
+        let errors = check(&format!("{}Shape::Circle(\"1\")", SHAPE)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+
+        let errors = check(&format!("{}Shape::Rect(1.0)", SHAPE)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_ARGUMENT_COUNT, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_call_only_variants_with_payloads() {
+        let errors = check(&format!("{}val c = Shape::Circle", SHAPE)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!(
+            Some(String::from("Pass its payload: `Shape::Circle(...)`")),
+            errors[0].help
+        );
+
+        let errors = check(&format!("{}val e = Shape::Empty()", SHAPE)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!(
+            Some(String::from("Remove the parentheses: `Shape::Empty`")),
+            errors[0].help
+        );
+
+        let input = format!(
+            "{}val s = Shape::Empty\nval n = match s\ncase Shape::Empty() {{ 0 }}\nelse {{ 1 }}",
+            SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+
+        let errors = check(&format!("{}val h = Suit::Hearts()", SUIT)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!(
+            Some(String::from("Remove the parentheses: `Suit::Hearts`")),
+            errors[0].help
+        );
+    }
+
+    #[test]
+    fn should_report_missing_enums_and_variants() {
+        let errors = check("Suit::Hearts").unwrap_err();
+        assert_eq!(SEMANTIC_MISSING_REFERENCE, errors[0].error_code);
+
+        let errors = check(&format!("{}Suit::Clubs", SUIT)).unwrap_err();
+        assert_eq!(SEMANTIC_INVALID_REFERENCE, errors[0].error_code);
+        assert_eq!(
+            "The enum Suit has no variant `Clubs`",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_not_mix_enums() {
+        let errors = check(&format!("{}{}Suit s = Shape::Empty", SUIT, SHAPE)).unwrap_err();
+        assert_eq!(
+            "But this expression has type Shape",
+            errors[0].labels[1].message
+        );
+    }
+
+    #[test]
+    fn should_report_duplicates() {
+        let errors = check(&format!("{}{}", SUIT, SUIT)).unwrap_err();
+        assert_eq!(SEMANTIC_DUPLICATED_REFERENCE, errors[0].error_code);
+
+        let errors = check("enum Suit {\n    Hearts\n    Hearts\n}").unwrap_err();
+        assert_eq!(
+            "A variant with name Hearts was already declared in this enum",
+            errors[0].labels[0].message
+        );
+
+        let errors = check("enum Shape {\n    Rect(Float side, Float side)\n}").unwrap_err();
+        assert_eq!(
+            "A field with name side was already declared in this variant",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_match_variants() {
+        let input = format!(
+            "{}fun area(Shape shape) -> Float {{\n    match shape\n    case Shape::Circle(radius) {{ radius * radius * 3.14 }}\n    case Shape::Rect(height, width) if width > 0.0 {{ width * height }}\n    case Shape::Rect() {{ 0.0 }}\n    case Shape::Empty {{ 0.0 }}\n}}",
+            SHAPE
+        );
+        assert!(check(&input).is_ok());
+
+        let input = format!(
+            "{}fun name(Suit suit) -> String {{\n    match suit\n    case Suit::Hearts {{ \"hearts\" }}\n    case Suit::Spades {{ \"spades\" }}\n}}",
+            SUIT
+        );
+        assert!(check(&input).is_ok());
+    }
+
+    #[test]
+    fn should_check_exhaustiveness_of_enums() {
+        let input = format!(
+            "{}fun f(Shape shape) -> Int {{\n    match shape\n    case Shape::Circle(radius) {{ 1 }}\n    case Shape::Rect(width, height) if width > height {{ 2 }}\n}}",
+            SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_NON_EXHAUSTIVE_MATCH, errors[0].error_code);
+        assert_eq!(
+            "This match doesn't handle the values: Shape::Rect, Shape::Empty",
+            errors[0].labels[0].message
+        );
+
+        let input = format!(
+            "{}?Suit suit = null\nval n = match suit\ncase Suit::Hearts {{ 1 }}\ncase null {{ 0 }}",
+            SUIT
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(
+            "This match doesn't handle the values: Suit::Spades",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_check_variant_patterns() {
+        // Bindings are the names of fields
+        let input = format!(
+            "{}val s = Shape::Empty\nval n = match s\ncase Shape::Circle(r) {{ r }}\nelse {{ 0.0 }}",
+            SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_INVALID_REFERENCE, errors[0].error_code);
+        assert_eq!(
+            "The variant Shape::Circle has no field `r`",
+            errors[0].labels[0].message
+        );
+
+        // Bindings are only visible inside their arm
+        let input = format!(
+            "{}val s = Shape::Empty\nval n = match s\ncase Shape::Circle(radius) {{ radius }}\nelse {{ radius }}",
+            SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_MISSING_REFERENCE, errors[0].error_code);
+
+        // Patterns with bindings must be alone in their arm
+        let input = format!(
+            "{}val s = Shape::Empty\nval n = match s\ncase Shape::Circle(radius), Shape::Empty {{ 1 }}\nelse {{ 0 }}",
+            SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(COMPILER_TODO, errors[0].error_code);
+
+        // Patterns must be of the type of the subject
+        let input = format!(
+            "{}{}val s = Shape::Empty\nval n = match s\ncase Suit::Hearts {{ 1 }}\nelse {{ 0 }}",
+            SUIT, SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+    }
+}
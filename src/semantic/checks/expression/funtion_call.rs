@@ -1,107 +1,224 @@
+use std::collections::HashMap;
+
 use crate::{
     error_handling::{
-        error_messages::{SEMANTIC_MISMATCHED_ARGUMENT_COUNT, SEMANTIC_MISMATCHED_TYPES},
+        error_messages::{
+            SEMANTIC_CANNOT_INFER_TYPE, SEMANTIC_MISMATCHED_ARGUMENT_COUNT,
+            SEMANTIC_MISMATCHED_TYPES,
+        },
         ErrorContainer, ErrorLabel,
     },
     semantic::{
         impls::SemanticCheck,
         symbol_table::SymbolTable,
-        types::{Type, Typed},
+        types::{is_assignable_expression, Type, Typed},
     },
-    syntax::ast::{functions::FunctionCall, Positionable},
+    syntax::ast::{functions::FunctionCall, Expression, Positionable},
 };
 
+use super::called_variant_error;
+
 impl SemanticCheck for FunctionCall<'_> {
     fn check_semantics(
         &self,
         scope: &SymbolTable,
-    ) -> Result<(), crate::error_handling::MistiError> {
-        let fun = &*self.function;
-        let arguments = &*self.arguments.arguments;
+    ) -> Result<(), Vec<crate::error_handling::MistiError>> {
+        // Callees other than names are checked like any expression,
+        // e.g. the body of a lambda or the arguments of a call that returns a function
+        if !matches!(
+            &*self.function,
+            Expression::Identifier(_) | Expression::EnumVariant(_, _, _)
+        ) {
+            self.function.check_semantics(scope)?;
+        }
 
-        let function_datatype = fun.get_type(scope)?;
-        let Type::Function(parameters, _) = function_datatype else {
-            let (error_start, error_end) = fun.get_position();
-            let label = ErrorLabel {
-                message: format!(
-                    "Expected this expression to be a function, found a {:?}",
-                    function_datatype
-                ),
-                start: error_start,
-                end: error_end,
-            };
-            let econtainer = ErrorContainer {
-                error_code: SEMANTIC_MISMATCHED_TYPES,
-                error_offset: error_start,
-                labels: vec![label],
-                note: None,
-                help: None,
-            };
-            return Err(econtainer);
+        let function_datatype = self.function.get_type(scope)?;
+
+        // The variants without a payload are values, not functions
+        if let (Expression::EnumVariant(enum_name, variant, _), Type::Enum(_, _)) =
+            (&*self.function, &function_datatype)
+        {
+            return Err(vec![called_variant_error(enum_name, variant, self)]);
+        }
+
+        check_arguments(self, function_datatype, scope)
+    }
+}
+
+/// Checks that the arguments of `call` can be passed to a
+/// function of type `function_datatype`, e.g. a method
+pub fn check_arguments(
+    call: &FunctionCall,
+    function_datatype: Type,
+    scope: &SymbolTable,
+) -> Result<(), Vec<crate::error_handling::MistiError>> {
+    let fun = &*call.function;
+    let arguments = &*call.arguments.arguments;
+
+    // The type parameters of a generic function are inferred later
+    let (type_params, function_datatype) = match function_datatype {
+        Type::Polymorphic(type_params, function) => (type_params, *function),
+        t => (vec![], t),
+    };
+    // The error was already reported, only the arguments are checked
+    if let Type::Unknown = function_datatype {
+        let errors: Vec<_> = arguments
+            .iter()
+            .filter_map(|argument| argument.check_semantics(scope).err())
+            .flatten()
+            .collect();
+        return match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
         };
+    }
+    let Type::Function(parameters, _) = function_datatype else {
+        let (error_start, error_end) = fun.get_position();
+        let label = ErrorLabel {
+            message: format!(
+                "Expected this expression to be a function, found {}",
+                function_datatype
+            ),
+            start: error_start,
+            end: error_end,
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_MISMATCHED_TYPES,
+            error_offset: error_start,
+            labels: vec![label],
+            note: None,
+            help: None,
+        };
+        return Err(vec![econtainer]);
+    };
+
+    // Check parameters length
+    if parameters.len() != arguments.len() {
+        let (error_start, error_end) = call.arguments.get_position();
+
+        let label = ErrorLabel {
+            message: format!(
+                "Expected {} arguments, got {}",
+                parameters.len(),
+                arguments.len(),
+            ),
+            start: error_start,
+            end: error_end,
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_MISMATCHED_ARGUMENT_COUNT,
+            error_offset: error_start,
+            labels: vec![label],
+            note: None,
+            help: None,
+        };
+        return Err(vec![econtainer]);
+    }
 
-        // Check parameters length
-        if parameters.len() != arguments.len() {
-            let (error_start, error_end) = self.arguments.get_position();
+    // Check that each argument is valid and get its datatype.
+    // Every argument is checked, even if a previous one failed
+    let mut errors = Vec::new();
+    let mut argument_datatypes = Vec::new();
+    for argument in arguments.iter() {
+        if let Err(argument_errors) = argument.check_semantics(scope) {
+            errors.extend(argument_errors);
+            continue;
+        }
 
+        match argument.get_type(scope) {
+            Ok(t) => argument_datatypes.push(t),
+            Err(error) => errors.push(error),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Infer the type parameters from the arguments,
+    // and replace them in the parameters
+    let mut bindings = HashMap::new();
+    for (parameter, argument_datatype) in parameters.iter().zip(argument_datatypes.iter()) {
+        parameter.infer_type_params(argument_datatype, &type_params, &mut bindings);
+    }
+
+    // Check that each argument matches the required datatype
+    for ((parameter, argument), argument_datatype) in parameters
+        .iter()
+        .zip(arguments.iter())
+        .zip(argument_datatypes.iter())
+    {
+        let parameter = parameter.substitute(&bindings).resolve(scope);
+        if !is_assignable_expression(argument, argument_datatype, &parameter) {
+            // The argument and the parameter have diferent types
+            let (error_start, error_end) = argument.get_position();
             let label = ErrorLabel {
-                message: format!(
-                    "Expected {} arguments, got {}",
-                    parameters.len(),
-                    arguments.len(),
-                ),
+                message: format!("Expected a {}, got {}", parameter, argument_datatype),
                 start: error_start,
                 end: error_end,
             };
             let econtainer = ErrorContainer {
-                error_code: SEMANTIC_MISMATCHED_ARGUMENT_COUNT,
+                error_code: SEMANTIC_MISMATCHED_TYPES,
                 error_offset: error_start,
                 labels: vec![label],
                 note: None,
                 help: None,
             };
-            return Err(econtainer);
+            errors.push(econtainer);
         }
+    }
 
-        // Check that each argument matches the required datatype
-        for i in 0..parameters.len() {
-            let parameter = &parameters[i];
-            let argument = &arguments[i];
-
-            let argument_datatype = argument.get_type(scope)?;
-            if !argument_datatype.is_value(parameter) {
-                // The argument and the parameter have diferent types
-                let (error_start, error_end) = argument.get_position();
-                let label = ErrorLabel {
-                    message: format!("Expected a {}, got {:?}", parameter, argument_datatype),
-                    start: error_start,
-                    end: error_end,
-                };
-                let econtainer = ErrorContainer {
-                    error_code: SEMANTIC_MISMATCHED_TYPES,
-                    error_offset: error_start,
-                    labels: vec![label],
-                    note: None,
-                    help: None,
-                };
-                return Err(econtainer);
-            }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Type parameters that only appear in the return type cannot be
+    // inferred. Mismatched arguments are reported instead, if any
+    for type_param in type_params.iter() {
+        if bindings.contains_key(type_param) {
+            continue;
         }
 
-        Ok(())
+        let (error_start, error_end) = fun.get_position();
+        let label = ErrorLabel {
+            message: format!(
+                "Cannot infer the type parameter {} from the arguments of this call",
+                type_param
+            ),
+            start: error_start,
+            end: error_end,
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_CANNOT_INFER_TYPE,
+            error_offset: error_start,
+            labels: vec![label],
+            note: Some(String::from(
+                "Every type parameter must be used in the parameters of the function",
+            )),
+            help: None,
+        };
+        errors.push(econtainer);
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         error_handling::error_messages::{
-            SEMANTIC_INVALID_REFERENCE, SEMANTIC_MISMATCHED_TYPES, SEMANTIC_MISSING_REFERENCE,
+            SEMANTIC_CANNOT_INFER_TYPE, SEMANTIC_DUPLICATED_REFERENCE, SEMANTIC_IMMUTABLE_VARIABLE,
+            SEMANTIC_INVALID_REFERENCE, SEMANTIC_MISMATCHED_ARGUMENT_COUNT,
+            SEMANTIC_MISMATCHED_TYPES, SEMANTIC_MISSING_REFERENCE,
         },
         lexic::{get_tokens, token::Token},
         semantic::{
             impls::SemanticCheck,
             symbol_table::SymbolTable,
+            test_utils::check,
             types::{global::INT, Type},
         },
         syntax::{
@@ -131,7 +248,8 @@ mod tests {
         let output = expr.check_semantics(&scope);
         match output {
             Ok(_) => panic!("Expected an error"),
-            Err(err) => {
+            Err(errors) => {
+                let err = &errors[0];
                 assert_eq!(err.error_code, SEMANTIC_MISSING_REFERENCE);
                 assert_eq!(err.error_offset, 0);
 
@@ -154,18 +272,154 @@ mod tests {
         let output = expr.check_semantics(&scope);
         match output {
             Ok(_) => panic!("Expected an error"),
-            Err(err) => {
+            Err(errors) => {
+                let err = &errors[0];
                 assert_eq!(err.error_code, SEMANTIC_MISMATCHED_TYPES);
                 assert_eq!(err.error_offset, 0);
 
                 let label = &err.labels[0];
                 assert_eq!(
                     label.message,
-                    "Expected this expression to be a function, found a Value(\"Int\")"
+                    "Expected this expression to be a function, found Int"
                 );
                 assert_eq!(label.start, 0);
                 assert_eq!(label.end, 6);
             }
         }
     }
+
+    #[test]
+    fn should_check_calls_against_declared_signatures() {
+        let declaration = "fun greet(String name, Int times) -> String {\n    name\n}\n";
+
+        let input = format!("{}greet(\"John\", 3)", declaration);
+        assert!(check(&input).is_ok());
+
+        let input = format!("{}greet(\"John\")", declaration);
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_ARGUMENT_COUNT, errors[0].error_code);
+        assert_eq!("Expected 2 arguments, got 1", errors[0].labels[0].message);
+
+        // Every mismatched argument is reported
+        let input = format!("{}greet(3, \"John\")", declaration);
+        let errors = check(&input).unwrap_err();
+        assert_eq!(2, errors.len());
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!("Expected a String, got Int", errors[0].labels[0].message);
+        assert_eq!("Expected a Int, got String", errors[1].labels[0].message);
+    }
+
+    #[test]
+    fn should_check_generic_parameters() {
+        let declaration = "fun sum(Array[Int] items) -> Int {\n    0\n}\n";
+
+        let input = format!("{}sum([1, 2])", declaration);
+        assert!(check(&input).is_ok());
+
+        let input = format!("{}sum([\"a\"])", declaration);
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!(
+            "Expected a Array[Int], got Array[String]",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_infer_type_params_from_arguments() {
+        let declaration = "fun first[T](Array[T] items) -> T {\n    items[0]\n}\n";
+
+        let input = format!("{}Int x = first([1, 2])", declaration);
+        assert!(check(&input).is_ok());
+
+        let input = format!(
+            "{}String x = first([\"a\"])\nfirst([first([1])])",
+            declaration
+        );
+        assert!(check(&input).is_ok());
+
+        let input = format!("{}String x = first([1, 2])", declaration);
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_DUPLICATED_REFERENCE, errors[0].error_code);
+
+        let input = format!("{}first(10)", declaration);
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!("Expected a Array[T], got Int", errors[0].labels[0].message);
+    }
+
+    #[test]
+    fn should_check_arguments_against_inferred_types() {
+        let declaration = "fun pair[T](T a, T b) -> Array[T] {\n    [a, b]\n}\n";
+
+        let input = format!("{}Array[String] xs = pair(\"a\", \"b\")", declaration);
+        assert!(check(&input).is_ok());
+
+        let input = format!("{}pair(1, \"b\")", declaration);
+        let errors = check(&input).unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!("Expected a Int, got String", errors[0].labels[0].message);
+    }
+
+    #[test]
+    fn should_fail_on_type_params_that_cannot_be_inferred() {
+        let input = "fun make[T](Int size) -> Int {\n    size\n}\nmake(10)";
+        let errors = check(input).unwrap_err();
+        assert_eq!(SEMANTIC_CANNOT_INFER_TYPE, errors[0].error_code);
+        assert_eq!(
+            "Cannot infer the type parameter T from the arguments of this call",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_treat_type_params_as_opaque_inside_the_function() {
+        let input = "fun id[T](T value) -> T {\n    10\n}";
+        let errors = check(input).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_check_nested_calls() {
+        let input = "fun double(Int x) -> Int {\n    x + x\n}\ndouble(double(1, 2))";
+        let errors = check(input).unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!(SEMANTIC_MISMATCHED_ARGUMENT_COUNT, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_check_calls_in_every_position() {
+        let declaration = "fun f(Int x) -> Int {\n    x\n}\nvar Int x = 0\n";
+        let positions = [
+            "x = f(\"hello\")",
+            "if f(\"s\") > 0 {\n}",
+            "while f(\"s\") > 0 {\n}",
+            "val z = [f(\"s\")]",
+            "for i in [f(\"s\")] {\n}",
+        ];
+        for position in positions {
+            let errors = check(&format!("{}{}", declaration, position)).unwrap_err();
+            assert_eq!(
+                SEMANTIC_MISMATCHED_TYPES, errors[0].error_code,
+                "{}",
+                position
+            );
+        }
+
+        let errors = check(&format!("{}x = f(1, 2, 3)", declaration)).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_ARGUMENT_COUNT, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_allow_recursive_calls() {
+        let input = "fun count(Int n) -> Int {\n    count(n - 1)\n}";
+        assert!(check(input).is_ok());
+    }
+
+    #[test]
+    fn should_not_allow_assigning_parameters() {
+        let input = "fun f(Int x) {\n    x = 10\n}";
+        let errors = check(input).unwrap_err();
+        assert_eq!(SEMANTIC_IMMUTABLE_VARIABLE, errors[0].error_code);
+    }
 }
@@ -0,0 +1,141 @@
+use crate::{
+    error_handling::MistiError,
+    semantic::{
+        checks::function_declaration::{check_function, record_captures},
+        impls::SemanticCheck,
+        symbol_table::SymbolTable,
+    },
+    syntax::ast::functions::Lambda,
+};
+
+impl SemanticCheck for Lambda<'_> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        // The symbols declared before the lambda and used in its body are captured
+        let reference_counts = scope.reference_counts();
+        let result = check_function(&self.params_list, &self.return_type, &self.block, scope);
+        record_captures(scope, &reference_counts, &self.captures);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error_handling::error_messages::SEMANTIC_MISMATCHED_TYPES,
+        lexic::{get_tokens, token::Token},
+        semantic::{
+            impls::SemanticCheck,
+            std::populate,
+            symbol_table::SymbolTable,
+            test_utils::check,
+            types::{Type, Typed},
+        },
+        syntax::{
+            ast::{functions::Capture, Expression},
+            parseable::Parseable,
+        },
+    };
+
+    fn scope() -> SymbolTable {
+        let mut scope = SymbolTable::new();
+        populate(&mut scope);
+        scope.declare(
+            &Token::new_identifier("offset".into(), 0),
+            value("Int"),
+            false,
+        );
+        scope.declare(
+            &Token::new_identifier("total".into(), 0),
+            value("Int"),
+            true,
+        );
+        scope.declare_function(
+            &Token::new_identifier("add".into(), 0),
+            Type::Function(vec![value("Int"), value("Int")], Box::new(value("Int"))),
+        );
+        scope
+    }
+
+    fn value(name: &str) -> Type {
+        Type::Value(name.into())
+    }
+
+    fn captures(input: &str) -> Vec<Capture> {
+        let tokens = get_tokens(&String::from(input)).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+        let Expression::Lambda(lambda) = &expression else {
+            panic!("Expected a lambda")
+        };
+
+        let scope = scope();
+        lambda.check_semantics(&scope).unwrap();
+        let captures = lambda.captures.borrow().clone();
+        captures
+    }
+
+    #[test]
+    fn should_capture_outer_variables() {
+        let input = "fn(Int x) -> Int {\n    total = total + x\n    x + offset\n}";
+        let expected = vec![
+            Capture {
+                name: "offset".into(),
+                is_mutable: false,
+            },
+            Capture {
+                name: "total".into(),
+                is_mutable: true,
+            },
+        ];
+        assert_eq!(expected, captures(input));
+    }
+
+    #[test]
+    fn should_not_capture_parameters_or_functions() {
+        assert!(captures("fn(Int x) -> Int { add(x, 1) }").is_empty());
+        assert!(captures("fn(Int x) { print(\"{x}\") }").is_empty());
+    }
+
+    #[test]
+    fn should_capture_variables_of_nested_lambdas() {
+        let expected = vec![Capture {
+            name: "offset".into(),
+            is_mutable: false,
+        }];
+        assert_eq!(expected, captures("fn() { fn() -> Int { offset } }"));
+    }
+
+    #[test]
+    fn should_type_lambdas_as_functions() {
+        let tokens = get_tokens(&String::from("fn(Int x, String y) -> Int { x }")).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+
+        let expected = Type::Function(vec![value("Int"), value("String")], Box::new(value("Int")));
+        assert_eq!(expected, expression.get_type(&scope()).unwrap());
+    }
+
+    #[test]
+    fn should_check_the_body_of_lambdas() {
+        let tokens = get_tokens(&String::from("fn() -> Int { \"a\" }")).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+
+        let errors = expression.check_semantics(&scope()).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_check_lambdas_in_every_position() {
+        let lambda = "fn(Int x) -> Int { \"not an int\" }";
+        let inputs = [
+            format!("var f = fn(Int x) -> Int {{ x }}\nf = {}", lambda),
+            format!("val fs = [{}]", lambda),
+            format!("if [{}][0](1) > 0 {{\n}}", lambda),
+            format!("while [{}][0](1) > 0 {{\n}}", lambda),
+            format!("for f in [{}] {{\n}}", lambda),
+        ];
+        for input in inputs {
+            let errors = check(&input).unwrap_err();
+            assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code, "{}", input);
+        }
+    }
+}
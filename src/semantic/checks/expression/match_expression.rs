@@ -0,0 +1,632 @@
+use crate::{
+    error_handling::{
+        error_messages::{
+            COMPILER_TODO, SEMANTIC_DUPLICATED_REFERENCE, SEMANTIC_INVALID_REFERENCE,
+            SEMANTIC_MISMATCHED_TYPES, SEMANTIC_NON_EXHAUSTIVE_MATCH,
+        },
+        ErrorContainer, ErrorLabel, MistiError,
+    },
+    lexic::token::Token,
+    semantic::{
+        checks::function_declaration::record_captures,
+        impls::SemanticCheck,
+        symbol_table::SymbolTable,
+        types::{
+            expression::get_variant,
+            global::{BOOL, FLOAT, NULL, UNIT},
+            Type, Typed,
+        },
+    },
+    syntax::ast::{
+        functions::FunctionCall,
+        match_expression::{Match, MatchArm},
+        Block, BlockMember, Expression, Positionable,
+    },
+};
+
+use super::called_variant_error;
+
+impl SemanticCheck for Match<'_> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        self.subject.check_semantics(scope)?;
+        let subject_type = self
+            .subject
+            .get_type(scope)
+            .map_err(|error| vec![error])?
+            .resolve(scope);
+
+        // The variables declared before the match and used in its arms are captured
+        let reference_counts = scope.reference_counts();
+        let result = self.check_arms(&subject_type, scope);
+        record_captures(scope, &reference_counts, &self.captures);
+
+        result
+    }
+}
+
+impl Typed for Match<'_> {
+    /// The type of a match is the type of its arms. All arms must have a
+    /// type in common, e.g. Int & Float, or a type and null
+    fn get_type(&self, scope: &SymbolTable) -> Result<Type, MistiError> {
+        self.arms_type(&self.arm_scopes(scope), scope)
+    }
+}
+
+impl<'a> Match<'a> {
+    /// Checks the patterns, guards and values of the arms,
+    /// and that they handle every value of the subject
+    fn check_arms(&self, subject_type: &Type, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        let bool_type = Type::Value(BOOL.into());
+        let arm_scopes = self.arm_scopes(scope);
+
+        // Every arm is checked, even if a previous one failed
+        let mut errors = Vec::new();
+        for (arm, arm_scope) in self.arms.iter().zip(arm_scopes.iter()) {
+            for pattern in arm.patterns.iter() {
+                if let Err(pattern_errors) = check_pattern(pattern, subject_type, scope) {
+                    errors.extend(pattern_errors);
+                }
+            }
+            if let Err(error) = check_bindings_are_alone(arm) {
+                errors.push(error);
+            }
+
+            // The guard can use the fields bound by the pattern
+            if let Some(guard) = &arm.guard {
+                match guard.check_semantics(arm_scope) {
+                    Ok(()) => {
+                        if let Err(error) = check_guard(guard, &bool_type, arm_scope) {
+                            errors.push(error);
+                        }
+                    }
+                    Err(guard_errors) => errors.extend(guard_errors),
+                }
+            }
+        }
+
+        for (block, block_scope) in self.scoped_blocks(&arm_scopes, scope) {
+            if let Some(value) = arm_value(block) {
+                if let Err(value_errors) = value.check_semantics(block_scope) {
+                    errors.extend(value_errors);
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        // The arms must have compatible types. PHP doesn't convert
+        // the Ints among Floats on its own
+        let match_type = self
+            .arms_type(&arm_scopes, scope)
+            .map_err(|error| vec![error])?;
+        if match_type.is_value(FLOAT) {
+            self.to_float.set(true);
+        }
+
+        self.check_exhaustiveness(subject_type)
+            .map_err(|error| vec![error])
+    }
+
+    /// Creates the scope of each arm, which has the fields
+    /// bound by its pattern, e.g. `radius` in `case Shape::Circle(radius)`
+    fn arm_scopes(&self, scope: &SymbolTable) -> Vec<SymbolTable> {
+        self.arms
+            .iter()
+            .map(|arm| {
+                let arm_scope = SymbolTable::new_from_parent(scope);
+                for pattern in arm.patterns.iter() {
+                    for (binding, datatype) in pattern_bindings(pattern, scope) {
+                        arm_scope.declare(binding, datatype, false);
+                    }
+                }
+                arm_scope
+            })
+            .collect()
+    }
+
+    /// Returns the blocks of all the arms with their scope,
+    /// and the else block last, with `scope`
+    fn scoped_blocks<'s>(
+        &'s self,
+        arm_scopes: &'s [SymbolTable],
+        scope: &'s SymbolTable,
+    ) -> impl Iterator<Item = (&'s Block<'a>, &'s SymbolTable)> {
+        self.arms
+            .iter()
+            .map(|arm| &arm.body)
+            .zip(arm_scopes.iter())
+            .chain(self.else_block.iter().map(move |block| (block, scope)))
+    }
+
+    /// Returns the type of the arms, each one in its scope
+    fn arms_type(
+        &self,
+        arm_scopes: &[SymbolTable],
+        scope: &SymbolTable,
+    ) -> Result<Type, MistiError> {
+        let mut match_type: Option<Type> = None;
+
+        for (block, block_scope) in self.scoped_blocks(arm_scopes, scope) {
+            let arm_type = match arm_value(block) {
+                Some(value) => value.get_type(block_scope)?,
+                None => Type::Value(UNIT.into()),
+            };
+
+            let Some(previous_type) = match_type else {
+                match_type = Some(arm_type);
+                continue;
+            };
+
+            let common_type = match previous_type.common_type(&arm_type) {
+                Some(common_type) => common_type,
+                None if previous_type.is_null() => Type::nullable(arm_type),
+                None if arm_type.is_null() => Type::nullable(previous_type),
+                None => {
+                    let (error_start, error_end) = block.get_position();
+                    let label = ErrorLabel {
+                        message: format!(
+                            "This arm has type {}, but the previous arms have type {}",
+                            arm_type, previous_type
+                        ),
+                        start: error_start,
+                        end: error_end + 1,
+                    };
+                    let econtainer = ErrorContainer {
+                        error_code: SEMANTIC_MISMATCHED_TYPES,
+                        error_offset: error_start,
+                        labels: vec![label],
+                        note: None,
+                        help: None,
+                    };
+                    return Err(econtainer);
+                }
+            };
+            match_type = Some(common_type);
+        }
+
+        Ok(match_type.expect("A match has at least one arm"))
+    }
+
+    /// Checks that a value of `subject_type` always matches an arm.
+    ///
+    /// A match with an else arm is exhaustive. Otherwise, the arms
+    /// without a guard must list every value of the subject, which
+    /// is only possible for types with a few values, e.g. Bool or enums
+    fn check_exhaustiveness(&self, subject_type: &Type) -> Result<(), MistiError> {
+        if self.else_block.is_some() {
+            return Ok(());
+        }
+
+        let patterns: Vec<&Expression> = self
+            .arms
+            .iter()
+            .filter(|arm| arm.guard.is_none())
+            .flat_map(|arm| arm.patterns.iter())
+            .collect();
+
+        let message = match missing_values(subject_type, &patterns) {
+            Some(missing) if missing.is_empty() => return Ok(()),
+            Some(missing) => format!(
+                "This match doesn't handle the values: {}",
+                missing.join(", ")
+            ),
+            None => format!(
+                "This match doesn't handle every value of type {}",
+                subject_type
+            ),
+        };
+
+        let (_, error_end) = self.subject.get_position();
+        let label = ErrorLabel {
+            message,
+            start: self.keyword.position,
+            end: error_end,
+        };
+        Err(ErrorContainer {
+            error_code: SEMANTIC_NON_EXHAUSTIVE_MATCH,
+            error_offset: self.keyword.position,
+            labels: vec![label],
+            note: None,
+            help: Some(String::from("Add an `else` arm to handle the other values")),
+        })
+    }
+}
+
+/// Returns the values of `subject_type` that no pattern matches, or
+/// `None` if the type has too many values to list them, e.g. Int
+fn missing_values(subject_type: &Type, patterns: &[&Expression]) -> Option<Vec<String>> {
+    let is_matched = |value: &String| {
+        patterns
+            .iter()
+            .any(|pattern| pattern_value(pattern).as_ref() == Some(value))
+    };
+
+    let values = match subject_type {
+        Type::Nullable(inner) => {
+            let mut missing = missing_values(inner, patterns)?;
+            if !is_matched(&String::from("null")) {
+                missing.push(String::from("null"));
+            }
+            return Some(missing);
+        }
+        Type::Union(members) => {
+            let mut missing = Vec::new();
+            for member in members {
+                missing.extend(missing_values(member, patterns)?);
+            }
+            return Some(missing);
+        }
+        Type::Enum(name, variants) => variants
+            .iter()
+            .map(|variant| format!("{}::{}", name, variant))
+            .collect(),
+        t if t.is_value(BOOL) => vec![String::from("true"), String::from("false")],
+        t if t.is_value(NULL) => vec![String::from("null")],
+        _ => return None,
+    };
+
+    Some(
+        values
+            .into_iter()
+            .filter(|value| !is_matched(value))
+            .collect(),
+    )
+}
+
+/// Returns the value a pattern matches, for the types whose values can be
+/// listed. A variant with a payload matches any value of that variant
+fn pattern_value(pattern: &Expression) -> Option<String> {
+    match pattern {
+        Expression::Boolean(token) | Expression::Null(token) => Some(token.value.clone()),
+        Expression::EnumVariant(enum_name, variant, _) => {
+            Some(format!("{}::{}", enum_name.value, variant.value))
+        }
+        Expression::FunctionCall(call) => match &*call.function {
+            Expression::EnumVariant(enum_name, variant, _) => {
+                Some(format!("{}::{}", enum_name.value, variant.value))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the fields bound by a pattern, with their types,
+/// e.g. `radius` in `case Shape::Circle(radius)`.
+///
+/// Bindings that are not fields are skipped, `check_pattern` reports them
+fn pattern_bindings<'a>(pattern: &Expression<'a>, scope: &SymbolTable) -> Vec<(&'a Token, Type)> {
+    let Expression::FunctionCall(call) = pattern else {
+        return vec![];
+    };
+    let Expression::EnumVariant(enum_name, variant, _) = &*call.function else {
+        return vec![];
+    };
+    let Ok((_, variant)) = get_variant(enum_name, variant, scope) else {
+        return vec![];
+    };
+
+    call.arguments
+        .arguments
+        .iter()
+        .filter_map(|argument| match argument {
+            Expression::Identifier(binding) => variant
+                .fields
+                .iter()
+                .find(|field| field.name == binding.value)
+                .map(|field| (binding.token, field.datatype.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Checks that a pattern can match a value of `subject_type`
+fn check_pattern(
+    pattern: &Expression,
+    subject_type: &Type,
+    scope: &SymbolTable,
+) -> Result<(), Vec<MistiError>> {
+    let pattern_type = match pattern {
+        Expression::FunctionCall(call) => match &*call.function {
+            Expression::EnumVariant(enum_name, variant, _) => {
+                check_variant_pattern(enum_name, variant, call, scope)?
+            }
+            _ => pattern.get_type(scope)?,
+        },
+        _ => {
+            pattern.check_semantics(scope)?;
+            pattern.get_type(scope)?
+        }
+    };
+    if can_match(&pattern_type, subject_type) {
+        return Ok(());
+    }
+
+    let (error_start, error_end) = pattern.get_position();
+    let label = ErrorLabel {
+        message: format!(
+            "This {} can never match a value of type {}",
+            pattern_type, subject_type
+        ),
+        start: error_start,
+        end: error_end,
+    };
+    Err(vec![ErrorContainer {
+        error_code: SEMANTIC_MISMATCHED_TYPES,
+        error_offset: error_start,
+        labels: vec![label],
+        note: None,
+        help: None,
+    }])
+}
+
+/// Checks a pattern that destructures a variant, e.g. `Shape::Rect(width, height)`.
+/// The bindings are the names of fields of the variant, in any order.
+///
+/// Returns the type of the enum
+fn check_variant_pattern(
+    enum_name: &Token,
+    variant: &Token,
+    call: &FunctionCall,
+    scope: &SymbolTable,
+) -> Result<Type, Vec<MistiError>> {
+    let (variants, found_variant) =
+        get_variant(enum_name, variant, scope).map_err(|error| vec![error])?;
+    if found_variant.fields.is_empty() {
+        return Err(vec![called_variant_error(enum_name, variant, call)]);
+    }
+
+    let mut errors = Vec::new();
+    let mut bound = Vec::<&String>::new();
+    for argument in call.arguments.arguments.iter() {
+        // The parser only allows identifiers
+        let Expression::Identifier(binding) = argument else {
+            continue;
+        };
+
+        let (message, error_code) = if bound.contains(&&binding.value) {
+            (
+                format!(
+                    "The field `{}` is already bound in this pattern",
+                    binding.value
+                ),
+                SEMANTIC_DUPLICATED_REFERENCE,
+            )
+        } else if !found_variant.fields.iter().any(|f| f.name == binding.value) {
+            (
+                format!(
+                    "The variant {}::{} has no field `{}`",
+                    enum_name.value, variant.value, binding.value
+                ),
+                SEMANTIC_INVALID_REFERENCE,
+            )
+        } else {
+            bound.push(&binding.value);
+            continue;
+        };
+
+        let label = ErrorLabel {
+            message,
+            start: binding.position,
+            end: binding.get_end_position(),
+        };
+        errors.push(ErrorContainer {
+            error_code,
+            error_offset: binding.position,
+            labels: vec![label],
+            note: None,
+            help: None,
+        });
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(Type::enumeration(&enum_name.value, &variants))
+}
+
+/// Checks that a pattern that binds fields is the only pattern of its arm,
+/// otherwise the fields may not exist when the arm is taken
+fn check_bindings_are_alone(arm: &MatchArm) -> Result<(), MistiError> {
+    if arm.patterns.len() < 2 {
+        return Ok(());
+    }
+
+    let binding_pattern = arm.patterns.iter().find(|pattern| match pattern {
+        Expression::FunctionCall(call) => !call.arguments.arguments.is_empty(),
+        _ => false,
+    });
+    let Some(binding_pattern) = binding_pattern else {
+        return Ok(());
+    };
+
+    let (error_start, error_end) = binding_pattern.get_position();
+    let label = ErrorLabel {
+        message: String::from("A pattern that binds fields must be the only pattern of its arm"),
+        start: error_start,
+        end: error_end,
+    };
+    Err(ErrorContainer {
+        error_code: COMPILER_TODO,
+        error_offset: error_start,
+        labels: vec![label],
+        note: None,
+        help: Some(String::from("Split the patterns into several arms")),
+    })
+}
+
+/// PHP's match compares values strictly, with `===`, so a pattern
+/// must have one of the types the subject can have. E.g. the
+/// pattern `1` never matches the Float `1.0`
+fn can_match(pattern_type: &Type, subject_type: &Type) -> bool {
+    match subject_type {
+        Type::Nullable(inner) => pattern_type.is_null() || can_match(pattern_type, inner),
+        Type::Union(members) => members.iter().any(|member| can_match(pattern_type, member)),
+        _ => pattern_type.equals(subject_type),
+    }
+}
+
+/// Checks that the guard of an arm is a Bool
+fn check_guard(
+    guard: &Expression,
+    bool_type: &Type,
+    scope: &SymbolTable,
+) -> Result<(), MistiError> {
+    let guard_type = guard.get_type(scope)?;
+    if guard_type.is_assignable_to(bool_type) {
+        return Ok(());
+    }
+
+    let (error_start, error_end) = guard.get_position();
+    let label = ErrorLabel {
+        message: format!("Expected a condition of type Bool, found {}", guard_type),
+        start: error_start,
+        end: error_end,
+    };
+    Err(ErrorContainer {
+        error_code: SEMANTIC_MISMATCHED_TYPES,
+        error_offset: error_start,
+        labels: vec![label],
+        note: None,
+        help: None,
+    })
+}
+
+/// Returns the value computed by the block of an arm,
+/// `None` if the block is empty
+pub fn arm_value<'a>(block: &'a Block) -> Option<&'a Expression<'a>> {
+    match block.members.as_slice() {
+        [] => None,
+        [BlockMember::Expr(value)] => Some(value),
+        _ => unreachable!(
+            "Compiler bug: the parser only allows an expression in the arms of a match"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error_handling::{
+            error_messages::{SEMANTIC_MISMATCHED_TYPES, SEMANTIC_NON_EXHAUSTIVE_MATCH},
+            MistiError,
+        },
+        lexic::get_tokens,
+        semantic::{
+            impls::SemanticCheck,
+            std::populate,
+            symbol_table::SymbolTable,
+            types::{Type, Typed},
+        },
+        syntax::{ast::Expression, parseable::Parseable},
+    };
+
+    /// Checks `input` in a scope with `value` of type `value_type`
+    fn check(input: &str, value_type: Type) -> Result<Type, Vec<MistiError>> {
+        let tokens = get_tokens(&String::from(input)).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+        let mut scope = SymbolTable::new();
+        populate(&mut scope);
+        scope.insert("value".into(), value_type);
+        scope.insert("flag".into(), Type::Value("Bool".into()));
+
+        expression.check_semantics(&scope)?;
+        expression.get_type(&scope).map_err(|error| vec![error])
+    }
+
+    fn value(name: &str) -> Type {
+        Type::Value(name.into())
+    }
+
+    #[test]
+    fn should_type_match_as_its_arms() {
+        let input = "match value\ncase 1, 2 { \"small\" }\nelse { \"big\" }";
+        assert_eq!(value("String"), check(input, value("Int")).unwrap());
+
+        let input = "match value\ncase 1 { 1 }\ncase 2 { 2.5 }\nelse { 3 }";
+        assert_eq!(value("Float"), check(input, value("Int")).unwrap());
+
+        let input = "match value\ncase 1 { \"one\" }\nelse { null }";
+        assert_eq!(
+            Type::nullable(value("String")),
+            check(input, value("Int")).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_reject_arms_of_different_types() {
+        let input = "match value\ncase 1 { \"one\" }\nelse { 2 }";
+        let errors = check(input, value("Int")).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!(
+            "This arm has type Int, but the previous arms have type String",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_reject_patterns_of_other_types() {
+        let input = "match value\ncase \"a\" { 1 }\nelse { 2 }";
+        let errors = check(input, value("Int")).unwrap_err();
+        assert_eq!(
+            "This String can never match a value of type Int",
+            errors[0].labels[0].message
+        );
+
+        // Values are compared strictly
+        let input = "match value\ncase 1 { 1 }\nelse { 2 }";
+        assert!(check(input, value("Float")).is_err());
+
+        let input = "match value\ncase 1 { 1 }\ncase \"a\" { 2 }\ncase null { 3 }\nelse { 4 }";
+        let subject_type = Type::nullable(Type::union(vec![value("Int"), value("String")]));
+        assert!(check(input, subject_type).is_ok());
+    }
+
+    #[test]
+    fn should_check_guards() {
+        let input = "match value\ncase 1 if flag { 1 }\nelse { 2 }";
+        assert!(check(input, value("Int")).is_ok());
+
+        let input = "match value\ncase 1 if value { 1 }\nelse { 2 }";
+        let errors = check(input, value("Int")).unwrap_err();
+        assert_eq!(
+            "Expected a condition of type Bool, found Int",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_check_exhaustiveness() {
+        let input = "match value\ncase true { 1 }\ncase false { 0 }";
+        assert!(check(input, value("Bool")).is_ok());
+
+        let input = "match value\ncase true { 1 }";
+        let errors = check(input, value("Bool")).unwrap_err();
+        assert_eq!(SEMANTIC_NON_EXHAUSTIVE_MATCH, errors[0].error_code);
+        assert_eq!(
+            "This match doesn't handle the values: false",
+            errors[0].labels[0].message
+        );
+
+        let input = "match value\ncase true { 1 }\ncase false { 0 }";
+        let errors = check(input, Type::nullable(value("Bool"))).unwrap_err();
+        assert_eq!(
+            "This match doesn't handle the values: null",
+            errors[0].labels[0].message
+        );
+
+        // Arms with guards may not be taken
+        let input = "match value\ncase true if flag { 1 }\ncase false { 0 }";
+        assert!(check(input, value("Bool")).is_err());
+
+        let input = "match value\ncase 1 { 1 }";
+        let errors = check(input, value("Int")).unwrap_err();
+        assert_eq!(
+            "This match doesn't handle every value of type Int",
+            errors[0].labels[0].message
+        );
+    }
+}
@@ -1,20 +1,28 @@
 use crate::{
     error_handling::{
-        error_messages::{COMPILER_TODO, SEMANTIC_INVALID_REFERENCE, SEMANTIC_MISMATCHED_TYPES},
+        error_messages::{COMPILER_TODO, SEMANTIC_MISMATCHED_TYPES},
         ErrorContainer, ErrorLabel, MistiError,
     },
+    lexic::token::Token,
     semantic::{
         impls::SemanticCheck,
         symbol_table::SymbolTable,
-        types::{Type, Typed},
+        types::{
+            expression::{get_member, get_variant, is_member_access},
+            global::INT,
+            operator::{check_operator, right_operand_scope},
+            Type, Typed,
+        },
     },
-    syntax::ast::{Expression, Positionable},
+    syntax::ast::{functions::FunctionCall, Expression, Positionable},
 };
 
 mod funtion_call;
+mod lambda;
+pub mod match_expression;
 
 impl SemanticCheck for Expression<'_> {
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
         match self {
             Expression::FunctionCall(f) => f.check_semantics(scope),
             // These are empty because they have nothing to check,
@@ -23,181 +31,53 @@ impl SemanticCheck for Expression<'_> {
             Expression::Float(_) => Ok(()),
             Expression::String(_) => Ok(()),
             Expression::Boolean(_) => Ok(()),
-            Expression::Identifier(_) => Ok(()),
-            Expression::UnaryOperator(operator, expression) => {
-                // There are a limited amount of unary operators,
-                // so their checking is not generalized
-                let expr_type = expression.get_type(scope)?;
-                match (operator.value.as_str(), expr_type) {
-                    ("!", Type::Value(t)) => {
-                        if t == "Bool" {
-                            // Ok, empty
-                            return Ok(());
-                        } else {
-                            // Error: unary negation can only be applied to a Bool
-                            let (error_start, error_end) = expression.get_position();
-                            let label = ErrorLabel {
-                                message: format!("Expected a Bool, got {}", t),
-                                start: error_start,
-                                end: error_end,
-                            };
-                            let econtainer = ErrorContainer {
-                                error_code: SEMANTIC_MISMATCHED_TYPES,
-                                error_offset: error_start,
-                                labels: vec![label],
-                                note: None,
-                                help: None,
-                            };
-                            return Err(econtainer);
-                        }
-                    }
-                    ("!", Type::Function(_, _)) => {
-                        // Error: unary negation can only be applied to a Bool
-                        let (error_start, error_end) = expression.get_position();
-                        let label = ErrorLabel {
-                            message: format!("Expected a Bool, got a function"),
-                            start: error_start,
-                            end: error_end,
-                        };
-                        let econtainer = ErrorContainer {
-                            error_code: SEMANTIC_MISMATCHED_TYPES,
-                            error_offset: error_start,
-                            labels: vec![label],
-                            note: None,
-                            help: None,
-                        };
-                        return Err(econtainer);
-                    }
-                    ("-", Type::Value(t)) => {
-                        if t == "Int" || t == "Float" {
-                            // Ok, empty
-                            return Ok(());
-                        } else {
-                            // Error: unary negation can only be applied to a Number
-                            let (error_start, error_end) = expression.get_position();
-                            let label = ErrorLabel {
-                                message: format!("Expected a Float or Int, got a {}", t),
-                                start: error_start,
-                                end: error_end,
-                            };
-                            let econtainer = ErrorContainer {
-                                error_code: SEMANTIC_MISMATCHED_TYPES,
-                                error_offset: error_start,
-                                labels: vec![label],
-                                note: None,
-                                help: None,
-                            };
-                            return Err(econtainer);
-                        }
-                    }
-                    ("-", Type::Function(_, _)) => {
-                        // Error: unary negation can only be applied to a Bool
-                        let (error_start, error_end) = expression.get_position();
-                        let label = ErrorLabel {
-                            message: format!("Expected a Float or Int, got a function"),
-                            start: error_start,
-                            end: error_end,
-                        };
-                        let econtainer = ErrorContainer {
-                            error_code: SEMANTIC_MISMATCHED_TYPES,
-                            error_offset: error_start,
-                            labels: vec![label],
-                            note: None,
-                            help: None,
-                        };
-                        return Err(econtainer);
-                    }
-                    (op, _) => {
-                        // Compiler error: something that shouldn't be
-                        // parsed as a unary operator was found.
-                        unreachable!("Found a unary operator that shouldn't be: {}", op)
-                    }
+            Expression::Null(_) => Ok(()),
+            // The identifier must exist, and its usage is recorded
+            Expression::Identifier(_) => {
+                self.get_type(scope)?;
+                Ok(())
+            }
+            // The variants with a payload are only values when called
+            Expression::EnumVariant(enum_name, variant, is_class) => match self.get_type(scope)? {
+                Type::Function(_, _) => Err(vec![uncalled_variant_error(enum_name, variant)]),
+                _ => {
+                    let (variants, _) =
+                        get_variant(enum_name, variant, scope).map_err(|error| vec![error])?;
+                    is_class.set(variants.iter().any(|v| !v.fields.is_empty()));
+                    Ok(())
                 }
+            },
+            Expression::UnaryOperator(operator, expression) => {
+                expression.check_semantics(scope)?;
+                check_operator(operator, &[expression], scope).map_err(|error| vec![error])
             }
-            Expression::BinaryOperator(left_expr, right_expr, op) => {
-                // Operators are treated as functions
-                let (op_params, _) = match scope.get_type(&op.value) {
-                    Some(Type::Function(params, return_t)) => (params, return_t),
-                    Some(t) => {
-                        // If a operator is stored as anything else
-                        // it's a bug in the compiler
-                        unreachable!("Compiler bug: a binary operator was registered in the symbol table as a value of type {:?}", t)
-                    }
-                    None => {
-                        // If the operator is not found its a user error,
-                        // because we allow arbitrary operators
-                        let (error_start, error_end) = (op.position, op.get_end_position());
-                        let label = ErrorLabel {
-                            message: format!("The binary operator {} does not exist", op.value),
-                            start: error_start,
-                            end: error_end,
-                        };
-                        let econtainer = ErrorContainer {
-                            error_code: SEMANTIC_INVALID_REFERENCE,
-                            error_offset: error_start,
-                            labels: vec![label],
-                            note: None,
-                            help: None,
-                        };
-                        return Err(econtainer);
+            Expression::BinaryOperator(left_expr, right_expr, op)
+                if is_member_access(&op.value) =>
+            {
+                // The member is not a value in the scope, so only the object is checked
+                left_expr.check_semantics(scope)?;
+
+                // The arguments of a method call are checked against the method
+                if let Expression::FunctionCall(call) = &**right_expr {
+                    if let Expression::Identifier(method) = &*call.function {
+                        let method = get_member(left_expr, method, &op.value, scope)
+                            .map_err(|error| vec![error])?;
+                        funtion_call::check_arguments(call, method.datatype, scope)?;
                     }
-                };
-
-                if op_params.len() != 2 {
-                    // If an operator has any other number
-                    // of parameters, it's a bug in the compiler
-                    unreachable!(
-                        "Compiler bug: a binary operator didn't have 2 parameters: {:?}",
-                        op_params
-                    )
-                }
-
-                let left_expr_type = left_expr.get_type(scope)?;
-                let right_expr_type = right_expr.get_type(scope)?;
-
-                if !left_expr_type.is_value(&op_params[0]) {
-                    let (error_start, error_end) = left_expr.get_position();
-                    let label = ErrorLabel {
-                        message: format!(
-                            "Expected a {}, got a {:?} on the left side of the {} operator",
-                            op_params[0], left_expr_type, op.value
-                        ),
-                        start: error_start,
-                        end: error_end,
-                    };
-                    let econtainer = ErrorContainer {
-                        error_code: SEMANTIC_MISMATCHED_TYPES,
-                        error_offset: error_start,
-                        labels: vec![label],
-                        note: None,
-                        help: None,
-                    };
-                    return Err(econtainer);
-                }
-
-                if !right_expr_type.is_value(&op_params[1]) {
-                    let (error_start, error_end) = right_expr.get_position();
-                    let label = ErrorLabel {
-                        message: format!(
-                            "Expected a {}, got a {:?} on the right side of the {} operator",
-                            op_params[1], right_expr_type, op.value
-                        ),
-                        start: error_start,
-                        end: error_end,
-                    };
-                    let econtainer = ErrorContainer {
-                        error_code: SEMANTIC_MISMATCHED_TYPES,
-                        error_offset: error_start,
-                        labels: vec![label],
-                        note: None,
-                        help: None,
-                    };
-                    return Err(econtainer);
                 }
 
-                // After all these checks, we are ok
+                self.get_type(scope)?;
                 Ok(())
             }
+            Expression::BinaryOperator(left_expr, right_expr, op) => {
+                left_expr.check_semantics(scope)?;
+                right_expr.check_semantics(&right_operand_scope(op, left_expr, scope))?;
+
+                // Operators are treated as functions, overloaded for the
+                // types they work with. The type parameters of generic
+                // operators are inferred from the operands
+                check_operator(op, &[left_expr, right_expr], scope).map_err(|error| vec![error])
+            }
             Expression::Array(arr) => {
                 // There is some duplicated logic here with
                 // the typechecking of an array in the impl
@@ -225,25 +105,30 @@ impl SemanticCheck for Expression<'_> {
                         note: None,
                         help: None,
                     };
-                    return Err(econtainer);
+                    return Err(vec![econtainer]);
+                }
+
+                for exp in arr.exps.iter() {
+                    exp.check_semantics(scope)?;
                 }
 
                 let mut expressions = arr.exps.iter();
                 let first_expr = expressions.next().unwrap();
-                let first_type = first_expr.get_type(scope)?;
+                let mut array_type = first_expr.get_type(scope)?;
 
-                // then check that every expression has the same type
+                // then check that every expression has a type in
+                // common with the previous ones, e.g. Int & Float
                 for exp in expressions {
                     let exp_type = exp.get_type(scope)?;
-                    if !exp_type.equals(&first_type) {
-                        // TODO: subtyping
-
+                    if let Some(common_type) = array_type.common_type(&exp_type) {
+                        array_type = common_type;
+                    } else {
                         // error, found an item with a diferent datatype
                         let (error_start, error_end) = exp.get_position();
                         let label = ErrorLabel {
                         message: format!(
-                                "All elements of an array must have the same datatype. Expected {:?}, got {:?}",
-                                first_type,
+                                "All elements of an array must have the same datatype. Expected {}, got {}",
+                                array_type,
                                 exp_type,
                         ),
                         start: error_start,
@@ -256,17 +141,119 @@ impl SemanticCheck for Expression<'_> {
                             note: None,
                             help: None,
                         };
-                        return Err(econtainer);
+                        return Err(vec![econtainer]);
                     }
                 }
 
                 Ok(())
             }
-            Expression::ArrayAcccess(_array_access) => unimplemented!("typecheck array access"),
+            Expression::ArrayAcccess(array_access) => {
+                array_access.left_expr.check_semantics(scope)?;
+                array_access.idx_expr.check_semantics(scope)?;
+
+                // The type of the array is checked when getting its type
+                array_access.left_expr.get_type(scope)?;
+                self.get_type(scope)?;
+
+                let index_type = array_access.idx_expr.get_type(scope)?;
+                if !index_type.is_value(INT) {
+                    let (error_start, error_end) = array_access.idx_expr.get_position();
+                    let label = ErrorLabel {
+                        message: format!("Expected an Int, got {}", index_type),
+                        start: error_start,
+                        end: error_end,
+                    };
+                    let econtainer = ErrorContainer {
+                        error_code: SEMANTIC_MISMATCHED_TYPES,
+                        error_offset: error_start,
+                        labels: vec![label],
+                        note: None,
+                        help: None,
+                    };
+                    return Err(vec![econtainer]);
+                }
+
+                Ok(())
+            }
+            Expression::TypeTest(type_test) => {
+                type_test.expression.check_semantics(scope)?;
+
+                // A test that is always false is a mistake
+                let value_type = type_test.expression.get_type(scope)?;
+                let tested_type = Type::from(&type_test.datatype);
+                if !tested_type.is_assignable_to(&value_type.resolve(scope)) {
+                    let (error_start, error_end) = type_test.datatype.get_position();
+                    let label = ErrorLabel {
+                        message: format!(
+                            "This value can never be a {}, its type is {}",
+                            tested_type, value_type
+                        ),
+                        start: error_start,
+                        end: error_end,
+                    };
+                    let econtainer = ErrorContainer {
+                        error_code: SEMANTIC_MISMATCHED_TYPES,
+                        error_offset: error_start,
+                        labels: vec![label],
+                        note: None,
+                        help: None,
+                    };
+                    return Err(vec![econtainer]);
+                }
+
+                Ok(())
+            }
+            Expression::Match(match_expression) => match_expression.check_semantics(scope),
+            Expression::Lambda(lambda) => lambda.check_semantics(scope),
         }
     }
 }
 
+/// The error of a variant with a payload used without calling it
+fn uncalled_variant_error(enum_name: &Token, variant: &Token) -> MistiError {
+    let label = ErrorLabel {
+        message: format!(
+            "{}::{} has a payload, it has to be called to create a value",
+            enum_name.value, variant.value
+        ),
+        start: enum_name.position,
+        end: variant.get_end_position(),
+    };
+    ErrorContainer {
+        error_code: SEMANTIC_MISMATCHED_TYPES,
+        error_offset: enum_name.position,
+        labels: vec![label],
+        note: None,
+        help: Some(format!(
+            "Pass its payload: `{}::{}(...)`",
+            enum_name.value, variant.value
+        )),
+    }
+}
+
+/// The error of a variant without a payload called like a function
+fn called_variant_error(enum_name: &Token, variant: &Token, call: &FunctionCall) -> MistiError {
+    let (_, error_end) = call.get_position();
+    let label = ErrorLabel {
+        message: format!(
+            "{}::{} doesn't have a payload, it is not called",
+            enum_name.value, variant.value
+        ),
+        start: enum_name.position,
+        end: error_end,
+    };
+    ErrorContainer {
+        error_code: SEMANTIC_MISMATCHED_TYPES,
+        error_offset: enum_name.position,
+        labels: vec![label],
+        note: None,
+        help: Some(format!(
+            "Remove the parentheses: `{}::{}`",
+            enum_name.value, variant.value
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -275,7 +262,7 @@ mod tests {
         syntax::{
             ast::{
                 functions::{ArgumentsList, FunctionCall},
-                Expression,
+                Expression, Identifier,
             },
             parseable::Parseable,
         },
@@ -295,7 +282,7 @@ mod tests {
         populate(&mut scope);
 
         let expr_token = Token::new_identifier("print".into(), 0);
-        let expr_function = Expression::Identifier(&expr_token);
+        let expr_function = Expression::Identifier(Identifier::new(&expr_token));
 
         let arg_t = Token::new_int(String::from("322"), 6);
         let arg_1 = Expression::Int(&arg_t);
@@ -314,7 +301,7 @@ mod tests {
             Ok(_) => panic!("Expected semantic error, got ok"),
             Err(e) => {
                 //assert_eq!(e.reason, "Expected a String, got Value(\"Int\")");
-                assert_eq!(e.error_offset, 6);
+                assert_eq!(e[0].error_offset, 6);
                 //assert_eq!(e.error_end, 9);
             }
         }
@@ -327,7 +314,7 @@ mod tests {
         populate(&mut scope);
 
         let expr_token = Token::new_identifier("print".into(), 0);
-        let expr_function = Expression::Identifier(&expr_token);
+        let expr_function = Expression::Identifier(Identifier::new(&expr_token));
 
         let arguments = ArgumentsList {
             arguments: vec![],
@@ -344,7 +331,7 @@ mod tests {
             Ok(_) => panic!("Expected semantic error, got ok"),
             Err(e) => {
                 //assert_eq!(e.reason, "Expected 1 arguments, got 0");
-                assert_eq!(e.error_offset, 5);
+                assert_eq!(e[0].error_offset, 5);
                 //assert_eq!(e.error_end, 7);
             }
         }
@@ -357,7 +344,7 @@ mod tests {
         populate(&mut scope);
 
         let expr_token = Token::new_identifier("print".into(), 0);
-        let expr_function = Expression::Identifier(&expr_token);
+        let expr_function = Expression::Identifier(Identifier::new(&expr_token));
 
         let arg_t = Token::new_int(String::from("322"), 6);
         let arg_1 = Expression::Int(&arg_t);
@@ -380,7 +367,7 @@ mod tests {
             Ok(_) => panic!("Expected semantic error, got ok"),
             Err(e) => {
                 //assert_eq!(e.reason, "Expected 1 arguments, got 2");
-                assert_eq!(e.error_offset, 5);
+                assert_eq!(e[0].error_offset, 5);
                 //assert_eq!(e.error_end, 15);
             }
         }
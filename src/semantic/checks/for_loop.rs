@@ -11,11 +11,12 @@ use crate::{
 };
 
 impl SemanticCheck for ForLoop<'_> {
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
         // TODO: Implement a generic Collection interface?
         // Use Traversable from PHP?
         // for now this is restricted to arrays
 
+        self.collection.check_semantics(scope)?;
         let collection_type = self.collection.get_type(scope)?;
         // This will be a single Type,
         let item_type = match collection_type {
@@ -44,7 +45,7 @@ impl SemanticCheck for ForLoop<'_> {
                     note: None,
                     help: None,
                 };
-                return Err(econtainer);
+                return Err(vec![econtainer]);
             }
         };
         let item_type = &item_type[0];
@@ -55,10 +56,10 @@ impl SemanticCheck for ForLoop<'_> {
         if let Some(key) = self.key {
             // Since for now this only supports Array[T], key
             // can only be a Int
-            loop_scope.insert(key.value.clone(), Type::Value("Int".into()));
+            loop_scope.declare(key, Type::Value("Int".into()), false);
         }
         // TODO: Add lifetimes to scoping instead of cloning
-        loop_scope.insert(self.value.value.clone(), item_type.clone());
+        loop_scope.declare(self.value, item_type.clone(), false);
 
         // Check every statement inside the block
         self.body.check_semantics(&loop_scope)?;
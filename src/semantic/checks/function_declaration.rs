@@ -1,14 +1,29 @@
+use std::cell::RefCell;
+
 use crate::{
-    error_handling::{error_messages::SEMANTIC_DUPLICATED_REFERENCE, ErrorContainer, ErrorLabel},
-    semantic::{impls::SemanticCheck, symbol_table::SymbolTable, types::Type},
-    syntax::ast::FunctionDeclaration,
+    error_handling::{
+        error_messages::{SEMANTIC_DUPLICATED_REFERENCE, SEMANTIC_MISSING_RETURN},
+        ErrorContainer, ErrorLabel,
+    },
+    lexic::token::Token,
+    semantic::{
+        impls::SemanticCheck,
+        symbol_table::SymbolTable,
+        types::{global::UNIT, mark_declared_types, Type},
+    },
+    syntax::ast::{
+        datatype::Datatype, functions::Capture, Block, FunctionDeclaration, ParamsList,
+        Positionable,
+    },
 };
 
+use super::return_statement::{check_function_body, returns_on_every_path};
+
 impl SemanticCheck for FunctionDeclaration<'_> {
     fn check_semantics(
         &self,
         scope: &SymbolTable,
-    ) -> Result<(), crate::error_handling::MistiError> {
+    ) -> Result<(), Vec<crate::error_handling::MistiError>> {
         let function_name = self.identifier.value.clone();
 
         // Check that the function is not already defined
@@ -30,23 +45,219 @@ impl SemanticCheck for FunctionDeclaration<'_> {
                 note: None,
                 help: None,
             };
-            return Err(econtainer);
+            return Err(vec![econtainer]);
         }
 
-        // Create a new scope and use it in the function block
-        let function_scope = SymbolTable::new_from_parent(scope);
+        // The function is registered before checking its body, so that it
+        // can call itself, and even if its body has errors, so that its
+        // usages don't report more errors.
+        // PHP functions are always global, so a function declared inside
+        // another function is a variable that holds a closure
+        let is_nested = scope.get_return_type().is_some();
+        self.is_nested.set(is_nested);
+        if is_nested {
+            scope.declare(self.identifier, function_type(self), false);
+        } else {
+            scope.declare_function(self.identifier, function_type(self));
+        }
+
+        // The variables declared before the function and used in its body
+        // are captured, including a nested function that calls itself
+        let reference_counts = scope.reference_counts();
+        let result = check_function(&self.params_list, &self.return_type, &self.block, scope);
+        record_captures(scope, &reference_counts, &self.captures);
+
+        result
+    }
+}
+
+/// Records in `captures` the variables of `scope` that have been referenced
+/// since `counts` was taken. Used for functions, lambdas and matches
+pub fn record_captures(scope: &SymbolTable, counts: &[usize], captures: &RefCell<Vec<Capture>>) {
+    let mut captures = captures.borrow_mut();
+    for symbol in scope.referenced_since(counts) {
+        // Functions are referenced by their name. Symbols without a declaration
+        // are the stdlib and `self`, which PHP closures bind on their own
+        if symbol.is_function || symbol.declaration.is_none() {
+            continue;
+        }
 
-        // TODO: Check the return type of the function body
-        // This should be the last expression in the block
-        for stmt in self.block.members.iter() {
-            stmt.check_semantics(&function_scope)?;
+        let capture = Capture {
+            name: symbol.name,
+            is_mutable: symbol.is_mutable,
+        };
+        // The same function may be checked more than once
+        if !captures.contains(&capture) {
+            captures.push(capture);
         }
+    }
+}
+
+/// Returns the type of a function declaration. Functions with type
+/// parameters are polymorphic, their type parameters are opaque types
+/// inside the function, and at each call they are inferred from the arguments
+pub fn function_type(declaration: &FunctionDeclaration) -> Type {
+    signature_type(
+        &declaration.type_params,
+        &declaration.params_list,
+        &declaration.return_type,
+    )
+}
+
+/// Returns the type of a function from the parts of its signature.
+/// Also used for the methods of interfaces, which don't have a body
+pub fn signature_type(
+    type_params: &[&Token],
+    params_list: &ParamsList,
+    return_datatype: &Option<Datatype>,
+) -> Type {
+    let parameter_types: Vec<Type> = params_list
+        .parameters
+        .iter()
+        .map(|parameter| Type::from(&parameter.datatype))
+        .collect();
 
-        // TODO: Check that the return type of the function
-        // matches the return type of the last expression
+    let function_type = Type::Function(parameter_types, Box::new(return_type(return_datatype)));
+    if type_params.is_empty() {
+        function_type
+    } else {
+        let type_params = type_params.iter().map(|t| t.value.clone()).collect();
+        Type::Polymorphic(type_params, Box::new(function_type))
+    }
+}
+
+/// A function without a return type returns Unit
+fn return_type(return_datatype: &Option<Datatype>) -> Type {
+    match return_datatype {
+        Some(t) => Type::from(t),
+        None => Type::Value(UNIT.into()),
+    }
+}
+
+/// Checks the body of a function, in a new scope inside `scope`
+/// where its parameters are declared. Also used for methods and lambdas
+pub fn check_function(
+    params_list: &ParamsList,
+    return_datatype: &Option<Datatype>,
+    block: &Block,
+    scope: &SymbolTable,
+) -> Result<(), Vec<crate::error_handling::MistiError>> {
+    let return_type = return_type(return_datatype);
+    for parameter in params_list.parameters.iter() {
+        mark_declared_types(&parameter.datatype, scope);
+    }
+    if let Some(return_datatype) = return_datatype {
+        mark_declared_types(return_datatype, scope);
+    }
+
+    // Create a new scope and use it in the function block.
+    // Parameters are immutable
+    let function_scope = SymbolTable::new_function_scope(scope, return_type.clone());
+    for parameter in params_list.parameters.iter() {
+        let datatype = Type::from(&parameter.datatype);
+        function_scope.declare(parameter.identifier, datatype, false);
+    }
+
+    // Unit functions don't return their last expression
+    if return_type.is_unit() {
+        return block.check_semantics(&function_scope);
+    }
+
+    // The expressions at the end of the body are returned
+    check_function_body(block, &return_type, &function_scope)?;
+
+    if !returns_on_every_path(block) {
+        // Safe to unwrap, functions without a return type return Unit
+        let (return_type_start, return_type_end) = return_datatype.as_ref().unwrap().get_position();
+        let label1 = ErrorLabel {
+            message: format!("The function returns {} here", return_type),
+            start: return_type_start,
+            end: return_type_end,
+        };
+        let label2 = ErrorLabel {
+            message: String::from("But the function can end here without returning a value"),
+            start: block.end,
+            end: block.end + 1,
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_MISSING_RETURN,
+            error_offset: block.end,
+            labels: vec![label1, label2],
+            note: None,
+            help: Some(String::from(
+                "Add a `return` statement, or end the function with an expression",
+            )),
+        };
+        return Err(vec![econtainer]);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexic::get_tokens,
+        semantic::check_semantics,
+        syntax::{
+            ast::{functions::Capture, BlockMember, ModuleMembers, Statement},
+            build_ast,
+        },
+    };
+
+    #[test]
+    fn should_capture_module_variables() {
+        let tokens = get_tokens(&String::from(
+            "val rate = 3\nvar total = 0\nfun charge(Int amount) -> Int {\n    total += amount\n    amount * rate\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        check_semantics(&ast).unwrap();
+
+        let ModuleMembers::Stmt(Statement::FnDecl(fun)) = &ast.productions[2] else {
+            panic!("Expected a function declaration")
+        };
+        let expected = vec![
+            Capture {
+                name: "rate".into(),
+                is_mutable: false,
+            },
+            Capture {
+                name: "total".into(),
+                is_mutable: true,
+            },
+        ];
+        assert!(!fun.is_nested.get());
+        assert_eq!(expected, *fun.captures.borrow());
+    }
 
-        scope.insert(function_name, Type::Function(vec![], "Unit".into()));
+    #[test]
+    fn should_declare_nested_functions_as_variables() {
+        let tokens = get_tokens(&String::from(
+            "fun outer(Int a) -> Int {\n    fun inner(Int n) -> Int {\n        if n <= 0 {\n            return a\n        }\n        inner(n - 1)\n    }\n    inner(2)\n}",
+        ))
+        .unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        check_semantics(&ast).unwrap();
 
-        Ok(())
+        let ModuleMembers::Stmt(Statement::FnDecl(outer)) = &ast.productions[0] else {
+            panic!("Expected a function declaration")
+        };
+        let BlockMember::Stmt(Statement::FnDecl(inner)) = &outer.block.members[0] else {
+            panic!("Expected a nested function declaration")
+        };
+        let expected = vec![
+            Capture {
+                name: "a".into(),
+                is_mutable: false,
+            },
+            Capture {
+                name: "inner".into(),
+                is_mutable: false,
+            },
+        ];
+        assert!(inner.is_nested.get());
+        assert_eq!(expected, *inner.captures.borrow());
+        assert!(outer.captures.borrow().is_empty());
     }
 }
@@ -0,0 +1,266 @@
+use crate::{
+    error_handling::{
+        error_messages::SEMANTIC_DUPLICATED_REFERENCE, ErrorContainer, ErrorLabel, MistiError,
+    },
+    lexic::token::Token,
+    semantic::{
+        impls::SemanticCheck,
+        symbol_table::{Member, SymbolTable},
+        types::mark_declared_types,
+    },
+    syntax::ast::interface::InterfaceDeclaration,
+};
+
+use super::function_declaration::signature_type;
+
+impl SemanticCheck for InterfaceDeclaration<'_> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        let interface_name = &self.identifier.value;
+
+        // Check that the interface is not already defined
+        if scope.test(interface_name)
+            || scope.get_enum(interface_name).is_some()
+            || scope.get_interface(interface_name).is_some()
+        {
+            return Err(vec![duplicated_error(
+                format!(
+                    "A symbol with name {} was already defined at this scope",
+                    interface_name,
+                ),
+                self.identifier,
+            )]);
+        }
+
+        let mut errors = Vec::new();
+        let mut methods = Vec::<Member>::new();
+
+        for method in self.methods.iter() {
+            let name = &method.identifier.value;
+            if methods.iter().any(|m| m.name == *name) {
+                errors.push(duplicated_error(
+                    format!(
+                        "A method with name {} was already declared in this interface",
+                        name
+                    ),
+                    method.identifier,
+                ));
+                continue;
+            }
+
+            methods.push(Member {
+                name: name.clone(),
+                datatype: signature_type(
+                    &method.type_params,
+                    &method.params_list,
+                    &method.return_type,
+                ),
+                is_mutable: false,
+            });
+        }
+
+        // The interface is registered even if it has errors,
+        // so that its usages don't report more errors
+        scope.declare_interface(interface_name.clone(), methods);
+
+        // The methods may receive or return the interface itself
+        for method in self.methods.iter() {
+            for parameter in method.params_list.parameters.iter() {
+                mark_declared_types(&parameter.datatype, scope);
+            }
+            if let Some(return_datatype) = &method.return_type {
+                mark_declared_types(return_datatype, scope);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+}
+
+fn duplicated_error(message: String, identifier: &Token) -> MistiError {
+    let label = ErrorLabel {
+        message,
+        start: identifier.position,
+        end: identifier.get_end_position(),
+    };
+    ErrorContainer {
+        error_code: SEMANTIC_DUPLICATED_REFERENCE,
+        error_offset: identifier.position,
+        labels: vec![label],
+        note: None,
+        help: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error_handling::error_messages::{
+            SEMANTIC_DUPLICATED_REFERENCE, SEMANTIC_INVALID_REFERENCE, SEMANTIC_MISMATCHED_TYPES,
+            SEMANTIC_MISSING_REFERENCE, SEMANTIC_UNIMPLEMENTED_INTERFACE,
+        },
+        semantic::test_utils::check,
+    };
+
+    const SHAPE: &str = "interface Shape {
+    fun area() -> Float
+    fun scale(Float factor)
+}
+";
+
+    const SQUARE: &str = "class Square(var Float side) : Shape {
+    fun area() -> Float {
+        self.side * self.side
+    }
+
+    fun scale(Float factor) {
+        self.side *= factor
+    }
+}
+";
+
+    #[test]
+    fn should_check_implementations() {
+        assert!(check(&format!("{}{}", SHAPE, SQUARE)).is_ok());
+
+        // Methods may return a subtype, and accept a supertype
+        let input = "interface Parser {\n    fun parse(String input) -> ?Int\n}\nclass IntParser : Parser {\n    fun parse(?String input) -> Int {\n        1\n    }\n}";
+        assert!(check(input).is_ok());
+    }
+
+    #[test]
+    fn should_use_interfaces_as_types() {
+        let input = format!(
+            "{}{}fun total(Shape shape) -> Float {{\n    shape.area()\n}}\nShape s = Square(2.0)\ns.scale(2)\nval t = total(Square(1.0))",
+            SHAPE, SQUARE
+        );
+        assert!(check(&input).is_ok());
+
+        // Classes that don't implement the interface can't be used
+        let input = format!(
+            "{}{}class Circle(val Float radius)\nShape s = Circle(1.0)",
+            SHAPE, SQUARE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(
+            "But this expression has type Circle",
+            errors[0].labels[1].message
+        );
+
+        let input = format!(
+            "{}{}class Circle(val Float radius)\nfun f(Shape shape) {{}}\nf(Circle(1.0))",
+            SHAPE, SQUARE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_MISMATCHED_TYPES, errors[0].error_code);
+        assert_eq!("Expected a Shape, got Circle", errors[0].labels[0].message);
+    }
+
+    #[test]
+    fn should_only_access_methods_of_the_interface() {
+        let input = format!(
+            "{}{}fun f(Shape shape) -> Float {{\n    shape.side\n}}",
+            SHAPE, SQUARE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_INVALID_REFERENCE, errors[0].error_code);
+        assert_eq!(
+            "The type Shape has no member `side`",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_report_missing_methods() {
+        let input = format!(
+            "{}class Square : Shape {{\n    fun area() -> Float {{\n        1.0\n    }}\n}}",
+            SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_UNIMPLEMENTED_INTERFACE, errors[0].error_code);
+        assert_eq!(
+            "The class Square is missing the method `scale` of the interface Shape",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_report_incompatible_methods() {
+        let input = format!(
+            "{}class Square : Shape {{\n    fun area() -> String {{\n        \"1\"\n    }}\n\n    fun scale(Float factor) {{}}\n}}",
+            SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_UNIMPLEMENTED_INTERFACE, errors[0].error_code);
+        assert_eq!(
+            "The member `area` of the class Square has type () -> String, but the interface Shape requires () -> Float",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_not_convert_ints_in_implementations() {
+        let input = format!(
+            "{}class Square : Shape {{\n    fun area() -> Int {{\n        1\n    }}\n\n    fun scale(Float factor) {{}}\n}}",
+            SHAPE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_UNIMPLEMENTED_INTERFACE, errors[0].error_code);
+        assert_eq!(
+            "The member `area` of the class Square has type () -> Int, but the interface Shape requires () -> Float",
+            errors[0].labels[0].message
+        );
+
+        let input = "interface Counter {\n    fun add(Int amount)\n}\nclass Total : Counter {\n    fun add(Float amount) {}\n}";
+        let errors = check(input).unwrap_err();
+        assert_eq!(SEMANTIC_UNIMPLEMENTED_INTERFACE, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_accept_classes_in_implementations_of_interfaces() {
+        // A method may return a class where the interface returns an interface
+        // the class implements, and accept the interface where it requires the class
+        let input = format!(
+            "{}{}interface Copyable {{\n    fun copy() -> Shape\n    fun same(Square other) -> Bool\n}}\nclass Copy : Copyable {{\n    fun copy() -> Square {{\n        Square(1.0)\n    }}\n\n    fun same(Shape other) -> Bool {{\n        true\n    }}\n}}",
+            SHAPE, SQUARE
+        );
+        assert!(check(&input).is_ok());
+
+        // But not the other way around
+        let input = format!(
+            "{}{}interface Copyable {{\n    fun copy() -> Square\n}}\nclass Copy : Copyable {{\n    fun copy() -> Shape {{\n        Square(1.0)\n    }}\n}}",
+            SHAPE, SQUARE
+        );
+        let errors = check(&input).unwrap_err();
+        assert_eq!(SEMANTIC_UNIMPLEMENTED_INTERFACE, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_report_missing_interfaces() {
+        let errors = check("class Square : Shape").unwrap_err();
+        assert_eq!(SEMANTIC_MISSING_REFERENCE, errors[0].error_code);
+
+        let errors = check("class Shape\nclass Square : Shape").unwrap_err();
+        assert_eq!(SEMANTIC_INVALID_REFERENCE, errors[0].error_code);
+        assert_eq!("Shape is not an interface", errors[0].labels[0].message);
+    }
+
+    #[test]
+    fn should_report_duplicates() {
+        let errors = check(&format!("{}{}", SHAPE, SHAPE)).unwrap_err();
+        assert_eq!(SEMANTIC_DUPLICATED_REFERENCE, errors[0].error_code);
+
+        let errors = check(&format!("{}class Shape", SHAPE)).unwrap_err();
+        assert_eq!(SEMANTIC_DUPLICATED_REFERENCE, errors[0].error_code);
+
+        let errors = check("interface Shape {\n    fun area() -> Float\n    fun area() -> Int\n}")
+            .unwrap_err();
+        assert_eq!(
+            "A method with name area was already declared in this interface",
+            errors[0].labels[0].message
+        );
+    }
+}
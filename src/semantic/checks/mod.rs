@@ -1,9 +1,13 @@
 pub mod assignment;
 pub mod binding;
 pub mod block;
+pub mod class_declaration;
 pub mod conditional;
+pub mod enum_declaration;
 pub mod expression;
 pub mod for_loop;
 pub mod function_declaration;
+pub mod interface_declaration;
+pub mod return_statement;
 pub mod top_level_declaration;
 pub mod while_loop;
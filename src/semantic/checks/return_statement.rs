@@ -0,0 +1,267 @@
+use crate::{
+    error_handling::{
+        error_messages::{SEMANTIC_INVALID_RETURN, SEMANTIC_MISMATCHED_TYPES},
+        ErrorContainer, ErrorLabel, MistiError,
+    },
+    semantic::{
+        impls::SemanticCheck,
+        symbol_table::SymbolTable,
+        types::{is_assignable_expression, Type, Typed},
+    },
+    syntax::ast::{Block, BlockMember, Expression, Positionable, Return, Statement},
+};
+
+impl SemanticCheck for Return<'_> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        let Some(return_type) = scope.get_return_type() else {
+            let label = ErrorLabel {
+                message: String::from("`return` can only be used inside a function"),
+                start: self.keyword.position,
+                end: self.keyword.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SEMANTIC_INVALID_RETURN,
+                error_offset: self.keyword.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            return Err(vec![econtainer]);
+        };
+
+        let Some(value) = &self.value else {
+            if return_type.is_unit() {
+                return Ok(());
+            }
+
+            let label = ErrorLabel {
+                message: format!(
+                    "Expected a value of type {} after this `return`",
+                    return_type
+                ),
+                start: self.keyword.position,
+                end: self.keyword.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SEMANTIC_MISMATCHED_TYPES,
+                error_offset: self.keyword.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            return Err(vec![econtainer]);
+        };
+
+        value.check_semantics(scope)?;
+
+        if return_type.is_unit() {
+            let (error_start, error_end) = value.get_position();
+            let label = ErrorLabel {
+                message: String::from("This function returns Unit, it can't return a value"),
+                start: error_start,
+                end: error_end,
+            };
+            let econtainer = ErrorContainer {
+                error_code: SEMANTIC_INVALID_RETURN,
+                error_offset: error_start,
+                labels: vec![label],
+                note: None,
+                help: Some(String::from("Declare the return type of the function")),
+            };
+            return Err(vec![econtainer]);
+        }
+
+        check_returned_value(value, &return_type, scope).map_err(|error| vec![error])
+    }
+}
+
+/// Checks that the type of a returned value, explicitly or implicitly,
+/// is the return type of the function
+pub fn check_returned_value(
+    value: &Expression,
+    return_type: &Type,
+    scope: &SymbolTable,
+) -> Result<(), MistiError> {
+    let value_type = value.get_type(scope)?;
+    if is_assignable_expression(value, &value_type, &return_type.resolve(scope)) {
+        return Ok(());
+    }
+
+    let (error_start, error_end) = value.get_position();
+    let label = ErrorLabel {
+        message: format!(
+            "Expected a value of type {}, found {}",
+            return_type, value_type
+        ),
+        start: error_start,
+        end: error_end,
+    };
+    Err(ErrorContainer {
+        error_code: SEMANTIC_MISMATCHED_TYPES,
+        error_offset: error_start,
+        labels: vec![label],
+        note: None,
+        help: None,
+    })
+}
+
+/// Checks the body of a function that returns `return_type`.
+///
+/// The expression at the end of the body, or at the end of each branch
+/// of an if/else at the end of the body, is implicitly returned. Its type
+/// is checked in the scope it's found in, e.g. inside an if/else that
+/// narrows the type of a variable
+pub fn check_function_body(
+    block: &Block,
+    return_type: &Type,
+    scope: &SymbolTable,
+) -> Result<(), Vec<MistiError>> {
+    let Some((last_member, members)) = block.members.split_last() else {
+        return Ok(());
+    };
+
+    // Check every member, collecting all the errors
+    let mut errors = Vec::new();
+    for member in members {
+        if let Err(member_errors) = member.check_semantics(scope) {
+            errors.extend(member_errors);
+        }
+    }
+
+    let last_member_result = match last_member {
+        BlockMember::Expr(expression) => expression.check_semantics(scope).and_then(|_| {
+            check_returned_value(expression, return_type, scope).map_err(|e| vec![e])
+        }),
+        BlockMember::Stmt(Statement::Conditional(conditional))
+            if conditional.else_block.is_some() =>
+        {
+            conditional.check_branches(scope, Some(return_type))
+        }
+        member => member.check_semantics(scope),
+    };
+    if let Err(member_errors) = last_member_result {
+        errors.extend(member_errors);
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(())
+}
+
+/// Checks if a function body returns a value on every path.
+///
+/// A block returns if it contains a `return`, if it ends with an expression
+/// that is implicitly returned, or if it contains an if/else whose branches
+/// all return. Loops may not run, so the returns inside them are not considered.
+pub fn returns_on_every_path(block: &Block) -> bool {
+    block_returns(block, true)
+}
+
+/// `is_tail` is true when the end of the block is the end of the function
+fn block_returns(block: &Block, is_tail: bool) -> bool {
+    let last_index = block.members.len().saturating_sub(1);
+
+    block.members.iter().enumerate().any(|(index, member)| {
+        let member_is_tail = is_tail && index == last_index;
+
+        match member {
+            BlockMember::Expr(_) => member_is_tail,
+            BlockMember::Stmt(Statement::Return(_)) => true,
+            BlockMember::Stmt(Statement::Conditional(conditional)) => {
+                let Some(else_block) = &conditional.else_block else {
+                    return false;
+                };
+
+                block_returns(&conditional.if_member.body, member_is_tail)
+                    && conditional
+                        .else_if_members
+                        .iter()
+                        .all(|member| block_returns(&member.body, member_is_tail))
+                    && block_returns(else_block, member_is_tail)
+            }
+            _ => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error_handling::error_messages::{
+            SEMANTIC_INVALID_RETURN, SEMANTIC_MISMATCHED_TYPES, SEMANTIC_MISSING_RETURN,
+        },
+        semantic::test_utils::check,
+    };
+
+    fn error_code(input: &str) -> u32 {
+        let errors = check(input).unwrap_err();
+        assert_eq!(1, errors.len());
+        errors[0].error_code
+    }
+
+    #[test]
+    fn should_accept_explicit_and_implicit_returns() {
+        assert!(check("fun f(Int a) -> Int {\n    return a\n}").is_ok());
+        assert!(check("fun f(Int a) -> Int {\n    a + 1\n}").is_ok());
+        assert!(check("fun f() {\n    return\n}").is_ok());
+        assert!(check("fun f() {\n    print(\"a\")\n}").is_ok());
+    }
+
+    #[test]
+    fn should_accept_returns_in_every_branch() {
+        let input = "fun f(Bool c) -> Int {\n    if c {\n        1\n    } else {\n        return 2\n    }\n}";
+        assert!(check(input).is_ok());
+
+        let input = "fun f(Bool c) -> Int {\n    if c {\n        return 1\n    }\n    2\n}";
+        assert!(check(input).is_ok());
+    }
+
+    #[test]
+    fn should_report_missing_returns() {
+        let input = "fun f(Bool c) -> Int {\n    if c {\n        return 1\n    }\n}";
+        assert_eq!(SEMANTIC_MISSING_RETURN, error_code(input));
+
+        let input = "fun f(Bool c) -> Int {\n    while c {\n        return 1\n    }\n}";
+        assert_eq!(SEMANTIC_MISSING_RETURN, error_code(input));
+
+        // The if/else is not at the end of the function
+        let input = "fun f(Bool c) -> Int {\n    if c {\n        1\n    } else {\n        2\n    }\n    while c {\n        print(\"a\")\n    }\n}";
+        assert_eq!(SEMANTIC_MISSING_RETURN, error_code(input));
+    }
+
+    #[test]
+    fn should_report_returns_of_the_wrong_type() {
+        assert_eq!(
+            SEMANTIC_MISMATCHED_TYPES,
+            error_code("fun f() -> Int {\n    return \"a\"\n}")
+        );
+        assert_eq!(
+            SEMANTIC_MISMATCHED_TYPES,
+            error_code("fun f() -> Int {\n    \"a\"\n}")
+        );
+        assert_eq!(
+            SEMANTIC_MISMATCHED_TYPES,
+            error_code("fun f() -> Int {\n    return\n}")
+        );
+        assert_eq!(
+            SEMANTIC_INVALID_RETURN,
+            error_code("fun f() {\n    return 1\n}")
+        );
+    }
+
+    #[test]
+    fn should_report_returns_outside_functions() {
+        assert_eq!(SEMANTIC_INVALID_RETURN, error_code("return 1"));
+        assert_eq!(
+            SEMANTIC_INVALID_RETURN,
+            error_code("if true {\n    return\n}")
+        );
+    }
+
+    #[test]
+    fn should_register_the_return_type_of_functions() {
+        assert!(check("fun f() -> Int {\n    1\n}\nInt x = f()").is_ok());
+    }
+}
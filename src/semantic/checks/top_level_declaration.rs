@@ -8,7 +8,7 @@ impl SemanticCheck for ModuleMembers<'_> {
     fn check_semantics(
         &self,
         scope: &crate::semantic::symbol_table::SymbolTable,
-    ) -> Result<(), crate::error_handling::MistiError> {
+    ) -> Result<(), Vec<crate::error_handling::MistiError>> {
         match self {
             ModuleMembers::Stmt(statement) => statement.check_semantics(scope),
             ModuleMembers::Expr(expression) => expression.check_semantics(scope),
@@ -18,14 +18,18 @@ impl SemanticCheck for ModuleMembers<'_> {
 
 // TODO: Move to its own file when it grows
 impl SemanticCheck for Statement<'_> {
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
         match self {
             Statement::Binding(b) => b.check_semantics(scope),
             Statement::FnDecl(f) => f.check_semantics(scope),
+            Statement::ClassDecl(c) => c.check_semantics(scope),
+            Statement::EnumDecl(e) => e.check_semantics(scope),
+            Statement::InterfaceDecl(i) => i.check_semantics(scope),
             Statement::Conditional(c) => c.check_semantics(scope),
             Statement::ForLoop(f) => f.check_semantics(scope),
             Statement::WhileLoop(w) => w.check_semantics(scope),
             Statement::Assignment(a) => a.check_semantics(scope),
+            Statement::Return(r) => r.check_semantics(scope),
         }
     }
 }
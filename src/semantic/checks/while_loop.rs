@@ -11,36 +11,51 @@ use crate::{
 };
 
 impl SemanticCheck for WhileLoop<'_> {
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError> {
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        // The condition and the block are checked independently,
+        // so that errors in both are reported
+        let mut errors = Vec::new();
+
         // Check condition is a bool
         let condition = &self.condition;
-        let condition_type = condition.get_type(scope)?;
-
-        if !condition_type.equals(&Type::Value("Bool".into())) {
-            let (error_start, error_end) = condition.get_position();
+        let condition_type = condition
+            .check_semantics(scope)
+            .and_then(|_| condition.get_type(scope).map_err(|error| vec![error]));
+        match condition_type {
+            Ok(condition_type) if !condition_type.is_assignable_to(&Type::Value("Bool".into())) => {
+                let (error_start, error_end) = condition.get_position();
 
-            let label = ErrorLabel {
-                message: format!(
-                    "Expected a condition of type Bool, found {:?}",
-                    condition_type
-                ),
-                start: error_start,
-                end: error_end,
-            };
-            let econtainer = ErrorContainer {
-                error_code: SEMANTIC_MISMATCHED_TYPES,
-                error_offset: error_start,
-                labels: vec![label],
-                note: Some(String::from("THP does not have truthy/falsey values.")),
-                help: None,
-            };
-            return Err(econtainer);
+                let label = ErrorLabel {
+                    message: format!(
+                        "Expected a condition of type Bool, found {}",
+                        condition_type
+                    ),
+                    start: error_start,
+                    end: error_end,
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SEMANTIC_MISMATCHED_TYPES,
+                    error_offset: error_start,
+                    labels: vec![label],
+                    note: Some(String::from("THP does not have truthy/falsey values.")),
+                    help: None,
+                };
+                errors.push(econtainer);
+            }
+            Ok(_) => {}
+            Err(condition_errors) => errors.extend(condition_errors),
         }
 
         // TODO: Define scoping rules for while loops
 
         // Check inner block
-        self.body.check_semantics(scope)?;
+        if let Err(block_errors) = self.body.check_semantics(scope) {
+            errors.extend(block_errors);
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
 
         Ok(())
     }
@@ -7,15 +7,28 @@ pub trait SemanticCheck {
     /// Checks the semantics of this AST node and performs typechecking
     ///
     /// Types are provided by the Typed trait, because not every AST node
-    /// will have a defined type
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError>;
+    /// will have a defined type.
+    ///
+    /// Nodes that contain other nodes (modules, blocks) keep checking
+    /// after an error, so more than one error may be returned.
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>>;
 }
 
 impl SemanticCheck for ModuleAST<'_> {
-    /// Checks that this AST is semantically correct, given a symbol table
-    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), MistiError> {
+    /// Checks that this AST is semantically correct, given a symbol table.
+    ///
+    /// Every declaration is checked, even if a previous one failed,
+    /// and all the errors found are returned.
+    fn check_semantics(&self, scope: &SymbolTable) -> Result<(), Vec<MistiError>> {
+        let mut errors = Vec::new();
         for declaration in &self.productions {
-            declaration.check_semantics(scope)?;
+            if let Err(declaration_errors) = declaration.check_semantics(scope) {
+                errors.extend(declaration_errors);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
         }
 
         Ok(())
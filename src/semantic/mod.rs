@@ -4,7 +4,10 @@ mod checks;
 mod impls;
 pub mod std;
 pub mod symbol_table;
-mod types;
+pub mod types;
+
+#[cfg(test)]
+mod test_utils;
 
 use impls::SemanticCheck;
 use symbol_table::SymbolTable;
@@ -15,8 +18,9 @@ use symbol_table::SymbolTable;
 // 3. Add the symbols declared to the symbol table, annotating them with their type
 // 4. Check if the symbols used are declared
 
-/// Checks that the AST is semantically correct
-pub fn check_semantics(ast: &ModuleAST) -> Result<(), MistiError> {
+/// Checks that the AST is semantically correct.
+/// Returns every semantic error found in the AST.
+pub fn check_semantics(ast: &ModuleAST) -> Result<(), Vec<MistiError>> {
     // For now there's only support for a single file
     // TODO: Receive a symbol table as a reference and work on it.
     // this way we can implement a unique symbol table for REPL session
@@ -31,20 +35,27 @@ pub fn check_semantics(ast: &ModuleAST) -> Result<(), MistiError> {
 pub fn check_semantics_with(
     ast: &ModuleAST,
     symbol_table: &mut SymbolTable,
-) -> Result<(), MistiError> {
+) -> Result<(), Vec<MistiError>> {
     ast.check_semantics(&symbol_table)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::semantic::types::Type;
+    use crate::{
+        error_handling::error_messages::{
+            SEMANTIC_NON_EXHAUSTIVE_MATCH, SEMANTIC_NO_MATCHING_OVERLOAD,
+        },
+        lexic::get_tokens,
+        semantic::types::Type,
+        syntax::build_ast,
+    };
 
-    use super::symbol_table::SymbolTable;
+    use super::{check_semantics, symbol_table::SymbolTable, test_utils::check};
 
     #[test]
     fn test_1() {
         let global_scope = SymbolTable::new();
-        let main_function = Type::Function(vec![], String::from("Unit"));
+        let main_function = Type::Function(vec![], Box::new(Type::Value("Unit".into())));
 
         global_scope.insert("main".into(), main_function);
 
@@ -55,11 +66,14 @@ mod tests {
     fn test_2() {
         let global_scope = SymbolTable::new();
 
-        let main_function = Type::Function(vec![], String::from("Unit"));
+        let main_function = Type::Function(vec![], Box::new(Type::Value("Unit".into())));
         global_scope.insert("main".into(), main_function);
         global_scope.insert("db_url".into(), Type::Value("String".into()));
 
-        let add_function = Type::Function(vec!["Int".into(), "Int".into()], "Int".into());
+        let add_function = Type::Function(
+            vec![Type::Value("Int".into()), Type::Value("Int".into())],
+            Box::new(Type::Value("Int".into())),
+        );
 
         global_scope.insert("add".into(), add_function);
 
@@ -78,4 +92,315 @@ mod tests {
         assert!(add_function_scope.test(&"a".into()));
         global_scope.insert("test".into(), Type::Value("Int".into()));
     }
+
+    #[test]
+    fn should_compare_generic_types_in_assignments() {
+        let tokens = get_tokens(&String::from("var a = [1]\na = [2]")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        assert!(check_semantics(&ast).is_ok());
+
+        let tokens = get_tokens(&String::from("var a = [1]\na = [\"b\"]")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let errors = check_semantics(&ast).unwrap_err();
+        assert_eq!(
+            "But this expression has type Array[String]",
+            errors[0].labels[1].message
+        );
+    }
+
+    #[test]
+    fn should_check_nullable_types() {
+        assert!(check("?String name = null\n?String other = \"a\"").is_ok());
+        assert!(check("?String name = null\nval other = name\n?String third = other").is_ok());
+
+        let errors = check("?String name = null\nString other = name").unwrap_err();
+        assert_eq!(
+            "But this expression has type ?String",
+            errors[0].labels[1].message
+        );
+
+        let errors = check("String name = null").unwrap_err();
+        assert_eq!(
+            "But this expression has type Null",
+            errors[0].labels[1].message
+        );
+
+        let errors = check("?String name = null\nprint(name)").unwrap_err();
+        assert_eq!(
+            "Expected a String, got ?String",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_check_null_coalescing() {
+        assert!(check("?String name = null\nString other = name ?? \"anon\"").is_ok());
+        assert!(check("?String a = null\n?String b = null\nString c = a ?? b ?? \"c\"").is_ok());
+
+        let errors = check("?String name = null\nString other = name ?? 10").unwrap_err();
+        assert_eq!(
+            "There is no overload of `??` for (?String, Int)",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_narrow_nullable_values_in_conditionals() {
+        let input =
+            "fun greet(?String name) {\n    if name != null {\n        print(name)\n    }\n}";
+        assert!(check(input).is_ok());
+
+        let input = "?String name = null\nif null == name {\n    print(\"none\")\n} else {\n    print(name)\n}";
+        assert!(check(input).is_ok());
+
+        // Outside the conditional the value is still nullable
+        let input = "fun greet(?String name) {\n    if name != null {\n        print(name)\n    }\n    print(name)\n}";
+        assert_eq!(1, check(input).unwrap_err().len());
+
+        // Mutable variables may be set to null inside the block
+        let input = "var ?String name = null\nif name != null {\n    print(name)\n}";
+        assert!(check(input).is_err());
+    }
+
+    #[test]
+    fn should_not_access_members_of_nullable_values() {
+        let errors = check("?String name = null\nname.length").unwrap_err();
+        assert_eq!(
+            "This value may be null, its type is ?String",
+            errors[0].labels[0].message
+        );
+
+        // No type has members yet
+        let errors = check("?String name = null\nname?.length").unwrap_err();
+        assert_eq!(
+            "The type String has no member `length`",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_check_union_types() {
+        assert!(check("Int | String value = 10\nInt | String other = \"a\"").is_ok());
+        assert!(check("fun f(Int | String value) {}\nf(10)\nf(\"a\")").is_ok());
+
+        let errors = check("Int | String value = 10\nString other = value").unwrap_err();
+        assert_eq!(
+            "But this expression has type Int | String",
+            errors[0].labels[1].message
+        );
+        assert!(check("Int | String value = true").is_err());
+    }
+
+    #[test]
+    fn should_narrow_unions_in_conditionals() {
+        let input = "fun describe(Int | String value) -> String {\n    if value is Int {\n        \"number\"\n    } else {\n        value\n    }\n}";
+        assert!(check(input).is_ok());
+
+        // Each else if is narrowed by the previous conditions
+        let input = "fun f(Int | String | Bool value) -> String {\n    if value is Int {\n        \"number\"\n    } else if value is Bool {\n        \"bool\"\n    } else {\n        value\n    }\n}";
+        assert!(check(input).is_ok());
+
+        // The implicit return is checked in the narrowed scope
+        let input = "fun f(Int | String value) -> Int {\n    if value is Int {\n        val doubled = value + value\n        doubled\n    } else {\n        0\n    }\n}";
+        assert!(check(input).is_ok());
+
+        let input = "fun f(?String value) -> String {\n    if !(value is String) {\n        \"none\"\n    } else {\n        value\n    }\n}";
+        assert!(check(input).is_ok());
+
+        let input = "fun f(Int | String value) -> String {\n    if value is Int {\n        value\n    } else {\n        value\n    }\n}";
+        let errors = check(input).unwrap_err();
+        assert_eq!(1, errors.len());
+    }
+
+    #[test]
+    fn should_reject_type_tests_that_are_always_false() {
+        let errors = check("Int value = 10\nvalue is String").unwrap_err();
+        assert_eq!(
+            "This value can never be a String, its type is Int",
+            errors[0].labels[0].message
+        );
+        assert!(check("Int | String value = 10\nBool b = value is String").is_ok());
+    }
+
+    #[test]
+    fn should_use_ints_where_floats_are_expected() {
+        assert!(check("val Float x = 1").is_ok());
+        assert!(check("fun f(Float x) {}\nf(1)").is_ok());
+        assert!(check("fun f() -> Float {\n    1\n}").is_ok());
+        assert!(check("fun f() -> Float {\n    return 1\n}").is_ok());
+
+        let errors = check("Int x = 1.5").unwrap_err();
+        assert_eq!(
+            "But this expression has type Float",
+            errors[0].labels[1].message
+        );
+    }
+
+    #[test]
+    fn should_type_arrays_of_numbers_as_floats() {
+        assert!(check("Array[Float] xs = [1, 2.5]").is_ok());
+        assert!(check("val xs = [1, 2.5, 3]\nArray[Float] ys = xs").is_ok());
+
+        let errors = check("val xs = [1, 2.5]\nArray[Int] ys = xs").unwrap_err();
+        assert_eq!(
+            "But this expression has type Array[Float]",
+            errors[0].labels[1].message
+        );
+        assert!(check("val xs = [1, \"a\"]").is_err());
+    }
+
+    #[test]
+    fn should_use_arrays_of_ints_where_arrays_of_floats_are_expected() {
+        assert!(check("val Array[Float] xs = [1, 2]").is_ok());
+        assert!(check("var Array[Float] xs = [1.5]\nxs = [1, 2]").is_ok());
+        assert!(check("fun f(Array[Float] xs) {}\nf([1, 2])").is_ok());
+        assert!(check("fun f() -> ?Array[Float] {\n    [1, 2]\n}").is_ok());
+
+        // Only array literals are converted
+        let errors = check("val xs = [1, 2]\nArray[Float] ys = xs").unwrap_err();
+        assert_eq!(
+            "But this expression has type Array[Int]",
+            errors[0].labels[1].message
+        );
+        assert!(check("val Array[Int] xs = [1.5, 2]").is_err());
+    }
+
+    #[test]
+    fn should_type_mixed_arithmetic_as_float() {
+        assert!(check("Int x = 1 + 2").is_ok());
+        assert!(check("Float x = 1 + 2.5").is_ok());
+        assert!(check("Float x = 2.5 - 1").is_ok());
+        assert!(check("Float x = -2.5").is_ok());
+        assert!(check("Bool b = 1 == 1.5").is_ok());
+
+        let errors = check("Int x = 1 + 2.5").unwrap_err();
+        assert_eq!(
+            "But this expression has type Float",
+            errors[0].labels[1].message
+        );
+        assert!(check("Int x = -2.5").is_err());
+        assert!(check("val x = 1 + \"a\"").is_err());
+    }
+
+    #[test]
+    fn should_resolve_operator_overloads() {
+        assert!(check("Float x = 10 / 4").is_ok());
+        assert!(check("Int x = 10 % 4").is_ok());
+        assert!(check("Bool b = 1 < 2.5").is_ok());
+        assert!(check("String s = \"a\" ++ \"b\"").is_ok());
+        assert!(check("Int x = 10 / 4").is_err());
+
+        let errors = check("val x = \"a\" * 2").unwrap_err();
+        assert_eq!(SEMANTIC_NO_MATCHING_OVERLOAD, errors[0].error_code);
+        assert_eq!(
+            "There is no overload of `*` for (String, Int)",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_check_logic_operators() {
+        assert!(check("Bool b = true && 1 < 2 || !false").is_ok());
+
+        let errors = check("val b = true && 1").unwrap_err();
+        assert_eq!(
+            "There is no overload of `&&` for (Bool, Int)",
+            errors[0].labels[0].message
+        );
+        assert!(check("val b = !10").is_err());
+    }
+
+    #[test]
+    fn should_narrow_the_right_side_of_logic_operators() {
+        let input = "fun f(Int | String value) -> Bool {\n    value is Int && value > 10\n}";
+        assert!(check(input).is_ok());
+
+        let input = "fun f(Int | String value) -> Bool {\n    value is String || value > 10\n}";
+        assert!(check(input).is_ok());
+
+        let input = "fun f(?Int value) -> Bool {\n    value != null && value > 10\n}";
+        assert!(check(input).is_ok());
+
+        // The left side is not narrowed by the right side
+        let input = "fun f(Int | String value) -> Bool {\n    value > 10 && value is Int\n}";
+        assert!(check(input).is_err());
+        let input = "fun f(Int | String value) -> Bool {\n    value is Int || value > 10\n}";
+        assert!(check(input).is_err());
+    }
+
+    #[test]
+    fn should_check_match_expressions() {
+        let input = "fun describe(Int value) -> String {\n    match value\n    case 1, 2 { \"small\" }\n    else { \"big\" }\n}";
+        assert!(check(input).is_ok());
+
+        let input = "fun sign(Bool positive) -> Int {\n    match positive\n    case true { 1 }\n    case false { -1 }\n}";
+        assert!(check(input).is_ok());
+
+        let input = "fun describe(Int value) -> Int {\n    match value\n    case 1 { \"one\" }\n    else { \"other\" }\n}";
+        assert!(check(input).is_err());
+
+        let errors = check("val b = true\nval x = match b\ncase true { 1 }").unwrap_err();
+        assert_eq!(SEMANTIC_NON_EXHAUSTIVE_MATCH, errors[0].error_code);
+    }
+
+    #[test]
+    fn should_use_functions_as_values() {
+        let prelude = "fun add(Int a, Int b) -> Int {\n    a + b\n}\nfun apply((Int, Int) -> Int f, Int x) -> Int {\n    f(x, x)\n}\n";
+        assert!(check(&format!("{prelude}val r = apply(add, 3)")).is_ok());
+        assert!(check(&format!(
+            "{prelude}val r = apply(fn(Int a, Int b) -> Int {{ a * b }}, 3)"
+        ))
+        .is_ok());
+        assert!(
+            check("val f = fn() -> (Int) -> Int { fn(Int x) -> Int { x } }\nInt r = f()(1)")
+                .is_ok()
+        );
+
+        let errors = check(&format!("{prelude}val r = apply(print, 3)")).unwrap_err();
+        assert_eq!(
+            "Expected a (Int, Int) -> Int, got (String) -> Void",
+            errors[0].labels[0].message
+        );
+
+        let errors = check(
+            "val x = 5
+x(1)",
+        )
+        .unwrap_err();
+        assert_eq!(
+            "Expected this expression to be a function, found Int",
+            errors[0].labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_declare_bindings_with_errors() {
+        let errors =
+            check("val a = missing\nval b = a + 1\nval c = a.name\nval d = a(1)[0]").unwrap_err();
+        assert_eq!(1, errors.len());
+        assert_eq!(
+            "Cannot find this identifier in this scope",
+            errors[0].labels[0].message
+        );
+
+        // The declared type is used
+        let errors = check("Int a = missing\nString b = a").unwrap_err();
+        assert_eq!(2, errors.len());
+        assert_eq!(
+            "But this expression has type Int",
+            errors[1].labels[1].message
+        );
+    }
+
+    #[test]
+    fn should_report_every_semantic_error() {
+        let tokens = get_tokens(&String::from("val a = b\nval c = 1\nval c = d")).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+
+        let errors = check_semantics(&ast).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].error_offset, 8);
+        assert_eq!(errors[1].error_offset, 24);
+    }
 }
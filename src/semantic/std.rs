@@ -5,7 +5,7 @@
 use super::{
     symbol_table::SymbolTable,
     types::{
-        global::{INT, STRING, VOID},
+        global::{BOOL, FLOAT, INT, STRING, VOID},
         Type,
     },
 };
@@ -13,14 +13,82 @@ use super::{
 /// Populates the symbol table with the stdlib
 pub fn populate(table: &mut SymbolTable) {
     // print: (String) -> (Void)
-    let print_fn = Type::Function(vec![STRING.into()], VOID.into());
-    table.insert("print".into(), print_fn);
+    let print_fn = Type::Function(
+        vec![Type::Value(STRING.into())],
+        Box::new(Type::Value(VOID.into())),
+    );
+    table.insert_function("print".into(), print_fn);
 
-    // + operator (Int, Int) -> Int
-    let plus_op = Type::Function(vec![INT.into(), INT.into()], INT.into());
-    table.insert("+".into(), plus_op);
+    // Operators are overloaded functions. The overloads are tried in order,
+    // so the ones over Ints come first: Ints can be used as Floats
+    let int = || Type::Value(INT.into());
+    let float = || Type::Value(FLOAT.into());
+    let bool = || Type::Value(BOOL.into());
+    let string = || Type::Value(STRING.into());
+    let function =
+        |params: Vec<Type>, return_type: Type| Type::Function(params, Box::new(return_type));
 
-    // - operator (Int, Int) -> Int
-    let plus_op = Type::Function(vec![INT.into(), INT.into()], INT.into());
-    table.insert("-".into(), plus_op);
+    // Arithmetic operators (Int, Int) -> Int & (Float, Float) -> Float
+    for operator in ["+", "*"] {
+        let overloads = vec![
+            function(vec![int(), int()], int()),
+            function(vec![float(), float()], float()),
+        ];
+        table.insert(operator.into(), Type::Overloaded(overloads));
+    }
+
+    // `-` is also the unary negation: (Int) -> Int & (Float) -> Float
+    let minus_op = vec![
+        function(vec![int(), int()], int()),
+        function(vec![float(), float()], float()),
+        function(vec![int()], int()),
+        function(vec![float()], float()),
+    ];
+    table.insert("-".into(), Type::Overloaded(minus_op));
+
+    // Division always returns a Float: (Float, Float) -> Float
+    table.insert("/".into(), function(vec![float(), float()], float()));
+
+    // % operator (Int, Int) -> Int
+    table.insert("%".into(), function(vec![int(), int()], int()));
+
+    // Comparison operators (Float, Float) -> Bool, which also compare Ints
+    for operator in ["<", "<=", ">", ">="] {
+        table.insert(operator.into(), function(vec![float(), float()], bool()));
+    }
+
+    // ! operator (Bool) -> Bool
+    table.insert("!".into(), function(vec![bool()], bool()));
+
+    // && & || operators (Bool, Bool) -> Bool
+    for operator in ["&&", "||"] {
+        table.insert(operator.into(), function(vec![bool(), bool()], bool()));
+    }
+
+    // ++ operator, string concatenation (String, String) -> String
+    table.insert("++".into(), function(vec![string(), string()], string()));
+
+    // == & != operators [T](T, T) -> Bool
+    for operator in ["==", "!="] {
+        let equality_op = Type::Polymorphic(
+            vec!["T".into()],
+            Box::new(Type::Function(
+                vec![Type::Value("T".into()), Type::Value("T".into())],
+                Box::new(Type::Value(BOOL.into())),
+            )),
+        );
+        table.insert(operator.into(), equality_op);
+    }
+    // ?? operator [T](?T, T) -> T
+    let null_coalescing_op = Type::Polymorphic(
+        vec!["T".into()],
+        Box::new(Type::Function(
+            vec![
+                Type::nullable(Type::Value("T".into())),
+                Type::Value("T".into()),
+            ],
+            Box::new(Type::Value("T".into())),
+        )),
+    );
+    table.insert("??".into(), null_coalescing_op);
 }
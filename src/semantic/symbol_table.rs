@@ -1,10 +1,66 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
+use crate::lexic::token::Token;
+
 use super::types::Type;
 
-/// Public interface for the symbol table
+/// Start and end position of a piece of source code
+pub type Span = (usize, usize);
+
+/// Public interface for the symbol table.
+///
+/// Cloning a table gives another handle to the same scope
+#[derive(Clone)]
 pub struct SymbolTable {
     node: Rc<RefCell<SymbolTableNode>>,
+    /// Every symbol declared in this table and its children.
+    /// Symbols are kept here after their scope ends, so they
+    /// can be queried after the semantic analysis
+    symbols: Rc<RefCell<Vec<Rc<RefCell<Symbol>>>>>,
+}
+
+/// A symbol declared in the source code or in the stdlib
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub datatype: Type,
+    pub is_mutable: bool,
+    /// Functions declared with `fun`, classes and the functions of the stdlib.
+    /// PHP references them by their name instead of through a variable
+    pub is_function: bool,
+    /// Where the symbol was declared. Symbols of the stdlib don't have one
+    pub declaration: Option<Span>,
+    /// Every place the symbol is used, in the order they were found
+    pub references: Vec<Span>,
+}
+
+impl Symbol {
+    /// Returns the declaration or reference of this symbol
+    /// that contains `offset`, if any
+    pub fn span_at(&self, offset: usize) -> Option<Span> {
+        self.declaration
+            .iter()
+            .chain(self.references.iter())
+            .find(|(start, end)| *start <= offset && offset < *end)
+            .copied()
+    }
+}
+
+/// A property or a method of a class
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub name: String,
+    pub datatype: Type,
+    /// Only properties declared with `var` are mutable
+    pub is_mutable: bool,
+}
+
+/// A variant of an enum, and the fields of its payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub name: String,
+    /// Fields are immutable
+    pub fields: Vec<Member>,
 }
 
 // struct for a symbol table
@@ -12,7 +68,20 @@ struct SymbolTableNode {
     // the parent scope
     parent: Option<Rc<RefCell<SymbolTableNode>>>,
     // the current scope
-    scope: HashMap<String, (Type, bool)>,
+    scope: HashMap<String, Rc<RefCell<Symbol>>>,
+    /// The declared return type, if this is the scope of a function
+    return_type: Option<Type>,
+    /// Symbols of parent scopes that have a more specific type in this
+    /// scope, e.g. a nullable value inside an `if value != null`
+    narrowed: HashMap<String, Type>,
+    /// The members of the classes declared in this scope
+    classes: HashMap<String, Vec<Member>>,
+    /// The variants of the enums declared in this scope
+    enums: HashMap<String, Vec<Variant>>,
+    /// The methods of the interfaces declared in this scope
+    interfaces: HashMap<String, Vec<Member>>,
+    /// The classes declared in this scope that implement each interface
+    implementations: HashMap<String, Vec<String>>,
 }
 
 impl SymbolTable {
@@ -20,6 +89,7 @@ impl SymbolTable {
     pub fn new() -> SymbolTable {
         SymbolTable {
             node: Rc::new(RefCell::new(SymbolTableNode::new())),
+            symbols: Rc::new(RefCell::new(Vec::new())),
         }
     }
 
@@ -28,22 +98,88 @@ impl SymbolTable {
 
         SymbolTable {
             node: Rc::new(RefCell::new(new_table)),
+            symbols: Rc::clone(&parent.symbols),
         }
     }
 
+    /// Creates a new symbol table for the body of a function
+    /// that returns `return_type`
+    pub fn new_function_scope(parent: &SymbolTable, return_type: Type) -> SymbolTable {
+        let table = SymbolTable::new_from_parent(parent);
+        table.node.borrow_mut().return_type = Some(return_type);
+        table
+    }
+
     /// Inserts a new symbol into the current table scope
     pub fn insert(&self, key: String, value: Type) {
-        self.node.borrow_mut().insert(key, value, false);
+        self.insert_symbol(key, value, false, false, None);
     }
 
-    /// Inserts a new symbol into the current table scope
-    pub fn insert_mutable(&self, key: String, value: Type) {
-        self.node.borrow_mut().insert(key, value, true);
+    /// Inserts a new function into the current table scope
+    pub fn insert_function(&self, key: String, value: Type) {
+        self.insert_symbol(key, value, false, true, None);
     }
 
-    /// Inserts a new symbol into the current table scope
-    pub fn insert_custom(&self, key: String, value: Type, is_mutable: bool) {
-        self.node.borrow_mut().insert(key, value, is_mutable);
+    /// Inserts a new symbol into the current table scope,
+    /// declared by the identifier `token`
+    pub fn declare(&self, token: &Token, value: Type, is_mutable: bool) {
+        let span = (token.position, token.get_end_position());
+        self.insert_symbol(token.value.clone(), value, is_mutable, false, Some(span));
+    }
+
+    /// Inserts a new function or class into the current
+    /// table scope, declared by the identifier `token`
+    pub fn declare_function(&self, token: &Token, value: Type) {
+        let span = (token.position, token.get_end_position());
+        self.insert_symbol(token.value.clone(), value, false, true, Some(span));
+    }
+
+    fn insert_symbol(
+        &self,
+        key: String,
+        value: Type,
+        is_mutable: bool,
+        is_function: bool,
+        declaration: Option<Span>,
+    ) {
+        let symbol = Rc::new(RefCell::new(Symbol {
+            name: key.clone(),
+            datatype: value,
+            is_mutable,
+            is_function,
+            declaration,
+            references: vec![],
+        }));
+
+        self.symbols.borrow_mut().push(Rc::clone(&symbol));
+        self.node.borrow_mut().insert(key, symbol);
+    }
+
+    /// Gives the symbol `key`, declared in a parent scope,
+    /// the type `value` in the current scope and its children
+    pub fn narrow(&self, key: String, value: Type) {
+        self.node.borrow_mut().narrowed.insert(key, value);
+    }
+
+    /// Records that the identifier `token` is a usage of the symbol
+    /// with the same name, in the current or parent scopes.
+    ///
+    /// Returns the type of the symbol, if it exists
+    pub fn reference(&self, token: &Token) -> Option<Type> {
+        let symbol = self.node.borrow().get_symbol(&token.value)?;
+        let narrowed_type = self.node.borrow().get_narrowed_type(&token.value);
+        let mut symbol = symbol.borrow_mut();
+
+        // The same expression may be checked more than once
+        let span = (token.position, token.get_end_position());
+        if !symbol.references.contains(&span) {
+            symbol.references.push(span);
+        }
+
+        match narrowed_type {
+            Some(datatype) => Some(datatype),
+            None => Some(symbol.datatype.clone()),
+        }
     }
 
     /// Tests if a symbol is declared in the current or parent scopes
@@ -51,15 +187,119 @@ impl SymbolTable {
         self.node.borrow_mut().test(key)
     }
 
+    /// Tests if the symbol `key`, in the current or parent scopes,
+    /// is a function declared with `fun`, a class or a function of the stdlib
+    pub fn is_function(&self, key: &String) -> bool {
+        match self.node.borrow().get_symbol(key) {
+            Some(symbol) => symbol.borrow().is_function,
+            None => false,
+        }
+    }
+
     /// Gets the datatype of a symbol, if it exists
     pub fn get_type<'a>(&'a self, key: &String) -> Option<Type> {
         self.node.borrow_mut().get_type(key)
     }
 
-    /// Gets the datatype of a symbol, if it exists, and if its mutable
+    /// Gets the declared datatype of a symbol, if it exists, and if its mutable
     pub fn get_type_and_mut<'a>(&'a self, key: &String) -> Option<(Type, bool)> {
         self.node.borrow_mut().get_type_and_mut(key)
     }
+
+    /// Returns the return type of the innermost function that contains
+    /// this scope, or None if this scope is not inside a function
+    pub fn get_return_type(&self) -> Option<Type> {
+        self.node.borrow().get_return_type()
+    }
+
+    /// Registers the members of the class `class_name` in the current scope
+    pub fn declare_class(&self, class_name: String, members: Vec<Member>) {
+        self.node.borrow_mut().classes.insert(class_name, members);
+    }
+
+    /// Returns the member `member` of the class `class_name`, if both exist
+    pub fn get_member(&self, class_name: &String, member: &String) -> Option<Member> {
+        self.node.borrow().get_member(class_name, member)
+    }
+
+    /// Tests if `name` is a class, an enum or an interface,
+    /// in the current or parent scopes
+    pub fn is_declared_type(&self, name: &String) -> bool {
+        let node = self.node.borrow();
+        node.is_class(name) || node.get_enum(name).is_some() || node.get_interface(name).is_some()
+    }
+
+    /// Registers the variants of the enum `enum_name` in the current scope
+    pub fn declare_enum(&self, enum_name: String, variants: Vec<Variant>) {
+        self.node.borrow_mut().enums.insert(enum_name, variants);
+    }
+
+    /// Returns the variants of the enum `enum_name`, if it exists
+    pub fn get_enum(&self, enum_name: &String) -> Option<Vec<Variant>> {
+        self.node.borrow().get_enum(enum_name)
+    }
+
+    /// Registers the methods of the interface `interface_name` in the current scope
+    pub fn declare_interface(&self, interface_name: String, methods: Vec<Member>) {
+        self.node
+            .borrow_mut()
+            .interfaces
+            .insert(interface_name, methods);
+    }
+
+    /// Returns the methods of the interface `interface_name`, if it exists
+    pub fn get_interface(&self, interface_name: &String) -> Option<Vec<Member>> {
+        self.node.borrow().get_interface(interface_name)
+    }
+
+    /// Registers that the class `class_name` implements the interface `interface_name`
+    pub fn declare_implementation(&self, interface_name: String, class_name: String) {
+        self.node
+            .borrow_mut()
+            .implementations
+            .entry(interface_name)
+            .or_default()
+            .push(class_name);
+    }
+
+    /// Returns the names of the classes that implement
+    /// the interface `interface_name`, in any visible scope
+    pub fn get_implementors(&self, interface_name: &String) -> Vec<String> {
+        self.node.borrow().get_implementors(interface_name)
+    }
+
+    /// Returns how many times each symbol has been referenced so far.
+    /// Used with `referenced_since` to find the symbols a piece of code uses
+    pub fn reference_counts(&self) -> Vec<usize> {
+        self.symbols
+            .borrow()
+            .iter()
+            .map(|symbol| symbol.borrow().references.len())
+            .collect()
+    }
+
+    /// Returns the symbols that existed when `counts` was taken with
+    /// `reference_counts`, and have been referenced since then
+    pub fn referenced_since(&self, counts: &[usize]) -> Vec<Symbol> {
+        self.symbols
+            .borrow()
+            .iter()
+            .zip(counts.iter())
+            .map(|(symbol, count)| (symbol.borrow(), count))
+            .filter(|(symbol, count)| symbol.references.len() > **count)
+            .map(|(symbol, _)| symbol.clone())
+            .collect()
+    }
+
+    /// Returns the symbol declared or used at `offset`, in any scope
+    pub fn symbol_at(&self, offset: usize) -> Option<Symbol> {
+        self.symbols
+            .borrow()
+            .iter()
+            .map(|symbol| symbol.borrow())
+            .find(|symbol| symbol.span_at(offset).is_some())
+            .map(|symbol| symbol.clone())
+    }
 }
 
 impl SymbolTableNode {
@@ -68,6 +308,12 @@ impl SymbolTableNode {
         SymbolTableNode {
             parent: None,
             scope: HashMap::new(),
+            return_type: None,
+            narrowed: HashMap::new(),
+            classes: HashMap::new(),
+            enums: HashMap::new(),
+            interfaces: HashMap::new(),
+            implementations: HashMap::new(),
         }
     }
 
@@ -76,12 +322,18 @@ impl SymbolTableNode {
         SymbolTableNode {
             parent: Some(Rc::clone(&parent)),
             scope: HashMap::new(),
+            return_type: None,
+            narrowed: HashMap::new(),
+            classes: HashMap::new(),
+            enums: HashMap::new(),
+            interfaces: HashMap::new(),
+            implementations: HashMap::new(),
         }
     }
 
     /// Inserts a new symbol into the current scope
-    pub fn insert(&mut self, key: String, value: Type, is_mutable: bool) {
-        self.scope.insert(key, (value, is_mutable));
+    pub fn insert(&mut self, key: String, symbol: Rc<RefCell<Symbol>>) {
+        self.scope.insert(key, symbol);
     }
 
     /// Tests if a symbol is declared in the current or parent scopes
@@ -99,39 +351,259 @@ impl SymbolTableNode {
         }
     }
 
-    /// Returns the symbol's datatype
-    pub fn get_type<'a>(&'a mut self, key: &String) -> Option<Type> {
-        // Try to get the type in the current scope
-        if let Some((entry, _)) = self.scope.get(key) {
-            // TODO: Change to allow other types of datatypes: functions, classes, maps
-            return Some(entry.clone());
+    /// Returns the symbol, searching the current and parent scopes
+    pub fn get_symbol(&self, key: &String) -> Option<Rc<RefCell<Symbol>>> {
+        if let Some(symbol) = self.scope.get(key) {
+            return Some(Rc::clone(symbol));
         }
 
-        // Try to get the type in the parent scope
         match &self.parent {
-            Some(parent) => {
-                parent.as_ref().borrow_mut().get_type(key)
-                // parent.get_type(key)
-            }
+            Some(parent) => parent.as_ref().borrow().get_symbol(key),
             None => None,
         }
     }
 
-    /// Returns the symbol's datatype and mutability
-    pub fn get_type_and_mut<'a>(&'a mut self, key: &String) -> Option<(Type, bool)> {
-        // Try to get the type in the current scope
-        if let Some((entry, mutable)) = self.scope.get(key) {
-            // TODO: Change to allow other types of datatypes: functions, classes, maps
-            return Some((entry.clone(), *mutable));
+    /// Returns a member of a class, searching the current and parent scopes
+    pub fn get_member(&self, class_name: &String, member: &String) -> Option<Member> {
+        if let Some(members) = self.classes.get(class_name) {
+            return members.iter().find(|m| m.name == *member).cloned();
+        }
+        if let Some(methods) = self.interfaces.get(class_name) {
+            return methods.iter().find(|m| m.name == *member).cloned();
         }
 
-        // Try to get the type in the parent scope
         match &self.parent {
-            Some(parent) => {
-                parent.as_ref().borrow_mut().get_type_and_mut(key)
-                // parent.get_type(key)
-            }
+            Some(parent) => parent.as_ref().borrow().get_member(class_name, member),
             None => None,
         }
     }
+
+    /// Tests if a class exists, searching the current and parent scopes
+    pub fn is_class(&self, class_name: &String) -> bool {
+        if self.classes.contains_key(class_name) {
+            return true;
+        }
+
+        match &self.parent {
+            Some(parent) => parent.as_ref().borrow().is_class(class_name),
+            None => false,
+        }
+    }
+
+    /// Returns the variants of an enum, searching the current and parent scopes
+    pub fn get_enum(&self, enum_name: &String) -> Option<Vec<Variant>> {
+        if let Some(variants) = self.enums.get(enum_name) {
+            return Some(variants.clone());
+        }
+
+        match &self.parent {
+            Some(parent) => parent.as_ref().borrow().get_enum(enum_name),
+            None => None,
+        }
+    }
+
+    /// Returns the methods of an interface, searching the current and parent scopes
+    pub fn get_interface(&self, interface_name: &String) -> Option<Vec<Member>> {
+        if let Some(methods) = self.interfaces.get(interface_name) {
+            return Some(methods.clone());
+        }
+
+        match &self.parent {
+            Some(parent) => parent.as_ref().borrow().get_interface(interface_name),
+            None => None,
+        }
+    }
+
+    /// Returns the classes that implement an interface,
+    /// collected from the current and parent scopes
+    pub fn get_implementors(&self, interface_name: &String) -> Vec<String> {
+        let mut implementors = match &self.parent {
+            Some(parent) => parent.as_ref().borrow().get_implementors(interface_name),
+            None => Vec::new(),
+        };
+        if let Some(classes) = self.implementations.get(interface_name) {
+            implementors.extend(classes.iter().cloned());
+        }
+        implementors
+    }
+
+    /// Returns the return type of the innermost function scope
+    pub fn get_return_type(&self) -> Option<Type> {
+        if let Some(return_type) = &self.return_type {
+            return Some(return_type.clone());
+        }
+
+        match &self.parent {
+            Some(parent) => parent.as_ref().borrow().get_return_type(),
+            None => None,
+        }
+    }
+
+    /// Returns the narrowed type of a symbol, if a scope between
+    /// this one and the one where the symbol is declared narrowed it
+    pub fn get_narrowed_type(&self, key: &String) -> Option<Type> {
+        if self.scope.contains_key(key) {
+            return None;
+        }
+        if let Some(datatype) = self.narrowed.get(key) {
+            return Some(datatype.clone());
+        }
+
+        match &self.parent {
+            Some(parent) => parent.as_ref().borrow().get_narrowed_type(key),
+            None => None,
+        }
+    }
+
+    /// Returns the symbol's datatype, narrowed in this scope if it was
+    pub fn get_type<'a>(&'a mut self, key: &String) -> Option<Type> {
+        // TODO: Change to allow other types of datatypes: functions, classes, maps
+        let symbol = self.get_symbol(key)?;
+        if let Some(datatype) = self.get_narrowed_type(key) {
+            return Some(datatype);
+        }
+        let datatype = symbol.borrow().datatype.clone();
+        Some(datatype)
+    }
+
+    /// Returns the symbol's datatype and mutability
+    pub fn get_type_and_mut<'a>(&'a mut self, key: &String) -> Option<(Type, bool)> {
+        let symbol = self.get_symbol(key)?;
+        let symbol = symbol.borrow();
+        Some((symbol.datatype.clone(), symbol.is_mutable))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexic::get_tokens,
+        semantic::{check_semantics_with, types::Typed},
+        syntax::{ast::Expression, build_ast, parseable::Parseable},
+    };
+
+    use super::*;
+
+    fn analyze(input: &str) -> SymbolTable {
+        let tokens = get_tokens(&String::from(input)).unwrap();
+        let ast = build_ast(&tokens).unwrap();
+        let mut table = SymbolTable::new();
+        crate::semantic::std::populate(&mut table);
+        check_semantics_with(&ast, &mut table).unwrap();
+        table
+    }
+
+    #[test]
+    fn should_record_declaration_and_references() {
+        let table = analyze("val name = \"a\"\nval other = name\nprint(name)");
+
+        let symbol = table.symbol_at(4).unwrap();
+        assert_eq!("name", symbol.name);
+        assert_eq!(Some((4, 8)), symbol.declaration);
+        assert_eq!(vec![(27, 31), (38, 42)], symbol.references);
+
+        // Querying from a reference returns the same symbol
+        assert_eq!(Some(symbol), table.symbol_at(39));
+    }
+
+    #[test]
+    fn should_record_references_to_stdlib_symbols() {
+        let table = analyze("print(\"a\")\nprint(\"b\")");
+
+        let symbol = table.symbol_at(0).unwrap();
+        assert_eq!("print", symbol.name);
+        assert_eq!(None, symbol.declaration);
+        assert_eq!(vec![(0, 5), (11, 16)], symbol.references);
+    }
+
+    #[test]
+    fn should_keep_symbols_of_inner_scopes() {
+        let table = analyze("fun f(Int count) {\n    count\n}\nval g = f");
+
+        let parameter = table.symbol_at(23).unwrap();
+        assert_eq!("count", parameter.name);
+        assert_eq!(Some((10, 15)), parameter.declaration);
+        assert_eq!(vec![(23, 28)], parameter.references);
+
+        let function = table.symbol_at(39).unwrap();
+        assert_eq!("f", function.name);
+        assert_eq!(Some((4, 5)), function.declaration);
+        assert_eq!(vec![(39, 40)], function.references);
+    }
+
+    #[test]
+    fn should_distinguish_symbols_with_the_same_name() {
+        let table = analyze("fun a(Int x) {\n    x\n}\nfun b(Int x) {\n    x\n}");
+
+        let first = table.symbol_at(19).unwrap();
+        let second = table.symbol_at(42).unwrap();
+        assert_eq!(Some((10, 11)), first.declaration);
+        assert_eq!(Some((33, 34)), second.declaration);
+    }
+
+    #[test]
+    fn should_find_return_type_of_innermost_function() {
+        let global = SymbolTable::new();
+        assert_eq!(None, global.get_return_type());
+
+        let function = SymbolTable::new_function_scope(&global, Type::Value("Int".into()));
+        let block = SymbolTable::new_from_parent(&function);
+        assert_eq!(Some(Type::Value("Int".into())), block.get_return_type());
+
+        let inner = SymbolTable::new_function_scope(&block, Type::Value("String".into()));
+        assert_eq!(Some(Type::Value("String".into())), inner.get_return_type());
+    }
+
+    #[test]
+    fn should_narrow_symbols_in_child_scopes() {
+        let global = SymbolTable::new();
+        let nullable = Type::nullable(Type::Value("String".into()));
+        global.insert("name".into(), nullable.clone());
+
+        let narrowed = SymbolTable::new_from_parent(&global);
+        narrowed.narrow("name".into(), Type::Value("String".into()));
+        let inner = SymbolTable::new_from_parent(&narrowed);
+        assert_eq!(
+            Some(Type::Value("String".into())),
+            inner.get_type(&"name".into())
+        );
+        assert_eq!(Some(nullable.clone()), global.get_type(&"name".into()));
+
+        // A declaration in a child scope shadows the narrowed symbol
+        inner.insert("name".into(), Type::Value("Int".into()));
+        assert_eq!(
+            Some(Type::Value("Int".into())),
+            inner.get_type(&"name".into())
+        );
+    }
+
+    #[test]
+    fn should_distinguish_functions_from_variables() {
+        let table = analyze("fun add(Int a) -> Int {\n    a\n}\nval f = add");
+
+        assert!(table.is_function(&"add".into()));
+        assert!(table.is_function(&"print".into()));
+        assert!(!table.is_function(&"f".into()));
+    }
+
+    #[test]
+    fn should_find_symbols_referenced_since_a_point() {
+        let table = analyze("val a = 1\nval b = 2\nval c = a");
+        let counts = table.reference_counts();
+
+        let tokens = get_tokens(&String::from("b + b")).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+        expression.get_type(&table).unwrap();
+
+        let symbols = table.referenced_since(&counts);
+        assert_eq!(1, symbols.len());
+        assert_eq!("b", symbols[0].name);
+    }
+
+    #[test]
+    fn should_not_find_symbols_outside_identifiers() {
+        let table = analyze("val x = 10");
+        assert_eq!(None, table.symbol_at(0));
+        assert_eq!(None, table.symbol_at(8));
+    }
 }
@@ -0,0 +1,13 @@
+//! Helpers shared by the tests of the semantic analysis
+
+use crate::{error_handling::MistiError, lexic::get_tokens, syntax::build_ast};
+
+use super::check_semantics;
+
+/// Checks the semantics of the THP program `input`,
+/// which must be free of lexic & syntax errors
+pub fn check(input: &str) -> Result<(), Vec<MistiError>> {
+    let tokens = get_tokens(&String::from(input)).unwrap();
+    let ast = build_ast(&tokens).unwrap();
+    check_semantics(&ast)
+}
@@ -6,11 +6,19 @@ use crate::{
         },
         ErrorContainer, ErrorLabel, MistiError,
     },
-    semantic::symbol_table::SymbolTable,
-    syntax::ast::Expression,
+    lexic::token::Token,
+    semantic::{
+        checks::function_declaration::signature_type,
+        symbol_table::{Member, SymbolTable, Variant},
+    },
+    syntax::ast::{functions::FunctionCall, Expression, Positionable},
 };
 
-use super::{Type, Typed};
+use super::{
+    global::{BOOL, FLOAT, NULL},
+    operator::operator_return_type,
+    Type, Typed,
+};
 
 impl Typed for Expression<'_> {
     fn get_type(&self, scope: &SymbolTable) -> Result<Type, MistiError> {
@@ -19,9 +27,10 @@ impl Typed for Expression<'_> {
             Expression::Float(_) => Ok(Type::Value("Float".into())),
             Expression::String(_) => Ok(Type::Value("String".into())),
             Expression::Boolean(_) => Ok(Type::Value("Bool".into())),
+            Expression::Null(_) => Ok(Type::Value(NULL.into())),
             Expression::Identifier(identifier) => {
                 // Attempt to get the datatype of the identifier in the current scope
-                let datatype = match scope.get_type(&identifier.value) {
+                let datatype = match scope.reference(identifier) {
                     Some(x) => x,
                     None => {
                         let label = ErrorLabel {
@@ -40,153 +49,57 @@ impl Typed for Expression<'_> {
                     }
                 };
 
+                // Functions are referenced differently than variables in PHP
+                identifier
+                    .is_function
+                    .set(scope.is_function(&identifier.value));
+
                 // TODO: use lifetimes
                 Ok(datatype)
             }
-            Expression::FunctionCall(f) => {
-                // TODO: allow arbitrary expressions and
-                // check that they resolve into a function
-                // (e.g. object.member)
-
-                match &*f.function {
-                    Expression::Identifier(id) => {
-                        match scope.get_type(&id.value) {
-                            Some(Type::Function(_, return_type)) => {
-                                // Return the return type of the function,
-                                // not the function itself
-                                Ok(Type::Value(return_type))
-                            }
-                            Some(_) => {
-                                let label = ErrorLabel {
-                                    message: String::from(
-                                        "Expected this identifier to be a function",
-                                    ),
-                                    start: id.position,
-                                    end: id.get_end_position(),
-                                };
-                                let econtainer = ErrorContainer {
-                                    error_code: SEMANTIC_INVALID_REFERENCE,
-                                    error_offset: id.position,
-                                    labels: vec![label],
-                                    note: None,
-                                    help: None,
-                                };
-                                return Err(econtainer);
-                            }
-                            None => {
-                                let label = ErrorLabel {
-                                    message: String::from(
-                                        "Cannot find this identifier in this scope",
-                                    ),
-                                    start: id.position,
-                                    end: id.get_end_position(),
-                                };
-                                let econtainer = ErrorContainer {
-                                    error_code: SEMANTIC_INVALID_REFERENCE,
-                                    error_offset: id.position,
-                                    labels: vec![label],
-                                    note: None,
-                                    help: None,
-                                };
-                                return Err(econtainer);
-                            }
-                        }
-                    }
-                    _ => unimplemented!(
-                        "Get datatype of an expression that may resolve into a function call"
-                    ),
-                }
+            Expression::EnumVariant(enum_name, variant, _) => {
+                variant_type(enum_name, variant, scope)
             }
-            Expression::UnaryOperator(op, exp) => {
-                let expr_type = match exp.get_type(scope) {
-                    Ok(t) => t,
-                    Err(_reason) => {
-                        let label = ErrorLabel {
-                            message: String::from("Error getting type of this expression"),
-                            // TODO: Fix these positions
-                            start: 0,
-                            end: 1,
-                        };
-                        let econtainer = ErrorContainer {
-                            error_code: COMPILER_TODO,
-                            error_offset: 0,
-                            labels: vec![label],
-                            note: None,
-                            help: None,
-                        };
-                        return Err(econtainer);
-                    }
-                };
-
-                // Only supported unary operator: - & !
-                if op.value == "-" {
-                    if !expr_type.is_value("Int") && !expr_type.is_value("Float") {
-                        let label = ErrorLabel {
-                            message: format!("Expected an `Int` or `Float`, got {:?}", expr_type),
-                            // TODO: Fix positioning
-                            start: 0,
-                            end: 1,
-                        };
-                        let econtainer = ErrorContainer {
-                            error_code: SEMANTIC_MISMATCHED_TYPES,
-                            error_offset: 0,
-                            labels: vec![label],
-                            note: None,
-                            help: None,
-                        };
-                        return Err(econtainer);
-                    } else {
-                        return Ok(Type::Value("Int".into()));
-                    }
-                } else if op.value == "!" {
-                    if !expr_type.is_value("Bool") {
-                        let label = ErrorLabel {
-                            message: format!("Expected a `Bool`, got {:?}", expr_type),
-                            // TODO: Fix positioning
-                            start: 0,
-                            end: 1,
-                        };
-                        let econtainer = ErrorContainer {
-                            error_code: SEMANTIC_MISMATCHED_TYPES,
-                            error_offset: 0,
-                            labels: vec![label],
-                            note: None,
-                            help: None,
-                        };
-                        return Err(econtainer);
-                    } else {
-                        return Ok(Type::Value("Bool".into()));
-                    }
-                }
-
-                unreachable!("Illegal state: Found an unexpected unary operator during semantic analysis: {}", op.value);
-            }
-            Expression::BinaryOperator(_, _, operator) => {
-                match scope.get_type(&operator.value) {
-                    Some(Type::Function(_, return_type)) => Ok(Type::Value(return_type)),
-                    Some(_) => {
-                        unreachable!(
-                            "Compiler error: The operator {} was defined but it wasn't a function",
-                            operator.value
-                        )
+            Expression::FunctionCall(f) => match &*f.function {
+                Expression::Identifier(id) => match scope.reference(id) {
+                    Some(function_type) => {
+                        id.is_function.set(scope.is_function(&id.value));
+                        call_return_type(function_type, f, scope)
                     }
                     None => {
                         let label = ErrorLabel {
-                            message: format!("Unsupported binary operator"),
-                            // TODO: Fix positioning
-                            start: 0,
-                            end: 1,
+                            message: String::from("Cannot find this identifier in this scope"),
+                            start: id.position,
+                            end: id.get_end_position(),
                         };
                         let econtainer = ErrorContainer {
-                            error_code: SEMANTIC_MISMATCHED_TYPES,
-                            error_offset: 0,
+                            error_code: SEMANTIC_INVALID_REFERENCE,
+                            error_offset: id.position,
                             labels: vec![label],
                             note: None,
                             help: None,
                         };
-                        return Err(econtainer);
+                        Err(econtainer)
                     }
+                },
+                Expression::EnumVariant(enum_name, variant, _) => {
+                    call_return_type(variant_type(enum_name, variant, scope)?, f, scope)
                 }
+                // Any expression whose type is a function can be called,
+                // e.g. a lambda or the function returned by another call
+                function => call_return_type(function.get_type(scope)?, f, scope),
+            },
+            Expression::UnaryOperator(operator, expression) => {
+                operator_return_type(operator, &[expression], scope)
+            }
+            Expression::BinaryOperator(left_expr, right_expr, operator)
+                if is_member_access(&operator.value) =>
+            {
+                get_member_access_type(left_expr, right_expr, operator, scope)
+            }
+            Expression::BinaryOperator(left_expr, right_expr, operator) => {
+                // The return type may depend on the operands, e.g. `??` or `1 + 2.5`
+                operator_return_type(operator, &[left_expr, right_expr], scope)
             }
             Expression::Array(arr) => {
                 // The first expression found determines the
@@ -213,14 +126,284 @@ impl Typed for Expression<'_> {
                     return Err(econtainer);
                 }
 
-                // Just get the first type and use it
-                // Checking of the types of every element in the array
+                // The type of the elements is their common type, e.g. Float
+                // for Int & Float. Checking that there is a common type
                 // is done by SemanticCheck
-                let first_type = arr.exps[0].get_type(scope)?;
-                Ok(Type::Generic("Array".into(), vec![first_type]))
+                let mut array_type = arr.exps[0].get_type(scope)?;
+                for exp in arr.exps.iter().skip(1) {
+                    if let Some(common_type) = array_type.common_type(&exp.get_type(scope)?) {
+                        array_type = common_type;
+                    }
+                }
+                // PHP doesn't convert the Ints among Floats on its own
+                if array_type.is_value(FLOAT) {
+                    arr.to_float.set(true);
+                }
+                Ok(Type::Generic("Array".into(), vec![array_type]))
+            }
+            Expression::ArrayAcccess(array_access) => {
+                // The type of an element is the type parameter of the array
+                match array_access.left_expr.get_type(scope)? {
+                    Type::Generic(name, params) if name == "Array" && params.len() == 1 => {
+                        Ok(params[0].clone())
+                    }
+                    Type::Unknown => Ok(Type::Unknown),
+                    t => {
+                        let (error_start, error_end) = array_access.left_expr.get_position();
+                        let label = ErrorLabel {
+                            message: format!("Expected an Array, got {}", t),
+                            start: error_start,
+                            end: error_end,
+                        };
+                        let econtainer = ErrorContainer {
+                            error_code: SEMANTIC_MISMATCHED_TYPES,
+                            error_offset: error_start,
+                            labels: vec![label],
+                            note: None,
+                            help: None,
+                        };
+                        Err(econtainer)
+                    }
+                }
+            }
+            Expression::TypeTest(_) => Ok(Type::Value(BOOL.into())),
+            Expression::Match(match_expression) => match_expression.get_type(scope),
+            // Lambdas can't have type parameters
+            Expression::Lambda(lambda) => Ok(signature_type(
+                &[],
+                &lambda.params_list,
+                &lambda.return_type,
+            )),
+        }
+    }
+}
+
+/// Returns the type of the value returned by a call to a function of
+/// type `function_type`. The return type of a generic function may have
+/// type parameters, which are inferred from the arguments
+fn call_return_type(
+    function_type: Type,
+    call: &FunctionCall,
+    scope: &SymbolTable,
+) -> Result<Type, MistiError> {
+    match function_type {
+        // Return the return type of the function,
+        // not the function itself
+        Type::Function(_, return_type) => Ok(*return_type),
+        function @ Type::Polymorphic(_, _) => {
+            let mut argument_types = Vec::new();
+            for argument in call.arguments.arguments.iter() {
+                argument_types.push(argument.get_type(scope)?);
             }
-            Expression::ArrayAcccess(_array_access) => unimplemented!("typecheck array access"),
+
+            match function.instantiate(&argument_types) {
+                Type::Function(_, return_type) => Ok(*return_type),
+                _ => unreachable!("Only functions have type parameters"),
+            }
+        }
+        // The error was already reported
+        Type::Unknown => Ok(Type::Unknown),
+        _ => {
+            let (error_start, error_end) = call.function.get_position();
+            let label = ErrorLabel {
+                message: String::from("Expected this expression to be a function"),
+                start: error_start,
+                end: error_end,
+            };
+            let econtainer = ErrorContainer {
+                error_code: SEMANTIC_INVALID_REFERENCE,
+                error_offset: error_start,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            Err(econtainer)
+        }
+    }
+}
+
+/// Returns the type of a variant of an enum. The variants with a payload
+/// are functions that create a value, e.g. `Shape::Circle(1.0)`, and the
+/// others are values, e.g. `Shape::Empty`
+pub fn variant_type(
+    enum_name: &Token,
+    variant: &Token,
+    scope: &SymbolTable,
+) -> Result<Type, MistiError> {
+    let (variants, variant) = get_variant(enum_name, variant, scope)?;
+    let enum_type = Type::enumeration(&enum_name.value, &variants);
+
+    if variant.fields.is_empty() {
+        return Ok(enum_type);
+    }
+
+    let field_types = variant.fields.into_iter().map(|f| f.datatype).collect();
+    Ok(Type::Function(field_types, Box::new(enum_type)))
+}
+
+/// Returns all the variants of the enum `enum_name`, and its variant `variant`
+pub fn get_variant(
+    enum_name: &Token,
+    variant: &Token,
+    scope: &SymbolTable,
+) -> Result<(Vec<Variant>, Variant), MistiError> {
+    let Some(variants) = scope.get_enum(&enum_name.value) else {
+        let label = ErrorLabel {
+            message: String::from("Cannot find this enum in this scope"),
+            start: enum_name.position,
+            end: enum_name.get_end_position(),
+        };
+        let econtainer = ErrorContainer {
+            error_code: SEMANTIC_MISSING_REFERENCE,
+            error_offset: enum_name.position,
+            labels: vec![label],
+            note: None,
+            help: None,
+        };
+        return Err(econtainer);
+    };
+
+    match variants.iter().find(|v| v.name == variant.value) {
+        Some(found) => {
+            let found = found.clone();
+            Ok((variants, found))
         }
+        None => {
+            let label = ErrorLabel {
+                message: format!(
+                    "The enum {} has no variant `{}`",
+                    enum_name.value, variant.value
+                ),
+                start: variant.position,
+                end: variant.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SEMANTIC_INVALID_REFERENCE,
+                error_offset: variant.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            Err(econtainer)
+        }
+    }
+}
+
+/// Checks if an operator accesses a member of a value: `.` or `?.`
+pub fn is_member_access(operator: &str) -> bool {
+    operator == "." || operator == "?."
+}
+
+/// Returns the type of a member access, e.g. `person.name`, `person?.name`
+/// or the method call `person.greet()`.
+///
+/// `?.` can be used on nullable values, and its result is nullable
+fn get_member_access_type(
+    object: &Expression,
+    member: &Expression,
+    operator: &Token,
+    scope: &SymbolTable,
+) -> Result<Type, MistiError> {
+    let member_type = match member {
+        Expression::Identifier(property) => {
+            get_member(object, property, &operator.value, scope)?.datatype
+        }
+        Expression::FunctionCall(call) => match &*call.function {
+            Expression::Identifier(method) => {
+                let method = get_member(object, method, &operator.value, scope)?;
+                call_return_type(method.datatype, call, scope)?
+            }
+            _ => return Err(member_name_error(&call.function)),
+        },
+        _ => return Err(member_name_error(member)),
+    };
+
+    if operator.value == "?." {
+        Ok(Type::nullable(member_type))
+    } else {
+        Ok(member_type)
+    }
+}
+
+/// Returns the member named `member` of the value `object`, accessed
+/// with the operator `operator`.
+///
+/// `.` cannot be used on nullable values
+pub fn get_member(
+    object: &Expression,
+    member: &Token,
+    operator: &str,
+    scope: &SymbolTable,
+) -> Result<Member, MistiError> {
+    let object_type = match (operator, object.get_type(scope)?) {
+        ("?.", Type::Nullable(t)) => *t,
+        ("?.", t) => t,
+        (_, Type::Nullable(t)) => {
+            let (error_start, error_end) = object.get_position();
+            let label = ErrorLabel {
+                message: format!("This value may be null, its type is ?{}", t),
+                start: error_start,
+                end: error_end,
+            };
+            let econtainer = ErrorContainer {
+                error_code: SEMANTIC_MISMATCHED_TYPES,
+                error_offset: error_start,
+                labels: vec![label],
+                note: None,
+                help: Some(String::from(
+                    "Use `?.` to access the member only if the value is not null",
+                )),
+            };
+            return Err(econtainer);
+        }
+        (_, t) => t,
+    };
+
+    // Only classes and interfaces have members
+    let class_member = match &object_type {
+        Type::Value(class_name) | Type::Interface(class_name, _) => {
+            scope.get_member(class_name, &member.value)
+        }
+        // The error was already reported
+        Type::Unknown => Some(Member {
+            name: member.value.clone(),
+            datatype: Type::Unknown,
+            is_mutable: true,
+        }),
+        _ => None,
+    };
+    if let Some(class_member) = class_member {
+        return Ok(class_member);
+    }
+
+    let label = ErrorLabel {
+        message: format!("The type {} has no member `{}`", object_type, member.value),
+        start: member.position,
+        end: member.get_end_position(),
+    };
+    let econtainer = ErrorContainer {
+        error_code: SEMANTIC_INVALID_REFERENCE,
+        error_offset: member.position,
+        labels: vec![label],
+        note: None,
+        help: None,
+    };
+    Err(econtainer)
+}
+
+fn member_name_error(member: &Expression) -> MistiError {
+    let (error_start, error_end) = member.get_position();
+    let label = ErrorLabel {
+        message: String::from("Expected the name of a member here"),
+        start: error_start,
+        end: error_end,
+    };
+    ErrorContainer {
+        error_code: COMPILER_TODO,
+        error_offset: error_start,
+        labels: vec![label],
+        note: None,
+        help: None,
     }
 }
 
@@ -235,7 +418,7 @@ mod tests {
         },
         syntax::ast::{
             functions::{ArgumentsList, FunctionCall},
-            Expression,
+            Expression, Identifier,
         },
     };
 
@@ -245,13 +428,13 @@ mod tests {
         populate(&mut scope);
 
         let identifier_token = Token::new_identifier("print".into(), 0);
-        let expr = Expression::Identifier(&identifier_token);
+        let expr = Expression::Identifier(Identifier::new(&identifier_token));
 
         match expr.get_type(&scope) {
             Ok(Type::Function(params, return_type)) => {
                 assert_eq!(params.len(), 1);
-                assert_eq!(params[0], "String");
-                assert_eq!(return_type, "Void");
+                assert_eq!(params[0], Type::Value("String".into()));
+                assert_eq!(*return_type, Type::Value("Void".into()));
             }
             Ok(t) => panic!("Expected a Function, got {:?}", t),
             Err(e) => panic!("Expected Ok, got Err: {:?}", e),
@@ -261,7 +444,7 @@ mod tests {
     #[test]
     fn should_error_on_invalid_identifier() {
         let identifier_token = Token::new_identifier("print".into(), 0);
-        let expr = Expression::Identifier(&identifier_token);
+        let expr = Expression::Identifier(Identifier::new(&identifier_token));
         let scope = SymbolTable::new();
 
         let expr_type = expr.get_type(&scope);
@@ -282,7 +465,7 @@ mod tests {
         populate(&mut scope);
 
         let id_token = Token::new_identifier("print".into(), 0);
-        let fn_expr = Expression::Identifier(&id_token);
+        let fn_expr = Expression::Identifier(Identifier::new(&id_token));
 
         let args = ArgumentsList {
             arguments: vec![],
@@ -309,7 +492,7 @@ mod tests {
         scope.insert("print".into(), Type::Value("Int".into()));
 
         let id_token = Token::new_identifier("print".into(), 0);
-        let fn_expr = Expression::Identifier(&id_token);
+        let fn_expr = Expression::Identifier(Identifier::new(&id_token));
 
         let args = ArgumentsList {
             arguments: vec![],
@@ -338,7 +521,7 @@ mod tests {
         let scope = SymbolTable::new();
 
         let id_token = Token::new_identifier("print".into(), 0);
-        let fn_expr = Expression::Identifier(&id_token);
+        let fn_expr = Expression::Identifier(Identifier::new(&id_token));
 
         let args = ArgumentsList {
             arguments: vec![],
@@ -1,3 +1,8 @@
 pub const STRING: &str = "String";
 pub const INT: &str = "Int";
+pub const FLOAT: &str = "Float";
+pub const BOOL: &str = "Bool";
 pub const VOID: &str = "Void";
+pub const UNIT: &str = "Unit";
+/// The type of the `null` literal
+pub const NULL: &str = "Null";
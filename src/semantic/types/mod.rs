@@ -1,26 +1,63 @@
 //! This crate provides an interface and implementations
 //! for determining the datatypes of the language constructs.
 
-use crate::error_handling::MistiError;
+use std::{collections::HashMap, fmt::Display};
 
-use super::symbol_table::SymbolTable;
+use crate::{
+    error_handling::MistiError,
+    syntax::ast::{datatype::Datatype, Expression},
+};
 
-mod expression;
+use super::symbol_table::{SymbolTable, Variant};
+
+pub mod expression;
 pub mod global;
+pub mod operator;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     Value(String),
-    // TODO: Use Type instead of String to allow
-    // arbitrary types
-    Function(Vec<String>, String),
+    /// The types of the parameters, the return type.
+    ///
+    /// E.g.: (Int, String) -> Bool -> (vec![Int, String], Bool)
+    Function(Vec<Type>, Box<Type>),
     /// The concrete type, the type parameters.
     ///
     /// E.g.: Array[Int] -> ("Array", vec!["Int"])
     ///
     /// E.g.: Map[String, Float] -> ("Map", vec!["String", "Float"])
     Generic(String, Vec<Type>),
-    // TODO: tuple, union types
+    /// A function with type parameters: the names of
+    /// the type parameters, the function type.
+    ///
+    /// E.g.: fun first[T](Array[T] items) -> T -> (vec!["T"], (Array[T]) -> T)
+    Polymorphic(Vec<String>, Box<Type>),
+    /// A type that also allows `null`.
+    ///
+    /// E.g.: ?String -> Nullable(String)
+    Nullable(Box<Type>),
+    /// A value of any of the types. It's built with `Type::union`,
+    /// so it never contains other unions, nullable types or `Null`.
+    ///
+    /// E.g.: Int | String -> Union(vec![Int, String])
+    Union(Vec<Type>),
+    /// Several functions with the same name, e.g. the operator `+` for Ints
+    /// and for Floats. The first one that accepts the arguments is used.
+    ///
+    /// E.g.: (Int, Int) -> Int & (Float, Float) -> Float
+    Overloaded(Vec<Type>),
+    /// An enum: its name, and the names of its variants.
+    ///
+    /// E.g.: enum Suit { Hearts, Spades } -> ("Suit", vec!["Hearts", "Spades"])
+    Enum(String, Vec<String>),
+    /// An interface: its name, and the names of the classes that implement it.
+    ///
+    /// E.g.: class Square : Shape -> ("Shape", vec!["Square"])
+    Interface(String, Vec<String>),
+    /// The type of an expression that has errors. It can be used
+    /// anywhere, so its usages don't report more errors
+    Unknown,
+    // TODO: tuple types
 }
 
 impl Type {
@@ -32,17 +69,487 @@ impl Type {
         }
     }
 
-    /// Compares this type to another
+    /// Checks if a value of this type has to be converted to be a `target`:
+    /// an Int used where a Float is expected. PHP only converts it
+    /// where there is a type hint.
+    ///
+    /// An Int is converted for a union with Float, unless the union has Int
+    pub fn converts_to_float(&self, target: &Type) -> bool {
+        match target {
+            Type::Nullable(target) => self.converts_to_float(target),
+            Type::Union(members) => {
+                !self.is_subtype_of(target) && members.iter().any(|m| self.converts_to_float(m))
+            }
+            _ => self.is_value(global::INT) && target.is_value(global::FLOAT),
+        }
+    }
+
+    /// Creates the type of the enum `name`, with the variants `variants`
+    pub fn enumeration(name: &str, variants: &[Variant]) -> Type {
+        let variants = variants.iter().map(|v| v.name.clone()).collect();
+        Type::Enum(String::from(name), variants)
+    }
+
+    /// Replaces the names of enums and interfaces in this type, as they are
+    /// written in datatype annotations, with their enum or interface type,
+    /// e.g. `?Suit` with `?Enum(Suit, [Hearts, Spades])`. The parameters
+    /// & return types of functions and the parameters of generics are resolved too
+    pub fn resolve(&self, scope: &SymbolTable) -> Type {
+        match self {
+            Type::Value(name) => {
+                if let Some(variants) = scope.get_enum(name) {
+                    return Type::enumeration(name, &variants);
+                }
+                match scope.get_interface(name) {
+                    Some(_) => Type::Interface(name.clone(), scope.get_implementors(name)),
+                    None => self.clone(),
+                }
+            }
+            Type::Nullable(t) => Type::nullable(t.resolve(scope)),
+            Type::Union(members) => Type::union(members.iter().map(|m| m.resolve(scope)).collect()),
+            Type::Function(params, return_type) => Type::Function(
+                params.iter().map(|p| p.resolve(scope)).collect(),
+                Box::new(return_type.resolve(scope)),
+            ),
+            Type::Generic(name, params) => Type::Generic(
+                name.clone(),
+                params.iter().map(|p| p.resolve(scope)).collect(),
+            ),
+            _ => self.clone(),
+        }
+    }
+
+    /// Creates the nullable version of a type.
+    /// Nullable types and `Null` are not wrapped again
+    pub fn nullable(datatype: Type) -> Type {
+        match datatype {
+            Type::Nullable(_) => datatype,
+            _ if datatype.is_null() => datatype,
+            _ => Type::Nullable(Box::new(datatype)),
+        }
+    }
+
+    /// Creates the union of some types. Nested unions are flattened and
+    /// repeated types removed. If one of the types is nullable or `Null`
+    /// the union is nullable, e.g. `?Int | String` is `?(Int | String)`.
+    /// A union of a single type is that type
+    pub fn union(datatypes: Vec<Type>) -> Type {
+        let mut is_nullable = false;
+        let mut members: Vec<Type> = Vec::new();
+        let mut pending = datatypes;
+        pending.reverse();
+
+        while let Some(datatype) = pending.pop() {
+            match datatype {
+                Type::Union(inner) => pending.extend(inner.into_iter().rev()),
+                Type::Nullable(inner) => {
+                    is_nullable = true;
+                    pending.push(*inner);
+                }
+                _ if datatype.is_null() => is_nullable = true,
+                _ if members.iter().any(|m| m.equals(&datatype)) => {}
+                _ => members.push(datatype),
+            }
+        }
+
+        let datatype = match members.len() {
+            0 => return Type::Value(String::from(global::NULL)),
+            1 => members.remove(0),
+            _ => Type::Union(members),
+        };
+        match is_nullable {
+            true => Type::nullable(datatype),
+            false => datatype,
+        }
+    }
+
+    /// Removes `removed`, and the types that can be used in its place,
+    /// from this type. E.g. `Int | String` without `Int` is `String`,
+    /// and `?String` without `Null` is `String`.
+    ///
+    /// The type is returned as it is if nothing would be left
+    pub fn without(&self, removed: &Type) -> Type {
+        let members = match self {
+            Type::Union(members) => members.clone(),
+            Type::Nullable(t) => {
+                let mut members = match t.as_ref() {
+                    Type::Union(members) => members.clone(),
+                    datatype => vec![datatype.clone()],
+                };
+                members.push(Type::Value(String::from(global::NULL)));
+                members
+            }
+            _ => return self.clone(),
+        };
+
+        let remaining: Vec<Type> = members
+            .into_iter()
+            .filter(|m| !m.is_assignable_to(removed))
+            .collect();
+        match remaining.is_empty() {
+            true => self.clone(),
+            false => Type::union(remaining),
+        }
+    }
+
+    /// Returns the number of parameters of a function, or
+    /// None if this type is not a function
+    pub fn arity(&self) -> Option<usize> {
+        match self {
+            Type::Function(params, _) => Some(params.len()),
+            Type::Polymorphic(_, function) => function.arity(),
+            _ => None,
+        }
+    }
+
+    /// Returns the most specific type that values of both types can be
+    /// used as, e.g. Float for Int & Float. None if there isn't one,
+    /// unions are not created
+    pub fn common_type(&self, other: &Type) -> Option<Type> {
+        if self.is_assignable_to(other) {
+            Some(other.clone())
+        } else if other.is_assignable_to(self) {
+            Some(self.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Checks if this type is `Null`, the type of the `null` literal
+    pub fn is_null(&self) -> bool {
+        self.is_value(global::NULL)
+    }
+
+    /// Checks if this type is Unit, the type of functions that don't
+    /// return a value. `Void` is the same type
+    pub fn is_unit(&self) -> bool {
+        self.is_value(global::UNIT) || self.is_value(global::VOID)
+    }
+
+    /// Compares this type to another, structurally.
+    /// Unit & Void are the same type.
+    ///
+    /// Enums and interfaces are compared by name, so they are the same
+    /// type as their name in a datatype annotation, e.g. `Value(Suit)`
     pub fn equals(&self, other: &Self) -> bool {
         use Type::*;
 
         match (self, other) {
-            (Value(v1), Value(v2)) => v1 == v2,
-            (Function(_, _), Function(_, _)) => unimplemented!("Comparison of 2 function types"),
-            (Generic(_, _), Generic(_, _)) => unimplemented!("Comparison of 2 generic types"),
+            (Value(v1), Value(v2)) => v1 == v2 || (self.is_unit() && other.is_unit()),
+            (Enum(name1, _), Enum(name2, _)) => name1 == name2,
+            (Value(name1), Enum(name2, _)) | (Enum(name1, _), Value(name2)) => name1 == name2,
+            (Interface(name1, _), Interface(name2, _)) => name1 == name2,
+            (Value(name1), Interface(name2, _)) | (Interface(name1, _), Value(name2)) => {
+                name1 == name2
+            }
+            (Function(params1, return1), Function(params2, return2)) => {
+                params1.len() == params2.len()
+                    && params1.iter().zip(params2).all(|(p1, p2)| p1.equals(p2))
+                    && return1.equals(return2)
+            }
+            (Generic(name1, params1), Generic(name2, params2)) => {
+                name1 == name2
+                    && params1.len() == params2.len()
+                    && params1.iter().zip(params2).all(|(p1, p2)| p1.equals(p2))
+            }
+            (Polymorphic(type_params1, t1), Polymorphic(type_params2, t2)) => {
+                type_params1 == type_params2 && t1.equals(t2)
+            }
+            (Nullable(t1), Nullable(t2)) => t1.equals(t2),
+            // The order of the members doesn't matter
+            (Union(members1), Union(members2)) => {
+                members1.len() == members2.len()
+                    && members1
+                        .iter()
+                        .all(|m1| members2.iter().any(|m2| m1.equals(m2)))
+            }
+            (Overloaded(overloads1), Overloaded(overloads2)) => {
+                overloads1.len() == overloads2.len()
+                    && overloads1
+                        .iter()
+                        .zip(overloads2)
+                        .all(|(o1, o2)| o1.equals(o2))
+            }
             _ => false,
         }
     }
+
+    /// Checks if a value of this type can be used where
+    /// a value of type `target` is expected.
+    ///
+    /// A function can be used in place of another if it accepts the
+    /// parameters of the other, and its return value can be used in place
+    /// of the other's. The type parameters of generics must be equal.
+    ///
+    /// `null` and values of a type can be used where the nullable
+    /// version of the type is expected, but not the other way around.
+    ///
+    /// A union can be used where all its members can, and a value can
+    /// be used where a union with its type is expected.
+    ///
+    /// An instance of a class can be used where an interface
+    /// the class implements is expected.
+    ///
+    /// An Int can be used where a Float is expected.
+    ///
+    /// Unknown types can be used anywhere, and anything can be
+    /// used where an Unknown type is expected.
+    pub fn is_assignable_to(&self, target: &Self) -> bool {
+        self.conforms_to(target, true)
+    }
+
+    /// Checks if this type can be used where `target` is expected without
+    /// converting it, like `is_assignable_to` but an Int is not a Float.
+    ///
+    /// PHP requires this of the methods that implement an interface:
+    /// their parameters accept supertypes, and their return types are subtypes
+    pub fn is_subtype_of(&self, target: &Self) -> bool {
+        self.conforms_to(target, false)
+    }
+
+    /// Checks if this type can be used where `target` is expected,
+    /// with Ints used as Floats if `widens_ints`
+    fn conforms_to(&self, target: &Self, widens_ints: bool) -> bool {
+        use Type::*;
+
+        match (self, target) {
+            (Unknown, _) | (_, Unknown) => true,
+            (_, Nullable(_)) if self.is_null() => true,
+            (Union(members), _) => members.iter().all(|m| m.conforms_to(target, widens_ints)),
+            (Nullable(t1), Nullable(t2)) => t1.conforms_to(t2, widens_ints),
+            (_, Nullable(t2)) => self.conforms_to(t2, widens_ints),
+            (_, Union(members)) => members.iter().any(|m| self.conforms_to(m, widens_ints)),
+            (Function(params1, return1), Function(params2, return2)) => {
+                params1.len() == params2.len()
+                    && params1
+                        .iter()
+                        .zip(params2)
+                        .all(|(p1, p2)| p2.conforms_to(p1, widens_ints))
+                    && return1.conforms_to(return2, widens_ints)
+            }
+            (Value(class_name), Interface(_, implementors))
+                if implementors.contains(class_name) =>
+            {
+                true
+            }
+            (Value(v1), Value(v2)) if v1 == global::INT && v2 == global::FLOAT => widens_ints,
+            _ => self.equals(target),
+        }
+    }
+
+    /// Replaces the type parameters found in this type with their
+    /// types in `bindings`. Type parameters without a type are kept
+    pub fn substitute(&self, bindings: &HashMap<String, Type>) -> Type {
+        use Type::*;
+
+        match self {
+            Value(v) => match bindings.get(v) {
+                Some(t) => t.clone(),
+                None => self.clone(),
+            },
+            Function(params, return_type) => Function(
+                params.iter().map(|p| p.substitute(bindings)).collect(),
+                Box::new(return_type.substitute(bindings)),
+            ),
+            Generic(name, params) => Generic(
+                name.clone(),
+                params.iter().map(|p| p.substitute(bindings)).collect(),
+            ),
+            Polymorphic(type_params, t) => {
+                // The inner type parameters shadow the outer ones
+                let mut bindings = bindings.clone();
+                for type_param in type_params {
+                    bindings.remove(type_param);
+                }
+                Polymorphic(type_params.clone(), Box::new(t.substitute(&bindings)))
+            }
+            Nullable(t) => Type::nullable(t.substitute(bindings)),
+            Union(members) => Type::union(members.iter().map(|m| m.substitute(bindings)).collect()),
+            Overloaded(overloads) => {
+                Overloaded(overloads.iter().map(|o| o.substitute(bindings)).collect())
+            }
+            Enum(_, _) => self.clone(),
+            Interface(_, _) => self.clone(),
+            Unknown => self.clone(),
+        }
+    }
+
+    /// If this is a generic function, infers its type parameters from the
+    /// types of the arguments of a call, and returns the function with the
+    /// type parameters replaced. Other types are returned as they are
+    pub fn instantiate(&self, arguments: &[Type]) -> Type {
+        let Type::Polymorphic(type_params, function) = self else {
+            return self.clone();
+        };
+
+        let mut bindings = HashMap::new();
+        if let Type::Function(parameters, _) = function.as_ref() {
+            for (parameter, argument) in parameters.iter().zip(arguments) {
+                parameter.infer_type_params(argument, type_params, &mut bindings);
+            }
+        }
+
+        function.substitute(&bindings)
+    }
+
+    /// Infers the types of `type_params` by matching this type, which
+    /// may contain them, against the type of an actual value.
+    ///
+    /// The first type found for a type parameter is the one kept, unless
+    /// a later type has a common type with it, e.g. Int & Float are Float.
+    /// Later mismatches are found when checking assignability.
+    /// `null` doesn't determine a type parameter, e.g. in `x == null`.
+    pub fn infer_type_params(
+        &self,
+        actual: &Type,
+        type_params: &[String],
+        bindings: &mut HashMap<String, Type>,
+    ) {
+        use Type::*;
+
+        match (self, actual) {
+            (_, _) if actual.is_null() => {}
+            (Nullable(t1), Nullable(t2)) => t1.infer_type_params(t2, type_params, bindings),
+            (Nullable(t1), _) => t1.infer_type_params(actual, type_params, bindings),
+            (Value(v), _) if type_params.contains(v) => {
+                let binding = bindings.entry(v.clone()).or_insert_with(|| actual.clone());
+                if let Some(common_type) = binding.common_type(actual) {
+                    *binding = common_type;
+                }
+            }
+            (Generic(name1, params1), Generic(name2, params2))
+                if name1 == name2 && params1.len() == params2.len() =>
+            {
+                for (p1, p2) in params1.iter().zip(params2) {
+                    p1.infer_type_params(p2, type_params, bindings);
+                }
+            }
+            (Function(params1, return1), Function(params2, return2))
+                if params1.len() == params2.len() =>
+            {
+                for (p1, p2) in params1.iter().zip(params2) {
+                    p1.infer_type_params(p2, type_params, bindings);
+                }
+                return1.infer_type_params(return2, type_params, bindings);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Writes the type as it's written in THP code, e.g. `Array[Int]`
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Value(v) => write!(f, "{}", v),
+            Type::Function(params, return_type) => {
+                let params: Vec<String> = params.iter().map(|p| p.to_string()).collect();
+                write!(f, "({}) -> {}", params.join(", "), return_type)
+            }
+            Type::Generic(name, params) => {
+                let params: Vec<String> = params.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}[{}]", name, params.join(", "))
+            }
+            Type::Polymorphic(type_params, t) => write!(f, "[{}]{}", type_params.join(", "), t),
+            // `?Int | String` would be ambiguous
+            Type::Nullable(t) if matches!(**t, Type::Union(_)) => write!(f, "{} | Null", t),
+            Type::Nullable(t) => write!(f, "?{}", t),
+            Type::Union(members) => {
+                let members: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+                write!(f, "{}", members.join(" | "))
+            }
+            Type::Overloaded(overloads) => {
+                let overloads: Vec<String> = overloads.iter().map(|o| o.to_string()).collect();
+                write!(f, "{}", overloads.join(" & "))
+            }
+            Type::Enum(name, _) => write!(f, "{}", name),
+            Type::Interface(name, _) => write!(f, "{}", name),
+            Type::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Converts a datatype annotation of the source code to a type,
+/// e.g. `Array[Int]` to `Generic("Array", [Value("Int")])`
+impl From<&Datatype<'_>> for Type {
+    fn from(datatype: &Datatype<'_>) -> Self {
+        let name = datatype.name.value.clone();
+        let datatype_type = if let Some(return_type) = &datatype.return_type {
+            Type::Function(
+                datatype.type_params.iter().map(Type::from).collect(),
+                Box::new(Type::from(&**return_type)),
+            )
+        } else if datatype.type_params.is_empty() {
+            Type::Value(name)
+        } else {
+            Type::Generic(name, datatype.type_params.iter().map(Type::from).collect())
+        };
+
+        let datatype_type = match datatype.nullable {
+            Some(_) => Type::nullable(datatype_type),
+            None => datatype_type,
+        };
+
+        if datatype.alternatives.is_empty() {
+            return datatype_type;
+        }
+        let mut members = vec![datatype_type];
+        members.extend(datatype.alternatives.iter().map(Type::from));
+        Type::union(members)
+    }
+}
+
+/// Marks the names in a datatype written in the code that are classes,
+/// enums or interfaces, which PHP uses as type hints
+pub fn mark_declared_types(datatype: &Datatype, scope: &SymbolTable) {
+    let is_declared =
+        datatype.return_type.is_none() && scope.is_declared_type(&datatype.name.value);
+    datatype.is_declared.set(is_declared);
+
+    let inner_datatypes = datatype
+        .type_params
+        .iter()
+        .chain(datatype.return_type.as_deref())
+        .chain(datatype.alternatives.iter());
+    for inner_datatype in inner_datatypes {
+        mark_declared_types(inner_datatype, scope);
+    }
+}
+
+/// Checks that `expression`, whose type is `value_type`, can be used
+/// where a `target` is expected.
+///
+/// An array literal takes the element type of the target, e.g. `[1, 2]`
+/// is an Array[Float] where one is expected, and its elements are converted
+pub fn is_assignable_expression(expression: &Expression, value_type: &Type, target: &Type) -> bool {
+    if value_type.is_assignable_to(target) {
+        return true;
+    }
+
+    match (expression, value_type, target) {
+        (_, _, Type::Nullable(target)) => is_assignable_expression(expression, value_type, target),
+        (
+            Expression::Array(array),
+            Type::Generic(name, params),
+            Type::Generic(target_name, target_params),
+        ) if name == "Array"
+            && target_name == "Array"
+            && params.len() == 1
+            && target_params.len() == 1 =>
+        {
+            if params[0].converts_to_float(&target_params[0]) {
+                array.to_float.set(true);
+                return true;
+            }
+
+            // The elements may be arrays of Ints, e.g. in an Array[Array[Float]]
+            array
+                .exps
+                .iter()
+                .all(|element| is_assignable_expression(element, &params[0], &target_params[0]))
+        }
+        _ => false,
+    }
 }
 
 pub trait Typed {
@@ -52,3 +559,323 @@ pub trait Typed {
     /// Typeckecking is done by the trait SemanticCheck
     fn get_type(&self, scope: &SymbolTable) -> Result<Type, MistiError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        lexic::get_tokens,
+        semantic::symbol_table::SymbolTable,
+        syntax::{ast::datatype::Datatype, parseable::Parseable},
+    };
+
+    use std::collections::HashMap;
+
+    use super::{mark_declared_types, Type};
+
+    #[test]
+    fn should_display_types() {
+        assert_eq!("Int", Type::Value("Int".into()).to_string());
+        assert_eq!(
+            "(Int, String) -> Unit",
+            Type::Function(
+                vec![Type::Value("Int".into()), Type::Value("String".into())],
+                Box::new(Type::Value("Unit".into()))
+            )
+            .to_string()
+        );
+        assert_eq!(
+            "Map[String, Array[Int]]",
+            Type::Generic(
+                "Map".into(),
+                vec![
+                    Type::Value("String".into()),
+                    Type::Generic("Array".into(), vec![Type::Value("Int".into())])
+                ]
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn should_convert_datatypes() {
+        let tokens = get_tokens(&String::from("Map[String, Array[Int]]")).unwrap();
+        let (datatype, _) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(
+            Type::Generic(
+                "Map".into(),
+                vec![
+                    value("String"),
+                    Type::Generic("Array".into(), vec![value("Int")])
+                ]
+            ),
+            Type::from(&datatype)
+        );
+    }
+
+    fn value(name: &str) -> Type {
+        Type::Value(name.into())
+    }
+
+    #[test]
+    fn should_compare_function_types() {
+        let f1 = Type::Function(vec![value("Int")], Box::new(value("String")));
+        let f2 = Type::Function(vec![value("Int")], Box::new(value("String")));
+        assert!(f1.equals(&f2));
+
+        let different_param = Type::Function(vec![value("Float")], Box::new(value("String")));
+        let different_return = Type::Function(vec![value("Int")], Box::new(value("Int")));
+        let different_arity = Type::Function(vec![], Box::new(value("String")));
+        assert!(!f1.equals(&different_param));
+        assert!(!f1.equals(&different_return));
+        assert!(!f1.equals(&different_arity));
+        assert!(!f1.equals(&value("Int")));
+    }
+
+    #[test]
+    fn should_compare_generic_types() {
+        let array_int = Type::Generic("Array".into(), vec![value("Int")]);
+        assert!(array_int.equals(&Type::Generic("Array".into(), vec![value("Int")])));
+        assert!(!array_int.equals(&Type::Generic("Array".into(), vec![value("String")])));
+        assert!(!array_int.equals(&Type::Generic("Set".into(), vec![value("Int")])));
+
+        let map = Type::Generic("Map".into(), vec![value("String"), array_int.clone()]);
+        assert!(map.equals(&map.clone()));
+        assert!(!map.equals(&array_int));
+    }
+
+    #[test]
+    fn should_treat_unit_and_void_as_equal() {
+        assert!(value("Unit").equals(&value("Void")));
+        let f1 = Type::Function(vec![], Box::new(value("Unit")));
+        let f2 = Type::Function(vec![], Box::new(value("Void")));
+        assert!(f1.is_assignable_to(&f2));
+    }
+
+    #[test]
+    fn should_infer_type_params() {
+        let type_params = vec![String::from("T")];
+        let parameter = Type::Generic("Array".into(), vec![value("T")]);
+        let argument = Type::Generic("Array".into(), vec![value("Int")]);
+
+        let mut bindings = HashMap::new();
+        parameter.infer_type_params(&argument, &type_params, &mut bindings);
+        assert_eq!(Some(&value("Int")), bindings.get("T"));
+
+        // The first type found is kept
+        value("T").infer_type_params(&value("String"), &type_params, &mut bindings);
+        assert_eq!(Some(&value("Int")), bindings.get("T"));
+
+        // Unless both have a common type
+        let mut float_bindings = bindings.clone();
+        value("T").infer_type_params(&value("Float"), &type_params, &mut float_bindings);
+        assert_eq!(Some(&value("Float")), float_bindings.get("T"));
+
+        let function = Type::Function(vec![parameter], Box::new(value("T")));
+        assert_eq!(
+            Type::Function(vec![argument], Box::new(value("Int"))),
+            function.substitute(&bindings)
+        );
+    }
+
+    #[test]
+    fn should_not_infer_mismatched_types() {
+        let type_params = vec![String::from("T")];
+        let parameter = Type::Generic("Array".into(), vec![value("T")]);
+
+        let mut bindings = HashMap::new();
+        parameter.infer_type_params(&value("Int"), &type_params, &mut bindings);
+        assert!(bindings.is_empty());
+    }
+
+    #[test]
+    fn should_display_polymorphic_types() {
+        let function = Type::Function(
+            vec![Type::Generic("Array".into(), vec![value("T")])],
+            Box::new(value("T")),
+        );
+        assert_eq!(
+            "[T](Array[T]) -> T",
+            Type::Polymorphic(vec!["T".into()], Box::new(function)).to_string()
+        );
+    }
+
+    #[test]
+    fn should_check_nullable_assignability() {
+        let nullable_string = Type::nullable(value("String"));
+        assert_eq!("?String", nullable_string.to_string());
+
+        assert!(value("String").is_assignable_to(&nullable_string));
+        assert!(value("Null").is_assignable_to(&nullable_string));
+        assert!(nullable_string.is_assignable_to(&nullable_string.clone()));
+        assert!(!nullable_string.is_assignable_to(&value("String")));
+        assert!(!value("Null").is_assignable_to(&value("String")));
+        assert!(!value("Int").is_assignable_to(&nullable_string));
+
+        // Nullable types are not nested
+        assert_eq!(nullable_string, Type::nullable(nullable_string.clone()));
+        assert_eq!(value("Null"), Type::nullable(value("Null")));
+    }
+
+    #[test]
+    fn should_infer_type_params_of_nullable_types() {
+        let type_params = vec![String::from("T")];
+        let parameter = Type::nullable(value("T"));
+
+        let mut bindings = HashMap::new();
+        parameter.infer_type_params(&value("Null"), &type_params, &mut bindings);
+        assert!(bindings.is_empty());
+
+        parameter.infer_type_params(&Type::nullable(value("Int")), &type_params, &mut bindings);
+        assert_eq!(Some(&value("Int")), bindings.get("T"));
+    }
+
+    #[test]
+    fn should_build_unions() {
+        let int_or_string = Type::Union(vec![value("Int"), value("String")]);
+        assert_eq!("Int | String", int_or_string.to_string());
+
+        // Nested and repeated types
+        let union = Type::union(vec![
+            value("Int"),
+            Type::union(vec![value("String"), value("Int")]),
+        ]);
+        assert_eq!(int_or_string, union);
+        assert_eq!(value("Int"), Type::union(vec![value("Int"), value("Int")]));
+
+        // Nullable members
+        let union = Type::union(vec![Type::nullable(value("Int")), value("String")]);
+        assert_eq!(Type::nullable(int_or_string.clone()), union);
+        assert_eq!("Int | String | Null", union.to_string());
+        let union = Type::union(vec![value("Int"), value("Null")]);
+        assert_eq!(Type::nullable(value("Int")), union);
+    }
+
+    #[test]
+    fn should_convert_union_datatypes() {
+        let tokens = get_tokens(&String::from("Int | ?String")).unwrap();
+        let (datatype, _) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(
+            Type::nullable(Type::Union(vec![value("Int"), value("String")])),
+            Type::from(&datatype)
+        );
+    }
+
+    #[test]
+    fn should_convert_function_datatypes() {
+        let tokens = get_tokens(&String::from("?(Int, Array[Int]) -> String")).unwrap();
+        let (datatype, _) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        let function = Type::Function(
+            vec![
+                value("Int"),
+                Type::Generic("Array".into(), vec![value("Int")]),
+            ],
+            Box::new(value("String")),
+        );
+        assert_eq!(Type::nullable(function), Type::from(&datatype));
+    }
+
+    #[test]
+    fn should_check_union_assignability() {
+        let int_or_string = Type::union(vec![value("Int"), value("String")]);
+        let int_string_or_bool = Type::union(vec![value("Bool"), int_or_string.clone()]);
+
+        assert!(value("Int").is_assignable_to(&int_or_string));
+        assert!(!value("Bool").is_assignable_to(&int_or_string));
+        assert!(int_or_string.is_assignable_to(&int_string_or_bool));
+        assert!(!int_string_or_bool.is_assignable_to(&int_or_string));
+        assert!(!int_or_string.is_assignable_to(&value("Int")));
+
+        // The order of the members doesn't matter
+        let string_or_int = Type::union(vec![value("String"), value("Int")]);
+        assert!(string_or_int.equals(&int_or_string));
+
+        let nullable = Type::nullable(int_or_string.clone());
+        assert!(int_or_string.is_assignable_to(&nullable));
+        assert!(value("Null").is_assignable_to(&nullable));
+        assert!(Type::nullable(value("Int")).is_assignable_to(&nullable));
+        assert!(!nullable.is_assignable_to(&int_or_string));
+    }
+
+    #[test]
+    fn should_remove_types_from_unions() {
+        let int_or_string = Type::union(vec![value("Int"), value("String")]);
+        assert_eq!(value("String"), int_or_string.without(&value("Int")));
+        assert_eq!(int_or_string, int_or_string.without(&value("Bool")));
+
+        let nullable = Type::nullable(int_or_string.clone());
+        assert_eq!(int_or_string, nullable.without(&value("Null")));
+        assert_eq!(
+            Type::nullable(value("Int")),
+            nullable.without(&value("String"))
+        );
+        assert_eq!(
+            value("Null"),
+            Type::nullable(value("Int")).without(&value("Int"))
+        );
+
+        // Nothing would be left
+        assert_eq!(value("Int"), value("Int").without(&value("Int")));
+    }
+
+    #[test]
+    fn should_use_ints_as_floats() {
+        assert!(value("Int").is_assignable_to(&value("Float")));
+        assert!(!value("Float").is_assignable_to(&value("Int")));
+        assert!(value("Int").is_assignable_to(&Type::nullable(value("Float"))));
+
+        // Type parameters are not converted
+        let array_int = Type::Generic("Array".into(), vec![value("Int")]);
+        let array_float = Type::Generic("Array".into(), vec![value("Float")]);
+        assert!(!array_int.is_assignable_to(&array_float));
+
+        assert_eq!(
+            Some(value("Float")),
+            value("Int").common_type(&value("Float"))
+        );
+        assert_eq!(
+            Some(value("Float")),
+            value("Float").common_type(&value("Int"))
+        );
+        assert_eq!(None, value("Int").common_type(&value("String")));
+    }
+
+    #[test]
+    fn should_not_use_ints_as_floats_in_subtypes() {
+        assert!(!value("Int").is_subtype_of(&value("Float")));
+        assert!(value("Int").is_subtype_of(&Type::nullable(value("Int"))));
+
+        let int_function = Type::Function(vec![], Box::new(value("Int")));
+        let float_function = Type::Function(vec![], Box::new(value("Float")));
+        assert!(int_function.is_assignable_to(&float_function));
+        assert!(!int_function.is_subtype_of(&float_function));
+    }
+
+    #[test]
+    fn should_check_assignability() {
+        assert!(value("Int").is_assignable_to(&value("Int")));
+        assert!(!value("Int").is_assignable_to(&value("String")));
+
+        let array_int = Type::Generic("Array".into(), vec![value("Int")]);
+        assert!(array_int.is_assignable_to(&array_int.clone()));
+        assert!(!array_int.is_assignable_to(&value("Int")));
+    }
+
+    #[test]
+    fn should_mark_declared_types() {
+        let scope = SymbolTable::new();
+        scope.declare_class("Point".into(), vec![]);
+        let tokens = get_tokens(&String::from("Array[?Point] | (Point) -> Int")).unwrap();
+        let (datatype, _) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        mark_declared_types(&datatype, &scope);
+        assert!(!datatype.is_declared.get());
+        assert!(datatype.type_params[0].is_declared.get());
+        let function = &datatype.alternatives[0];
+        assert!(!function.is_declared.get());
+        assert!(function.type_params[0].is_declared.get());
+        assert!(!function.return_type.as_ref().unwrap().is_declared.get());
+    }
+}
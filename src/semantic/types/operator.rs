@@ -0,0 +1,257 @@
+//! Typing of unary and binary operators.
+//!
+//! Operators are stored in the symbol table as functions. An operator
+//! that works over several types, e.g. `+` over Ints and Floats, is
+//! stored as an overloaded function, and each usage of the operator
+//! is resolved to one of its overloads.
+
+use crate::{
+    error_handling::{
+        error_messages::{SEMANTIC_INVALID_REFERENCE, SEMANTIC_NO_MATCHING_OVERLOAD},
+        ErrorContainer, ErrorLabel, MistiError,
+    },
+    lexic::token::Token,
+    semantic::{checks::conditional::narrowed_by, symbol_table::SymbolTable},
+    syntax::ast::Expression,
+};
+
+use super::{Type, Typed};
+
+/// Returns the type of the value computed by `operator` over `operands`
+pub fn operator_return_type(
+    operator: &Token,
+    operands: &[&Expression],
+    scope: &SymbolTable,
+) -> Result<Type, MistiError> {
+    let operand_types = get_operand_types(operator, operands, scope)?;
+    let overloads = get_overloads(operator, operand_types.len(), scope)?;
+
+    match resolve_overload(&overloads, &operand_types) {
+        Some(Type::Function(_, return_type)) => Ok(*return_type),
+        Some(_) => unreachable!("Compiler bug: an overload of an operator is not a function"),
+        None => Err(no_overload_error(operator, &operand_types, &overloads)),
+    }
+}
+
+/// Checks that `operator` can be applied to `operands`
+pub fn check_operator(
+    operator: &Token,
+    operands: &[&Expression],
+    scope: &SymbolTable,
+) -> Result<(), MistiError> {
+    operator_return_type(operator, operands, scope).map(|_| ())
+}
+
+/// Returns the scope where the right operand of `operator` is checked.
+///
+/// `&&` and `||` short circuit: in `a && b`, `b` is only evaluated if `a`
+/// is true, so a variable tested by `a` is narrowed in `b`, e.g. in
+/// `x != null && x > 0`
+pub fn right_operand_scope(
+    operator: &Token,
+    left: &Expression,
+    scope: &SymbolTable,
+) -> SymbolTable {
+    let narrowed_scope = match operator.value.as_str() {
+        "&&" => narrowed_by(left, true, scope),
+        "||" => narrowed_by(left, false, scope),
+        _ => None,
+    };
+    narrowed_scope.unwrap_or_else(|| scope.clone())
+}
+
+fn get_operand_types(
+    operator: &Token,
+    operands: &[&Expression],
+    scope: &SymbolTable,
+) -> Result<Vec<Type>, MistiError> {
+    match operands {
+        [left, right] => {
+            let right_scope = right_operand_scope(operator, left, scope);
+            Ok(vec![left.get_type(scope)?, right.get_type(&right_scope)?])
+        }
+        _ => {
+            let mut operand_types = Vec::new();
+            for operand in operands {
+                operand_types.push(operand.get_type(scope)?);
+            }
+            Ok(operand_types)
+        }
+    }
+}
+
+/// Returns the overloads of `operator` that take `arity` operands
+fn get_overloads(
+    operator: &Token,
+    arity: usize,
+    scope: &SymbolTable,
+) -> Result<Vec<Type>, MistiError> {
+    let overloads = match scope.get_type(&operator.value) {
+        Some(Type::Overloaded(overloads)) => overloads,
+        Some(t) => vec![t],
+        None => vec![],
+    };
+    let overloads: Vec<Type> = overloads
+        .into_iter()
+        .filter(|overload| overload.arity() == Some(arity))
+        .collect();
+
+    if overloads.is_empty() {
+        // If the operator is not found its a user error,
+        // because we allow arbitrary operators
+        let kind = if arity == 1 { "unary" } else { "binary" };
+        let label = ErrorLabel {
+            message: format!("The {} operator {} does not exist", kind, operator.value),
+            start: operator.position,
+            end: operator.get_end_position(),
+        };
+        return Err(ErrorContainer {
+            error_code: SEMANTIC_INVALID_REFERENCE,
+            error_offset: operator.position,
+            labels: vec![label],
+            note: None,
+            help: None,
+        });
+    }
+
+    Ok(overloads)
+}
+
+/// Returns the first overload that accepts operands of `operand_types`,
+/// with its type parameters inferred from them.
+///
+/// The overloads are tried in order, so the more specific
+/// ones must come first, e.g. `(Int, Int) -> Int` before
+/// `(Float, Float) -> Float`
+fn resolve_overload(overloads: &[Type], operand_types: &[Type]) -> Option<Type> {
+    overloads
+        .iter()
+        .map(|overload| overload.instantiate(operand_types))
+        .find(|overload| match overload {
+            Type::Function(params, _) => params
+                .iter()
+                .zip(operand_types)
+                .all(|(param, operand_type)| operand_type.is_assignable_to(param)),
+            _ => false,
+        })
+}
+
+fn no_overload_error(operator: &Token, operand_types: &[Type], overloads: &[Type]) -> MistiError {
+    let operand_types: Vec<String> = operand_types.iter().map(|t| t.to_string()).collect();
+    let overloads: Vec<String> = overloads.iter().map(|t| t.to_string()).collect();
+
+    let label = ErrorLabel {
+        message: format!(
+            "There is no overload of `{}` for ({})",
+            operator.value,
+            operand_types.join(", ")
+        ),
+        start: operator.position,
+        end: operator.get_end_position(),
+    };
+    ErrorContainer {
+        error_code: SEMANTIC_NO_MATCHING_OVERLOAD,
+        error_offset: operator.position,
+        labels: vec![label],
+        note: Some(format!(
+            "The overloads of `{}` are: {}",
+            operator.value,
+            overloads.join(", ")
+        )),
+        help: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error_handling::error_messages::{
+            SEMANTIC_INVALID_REFERENCE, SEMANTIC_NO_MATCHING_OVERLOAD,
+        },
+        lexic::get_tokens,
+        semantic::{std::populate, symbol_table::SymbolTable, types::Typed},
+        syntax::{ast::Expression, parseable::Parseable},
+    };
+
+    use super::*;
+
+    fn get_type(input: &str) -> Result<Type, MistiError> {
+        let tokens = get_tokens(&String::from(input)).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+        let mut scope = SymbolTable::new();
+        populate(&mut scope);
+        expression.get_type(&scope)
+    }
+
+    fn value(name: &str) -> Type {
+        Type::Value(name.into())
+    }
+
+    #[test]
+    fn should_resolve_overloads_in_order() {
+        assert_eq!(value("Int"), get_type("1 + 2").unwrap());
+        assert_eq!(value("Float"), get_type("1 + 2.5").unwrap());
+        assert_eq!(value("Float"), get_type("1.5 * 2.5").unwrap());
+        assert_eq!(value("Float"), get_type("1 / 2").unwrap());
+        assert_eq!(value("Int"), get_type("-1").unwrap());
+        assert_eq!(value("Float"), get_type("-1.5").unwrap());
+        assert_eq!(value("Bool"), get_type("1 < 2.5").unwrap());
+        assert_eq!(value("String"), get_type("\"a\" ++ \"b\"").unwrap());
+    }
+
+    #[test]
+    fn should_report_missing_overloads() {
+        let error = get_type("\"a\" * 2").unwrap_err();
+        assert_eq!(SEMANTIC_NO_MATCHING_OVERLOAD, error.error_code);
+        assert_eq!(
+            "There is no overload of `*` for (String, Int)",
+            error.labels[0].message
+        );
+        assert_eq!(
+            Some(String::from(
+                "The overloads of `*` are: (Int, Int) -> Int, (Float, Float) -> Float"
+            )),
+            error.note
+        );
+    }
+
+    #[test]
+    fn should_report_unknown_operators() {
+        let tokens = get_tokens(&String::from("1 + 2")).unwrap();
+        let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+        // A scope without the std operators
+        let scope = SymbolTable::new();
+
+        let error = expression.get_type(&scope).unwrap_err();
+        assert_eq!(SEMANTIC_INVALID_REFERENCE, error.error_code);
+        assert_eq!(
+            "The binary operator + does not exist",
+            error.labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_report_overloads_of_single_signature_operators() {
+        let mut scope = SymbolTable::new();
+        populate(&mut scope);
+
+        for (input, message) in [
+            (
+                "1 ++ \"b\"",
+                "There is no overload of `++` for (Int, String)",
+            ),
+            ("1 < \"b\"", "There is no overload of `<` for (Int, String)"),
+        ] {
+            let tokens = get_tokens(&String::from(input)).unwrap();
+            let (expression, _) = Expression::try_parse(&tokens, 0).unwrap();
+            let Expression::BinaryOperator(left, right, operator) = &expression else {
+                panic!("Expected a binary operator")
+            };
+
+            let error = check_operator(operator, &[left, right], &scope).unwrap_err();
+            assert_eq!(SEMANTIC_NO_MATCHING_OVERLOAD, error.error_code);
+            assert_eq!(operator.position, error.error_offset);
+            assert_eq!(message, error.labels[0].message);
+        }
+    }
+}
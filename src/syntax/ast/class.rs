@@ -0,0 +1,73 @@
+use crate::lexic::token::Token;
+
+use super::{var_binding::VariableBinding, FunctionDeclaration, ParamsList, Positionable};
+
+/// A class declaration, e.g.
+///
+/// ```thp
+/// class Point(val Int x, var Int y) : Printable {
+///     var Int moves = 0
+///
+///     fun move(Int dy) {
+///         self.y += dy
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ClassDeclaration<'a> {
+    /// The `class` keyword
+    pub keyword: &'a Token,
+    /// The name of the class, a datatype
+    pub identifier: &'a Token,
+    /// The parameters of the primary constructor, if any. The
+    /// parameters declared with `val` or `var` are also properties
+    pub constructor: Option<ParamsList<'a>>,
+    /// The names of the interfaces the class implements, after a colon `:`
+    pub interfaces: Vec<&'a Token>,
+    /// The body may be omitted, e.g. `class Point(val Int x, val Int y)`
+    pub body: Option<ClassBody<'a>>,
+}
+
+#[derive(Debug)]
+pub struct ClassBody<'a> {
+    /// The position of the open brace {
+    pub start: usize,
+    /// The position of the closed brace }
+    pub end: usize,
+    pub members: Vec<ClassMember<'a>>,
+}
+
+/// Enum for productions available in the body of a class
+#[derive(Debug)]
+pub enum ClassMember<'a> {
+    /// A property, initialized when the class is created
+    Field(VariableBinding<'a>),
+    /// A function that receives the instance as `self`
+    Method(FunctionDeclaration<'a>),
+}
+
+impl ClassDeclaration<'_> {
+    /// Returns the members declared in the body of the class
+    pub fn members(&self) -> impl Iterator<Item = &ClassMember<'_>> {
+        self.body.iter().flat_map(|body| body.members.iter())
+    }
+}
+
+impl Positionable for ClassBody<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        (self.start, self.end)
+    }
+}
+
+impl Positionable for ClassDeclaration<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        let end = match (&self.body, self.interfaces.last(), &self.constructor) {
+            // The end of a body is the position of its closing brace
+            (Some(body), _, _) => body.get_position().1 + 1,
+            (None, Some(interface), _) => interface.get_end_position(),
+            (None, None, Some(constructor)) => constructor.end,
+            (None, None, None) => self.identifier.get_end_position(),
+        };
+        (self.keyword.position, end)
+    }
+}
@@ -0,0 +1,44 @@
+use std::cell::Cell;
+
+use crate::lexic::token::Token;
+
+use super::Positionable;
+
+/// A datatype written in the source code,
+/// e.g. `Int`, `?String`, `Array[Int]`, `Map[String, Array[Float]]`, `Int | String`
+/// or `(Int, Int) -> Int`
+#[derive(Debug)]
+pub struct Datatype<'a> {
+    /// The `?` before the name, if the datatype is nullable
+    pub nullable: Option<&'a Token>,
+    /// The name of the datatype, e.g. `Array`.
+    /// The opening parenthesis if the datatype is a function
+    pub name: &'a Token,
+    /// Set by the semantic analysis if the name is a class, an enum
+    /// or an interface declared in the code
+    pub is_declared: Cell<bool>,
+    /// The type parameters inside the brackets, or the parameters of a function.
+    /// Empty if the datatype is not generic
+    pub type_params: Vec<Datatype<'a>>,
+    /// The datatype after the arrow `->`, if the datatype is a function
+    pub return_type: Option<Box<Datatype<'a>>>,
+    /// The datatypes after the first one, if the datatype is a union.
+    /// E.g. `String` and `Bool` in `Int | String | Bool`
+    pub alternatives: Vec<Datatype<'a>>,
+    /// The end position of the name, of the closing bracket if the
+    /// datatype is generic, or of the return type if it's a function
+    pub end: usize,
+}
+
+impl Positionable for Datatype<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        let end = match self.alternatives.last() {
+            Some(alternative) => alternative.end,
+            None => self.end,
+        };
+        match self.nullable {
+            Some(question_mark) => (question_mark.position, end),
+            None => (self.name.position, end),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::lexic::token::Token;
+
+use super::{ParamsList, Positionable};
+
+/// An enum declaration. Its variants may carry values, e.g.
+///
+/// ```thp
+/// enum Shape {
+///     Circle(Float radius)
+///     Rect(Float width, Float height)
+///     Empty
+/// }
+/// ```
+#[derive(Debug)]
+pub struct EnumDeclaration<'a> {
+    /// The `enum` keyword
+    pub keyword: &'a Token,
+    /// The name of the enum, a datatype
+    pub identifier: &'a Token,
+    pub variants: Vec<EnumVariant<'a>>,
+    /// The position of the closed brace }
+    pub end: usize,
+}
+
+#[derive(Debug)]
+pub struct EnumVariant<'a> {
+    /// The name of the variant, a datatype
+    pub identifier: &'a Token,
+    /// The fields of the values of this variant, if any
+    pub payload: Option<ParamsList<'a>>,
+}
+
+impl EnumDeclaration<'_> {
+    /// Checks if any variant has a payload. The variants of these
+    /// enums are PHP classes, e.g. `Shape_Empty`
+    pub fn has_payloads(&self) -> bool {
+        self.variants.iter().any(|variant| match &variant.payload {
+            Some(payload) => !payload.parameters.is_empty(),
+            None => false,
+        })
+    }
+}
+
+impl Positionable for EnumVariant<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        match &self.payload {
+            Some(payload) => (self.identifier.position, payload.end),
+            None => (self.identifier.position, self.identifier.get_end_position()),
+        }
+    }
+}
+
+impl Positionable for EnumDeclaration<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        (self.keyword.position, self.end + 1)
+    }
+}
@@ -1,4 +1,8 @@
-use super::{Expression, Positionable};
+use std::cell::RefCell;
+
+use crate::lexic::token::Token;
+
+use super::{datatype::Datatype, Block, Expression, ParamsList, Positionable};
 
 #[derive(Debug)]
 pub struct FunctionCall<'a> {
@@ -27,3 +31,31 @@ impl Positionable for ArgumentsList<'_> {
         (self.paren_open_pos, self.paren_close_pos)
     }
 }
+
+/// An anonymous function, e.g. `fn(Int x) -> Int { x + 1 }`
+#[derive(Debug)]
+pub struct Lambda<'a> {
+    /// The `fn` keyword
+    pub keyword: &'a Token,
+    pub params_list: ParamsList<'a>,
+    pub return_type: Option<Datatype<'a>>,
+    pub block: Block<'a>,
+    /// The variables of the enclosing scopes used in the body.
+    /// Set by the semantic analysis, PHP closures have to capture them
+    pub captures: RefCell<Vec<Capture>>,
+}
+
+/// A variable of an enclosing scope used inside a lambda
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture {
+    pub name: String,
+    /// Mutable variables are captured by reference, so that
+    /// the lambda and the enclosing scope share their value
+    pub is_mutable: bool,
+}
+
+impl Positionable for Lambda<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        (self.keyword.position, self.block.end + 1)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::lexic::token::Token;
+
+use super::{datatype::Datatype, ParamsList, Positionable};
+
+/// An interface declaration, the methods a class must have to implement it, e.g.
+///
+/// ```thp
+/// interface Shape {
+///     fun area() -> Float
+///     fun scale(Float factor)
+/// }
+///
+/// class Square(val Float side) : Shape {
+///     // ...
+/// }
+/// ```
+#[derive(Debug)]
+pub struct InterfaceDeclaration<'a> {
+    /// The `interface` keyword
+    pub keyword: &'a Token,
+    /// The name of the interface, a datatype
+    pub identifier: &'a Token,
+    pub methods: Vec<FunctionSignature<'a>>,
+    /// The position of the closed brace }
+    pub end: usize,
+}
+
+/// The declaration of a function without its body, e.g. `fun area() -> Float`
+#[derive(Debug)]
+pub struct FunctionSignature<'a> {
+    /// The `fun` keyword
+    pub keyword: &'a Token,
+    pub identifier: &'a Token,
+    /// The names of the type parameters, e.g. `T` in `fun first[T]()`
+    pub type_params: Vec<&'a Token>,
+    pub params_list: ParamsList<'a>,
+    pub return_type: Option<Datatype<'a>>,
+}
+
+impl Positionable for InterfaceDeclaration<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        (self.keyword.position, self.end + 1)
+    }
+}
+
+impl Positionable for FunctionSignature<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        let end = match &self.return_type {
+            Some(return_type) => return_type.get_position().1,
+            None => self.params_list.end,
+        };
+        (self.keyword.position, end)
+    }
+}
@@ -0,0 +1,58 @@
+use std::cell::{Cell, RefCell};
+
+use crate::lexic::token::Token;
+
+use super::{functions::Capture, Block, Expression, Positionable};
+
+/// A match expression, e.g.
+///
+/// ```thp
+/// match value
+/// case 1, 2 { "small" }
+/// case 3 if is_special { "special" }
+/// else { "big" }
+/// ```
+#[derive(Debug)]
+pub struct Match<'a> {
+    /// The `match` keyword
+    pub keyword: &'a Token,
+    /// The value matched against the arms
+    pub subject: Box<Expression<'a>>,
+    /// There is at least one arm
+    pub arms: Vec<MatchArm<'a>>,
+    pub else_block: Option<Block<'a>>,
+    /// The variables of the enclosing scopes used in the arms. Set by the
+    /// semantic analysis, some matches become a PHP function that captures them
+    pub captures: RefCell<Vec<Capture>>,
+    /// Whether the arms are Floats, so the Ints among them are converted.
+    /// Set by the semantic analysis
+    pub to_float: Cell<bool>,
+}
+
+/// A `case` of a match expression
+#[derive(Debug)]
+pub struct MatchArm<'a> {
+    /// The literal values this arm matches, at least one
+    pub patterns: Vec<Expression<'a>>,
+    /// The condition after `if`, the arm is only taken if it's true
+    pub guard: Option<Expression<'a>>,
+    pub body: Block<'a>,
+}
+
+impl Match<'_> {
+    /// Returns the blocks of all the arms, and the else block last
+    pub fn blocks(&self) -> impl Iterator<Item = &Block<'_>> {
+        self.arms
+            .iter()
+            .map(|arm| &arm.body)
+            .chain(self.else_block.iter())
+    }
+}
+
+impl Positionable for Match<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        let last_block = self.blocks().last().expect("A match has at least one arm");
+        // The end of a block is the position of its closing brace
+        (self.keyword.position, last_block.end + 1)
+    }
+}
@@ -1,11 +1,26 @@
+use std::{
+    cell::{Cell, RefCell},
+    ops::Deref,
+};
+
 use crate::lexic::token::Token;
 
-use self::functions::FunctionCall;
+use self::functions::{Capture, FunctionCall, Lambda};
+use class::ClassDeclaration;
+use datatype::Datatype;
+use enum_declaration::EnumDeclaration;
+use interface::InterfaceDeclaration;
 use loops::{ForLoop, WhileLoop};
+use match_expression::Match;
 use var_binding::VariableBinding;
 
+pub mod class;
+pub mod datatype;
+pub mod enum_declaration;
 pub mod functions;
+pub mod interface;
 pub mod loops;
+pub mod match_expression;
 pub mod var_binding;
 
 /// Trait that allows nodes to inform
@@ -33,19 +48,48 @@ pub enum ModuleMembers<'a> {
 pub enum Statement<'a> {
     Binding(VariableBinding<'a>),
     FnDecl(FunctionDeclaration<'a>),
+    ClassDecl(ClassDeclaration<'a>),
+    EnumDecl(EnumDeclaration<'a>),
+    InterfaceDecl(InterfaceDeclaration<'a>),
     // TODO: Implement conditionals as expressions
     Conditional(Conditional<'a>),
     ForLoop(ForLoop<'a>),
     WhileLoop(WhileLoop<'a>),
     Assignment(Assignment<'a>),
+    Return(Return<'a>),
 }
 
 #[derive(Debug)]
 pub struct Assignment<'a> {
-    /// The left side of the assignment
+    /// The value whose property is assigned, e.g. `self` in
+    /// `self.count = 0`. None if a variable is assigned
+    pub object: Option<Box<Expression<'a>>>,
+    /// The assigned variable or property
     pub identifier: &'a Token,
+    /// The assignment operator: `=`, `+=`, `-=`, etc.
+    pub operator: &'a Token,
     /// The right side of the assignment
     pub expression: Box<Expression<'a>>,
+    /// Whether the value is an Int assigned to a Float variable. Set by
+    /// the semantic analysis, PHP variables don't convert it on their own
+    pub to_float: Cell<bool>,
+}
+
+#[derive(Debug)]
+pub struct Return<'a> {
+    /// The `return` keyword
+    pub keyword: &'a Token,
+    /// The returned value. Functions that return Unit omit it
+    pub value: Option<Expression<'a>>,
+}
+
+impl Positionable for Return<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        match &self.value {
+            Some(value) => (self.keyword.position, value.get_position().1),
+            None => (self.keyword.position, self.keyword.get_end_position()),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -64,9 +108,19 @@ pub struct Condition<'a> {
 #[derive(Debug)]
 pub struct FunctionDeclaration<'a> {
     pub identifier: &'a Token,
-    pub return_type: Option<&'a Token>,
+    /// The names of the type parameters, e.g. `T` in `fun first[T]()`
+    pub type_params: Vec<&'a Token>,
+    pub return_type: Option<Datatype<'a>>,
     pub params_list: Box<ParamsList<'a>>,
     pub block: Box<Block<'a>>,
+    /// The variables of the enclosing scopes used in the body. Set by the
+    /// semantic analysis: a top level function declares them as `global`,
+    /// a nested function captures them like a lambda
+    pub captures: RefCell<Vec<Capture>>,
+    /// Whether the function is declared inside another function. Set by
+    /// the semantic analysis, PHP functions are always global, so a nested
+    /// function is a closure assigned to a variable
+    pub is_nested: Cell<bool>,
 }
 
 #[derive(Debug)]
@@ -104,8 +158,21 @@ impl Positionable for ParamsList<'_> {
 
 #[derive(Debug)]
 pub struct Parameter<'a> {
-    pub identifier: &'a String,
-    pub datatype: &'a String,
+    /// The `val` or `var` keyword of a parameter that also declares
+    /// a property. Only the parameters of a class constructor have it
+    pub modifier: Option<&'a Token>,
+    pub identifier: &'a Token,
+    pub datatype: Datatype<'a>,
+}
+
+impl Positionable for Parameter<'_> {
+    fn get_position(&self) -> (usize, usize) {
+        let start = match self.modifier {
+            Some(modifier) => modifier.position,
+            None => self.datatype.get_position().0,
+        };
+        (start, self.identifier.get_end_position())
+    }
 }
 
 #[derive(Debug)]
@@ -114,7 +181,11 @@ pub enum Expression<'a> {
     Float(&'a Token),
     String(&'a Token),
     Boolean(&'a Token),
-    Identifier(&'a Token),
+    Null(&'a Token),
+    Identifier(Identifier<'a>),
+    /// The enum and the variant, e.g. `Shape::Empty`. The semantic analysis
+    /// sets the flag if the enum has payloads, as its variants are PHP classes
+    EnumVariant(&'a Token, &'a Token, Cell<bool>),
     FunctionCall(FunctionCall<'a>),
     /// operator, right expression
     UnaryOperator(&'a Token, Box<Expression<'a>>),
@@ -122,6 +193,36 @@ pub enum Expression<'a> {
     BinaryOperator(Box<Expression<'a>>, Box<Expression<'a>>, &'a Token),
     Array(Array<'a>),
     ArrayAcccess(ArrayAccess<'a>),
+    TypeTest(TypeTest<'a>),
+    Match(Match<'a>),
+    Lambda(Lambda<'a>),
+}
+
+/// An identifier used as an expression, e.g. `count` in `count + 1`
+#[derive(Debug)]
+pub struct Identifier<'a> {
+    pub token: &'a Token,
+    /// Set by the semantic analysis if the identifier refers to a
+    /// function declared with `fun`. PHP references those by their
+    /// name, and everything else through a variable
+    pub is_function: Cell<bool>,
+}
+
+impl<'a> Identifier<'a> {
+    pub fn new(token: &'a Token) -> Identifier<'a> {
+        Identifier {
+            token,
+            is_function: Cell::new(false),
+        }
+    }
+}
+
+impl Deref for Identifier<'_> {
+    type Target = Token;
+
+    fn deref(&self) -> &Token {
+        self.token
+    }
 }
 
 #[derive(Debug)]
@@ -131,6 +232,9 @@ pub struct Array<'a> {
     pub start: usize,
     /// The position of the closed bracket ]
     pub end: usize,
+    /// Whether the elements are Floats, so the Ints among them are converted.
+    /// Set by the semantic analysis
+    pub to_float: Cell<bool>,
 }
 
 #[derive(Debug)]
@@ -140,6 +244,13 @@ pub struct ArrayAccess<'a> {
     pub end_pos: usize,
 }
 
+/// Checks the type of a value, e.g. `value is String`
+#[derive(Debug)]
+pub struct TypeTest<'a> {
+    pub expression: Box<Expression<'a>>,
+    pub datatype: Datatype<'a>,
+}
+
 impl Positionable for Expression<'_> {
     /// Returns the absolute start and end position
     /// of this expression
@@ -150,6 +261,10 @@ impl Positionable for Expression<'_> {
             Expression::Float(id) => (id.position, id.get_end_position()),
             Expression::String(id) => (id.position, id.get_end_position()),
             Expression::Boolean(id) => (id.position, id.get_end_position()),
+            Expression::Null(id) => (id.position, id.get_end_position()),
+            Expression::EnumVariant(enum_name, variant, _) => {
+                (enum_name.position, variant.get_end_position())
+            }
             Expression::FunctionCall(f) => f.get_position(),
             Expression::UnaryOperator(operator, exp) => {
                 let start = operator.position;
@@ -161,15 +276,18 @@ impl Positionable for Expression<'_> {
                 let (_, end) = right_expr.get_position();
                 (start, end)
             }
-            Expression::Array(Array {
-                start,
-                end,
-                exps: _,
-            }) => (*start, *end),
+            Expression::Array(array) => (array.start, array.end),
             Expression::ArrayAcccess(a) => {
                 let (start, _) = a.left_expr.get_position();
                 (start, a.end_pos)
             }
+            Expression::TypeTest(t) => {
+                let (start, _) = t.expression.get_position();
+                let (_, end) = t.datatype.get_position();
+                (start, end)
+            }
+            Expression::Match(m) => m.get_position(),
+            Expression::Lambda(l) => l.get_position(),
         }
     }
 }
@@ -1,11 +1,16 @@
+use std::cell::Cell;
+
 use crate::lexic::token::Token;
 
-use super::Expression;
+use super::{datatype::Datatype, Expression};
 
 #[derive(Debug)]
 pub struct VariableBinding<'a> {
-    pub datatype: Option<&'a Token>,
+    pub datatype: Option<Datatype<'a>>,
     pub identifier: &'a Token,
     pub expression: Expression<'a>,
     pub is_mutable: bool,
+    /// Whether the value is an Int assigned to a Float. Set by the
+    /// semantic analysis, PHP variables don't convert it on their own
+    pub to_float: Cell<bool>,
 }
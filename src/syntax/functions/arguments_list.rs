@@ -16,6 +16,9 @@ pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<ArgumentsList
         match parse_token_type(tokens, current_pos, TokenType::LeftParen) {
             Ok((t, next)) => (t, next),
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(t)) => return Err(ParsingError::Mismatch(t)),
             Err(ParsingError::Unmatched) => return Err(ParsingError::Unmatched),
         };
@@ -42,6 +45,9 @@ pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<ArgumentsList
             }
             // This should never happen
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(_)) => {
                 // Something other than a comma was found. It must be a closing paren )
                 // Still, break the loop, assume there are no more arguments
@@ -57,6 +63,9 @@ pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<ArgumentsList
         match parse_token_type(tokens, current_pos, TokenType::RightParen) {
             Ok((t, next)) => (t, next),
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(t)) => {
                 let label_1 = ErrorLabel {
                     message: String::from("The argument list starts here"),
@@ -5,13 +5,14 @@ use crate::{
     },
     lexic::token::{Token, TokenType},
     syntax::{
+        parseable::Parseable,
         utils::{parse_token_type, Tokenizer},
         ParsingError, ParsingResult,
     },
 };
 
 use super::super::{
-    ast::{Parameter, ParamsList},
+    ast::{datatype::Datatype, Parameter, ParamsList, Positionable},
     utils,
 };
 
@@ -21,7 +22,7 @@ params list = "("
             , ( datatype pair, (",", datatype pair)*, ","? )?
             , ")";
 
-datatype pair = datatype, identifier;
+datatype pair = ("val" | "var")?, datatype, identifier;
  */
 pub fn parse_params_list(tokens: &Vec<Token>, pos: usize) -> ParsingResult<ParamsList> {
     let mut current_pos = pos;
@@ -30,6 +31,9 @@ pub fn parse_params_list(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Param
         match parse_token_type(tokens, current_pos, TokenType::LeftParen) {
             Ok((t, next)) => (t, next),
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(t)) => return Err(ParsingError::Mismatch(t)),
             Err(ParsingError::Unmatched) => return Err(ParsingError::Unmatched),
         };
@@ -55,6 +59,9 @@ pub fn parse_params_list(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Param
             }
             // This should never happen
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(_)) => {
                 // Something other than a comma was found. It must be a closing paren )
                 // Still, break the loop, assume there are no more arguments
@@ -70,6 +77,9 @@ pub fn parse_params_list(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Param
         match parse_token_type(tokens, current_pos, TokenType::RightParen) {
             Ok((t, next)) => (t, next),
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(t)) => {
                 let label_1 = ErrorLabel {
                     message: String::from("The parameter list starts here"),
@@ -126,6 +136,8 @@ pub fn parse_params_list(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Param
 
 /// Parse a single parameter definition of the form:
 /// - `Type identifier`
+/// - `val Type identifier` or `var Type identifier`, for the
+///   parameters of a class constructor that declare properties
 ///
 /// There will be more constructs in the future, like:
 /// - `Type identifier = default_value`
@@ -133,16 +145,43 @@ pub fn parse_params_list(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Param
 /// - `Pattern identifier` (e.g. `Some[String] value`)?
 fn parse_param_definition(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Parameter> {
     let mut current_pos = pos;
-    let (datatype, next_pos) =
-        match utils::parse_token_type(tokens, current_pos, TokenType::Datatype) {
-            Ok((token, next)) => (token, next),
-            Err(ParsingError::Err(err)) => {
-                return Err(ParsingError::Err(err));
-            }
-            // If there is no datatype this construction doesn't apply.
-            // Return an unmatch and let the caller handle it
-            _ => return Err(ParsingError::Unmatched),
-        };
+
+    // `val` or `var`, optional
+    let (modifier, next_pos) = match tokens.get_significant(current_pos) {
+        Some((token, token_pos))
+            if token.token_type == TokenType::VAL || token.token_type == TokenType::VAR =>
+        {
+            (Some(token), token_pos + 1)
+        }
+        _ => (None, current_pos),
+    };
+    current_pos = next_pos;
+
+    let (datatype, next_pos) = match (Datatype::try_parse(tokens, current_pos), modifier) {
+        (Ok((datatype, next)), _) => (datatype, next),
+        (Err(ParsingError::Err(err)), _) => {
+            return Err(ParsingError::Err(err));
+        }
+        // After `val` or `var` there must be a datatype
+        (_, Some(modifier)) => {
+            let label = ErrorLabel {
+                message: format!("Expected a datatype after this `{}`", modifier.value),
+                start: modifier.position,
+                end: modifier.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_PARAMETER_DECLARATION,
+                error_offset: modifier.get_end_position(),
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            return Err(ParsingError::Err(econtainer));
+        }
+        // If there is no datatype this construction doesn't apply.
+        // Return an unmatch and let the caller handle it
+        _ => return Err(ParsingError::Unmatched),
+    };
     current_pos = next_pos;
 
     let (identifier, next_pos) =
@@ -151,6 +190,9 @@ fn parse_param_definition(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Para
             Err(ParsingError::Err(err)) => {
                 return Err(ParsingError::Err(err));
             }
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next));
+            }
             // However, if we fail to parse an identifier, it's an error
             Err(ParsingError::Mismatch(t)) => {
                 let label = ErrorLabel {
@@ -168,15 +210,15 @@ fn parse_param_definition(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Para
                 return Err(ParsingError::Err(econtainer));
             }
             Err(ParsingError::Unmatched) => {
-                let datatype_token = &tokens[pos];
+                let (datatype_start, datatype_end) = datatype.get_position();
                 let label = ErrorLabel {
                     message: String::from("Expected an identifier after this datatype"),
-                    start: datatype_token.position,
-                    end: datatype_token.get_end_position(),
+                    start: datatype_start,
+                    end: datatype_end,
                 };
                 let econtainer = ErrorContainer {
                     error_code: SYNTAX_INVALID_PARAMETER_DECLARATION,
-                    error_offset: datatype_token.get_end_position(),
+                    error_offset: datatype_end,
                     labels: vec![label],
                     note: None,
                     help: None,
@@ -187,8 +229,9 @@ fn parse_param_definition(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Para
 
     Ok((
         Parameter {
-            identifier: &identifier.value,
-            datatype: &datatype.value,
+            modifier,
+            identifier,
+            datatype,
         },
         next_pos,
     ))
@@ -234,8 +277,8 @@ mod tests {
         assert_eq!(next_pos, 4);
         assert_eq!(result.parameters.len(), 1);
         let first_param = &result.parameters[0];
-        assert_eq!(first_param.datatype, "Int");
-        assert_eq!(first_param.identifier, "x");
+        assert_eq!(first_param.datatype.name.value, "Int");
+        assert_eq!(first_param.identifier.value, "x");
     }
 
     #[test]
@@ -246,8 +289,8 @@ mod tests {
         assert_eq!(next_pos, 5);
         assert_eq!(result.parameters.len(), 1);
         let first_param = &result.parameters[0];
-        assert_eq!(first_param.datatype, "Int");
-        assert_eq!(first_param.identifier, "x");
+        assert_eq!(first_param.datatype.name.value, "Int");
+        assert_eq!(first_param.identifier.value, "x");
     }
 
     #[test]
@@ -258,11 +301,11 @@ mod tests {
         assert_eq!(next_pos, 7);
         assert_eq!(result.parameters.len(), 2);
         let first_param = &result.parameters[0];
-        assert_eq!(first_param.datatype, "Int");
-        assert_eq!(first_param.identifier, "x");
+        assert_eq!(first_param.datatype.name.value, "Int");
+        assert_eq!(first_param.identifier.value, "x");
         let second_param = &result.parameters[1];
-        assert_eq!(second_param.datatype, "String");
-        assert_eq!(second_param.identifier, "y");
+        assert_eq!(second_param.datatype.name.value, "String");
+        assert_eq!(second_param.identifier.value, "y");
     }
 
     #[test]
@@ -273,11 +316,11 @@ mod tests {
         assert_eq!(next_pos, 8);
         assert_eq!(result.parameters.len(), 2);
         let first_param = &result.parameters[0];
-        assert_eq!(first_param.datatype, "Int");
-        assert_eq!(first_param.identifier, "x");
+        assert_eq!(first_param.datatype.name.value, "Int");
+        assert_eq!(first_param.identifier.value, "x");
         let second_param = &result.parameters[1];
-        assert_eq!(second_param.datatype, "String");
-        assert_eq!(second_param.identifier, "y");
+        assert_eq!(second_param.datatype.name.value, "String");
+        assert_eq!(second_param.identifier.value, "y");
     }
 
     #[test]
@@ -288,11 +331,11 @@ mod tests {
         assert_eq!(next_pos, 11);
         assert_eq!(result.parameters.len(), 2);
         let first_param = &result.parameters[0];
-        assert_eq!(first_param.datatype, "Int");
-        assert_eq!(first_param.identifier, "x");
+        assert_eq!(first_param.datatype.name.value, "Int");
+        assert_eq!(first_param.identifier.value, "x");
         let second_param = &result.parameters[1];
-        assert_eq!(second_param.datatype, "String");
-        assert_eq!(second_param.identifier, "y");
+        assert_eq!(second_param.datatype.name.value, "String");
+        assert_eq!(second_param.identifier.value, "y");
     }
 
     #[test]
@@ -416,4 +459,36 @@ mod params_tests {
             _ => panic!("Expected a ParsingError::Err"),
         }
     }
+
+    #[test]
+    fn should_parse_param_with_modifier() {
+        let tokens = get_tokens(&String::from("(val Int x, var String y, Bool z)")).unwrap();
+        let (result, _) = parse_params_list(&tokens, 0).unwrap();
+
+        let modifiers: Vec<_> = result
+            .parameters
+            .iter()
+            .map(|p| p.modifier.map(|m| m.value.as_str()))
+            .collect();
+        assert_eq!(vec![Some("val"), Some("var"), None], modifiers);
+        assert_eq!("x", result.parameters[0].identifier.value);
+        assert_eq!("Int", result.parameters[0].datatype.name.value);
+    }
+
+    #[test]
+    fn should_fail_on_modifier_without_datatype() {
+        let tokens = get_tokens(&String::from("val x")).unwrap();
+        let result = parse_param_definition(&tokens, 0);
+
+        match result {
+            Err(ParsingError::Err(err)) => {
+                assert_eq!(SYNTAX_INVALID_PARAMETER_DECLARATION, err.error_code);
+                assert_eq!(
+                    "Expected a datatype after this `val`",
+                    err.labels[0].message
+                );
+            }
+            _ => panic!("Expected a ParsingError::Err"),
+        }
+    }
 }
@@ -12,11 +12,15 @@ use ast::ModuleAST;
 
 use self::parseable::{Parseable, ParsingError, ParsingResult};
 
-/// Builds the Misti AST from a vector of tokens
-pub fn build_ast<'a>(tokens: &'a Vec<Token>) -> Result<ModuleAST, MistiError> {
+/// Builds the Misti AST from a vector of tokens.
+///
+/// The parser recovers from syntax errors, so all the errors
+/// found in the module are returned.
+pub fn build_ast<'a>(tokens: &'a Vec<Token>) -> Result<ModuleAST, Vec<MistiError>> {
     match ModuleAST::try_parse(tokens, 0) {
         Ok((module, _)) => Ok(module),
-        Err(ParsingError::Err(error)) => Err(error),
+        Err(ParsingError::Err(error)) => Err(vec![error]),
+        Err(ParsingError::Recovered(errors, _)) => Err(errors),
         _ => {
             // This shouldn't happen. The module parser returns an error if it finds nothing to parse.
             unreachable!("Illegal state during parsing: The Module parse should always return a result or error")
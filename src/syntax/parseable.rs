@@ -16,6 +16,12 @@ pub enum ParsingError<'a> {
     /// For example, when parsing a function declaration
     /// the `fun` token is found, but then no identifier
     Err(ErrorContainer),
+    /// One or more errors were found, but the parser was able to
+    /// skip over them and continue until the position `usize`.
+    ///
+    /// This is returned by the parsers that do error recovery
+    /// (modules and blocks), and by the parsers that contain them.
+    Recovered(Vec<ErrorContainer>, usize),
 }
 
 /// Represents a type that can be parsed using Recursive Descent
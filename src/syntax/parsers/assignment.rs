@@ -1,10 +1,12 @@
+use std::cell::Cell;
+
 use crate::{
     error_handling::{error_messages::SYNTAX_INCOMPLETE_STATEMENT, ErrorContainer, ErrorLabel},
     lexic::token::{self, TokenType},
     syntax::{
-        ast::{Assignment, Expression},
+        ast::{Assignment, Expression, Identifier},
         parseable::{self, Parseable, ParsingError},
-        utils::{parse_token_type, try_many_operator},
+        utils::{parse_token_type, try_many_operator, try_operator},
     },
 };
 
@@ -17,11 +19,33 @@ impl<'a> Parseable<'a> for Assignment<'a> {
         current_pos: usize,
     ) -> parseable::ParsingResult<'a, Self::Item> {
         // parse the target identifier
-        let (identifier, next) = match parse_token_type(tokens, current_pos, TokenType::Identifier)
-        {
-            Ok(tuple) => tuple,
-            _ => return Err(ParsingError::Unmatched),
-        };
+        let (mut identifier, mut next) =
+            match parse_token_type(tokens, current_pos, TokenType::Identifier) {
+                Ok(tuple) => tuple,
+                _ => return Err(ParsingError::Unmatched),
+            };
+
+        // parse the properties accessed, e.g. `self.position.x`.
+        // The last one is assigned, the others form the object
+        let mut object: Option<Expression> = None;
+        while let (Ok((dot, after_dot)), Some(property)) = (
+            try_operator(tokens, next, String::from(".")),
+            tokens.get(next + 1),
+        ) {
+            if property.token_type != TokenType::Identifier {
+                break;
+            }
+
+            let previous = Expression::Identifier(Identifier::new(identifier));
+            object = Some(match object {
+                Some(object) => {
+                    Expression::BinaryOperator(Box::new(object), Box::new(previous), dot)
+                }
+                None => previous,
+            });
+            identifier = property;
+            next = after_dot + 1;
+        }
 
         // parse the equal sign
         let assignment_operators = vec![
@@ -91,8 +115,11 @@ impl<'a> Parseable<'a> for Assignment<'a> {
 
         // Build and return the assignment object
         let assignment = Assignment {
+            object: object.map(Box::new),
             identifier,
+            operator: equal_operator,
             expression: Box::new(expression),
+            to_float: Cell::new(false),
         };
 
         Ok((assignment, next))
@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use crate::{
     error_handling::{
         error_messages::{SYNTAX_INCOMPLETE_STATEMENT, SYNTAX_INVALID_VARIABLE_DECLARATION},
@@ -5,7 +7,7 @@ use crate::{
     },
     lexic::token::{Token, TokenType},
     syntax::{
-        ast::{var_binding::VariableBinding, Expression},
+        ast::{datatype::Datatype, var_binding::VariableBinding, Expression, Positionable},
         parseable::{Parseable, ParsingError, ParsingResult},
         utils::{parse_terminator, parse_token_type, try_operator},
     },
@@ -36,8 +38,9 @@ impl<'a> Parseable<'a> for VariableBinding<'a> {
         /*
          * datatype
          */
-        let (datatype, next_pos) = match parse_token_type(tokens, next_pos, TokenType::Datatype) {
+        let (datatype, next_pos) = match Datatype::try_parse(tokens, next_pos) {
             Ok((t, next)) => (Some(t), next),
+            Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
             _ => (None, next_pos),
         };
 
@@ -53,6 +56,14 @@ impl<'a> Parseable<'a> for VariableBinding<'a> {
         let (identifier, next_pos) = match parse_token_type(tokens, next_pos, TokenType::Identifier)
         {
             Ok((t, n)) => (t, n),
+            // A datatype followed by a paren is a class instantiation, e.g. `Point(1, 2)`,
+            // and followed by `::` is an enum variant, e.g. `Shape::Circle(1.0)`
+            Err(ParsingError::Mismatch(token))
+                if binding_token.is_none()
+                    && (token.token_type == TokenType::LeftParen || token.value == "::") =>
+            {
+                return Err(ParsingError::Unmatched)
+            }
             Err(ParsingError::Mismatch(token)) => {
                 // The parser found a token, but it's not an identifier
                 let label = ErrorLabel {
@@ -71,7 +82,7 @@ impl<'a> Parseable<'a> for VariableBinding<'a> {
             }
             _ => {
                 // The parser didn't find an Identifier after VAL/VAR or the Datatype
-                match (binding_token, datatype) {
+                match (binding_token, &datatype) {
                     (Some(binding_token), None) => {
                         let label = ErrorLabel {
                             message: format!(
@@ -90,17 +101,18 @@ impl<'a> Parseable<'a> for VariableBinding<'a> {
                         };
                         return Err(ParsingError::Err(econtainer));
                     }
-                    (_, Some(datatype_token)) => {
+                    (_, Some(datatype)) => {
+                        let (datatype_start, datatype_end) = datatype.get_position();
                         let label = ErrorLabel {
                             message: String::from(
                                 "There should be an identifier after this datatype",
                             ),
-                            start: datatype_token.position,
-                            end: datatype_token.get_end_position(),
+                            start: datatype_start,
+                            end: datatype_end,
                         };
                         let econtainer = ErrorContainer {
                             error_code: SYNTAX_INVALID_VARIABLE_DECLARATION,
-                            error_offset: datatype_token.position,
+                            error_offset: datatype_start,
                             labels: vec![label],
                             note: None,
                             help: None,
@@ -163,6 +175,7 @@ impl<'a> Parseable<'a> for VariableBinding<'a> {
          */
         let (expression, next_pos) = match Expression::try_parse(tokens, next_pos) {
             Ok((exp, next)) => (exp, next),
+            Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
             _ => {
                 let label = ErrorLabel {
                     message: String::from("Expected an expression after this equal `=` operator"),
@@ -207,6 +220,7 @@ impl<'a> Parseable<'a> for VariableBinding<'a> {
             identifier: &identifier,
             expression,
             is_mutable: is_var,
+            to_float: Cell::new(false),
         };
 
         Ok((binding, next_pos))
@@ -259,7 +273,7 @@ mod tests {
         let (binding, _) = VariableBinding::try_parse(&tokens, 0).unwrap();
 
         assert!(!binding.is_mutable);
-        assert_eq!("Int", binding.datatype.unwrap().value);
+        assert_eq!("Int", binding.datatype.unwrap().name.value);
         assert_eq!("identifier", binding.identifier.value);
     }
 
@@ -270,7 +284,7 @@ mod tests {
 
         assert!(binding.is_mutable);
         assert!(binding.datatype.is_some());
-        assert_eq!("Int", binding.datatype.unwrap().value);
+        assert_eq!("Int", binding.datatype.unwrap().name.value);
         assert_eq!("identifier", binding.identifier.value);
     }
 
@@ -281,7 +295,7 @@ mod tests {
 
         assert!(!binding.is_mutable);
         assert!(binding.datatype.is_some());
-        assert_eq!("Int", binding.datatype.unwrap().value);
+        assert_eq!("Int", binding.datatype.unwrap().name.value);
         assert_eq!("identifier", binding.identifier.value);
     }
 
@@ -4,13 +4,16 @@ use crate::{
     syntax::{
         ast::{Block, BlockMember, Expression, Statement},
         parseable::{Parseable, ParsingError, ParsingResult},
-        utils::{parse_token_type, Tokenizer},
+        utils::{parse_token_type, synchronize, Tokenizer},
     },
 };
 
 impl<'a> Parseable<'a> for Block<'a> {
     type Item = Block<'a>;
 
+    /// Parses a block of code. Errors in its members are recovered
+    /// from the same way as in `ModuleAST`, and are returned
+    /// with `ParsingError::Recovered` once the block is closed.
     fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
         let mut current_pos = current_pos;
 
@@ -20,6 +23,7 @@ impl<'a> Parseable<'a> for Block<'a> {
 
         // Parse BlockMember = Statement | Expression
         let mut members = Vec::<BlockMember>::new();
+        let mut errors = Vec::<ErrorContainer>::new();
 
         let tokens_len = tokens.len();
 
@@ -33,8 +37,14 @@ impl<'a> Parseable<'a> for Block<'a> {
                     continue;
                 }
                 Err(ParsingError::Err(error)) => {
-                    // TODO: Better error handling, write a better error message
-                    return Err(ParsingError::Err(error));
+                    errors.push(error);
+                    current_pos = synchronize(tokens, current_pos);
+                    continue;
+                }
+                Err(ParsingError::Recovered(inner_errors, next_pos)) => {
+                    errors.extend(inner_errors);
+                    current_pos = next_pos;
+                    continue;
                 }
                 _ => {}
             };
@@ -47,8 +57,14 @@ impl<'a> Parseable<'a> for Block<'a> {
                     continue;
                 }
                 Err(ParsingError::Err(error)) => {
-                    // TODO: Better error handling, write a better error message
-                    return Err(ParsingError::Err(error));
+                    errors.push(error);
+                    current_pos = synchronize(tokens, current_pos);
+                    continue;
+                }
+                Err(ParsingError::Recovered(inner_errors, next_pos)) => {
+                    errors.extend(inner_errors);
+                    current_pos = next_pos;
+                    continue;
                 }
                 _ => {}
             }
@@ -64,6 +80,14 @@ impl<'a> Parseable<'a> for Block<'a> {
             match parse_token_type(tokens, current_pos, TokenType::RightBrace) {
                 Ok((t, next)) => (t, next),
                 Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+                Err(ParsingError::Recovered(errs, _)) => {
+                    return Err(ParsingError::Recovered(errs, current_pos))
+                }
+                // The block is left open by an error in its members, e.g. in
+                // a nested match, so that error is reported instead
+                Err(ParsingError::Mismatch(_)) if !errors.is_empty() => {
+                    return Err(ParsingError::Err(errors.remove(0)))
+                }
                 Err(ParsingError::Mismatch(t)) => {
                     let label = ErrorLabel {
                         message: String::from("Expected a closing brace `}` here"),
@@ -103,6 +127,10 @@ impl<'a> Parseable<'a> for Block<'a> {
             };
         current_pos = next_pos;
 
+        if !errors.is_empty() {
+            return Err(ParsingError::Recovered(errors, current_pos));
+        }
+
         let block = Block {
             members,
             start: opening_brace.position,
@@ -204,4 +232,20 @@ mod tests {
 
         assert_eq!(block.members.len(), 1);
     }
+
+    #[test]
+    fn should_report_errors_that_leave_the_block_open() {
+        let tokens = get_tokens(&String::from(
+            "{\n    val m = match 1\n    case 1 {\n        val t = 1\n        t\n    }\n    else { 2 }\n}",
+        ))
+        .unwrap();
+
+        let Err(ParsingError::Err(error)) = Block::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+        assert_eq!(
+            "The arms of a match can only contain an expression",
+            error.labels[0].message
+        );
+    }
 }
@@ -0,0 +1,404 @@
+use crate::{
+    error_handling::{
+        error_messages::SYNTAX_INVALID_CLASS_DECLARATION, ErrorContainer, ErrorLabel,
+    },
+    lexic::token::{Token, TokenType},
+    syntax::{
+        ast::{
+            class::{ClassBody, ClassDeclaration, ClassMember},
+            var_binding::VariableBinding,
+            FunctionDeclaration,
+        },
+        functions::params_list::parse_params_list,
+        parseable::{Parseable, ParsingError, ParsingResult},
+        utils::{parse_token_type, synchronize, try_operator, Tokenizer},
+    },
+};
+
+/// Parses a class declaration.
+///
+/// ```ebnf
+/// class declaration = "class", datatype, params list?, interfaces?, class body?;
+///
+/// interfaces = ":", datatype, (",", datatype)*;
+///
+/// class body = "{", (variable binding | function declaration)*, "}";
+/// ```
+impl<'a> Parseable<'a> for ClassDeclaration<'a> {
+    type Item = ClassDeclaration<'a>;
+
+    fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
+        // `class` keyword
+        let (keyword, next_pos) = match parse_token_type(tokens, current_pos, TokenType::CLASS) {
+            Ok(tuple) => tuple,
+            _ => return Err(ParsingError::Unmatched),
+        };
+
+        // The name of the class
+        let (identifier, next_pos) = match parse_token_type(tokens, next_pos, TokenType::Datatype) {
+            Ok(tuple) => tuple,
+            Err(ParsingError::Mismatch(wrong_token)) => {
+                return Err(class_error(
+                    "Expected the name of the class here, starting with an uppercase letter",
+                    wrong_token.position,
+                    wrong_token.get_end_position(),
+                ))
+            }
+            _ => {
+                return Err(class_error(
+                    "Expected the name of the class after this `class` keyword",
+                    keyword.position,
+                    keyword.get_end_position(),
+                ))
+            }
+        };
+
+        // The primary constructor, optional
+        let (constructor, next_pos) = match parse_params_list(tokens, next_pos) {
+            Ok((params, next)) => (Some(params), next),
+            Err(ParsingError::Unmatched) | Err(ParsingError::Mismatch(_)) => (None, next_pos),
+            Err(error) => return Err(error),
+        };
+
+        // The interfaces, optional
+        let (interfaces, next_pos) = match try_operator(tokens, next_pos, String::from(":")) {
+            Ok((colon, next)) => parse_interfaces(tokens, colon, next)?,
+            Err(_) => (vec![], next_pos),
+        };
+
+        // The body, optional
+        let (body, next_pos) = match parse_class_body(tokens, next_pos) {
+            Ok((body, next)) => (Some(body), next),
+            Err(ParsingError::Unmatched) | Err(ParsingError::Mismatch(_)) => (None, next_pos),
+            Err(error) => return Err(error),
+        };
+
+        let class = ClassDeclaration {
+            keyword,
+            identifier,
+            constructor,
+            interfaces,
+            body,
+        };
+        Ok((class, next_pos))
+    }
+}
+
+/// Parses the names of the interfaces a class implements, after the `colon`
+fn parse_interfaces<'a>(
+    tokens: &'a Vec<Token>,
+    colon: &'a Token,
+    current_pos: usize,
+) -> ParsingResult<'a, Vec<&'a Token>> {
+    let mut interfaces = Vec::new();
+    let mut current_pos = current_pos;
+    let mut previous_token = colon;
+
+    loop {
+        let (interface, next_pos) = match parse_token_type(tokens, current_pos, TokenType::Datatype)
+        {
+            Ok(tuple) => tuple,
+            Err(ParsingError::Mismatch(wrong_token)) => {
+                return Err(class_error(
+                    "Expected the name of an interface here, starting with an uppercase letter",
+                    wrong_token.position,
+                    wrong_token.get_end_position(),
+                ))
+            }
+            _ => {
+                return Err(class_error(
+                    &format!(
+                        "Expected the name of an interface after this `{}`",
+                        previous_token.value
+                    ),
+                    previous_token.position,
+                    previous_token.get_end_position(),
+                ))
+            }
+        };
+        interfaces.push(interface);
+        current_pos = next_pos;
+
+        match parse_token_type(tokens, current_pos, TokenType::Comma) {
+            Ok((comma, next_pos)) => {
+                previous_token = comma;
+                current_pos = next_pos;
+            }
+            _ => return Ok((interfaces, current_pos)),
+        }
+    }
+}
+
+/// Parses the properties & methods of a class, between braces.
+///
+/// Errors in its members are recovered from the same way as in a `Block`
+fn parse_class_body<'a>(
+    tokens: &'a Vec<Token>,
+    current_pos: usize,
+) -> ParsingResult<'a, ClassBody<'a>> {
+    let (opening_brace, mut current_pos) =
+        parse_token_type(tokens, current_pos, TokenType::LeftBrace)?;
+
+    let mut members = Vec::<ClassMember>::new();
+    let mut errors = Vec::<ErrorContainer>::new();
+
+    // Minus one because last token is EOF
+    while current_pos < tokens.len() - 1 {
+        // Attempt to parse a property
+        match VariableBinding::try_parse(tokens, current_pos) {
+            Ok((field, next_pos)) => {
+                members.push(ClassMember::Field(field));
+                current_pos = next_pos;
+                continue;
+            }
+            Err(ParsingError::Err(error)) => {
+                errors.push(error);
+                current_pos = synchronize(tokens, current_pos);
+                continue;
+            }
+            _ => {}
+        }
+
+        // Attempt to parse a method
+        match FunctionDeclaration::try_parse(tokens, current_pos) {
+            Ok((method, next_pos)) => {
+                members.push(ClassMember::Method(method));
+                current_pos = next_pos;
+                continue;
+            }
+            Err(ParsingError::Err(error)) => {
+                errors.push(error);
+                current_pos = synchronize(tokens, current_pos);
+                continue;
+            }
+            Err(ParsingError::Recovered(inner_errors, next_pos)) => {
+                errors.extend(inner_errors);
+                current_pos = next_pos;
+                continue;
+            }
+            _ => {}
+        }
+
+        // If we reached this point we didn't match any member,
+        // the parsing of "}" will deal with any incorrect tokens
+        break;
+    }
+
+    // Parse closing brace
+    let (closing_brace, next_pos) = match parse_token_type(
+        tokens,
+        current_pos,
+        TokenType::RightBrace,
+    ) {
+        Ok(tuple) => tuple,
+        Err(ParsingError::Mismatch(wrong_token)) => {
+            let label = ErrorLabel {
+                message: String::from("Expected a property, a method or a closing brace `}` here"),
+                start: wrong_token.position,
+                end: wrong_token.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_CLASS_DECLARATION,
+                    error_offset: wrong_token.position,
+                    labels: vec![label],
+                    note: Some(String::from(
+                        "The body of a class can only contain properties, e.g. `val Int count = 0`, and methods",
+                    )),
+                    help: None,
+                };
+            return Err(ParsingError::Err(econtainer));
+        }
+        _ => {
+            let label_1 = ErrorLabel {
+                message: String::from("The body of the class starts here"),
+                start: opening_brace.position,
+                end: opening_brace.get_end_position(),
+            };
+            let label_2_pos = tokens.code_position_from_idx(current_pos);
+            let label_2 = ErrorLabel {
+                message: String::from("The code ends here without closing the class"),
+                start: label_2_pos,
+                end: label_2_pos + 1,
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_CLASS_DECLARATION,
+                error_offset: label_2_pos,
+                labels: vec![label_1, label_2],
+                note: None,
+                help: None,
+            };
+            return Err(ParsingError::Err(econtainer));
+        }
+    };
+
+    if !errors.is_empty() {
+        return Err(ParsingError::Recovered(errors, next_pos));
+    }
+
+    let body = ClassBody {
+        start: opening_brace.position,
+        end: closing_brace.position,
+        members,
+    };
+    Ok((body, next_pos))
+}
+
+fn class_error<'a>(message: &str, start: usize, end: usize) -> ParsingError<'a> {
+    let label = ErrorLabel {
+        message: String::from(message),
+        start,
+        end,
+    };
+    let econtainer = ErrorContainer {
+        error_code: SYNTAX_INVALID_CLASS_DECLARATION,
+        error_offset: start,
+        labels: vec![label],
+        note: None,
+        help: None,
+    };
+    ParsingError::Err(econtainer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    fn parse(input: &str) -> Vec<Token> {
+        get_tokens(&String::from(input)).unwrap()
+    }
+
+    #[test]
+    fn should_parse_class_without_body() {
+        let tokens = parse("class Point(val Int x, var Int y)");
+        let (class, next) = ClassDeclaration::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!("Point", class.identifier.value);
+        assert_eq!(2, class.constructor.unwrap().parameters.len());
+        assert!(class.body.is_none());
+        assert_eq!(11, next);
+    }
+
+    #[test]
+    fn should_parse_implemented_interfaces() {
+        let tokens = parse("class Point(val Int x) : Printable, Shape {\n}");
+        let (class, _) = ClassDeclaration::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(2, class.interfaces.len());
+        assert_eq!("Shape", class.interfaces[1].value);
+        assert!(class.body.is_some());
+
+        let tokens = parse("class Empty : Marker");
+        let (class, next) = ClassDeclaration::try_parse(&tokens, 0).unwrap();
+        assert_eq!("Marker", class.interfaces[0].value);
+        assert_eq!(4, next);
+    }
+
+    #[test]
+    fn should_error_on_missing_interfaces() {
+        let tokens = parse("class Point : Printable,");
+        let Err(ParsingError::Err(error)) = ClassDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+        assert_eq!(
+            "Expected the name of an interface after this `,`",
+            error.labels[0].message
+        );
+
+        let tokens = parse("class Point : printable");
+        let Err(ParsingError::Err(error)) = ClassDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+        assert_eq!(14, error.error_offset);
+    }
+
+    #[test]
+    fn should_parse_class_members() {
+        let tokens = parse(
+            "class Counter {\n    var Int count = 0\n\n    fun increment() {\n        self.count += 1\n    }\n}",
+        );
+        let (class, _) = ClassDeclaration::try_parse(&tokens, 0).unwrap();
+
+        assert!(class.constructor.is_none());
+        let body = class.body.unwrap();
+        assert_eq!(14, body.start);
+        match body.members.as_slice() {
+            [ClassMember::Field(field), ClassMember::Method(method)] => {
+                assert_eq!("count", field.identifier.value);
+                assert!(field.is_mutable);
+                assert_eq!("increment", method.identifier.value);
+            }
+            _ => panic!("Expected a field and a method"),
+        }
+    }
+
+    #[test]
+    fn should_not_parse_without_class_keyword() {
+        let tokens = parse("Point(1, 2)");
+        assert!(matches!(
+            ClassDeclaration::try_parse(&tokens, 0),
+            Err(ParsingError::Unmatched)
+        ));
+    }
+
+    #[test]
+    fn should_error_on_lowercase_name() {
+        let tokens = parse("class point()");
+        let Err(ParsingError::Err(error)) = ClassDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(SYNTAX_INVALID_CLASS_DECLARATION, error.error_code);
+        assert_eq!(6, error.error_offset);
+        assert_eq!(
+            "Expected the name of the class here, starting with an uppercase letter",
+            error.labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_error_on_missing_name() {
+        let tokens = parse("class");
+        let Err(ParsingError::Err(error)) = ClassDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "Expected the name of the class after this `class` keyword",
+            error.labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_error_on_statements_in_body() {
+        let tokens = parse("class Point {\n    print(1)\n}");
+        let Err(ParsingError::Err(error)) = ClassDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(SYNTAX_INVALID_CLASS_DECLARATION, error.error_code);
+        assert_eq!(18, error.error_offset);
+    }
+
+    #[test]
+    fn should_error_on_unclosed_body() {
+        let tokens = parse("class Point {\n    fun f() {}\n");
+        let Err(ParsingError::Err(error)) = ClassDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "The code ends here without closing the class",
+            error.labels[1].message
+        );
+    }
+
+    #[test]
+    fn should_recover_from_errors_in_members() {
+        let tokens = parse("class Point {\n    val x = \n    fun f() {}\n}");
+        match ClassDeclaration::try_parse(&tokens, 0) {
+            Err(ParsingError::Recovered(errors, _)) => assert_eq!(1, errors.len()),
+            _ => panic!("Expected the errors to be recovered"),
+        }
+    }
+}
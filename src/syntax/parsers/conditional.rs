@@ -22,6 +22,9 @@ impl<'a> Parseable<'a> for Conditional<'a> {
         let (if_expression, next) = match Expression::try_parse(tokens, next) {
             Ok(tuple) => tuple,
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(wrong_token)) => {
                 let label = ErrorLabel {
                     message: String::from("Expected a Bool expression here"),
@@ -55,9 +58,24 @@ impl<'a> Parseable<'a> for Conditional<'a> {
         };
 
         // if block
+        // Errors found inside the blocks. The parser recovers from them,
+        // so the rest of the conditional is still parsed.
+        let mut errors = Vec::new();
+
         let (if_block, next) = match Block::try_parse(tokens, next) {
             Ok(t) => t,
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(block_errors, next)) => {
+                // Keep parsing the rest of the conditional with an empty block,
+                // the errors are reported once the whole conditional is parsed
+                errors.extend(block_errors);
+                let empty_block = Block {
+                    start: next,
+                    end: next,
+                    members: vec![],
+                };
+                (empty_block, next)
+            }
             Err(ParsingError::Mismatch(wrong_token)) => {
                 let label = ErrorLabel {
                     message: String::from("Expected a block here, after the condition"),
@@ -118,6 +136,9 @@ impl<'a> Parseable<'a> for Conditional<'a> {
             let (condition, next) = match Expression::try_parse(tokens, next) {
                 Ok(tuple) => tuple,
                 Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+                Err(ParsingError::Recovered(errors, next)) => {
+                    return Err(ParsingError::Recovered(errors, next))
+                }
                 Err(ParsingError::Mismatch(wrong_token)) => {
                     let label = ErrorLabel {
                         message: String::from("Expected a Bool expression here"),
@@ -154,6 +175,17 @@ impl<'a> Parseable<'a> for Conditional<'a> {
             let (block, next) = match Block::try_parse(tokens, next) {
                 Ok(t) => t,
                 Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+                Err(ParsingError::Recovered(block_errors, next)) => {
+                    // Keep parsing the rest of the conditional with an empty block,
+                    // the errors are reported once the whole conditional is parsed
+                    errors.extend(block_errors);
+                    let empty_block = Block {
+                        start: next,
+                        end: next,
+                        members: vec![],
+                    };
+                    (empty_block, next)
+                }
                 Err(ParsingError::Mismatch(wrong_token)) => {
                     let label = ErrorLabel {
                         message: String::from("Expected a block here, after the condition"),
@@ -203,6 +235,17 @@ impl<'a> Parseable<'a> for Conditional<'a> {
                     let (block, next) = match Block::try_parse(tokens, next) {
                         Ok(t) => t,
                         Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+                        Err(ParsingError::Recovered(block_errors, next)) => {
+                            // Keep parsing the rest of the conditional with an empty block,
+                            // the errors are reported once the whole conditional is parsed
+                            errors.extend(block_errors);
+                            let empty_block = Block {
+                                start: next,
+                                end: next,
+                                members: vec![],
+                            };
+                            (empty_block, next)
+                        }
                         Err(ParsingError::Mismatch(wrong_token)) => {
                             let label = ErrorLabel {
                                 message: String::from("Expected a block here, after the condition"),
@@ -243,6 +286,10 @@ impl<'a> Parseable<'a> for Conditional<'a> {
             }
         };
 
+        if !errors.is_empty() {
+            return Err(ParsingError::Recovered(errors, next));
+        }
+
         // return
 
         let result = Conditional {
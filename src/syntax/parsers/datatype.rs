@@ -0,0 +1,462 @@
+use std::cell::Cell;
+
+use crate::{
+    error_handling::{error_messages::SYNTAX_INVALID_DATATYPE, ErrorContainer, ErrorLabel},
+    lexic::token::{Token, TokenType},
+    syntax::{
+        ast::{datatype::Datatype, Positionable},
+        parseable::{Parseable, ParsingError, ParsingResult},
+        utils::{parse_token_type, try_operator, Tokenizer},
+    },
+};
+
+impl<'a> Parseable<'a> for Datatype<'a> {
+    type Item = Datatype<'a>;
+
+    /// Parses a datatype, with its type parameters if it's generic,
+    /// and the other datatypes if it's a union.
+    ///
+    /// ```ebnf
+    /// datatype = single datatype, ("|", single datatype)*;
+    ///
+    /// single datatype = "?"?, Datatype, ("[", datatype, (",", datatype)*, ","?, "]")?
+    ///                 | "?"?, function datatype;
+    ///
+    /// function datatype = "(", (datatype, (",", datatype)*, ","?)?, ")", "->", datatype;
+    /// ```
+    fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
+        let (mut datatype, mut current_pos) = parse_single_datatype(tokens, current_pos)?;
+
+        // Union datatype
+        while let Ok((pipe, next_pos)) = try_operator(tokens, current_pos, "|".into()) {
+            match parse_single_datatype(tokens, next_pos) {
+                Ok((alternative, next_pos)) => {
+                    datatype.alternatives.push(alternative);
+                    current_pos = next_pos;
+                }
+                Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+                Err(_) => return Err(ParsingError::Err(missing_datatype_error(pipe))),
+            }
+        }
+
+        Ok((datatype, current_pos))
+    }
+}
+
+/// Parses a datatype that is not a union, with its `?` if it's nullable
+fn parse_single_datatype(tokens: &Vec<Token>, current_pos: usize) -> ParsingResult<Datatype> {
+    // Nullable datatype. Like the name of a datatype, the `?` may be
+    // preceded by new lines, e.g. in a binding after a declaration
+    let question_pos = match tokens.get_significant(current_pos) {
+        Some((_, pos)) => pos,
+        None => current_pos,
+    };
+    let Ok((question_mark, next_pos)) = try_operator(tokens, question_pos, "?".into()) else {
+        return parse_datatype(tokens, current_pos, None);
+    };
+
+    match parse_datatype(tokens, next_pos, Some(question_mark)) {
+        Ok(tuple) => Ok(tuple),
+        Err(ParsingError::Err(e)) => Err(ParsingError::Err(e)),
+        Err(_) => Err(ParsingError::Err(missing_datatype_error(question_mark))),
+    }
+}
+
+/// Builds the error of an operator, `?` or `|`, not followed by a datatype
+fn missing_datatype_error(operator: &Token) -> ErrorContainer {
+    let label = ErrorLabel {
+        message: format!("Expected a datatype after this `{}`", operator.value),
+        start: operator.position,
+        end: operator.get_end_position(),
+    };
+    ErrorContainer {
+        error_code: SYNTAX_INVALID_DATATYPE,
+        error_offset: operator.position,
+        labels: vec![label],
+        note: None,
+        help: None,
+    }
+}
+
+/// Parses a datatype after its `?`, if any
+fn parse_datatype<'a>(
+    tokens: &'a Vec<Token>,
+    current_pos: usize,
+    nullable: Option<&'a Token>,
+) -> ParsingResult<'a, Datatype<'a>> {
+    if let Ok((opening_paren, next_pos)) =
+        parse_token_type(tokens, current_pos, TokenType::LeftParen)
+    {
+        return parse_function_datatype(tokens, next_pos, nullable, opening_paren);
+    }
+
+    let (name, next_pos) = parse_token_type(tokens, current_pos, TokenType::Datatype)?;
+
+    // The opening bracket must be right after the name
+    let Some(opening_bracket) = tokens
+        .get(next_pos)
+        .filter(|t| t.token_type == TokenType::LeftBracket)
+    else {
+        let datatype = Datatype {
+            nullable,
+            name,
+            is_declared: Cell::new(false),
+            type_params: vec![],
+            return_type: None,
+            alternatives: vec![],
+            end: name.get_end_position(),
+        };
+        return Ok((datatype, next_pos));
+    };
+
+    let mut type_params = Vec::new();
+    let mut current_pos = next_pos + 1;
+    loop {
+        // Closing bracket, after the type parameters or a trailing comma
+        if let Some(t) = tokens.get(current_pos) {
+            if t.token_type == TokenType::RightBracket && !type_params.is_empty() {
+                let datatype = Datatype {
+                    nullable,
+                    name,
+                    is_declared: Cell::new(false),
+                    type_params,
+                    return_type: None,
+                    alternatives: vec![],
+                    end: t.get_end_position(),
+                };
+                return Ok((datatype, current_pos + 1));
+            }
+        }
+
+        // Type parameter
+        let (type_param, next_pos) = match Datatype::try_parse(tokens, current_pos) {
+            Ok(tuple) => tuple,
+            Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
+            Err(ParsingError::Mismatch(t)) => {
+                let label = ErrorLabel {
+                    message: String::from("Expected a datatype here"),
+                    start: t.position,
+                    end: t.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_DATATYPE,
+                    error_offset: t.position,
+                    labels: vec![label],
+                    note: None,
+                    help: None,
+                };
+                return Err(ParsingError::Err(econtainer));
+            }
+            Err(ParsingError::Unmatched) => {
+                let label = ErrorLabel {
+                    message: String::from("Expected a datatype after this bracket"),
+                    start: opening_bracket.position,
+                    end: opening_bracket.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_DATATYPE,
+                    error_offset: opening_bracket.position,
+                    labels: vec![label],
+                    note: None,
+                    help: None,
+                };
+                return Err(ParsingError::Err(econtainer));
+            }
+        };
+        type_params.push(type_param);
+        current_pos = next_pos;
+
+        // Comma or closing bracket
+        match tokens.get(current_pos) {
+            Some(t) if t.token_type == TokenType::Comma => current_pos += 1,
+            Some(t) if t.token_type == TokenType::RightBracket => {}
+            Some(t) => {
+                let label = ErrorLabel {
+                    message: String::from("Expected a comma `,` or a closing bracket `]` here"),
+                    start: t.position,
+                    end: t.get_end_position(),
+                };
+                let label_2 = ErrorLabel {
+                    message: String::from("The type parameters start here"),
+                    start: opening_bracket.position,
+                    end: opening_bracket.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_DATATYPE,
+                    error_offset: t.position,
+                    labels: vec![label, label_2],
+                    note: None,
+                    help: None,
+                };
+                return Err(ParsingError::Err(econtainer));
+            }
+            None => unreachable!("Stream of tokens finished before getting an EOF"),
+        }
+    }
+}
+
+/// Parses a function datatype after its opening parenthesis, e.g. `(Int, Int) -> Int`.
+///
+/// Until the arrow `->` the parenthesis may start an expression
+/// instead, so failing before it is a mismatch, not an error
+fn parse_function_datatype<'a>(
+    tokens: &'a Vec<Token>,
+    current_pos: usize,
+    nullable: Option<&'a Token>,
+    opening_paren: &'a Token,
+) -> ParsingResult<'a, Datatype<'a>> {
+    let mut parameters = Vec::new();
+    let mut current_pos = current_pos;
+    loop {
+        // Closing parenthesis, after the parameters or a trailing comma
+        if let Ok((_, next_pos)) = parse_token_type(tokens, current_pos, TokenType::RightParen) {
+            current_pos = next_pos;
+            break;
+        }
+
+        let Ok((parameter, next_pos)) = Datatype::try_parse(tokens, current_pos) else {
+            return Err(ParsingError::Mismatch(opening_paren));
+        };
+        parameters.push(parameter);
+        current_pos = next_pos;
+
+        // Comma or closing parenthesis
+        match parse_token_type(tokens, current_pos, TokenType::Comma) {
+            Ok((_, next_pos)) => current_pos = next_pos,
+            Err(_) => match parse_token_type(tokens, current_pos, TokenType::RightParen) {
+                Ok((_, next_pos)) => {
+                    current_pos = next_pos;
+                    break;
+                }
+                Err(_) => return Err(ParsingError::Mismatch(opening_paren)),
+            },
+        }
+    }
+
+    let Ok((arrow, next_pos)) = try_operator(tokens, current_pos, "->".into()) else {
+        return Err(ParsingError::Mismatch(opening_paren));
+    };
+
+    // At this point the `->` was matched, so the return type is required
+    let (return_type, next_pos) = match Datatype::try_parse(tokens, next_pos) {
+        Ok(tuple) => tuple,
+        Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+        Err(_) => {
+            let label = ErrorLabel {
+                message: String::from("Expected the return type of the function after this `->`"),
+                start: arrow.position,
+                end: arrow.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_DATATYPE,
+                error_offset: arrow.position,
+                labels: vec![label],
+                note: Some(String::from(
+                    "Functions that don't return a value return Unit, e.g. `(String) -> Unit`",
+                )),
+                help: None,
+            };
+            return Err(ParsingError::Err(econtainer));
+        }
+    };
+
+    let datatype = Datatype {
+        nullable,
+        name: opening_paren,
+        is_declared: Cell::new(false),
+        type_params: parameters,
+        end: return_type.get_position().1,
+        return_type: Some(Box::new(return_type)),
+        alternatives: vec![],
+    };
+    Ok((datatype, next_pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lexic::get_tokens, syntax::ast::Positionable};
+
+    #[test]
+    fn should_parse_simple_datatype() {
+        let tokens = get_tokens(&String::from("Int x")).unwrap();
+        let (datatype, next) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(1, next);
+        assert_eq!("Int", datatype.name.value);
+        assert!(datatype.type_params.is_empty());
+        assert_eq!(3, datatype.end);
+    }
+
+    #[test]
+    fn should_parse_generic_datatype() {
+        let tokens = get_tokens(&String::from("Map[String, Array[Int]] x")).unwrap();
+        let (datatype, next) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(9, next);
+        assert_eq!("Map", datatype.name.value);
+        assert_eq!(2, datatype.type_params.len());
+        assert_eq!("String", datatype.type_params[0].name.value);
+
+        let array = &datatype.type_params[1];
+        assert_eq!("Array", array.name.value);
+        assert_eq!("Int", array.type_params[0].name.value);
+        assert_eq!(23, datatype.end);
+    }
+
+    #[test]
+    fn should_parse_trailing_comma() {
+        let tokens = get_tokens(&String::from("Array[Int,]")).unwrap();
+        let (datatype, next) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(5, next);
+        assert_eq!(1, datatype.type_params.len());
+    }
+
+    #[test]
+    fn should_parse_nullable_datatype() {
+        let tokens = get_tokens(&String::from("?String x")).unwrap();
+        let (datatype, next) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(2, next);
+        assert!(datatype.nullable.is_some());
+        assert_eq!("String", datatype.name.value);
+        assert_eq!((0, 7), datatype.get_position());
+
+        let tokens = get_tokens(&String::from("Array[?Int]")).unwrap();
+        let (datatype, _) = Datatype::try_parse(&tokens, 0).unwrap();
+        assert!(datatype.nullable.is_none());
+        assert!(datatype.type_params[0].nullable.is_some());
+    }
+
+    #[test]
+    fn should_fail_on_question_mark_without_datatype() {
+        let tokens = get_tokens(&String::from("? x")).unwrap();
+        match Datatype::try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => {
+                assert_eq!(SYNTAX_INVALID_DATATYPE, error.error_code);
+                assert_eq!(0, error.error_offset);
+                assert_eq!(
+                    "Expected a datatype after this `?`",
+                    error.labels[0].message
+                );
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn should_parse_union_datatype() {
+        let tokens = get_tokens(&String::from("Int | ?String | Array[Int | Float] x")).unwrap();
+        let (datatype, next) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(11, next);
+        assert_eq!("Int", datatype.name.value);
+        assert_eq!(2, datatype.alternatives.len());
+        assert!(datatype.alternatives[0].nullable.is_some());
+
+        let array = &datatype.alternatives[1];
+        assert_eq!("Array", array.name.value);
+        assert_eq!("Float", array.type_params[0].alternatives[0].name.value);
+        assert_eq!((0, 34), datatype.get_position());
+    }
+
+    #[test]
+    fn should_fail_on_pipe_without_datatype() {
+        let tokens = get_tokens(&String::from("Int | x")).unwrap();
+        match Datatype::try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => {
+                assert_eq!(SYNTAX_INVALID_DATATYPE, error.error_code);
+                assert_eq!(4, error.error_offset);
+                assert_eq!(
+                    "Expected a datatype after this `|`",
+                    error.labels[0].message
+                );
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn should_not_parse_non_datatypes() {
+        let tokens = get_tokens(&String::from("x")).unwrap();
+        assert!(matches!(
+            Datatype::try_parse(&tokens, 0),
+            Err(ParsingError::Mismatch(_))
+        ));
+    }
+
+    #[test]
+    fn should_fail_on_empty_type_params() {
+        let tokens = get_tokens(&String::from("Array[]")).unwrap();
+        match Datatype::try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => {
+                assert_eq!(SYNTAX_INVALID_DATATYPE, error.error_code);
+                assert_eq!(6, error.error_offset);
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn should_fail_on_unclosed_type_params() {
+        let tokens = get_tokens(&String::from("Array[Int x")).unwrap();
+        match Datatype::try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => {
+                assert_eq!(SYNTAX_INVALID_DATATYPE, error.error_code);
+                assert_eq!(10, error.error_offset);
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn should_parse_function_datatype() {
+        let tokens = get_tokens(&String::from("(Int, String) -> Bool f")).unwrap();
+        let (datatype, next) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(7, next);
+        assert_eq!(2, datatype.type_params.len());
+        assert_eq!("String", datatype.type_params[1].name.value);
+        assert_eq!("Bool", datatype.return_type.as_ref().unwrap().name.value);
+        assert_eq!((0, 21), datatype.get_position());
+
+        let tokens = get_tokens(&String::from("?() -> Int | String")).unwrap();
+        let (datatype, _) = Datatype::try_parse(&tokens, 0).unwrap();
+
+        assert!(datatype.nullable.is_some());
+        assert!(datatype.type_params.is_empty());
+        assert!(datatype.alternatives.is_empty());
+        assert_eq!(1, datatype.return_type.unwrap().alternatives.len());
+    }
+
+    #[test]
+    fn should_not_parse_parenthesized_expressions() {
+        let tokens = get_tokens(&String::from("(Point(1, 2)).x")).unwrap();
+        assert!(matches!(
+            Datatype::try_parse(&tokens, 0),
+            Err(ParsingError::Mismatch(_))
+        ));
+
+        let tokens = get_tokens(&String::from("(Int)")).unwrap();
+        assert!(matches!(
+            Datatype::try_parse(&tokens, 0),
+            Err(ParsingError::Mismatch(_))
+        ));
+    }
+
+    #[test]
+    fn should_fail_on_function_datatype_without_return_type() {
+        let tokens = get_tokens(&String::from("(Int) -> x")).unwrap();
+        match Datatype::try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => {
+                assert_eq!(SYNTAX_INVALID_DATATYPE, error.error_code);
+                assert_eq!(6, error.error_offset);
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{
+    error_handling::{error_messages::SYNTAX_INVALID_ENUM_DECLARATION, ErrorContainer, ErrorLabel},
+    lexic::token::{Token, TokenType},
+    syntax::{
+        ast::enum_declaration::{EnumDeclaration, EnumVariant},
+        functions::params_list::parse_params_list,
+        parseable::{Parseable, ParsingError, ParsingResult},
+        utils::{parse_token_type, Tokenizer},
+    },
+};
+
+/// Parses an enum declaration.
+///
+/// ```ebnf
+/// enum declaration = "enum", datatype, "{", enum variant*, "}";
+///
+/// enum variant = datatype, params list?;
+/// ```
+impl<'a> Parseable<'a> for EnumDeclaration<'a> {
+    type Item = EnumDeclaration<'a>;
+
+    fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
+        // `enum` keyword
+        let (keyword, next_pos) = match parse_token_type(tokens, current_pos, TokenType::ENUM) {
+            Ok(tuple) => tuple,
+            _ => return Err(ParsingError::Unmatched),
+        };
+
+        // The name of the enum
+        let (identifier, next_pos) = match parse_token_type(tokens, next_pos, TokenType::Datatype) {
+            Ok(tuple) => tuple,
+            Err(ParsingError::Mismatch(wrong_token)) => {
+                return Err(enum_error(
+                    "Expected the name of the enum here, starting with an uppercase letter",
+                    wrong_token.position,
+                    wrong_token.get_end_position(),
+                ))
+            }
+            _ => {
+                return Err(enum_error(
+                    "Expected the name of the enum after this `enum` keyword",
+                    keyword.position,
+                    keyword.get_end_position(),
+                ))
+            }
+        };
+
+        // The opening brace
+        let (opening_brace, mut current_pos) =
+            match parse_token_type(tokens, next_pos, TokenType::LeftBrace) {
+                Ok(tuple) => tuple,
+                _ => {
+                    return Err(enum_error(
+                        "Expected the variants of the enum after its name, between braces",
+                        identifier.position,
+                        identifier.get_end_position(),
+                    ))
+                }
+            };
+
+        // The variants
+        let mut variants = Vec::new();
+        while let Ok((variant_identifier, next_pos)) =
+            parse_token_type(tokens, current_pos, TokenType::Datatype)
+        {
+            let (payload, next_pos) = match parse_params_list(tokens, next_pos) {
+                Ok((params, next)) => (Some(params), next),
+                Err(ParsingError::Unmatched) | Err(ParsingError::Mismatch(_)) => (None, next_pos),
+                Err(error) => return Err(error),
+            };
+
+            // The fields of a variant are always immutable
+            let modifier = payload
+                .iter()
+                .flat_map(|payload| payload.parameters.iter())
+                .find_map(|field| field.modifier);
+            if let Some(modifier) = modifier {
+                return Err(enum_error(
+                    &format!(
+                        "The fields of a variant can't be declared with `{}`",
+                        modifier.value
+                    ),
+                    modifier.position,
+                    modifier.get_end_position(),
+                ));
+            }
+
+            variants.push(EnumVariant {
+                identifier: variant_identifier,
+                payload,
+            });
+            current_pos = next_pos;
+        }
+
+        // The closing brace
+        let (closing_brace, next_pos) = match parse_token_type(
+            tokens,
+            current_pos,
+            TokenType::RightBrace,
+        ) {
+            Ok(tuple) => tuple,
+            Err(ParsingError::Mismatch(wrong_token)) => {
+                let label = ErrorLabel {
+                    message: String::from("Expected a variant or a closing brace `}` here"),
+                    start: wrong_token.position,
+                    end: wrong_token.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                        error_code: SYNTAX_INVALID_ENUM_DECLARATION,
+                        error_offset: wrong_token.position,
+                        labels: vec![label],
+                        note: None,
+                        help: Some(String::from(
+                            "The variants of an enum start with an uppercase letter, e.g. `Circle(Float radius)`",
+                        )),
+                    };
+                return Err(ParsingError::Err(econtainer));
+            }
+            _ => {
+                let label_1 = ErrorLabel {
+                    message: String::from("The variants of the enum start here"),
+                    start: opening_brace.position,
+                    end: opening_brace.get_end_position(),
+                };
+                let label_2_pos = tokens.code_position_from_idx(current_pos);
+                let label_2 = ErrorLabel {
+                    message: String::from("The code ends here without closing the enum"),
+                    start: label_2_pos,
+                    end: label_2_pos + 1,
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_ENUM_DECLARATION,
+                    error_offset: label_2_pos,
+                    labels: vec![label_1, label_2],
+                    note: None,
+                    help: None,
+                };
+                return Err(ParsingError::Err(econtainer));
+            }
+        };
+
+        let enum_declaration = EnumDeclaration {
+            keyword,
+            identifier,
+            variants,
+            end: closing_brace.position,
+        };
+        Ok((enum_declaration, next_pos))
+    }
+}
+
+fn enum_error<'a>(message: &str, start: usize, end: usize) -> ParsingError<'a> {
+    let label = ErrorLabel {
+        message: String::from(message),
+        start,
+        end,
+    };
+    let econtainer = ErrorContainer {
+        error_code: SYNTAX_INVALID_ENUM_DECLARATION,
+        error_offset: start,
+        labels: vec![label],
+        note: None,
+        help: None,
+    };
+    ParsingError::Err(econtainer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    fn parse(input: &str) -> Vec<Token> {
+        get_tokens(&String::from(input)).unwrap()
+    }
+
+    #[test]
+    fn should_parse_enum() {
+        let tokens = parse("enum Suit {\n    Hearts\n    Spades\n}");
+        let (enum_declaration, next) = EnumDeclaration::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!("Suit", enum_declaration.identifier.value);
+        assert_eq!(2, enum_declaration.variants.len());
+        assert_eq!("Spades", enum_declaration.variants[1].identifier.value);
+        assert!(!enum_declaration.has_payloads());
+        assert_eq!(34, enum_declaration.end);
+        assert_eq!(tokens.len() - 1, next);
+    }
+
+    #[test]
+    fn should_parse_variants_with_payloads() {
+        let tokens = parse("enum Shape {\n    Circle(Float radius)\n    Empty\n}");
+        let (enum_declaration, _) = EnumDeclaration::try_parse(&tokens, 0).unwrap();
+
+        assert!(enum_declaration.has_payloads());
+        let payload = enum_declaration.variants[0].payload.as_ref().unwrap();
+        assert_eq!("radius", payload.parameters[0].identifier.value);
+        assert!(enum_declaration.variants[1].payload.is_none());
+    }
+
+    #[test]
+    fn should_not_parse_without_enum_keyword() {
+        let tokens = parse("Shape::Empty");
+        assert!(matches!(
+            EnumDeclaration::try_parse(&tokens, 0),
+            Err(ParsingError::Unmatched)
+        ));
+    }
+
+    #[test]
+    fn should_error_on_lowercase_name() {
+        let tokens = parse("enum suit {}");
+        let Err(ParsingError::Err(error)) = EnumDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(SYNTAX_INVALID_ENUM_DECLARATION, error.error_code);
+        assert_eq!(5, error.error_offset);
+    }
+
+    #[test]
+    fn should_error_on_missing_braces() {
+        let tokens = parse("enum Suit");
+        let Err(ParsingError::Err(error)) = EnumDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "Expected the variants of the enum after its name, between braces",
+            error.labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_error_on_lowercase_variants() {
+        let tokens = parse("enum Suit {\n    hearts\n}");
+        let Err(ParsingError::Err(error)) = EnumDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(16, error.error_offset);
+        assert_eq!(
+            "Expected a variant or a closing brace `}` here",
+            error.labels[0].message
+        );
+        assert!(error.help.is_some());
+    }
+
+    #[test]
+    fn should_error_on_fields_with_modifiers() {
+        let tokens = parse("enum Shape {\n    Circle(val Float radius)\n}");
+        let Err(ParsingError::Err(error)) = EnumDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "The fields of a variant can't be declared with `val`",
+            error.labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_error_on_unclosed_enum() {
+        let tokens = parse("enum Suit {\n    Hearts");
+        let Err(ParsingError::Err(error)) = EnumDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "The code ends here without closing the enum",
+            error.labels[1].message
+        );
+    }
+}
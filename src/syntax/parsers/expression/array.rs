@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use crate::{
     lexic::token::{Token, TokenType},
     syntax::{
@@ -61,6 +63,7 @@ impl<'a> Parseable<'a> for Array<'a> {
             exps,
             start: open_bracket.position,
             end: closed_bracket.position,
+            to_float: Cell::new(false),
         };
         Ok((arr, next))
     }
@@ -8,15 +8,23 @@ use super::utils::parse_many;
 /// Parses a factor expression.
 ///
 /// ```ebnf
-/// comparison = term, ((">" | ">=" | "<" | "<="), term)*;
+/// comparison = type_test, ((">" | ">=" | "<" | "<="), type_test)*;
 /// ```
 pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
-    let (term, next_pos) = match super::term::try_parse(tokens, pos) {
+    let (term, next_pos) = match super::type_test::try_parse(tokens, pos) {
         Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
         _ => return Err(ParsingError::Unmatched),
     };
 
-    parse_many(tokens, next_pos, term, 0, &vec![">", ">=", "<", "<="])
+    parse_many(
+        tokens,
+        next_pos,
+        term,
+        0,
+        &vec![">", ">=", "<", "<="],
+        super::type_test::try_parse,
+    )
 }
 
 #[cfg(test)]
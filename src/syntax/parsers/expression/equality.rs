@@ -13,10 +13,18 @@ use super::utils::parse_many;
 pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
     let (comparison, next_pos) = match super::comparison::try_parse(tokens, pos) {
         Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
         _ => return Err(ParsingError::Unmatched),
     };
 
-    parse_many(tokens, next_pos, comparison, 0, &vec!["==", "!="])
+    parse_many(
+        tokens,
+        next_pos,
+        comparison,
+        0,
+        &vec!["==", "!="],
+        super::comparison::try_parse,
+    )
 }
 
 #[cfg(test)]
@@ -8,15 +8,23 @@ use super::utils::parse_many;
 /// Parses a factor expression.
 ///
 /// ```ebnf
-/// factor = dot_access, (("/" | "*" | "%"), dot_access)*;
+/// factor = unary, (("/" | "*" | "%"), unary)*;
 /// ```
 pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
-    let (unary, next_pos) = match super::dot_access::try_parse(tokens, pos) {
+    let (unary, next_pos) = match super::unary::try_parse(tokens, pos) {
         Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
         _ => return Err(ParsingError::Unmatched),
     };
 
-    parse_many(tokens, next_pos, unary, 0, &vec!["/", "*", "%"])
+    parse_many(
+        tokens,
+        next_pos,
+        unary,
+        0,
+        &vec!["/", "*", "%"],
+        super::unary::try_parse,
+    )
 }
 
 #[cfg(test)]
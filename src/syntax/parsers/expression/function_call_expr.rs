@@ -1,8 +1,11 @@
 use crate::{
-    error_handling::{error_messages::SYNTAX_INVALID_ARRAY_ACCESS, ErrorContainer, ErrorLabel},
+    error_handling::{
+        error_messages::{SYNTAX_INVALID_ARRAY_ACCESS, SYNTAX_INVALID_MEMBER_ACCESS},
+        ErrorContainer, ErrorLabel,
+    },
     lexic::token::{Token, TokenType},
     syntax::{
-        ast::{functions::FunctionCall, ArrayAccess, Expression},
+        ast::{functions::FunctionCall, ArrayAccess, Expression, Identifier},
         functions::arguments_list,
         parseable::Parseable,
         utils::parse_token_type,
@@ -10,51 +13,130 @@ use crate::{
     },
 };
 
-/// Parses a function call expression.
+use super::utils::try_binary_op;
+
+/// Parses a function call expression: a primary followed by calls,
+/// array accesses and member accesses, applied from left to right,
+/// e.g. `f(1)(2)`, `box.items[0]` or `box.get_items()[0]`.
+///
+/// Member accesses may be on the following lines, indented.
 ///
 /// ```ebnf
-/// function call expr = primary, "(", (arguments list)?, ")"
-///                    | primary, "[", expressions, "]"
-///                    | primary;
+/// function call expr = primary, postfix*;
+/// postfix = "(", (arguments list)?, ")"
+///         | "[", expression, "]"
+///         | ("." | "?." | "!."), identifier, ("(", (arguments list)?, ")")?;
 /// ```
 pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
-    let (primary_expr, next_pos) = match super::primary::try_parse(tokens, pos) {
+    let (expression, next_pos) = match super::primary::try_parse(tokens, pos) {
         Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
         _ => return Err(ParsingError::Unmatched),
     };
 
+    parse_postfix(tokens, next_pos, expression, 0)
+}
+
+/// Parses the postfix operations after `expression`, if any
+fn parse_postfix<'a>(
+    tokens: &'a Vec<Token>,
+    pos: usize,
+    expression: Expression<'a>,
+    indentation_level: u32,
+) -> ParsingResult<'a, Expression<'a>> {
     // Attempt to parse a function call
-    // Parse arguments list
-    match arguments_list::try_parse(tokens, next_pos) {
+    match arguments_list::try_parse(tokens, pos) {
         Ok((arguments, next_pos)) => {
             let fun_call = FunctionCall {
-                function: Box::new(primary_expr),
+                function: Box::new(expression),
                 arguments: Box::new(arguments),
             };
-
-            return Ok((Expression::FunctionCall(fun_call), next_pos));
+            let expression = Expression::FunctionCall(fun_call);
+            return parse_postfix(tokens, next_pos, expression, indentation_level);
         }
         Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
         _ => {}
     };
 
     // Attempt to parse an array access
-    match try_parse_array_access(tokens, next_pos) {
+    match try_parse_array_access(tokens, pos) {
         Ok(((array_idx, end_pos), next_pos)) => {
-            let fun_call = ArrayAccess {
-                left_expr: Box::new(primary_expr),
+            let array_access = ArrayAccess {
+                left_expr: Box::new(expression),
                 idx_expr: Box::new(array_idx),
                 end_pos,
             };
-
-            return Ok((Expression::ArrayAcccess(fun_call), next_pos));
+            let expression = Expression::ArrayAcccess(array_access);
+            return parse_postfix(tokens, next_pos, expression, indentation_level);
         }
         Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
         _ => {}
     };
 
-    // Return the parsed primary
-    return Ok((primary_expr, next_pos));
+    // Attempt to parse a member access. If there isn't one,
+    // the expression is returned as it is
+    try_binary_op(
+        tokens,
+        pos,
+        expression,
+        &vec![".", "?.", "!."],
+        indentation_level,
+        |tokens, next_pos, object, operator, indent_count| {
+            let (member, next_pos) = parse_member(tokens, next_pos, operator)?;
+            let expression =
+                Expression::BinaryOperator(Box::new(object), Box::new(member), operator);
+            parse_postfix(
+                tokens,
+                next_pos,
+                expression,
+                indentation_level + indent_count,
+            )
+        },
+    )
+}
+
+/// Parses the name of the member after the member access `operator`,
+/// and the arguments if the member is a method that's called
+fn parse_member<'a>(
+    tokens: &'a Vec<Token>,
+    pos: usize,
+    operator: &Token,
+) -> ParsingResult<'a, Expression<'a>> {
+    let member = match tokens.get(pos) {
+        Some(token) if token.token_type == TokenType::Identifier => token,
+        _ => {
+            let label = ErrorLabel {
+                message: format!(
+                    "Expected the name of a member after this `{}`",
+                    operator.value
+                ),
+                start: operator.position,
+                end: operator.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_MEMBER_ACCESS,
+                error_offset: operator.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            return Err(ParsingError::Err(econtainer));
+        }
+    };
+    let member_expression = Expression::Identifier(Identifier::new(member));
+
+    // A method call
+    match arguments_list::try_parse(tokens, pos + 1) {
+        Ok((arguments, next_pos)) => {
+            let method_call = FunctionCall {
+                function: Box::new(member_expression),
+                arguments: Box::new(arguments),
+            };
+            Ok((Expression::FunctionCall(method_call), next_pos))
+        }
+        Err(ParsingError::Err(err)) => Err(ParsingError::Err(err)),
+        _ => Ok((member_expression, pos + 1)),
+    }
 }
 
 pub fn try_parse_array_access(
@@ -98,6 +180,9 @@ pub fn try_parse_array_access(
             return Err(ParsingError::Err(econtainer));
         }
         Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+        Err(ParsingError::Recovered(errors, next)) => {
+            return Err(ParsingError::Recovered(errors, next))
+        }
     };
 
     // parse the closing bracket
@@ -110,4 +195,105 @@ pub fn try_parse_array_access(
 }
 
 #[cfg(test)]
-mod test {}
+mod test {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    #[test]
+    fn should_parse_chained_calls() {
+        let tokens = get_tokens(&String::from("make(1)(2)")).unwrap();
+        let (expression, next) = try_parse(&tokens, 0).unwrap();
+
+        let Expression::FunctionCall(outer) = expression else {
+            panic!("Expected a function call")
+        };
+        assert_eq!(1, outer.arguments.arguments.len());
+        assert!(matches!(*outer.function, Expression::FunctionCall(_)));
+        assert_eq!(7, next);
+    }
+
+    #[test]
+    fn should_call_array_items() {
+        let tokens = get_tokens(&String::from("handlers[0](event)")).unwrap();
+        let (expression, _) = try_parse(&tokens, 0).unwrap();
+
+        let Expression::FunctionCall(call) = expression else {
+            panic!("Expected a function call")
+        };
+        assert!(matches!(*call.function, Expression::ArrayAcccess(_)));
+    }
+
+    #[test]
+    fn should_index_members() {
+        let tokens = get_tokens(&String::from("obj.field[0]")).unwrap();
+        let (expression, next) = try_parse(&tokens, 0).unwrap();
+
+        let Expression::ArrayAcccess(access) = expression else {
+            panic!("Expected an array access")
+        };
+        let Expression::BinaryOperator(object, member, operator) = *access.left_expr else {
+            panic!("Expected a member access")
+        };
+        assert_eq!(".", operator.value);
+        assert!(matches!(*object, Expression::Identifier(ref t) if t.value == "obj"));
+        assert!(matches!(*member, Expression::Identifier(ref t) if t.value == "field"));
+        assert_eq!(6, next);
+    }
+
+    #[test]
+    fn should_index_the_result_of_methods() {
+        let tokens = get_tokens(&String::from("obj.method()[0]")).unwrap();
+        let (expression, next) = try_parse(&tokens, 0).unwrap();
+
+        let Expression::ArrayAcccess(access) = expression else {
+            panic!("Expected an array access")
+        };
+        let Expression::BinaryOperator(_, member, _) = *access.left_expr else {
+            panic!("Expected a member access")
+        };
+        assert!(matches!(*member, Expression::FunctionCall(_)));
+        assert_eq!(8, next);
+    }
+
+    #[test]
+    fn should_parse_member_accesses_from_left_to_right() {
+        let tokens = get_tokens(&String::from("a.b(1).c?.d")).unwrap();
+        let (expression, _) = try_parse(&tokens, 0).unwrap();
+
+        // ((a.b(1)).c)?.d
+        let Expression::BinaryOperator(object, _, operator) = expression else {
+            panic!("Expected a member access")
+        };
+        assert_eq!("?.", operator.value);
+        let Expression::BinaryOperator(object, _, operator) = *object else {
+            panic!("Expected a member access")
+        };
+        assert_eq!(".", operator.value);
+        assert!(matches!(*object, Expression::BinaryOperator(_, _, _)));
+    }
+
+    #[test]
+    fn should_parse_indented_member_accesses() {
+        let tokens = get_tokens(&String::from(
+            "items
+    .first()
+    .name",
+        ))
+        .unwrap();
+        let (expression, next) = try_parse(&tokens, 0).unwrap();
+
+        assert!(matches!(expression, Expression::BinaryOperator(_, _, _)));
+        assert_eq!(tokens.len() - 1, next);
+    }
+
+    #[test]
+    fn should_error_on_missing_member_name() {
+        let tokens = get_tokens(&String::from("obj.[0]")).unwrap();
+        let Err(ParsingError::Err(error)) = try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(SYNTAX_INVALID_MEMBER_ACCESS, error.error_code);
+        assert_eq!(3, error.error_offset);
+    }
+}
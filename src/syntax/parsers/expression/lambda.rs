@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+
+use crate::{
+    lexic::token::{Token, TokenType},
+    syntax::{
+        ast::functions::Lambda,
+        parseable::{Parseable, ParsingError, ParsingResult},
+        parsers::function_declaration::{parse_function_body, parse_params_and_return_type},
+        utils::parse_token_type,
+    },
+};
+
+/// Parses an anonymous function.
+///
+/// ```ebnf
+/// lambda = "fn", params list, ("->", Datatype)?, block;
+/// ```
+impl<'a> Parseable<'a> for Lambda<'a> {
+    type Item = Lambda<'a>;
+
+    fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
+        // `fn` keyword
+        let (keyword, next_pos) = match parse_token_type(tokens, current_pos, TokenType::FN) {
+            Ok(tuple) => tuple,
+            _ => return Err(ParsingError::Unmatched),
+        };
+
+        let ((params_list, return_type), next_pos) =
+            parse_params_and_return_type(tokens, next_pos, keyword)?;
+
+        let (block, next_pos) = parse_function_body(tokens, next_pos, &params_list, &return_type)?;
+
+        let lambda = Lambda {
+            keyword,
+            params_list,
+            return_type,
+            block,
+            captures: RefCell::new(vec![]),
+        };
+        Ok((lambda, next_pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error_handling::error_messages::SYNTAX_INVALID_FUNCTION_DECLARATION,
+        lexic::get_tokens,
+        syntax::ast::{BlockMember, Expression, Positionable},
+    };
+
+    #[test]
+    fn should_parse_lambda() {
+        let tokens = get_tokens(&String::from("fn(Int x) -> Int { x + 1 }")).unwrap();
+        let (lambda, next) = Lambda::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(1, lambda.params_list.parameters.len());
+        assert_eq!("x", lambda.params_list.parameters[0].identifier.value);
+        assert_eq!("Int", lambda.return_type.as_ref().unwrap().name.value);
+        assert!(matches!(
+            lambda.block.members[0],
+            BlockMember::Expr(Expression::BinaryOperator(_, _, _))
+        ));
+        assert_eq!((0, 26), lambda.get_position());
+        assert_eq!(tokens.len() - 1, next);
+    }
+
+    #[test]
+    fn should_parse_lambda_without_return_type() {
+        let tokens = get_tokens(&String::from("fn() {\n    print(\"hi\")\n}")).unwrap();
+        let (lambda, _) = Lambda::try_parse(&tokens, 0).unwrap();
+
+        assert!(lambda.params_list.parameters.is_empty());
+        assert!(lambda.return_type.is_none());
+        assert_eq!(1, lambda.block.members.len());
+    }
+
+    #[test]
+    fn should_error_on_missing_params() {
+        let tokens = get_tokens(&String::from("fn { 1 }")).unwrap();
+        let Err(ParsingError::Err(error)) = Lambda::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(SYNTAX_INVALID_FUNCTION_DECLARATION, error.error_code);
+    }
+
+    #[test]
+    fn should_error_on_missing_body() {
+        let tokens = get_tokens(&String::from("fn(Int x) -> Int")).unwrap();
+        let Err(ParsingError::Err(error)) = Lambda::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "Expected a block after this parameter list",
+            error.labels[0].message
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    lexic::token::Token,
+    syntax::{ast::Expression, ParsingError, ParsingResult},
+};
+
+use super::utils::parse_many;
+
+/// Parses a logical and expression.
+///
+/// ```ebnf
+/// logic_and = equality, ("&&", equality)*;
+/// ```
+pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
+    let (equality, next_pos) = match super::equality::try_parse(tokens, pos) {
+        Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
+        _ => return Err(ParsingError::Unmatched),
+    };
+
+    parse_many(
+        tokens,
+        next_pos,
+        equality,
+        0,
+        &vec!["&&"],
+        super::equality::try_parse,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    #[test]
+    fn should_parse_logic_and() {
+        let tokens = get_tokens(&String::from("a && b")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(3, next);
+        match result {
+            Expression::BinaryOperator(left, right, op) => {
+                assert_eq!("&&", op.value);
+                assert!(matches!(*left, Expression::Identifier(_)));
+                assert!(matches!(*right, Expression::Identifier(_)));
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_have_lower_precedence_than_equality() {
+        // (a == b) && (c != d)
+        let tokens = get_tokens(&String::from("a == b && c != d")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(7, next);
+        match result {
+            Expression::BinaryOperator(left, right, op) => {
+                assert_eq!("&&", op.value);
+                match (*left, *right) {
+                    (
+                        Expression::BinaryOperator(_, _, left_op),
+                        Expression::BinaryOperator(_, _, right_op),
+                    ) => {
+                        assert_eq!("==", left_op.value);
+                        assert_eq!("!=", right_op.value);
+                    }
+                    _ => panic!("Expected equalities on both sides"),
+                }
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_have_lower_precedence_than_comparison() {
+        // (a < b) && (c >= d)
+        let tokens = get_tokens(&String::from("a < b && c >= d")).unwrap();
+        let (result, _) = try_parse(&tokens, 0).unwrap();
+
+        match result {
+            Expression::BinaryOperator(left, right, op) => {
+                assert_eq!("&&", op.value);
+                match (*left, *right) {
+                    (
+                        Expression::BinaryOperator(_, _, left_op),
+                        Expression::BinaryOperator(_, _, right_op),
+                    ) => {
+                        assert_eq!("<", left_op.value);
+                        assert_eq!(">=", right_op.value);
+                    }
+                    _ => panic!("Expected comparisons on both sides"),
+                }
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_have_lower_precedence_than_unary_not() {
+        // (!a) && (!b)
+        let tokens = get_tokens(&String::from("!a && !b")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(5, next);
+        match result {
+            Expression::BinaryOperator(left, right, op) => {
+                assert_eq!("&&", op.value);
+                assert!(matches!(*left, Expression::UnaryOperator(_, _)));
+                assert!(matches!(*right, Expression::UnaryOperator(_, _)));
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_parse_indented() {
+        let tokens = get_tokens(&String::from("a\n  && b\n  && c")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(8, next);
+        match result {
+            Expression::BinaryOperator(left, _, op) => {
+                assert_eq!("&&", op.value);
+                assert!(matches!(*left, Expression::BinaryOperator(_, _, _)));
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+}
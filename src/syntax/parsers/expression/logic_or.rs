@@ -0,0 +1,111 @@
+use crate::{
+    lexic::token::Token,
+    syntax::{ast::Expression, ParsingError, ParsingResult},
+};
+
+use super::utils::parse_many;
+
+/// Parses a logical or expression.
+///
+/// ```ebnf
+/// logic_or = logic_and, ("||", logic_and)*;
+/// ```
+pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
+    let (logic_and, next_pos) = match super::logic_and::try_parse(tokens, pos) {
+        Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
+        _ => return Err(ParsingError::Unmatched),
+    };
+
+    parse_many(
+        tokens,
+        next_pos,
+        logic_and,
+        0,
+        &vec!["||"],
+        super::logic_and::try_parse,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    #[test]
+    fn should_parse_logic_or() {
+        let tokens = get_tokens(&String::from("a || b")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(3, next);
+        match result {
+            Expression::BinaryOperator(left, right, op) => {
+                assert_eq!("||", op.value);
+                assert!(matches!(*left, Expression::Identifier(_)));
+                assert!(matches!(*right, Expression::Identifier(_)));
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_have_lower_precedence_than_logic_and() {
+        // a || (b && c)
+        let tokens = get_tokens(&String::from("a || b && c")).unwrap();
+        let (result, _) = try_parse(&tokens, 0).unwrap();
+
+        match result {
+            Expression::BinaryOperator(left, right, op) => {
+                assert_eq!("||", op.value);
+                assert!(matches!(*left, Expression::Identifier(_)));
+                match *right {
+                    Expression::BinaryOperator(_, _, op) => assert_eq!("&&", op.value),
+                    _ => panic!("Expected `b && c` on the right side"),
+                }
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+
+        // (a && b) || c
+        let tokens = get_tokens(&String::from("a && b || c")).unwrap();
+        let (result, _) = try_parse(&tokens, 0).unwrap();
+
+        match result {
+            Expression::BinaryOperator(left, _, op) => {
+                assert_eq!("||", op.value);
+                match *left {
+                    Expression::BinaryOperator(_, _, op) => assert_eq!("&&", op.value),
+                    _ => panic!("Expected `a && b` on the left side"),
+                }
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_be_left_associative() {
+        // (a || b) || c
+        let tokens = get_tokens(&String::from("a || b || c")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(5, next);
+        match result {
+            Expression::BinaryOperator(left, right, _) => {
+                assert!(matches!(*left, Expression::BinaryOperator(_, _, _)));
+                assert!(matches!(*right, Expression::Identifier(_)));
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_not_parse_unfinished_logic_or() {
+        let tokens = get_tokens(&String::from("a ||")).unwrap();
+        let result = try_parse(&tokens, 0);
+
+        match result {
+            Err(ParsingError::Unmatched) => {}
+            _ => panic!("Expected an Unmatched error"),
+        }
+    }
+}
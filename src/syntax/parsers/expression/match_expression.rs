@@ -0,0 +1,449 @@
+use std::cell::{Cell, RefCell};
+
+use crate::{
+    error_handling::{
+        error_messages::{COMPILER_TODO, SYNTAX_INVALID_MATCH},
+        ErrorContainer, ErrorLabel,
+    },
+    lexic::token::{Token, TokenType},
+    syntax::{
+        ast::{
+            match_expression::{Match, MatchArm},
+            Block, BlockMember, Expression, Positionable,
+        },
+        parseable::{Parseable, ParsingError, ParsingResult},
+        utils::parse_token_type,
+    },
+};
+
+/// Parses a match expression.
+///
+/// ```ebnf
+/// match = "match", expression, match arm, (match arm)*, ("else", block)?;
+///
+/// match arm = "case", pattern, (",", pattern)*, ("if", expression)?, block;
+///
+/// pattern = number | "-", number | string | boolean | "null"
+///         | datatype, "::", datatype, ("(", (identifier, (",", identifier)*)?, ")")?;
+/// ```
+impl<'a> Parseable<'a> for Match<'a> {
+    type Item = Match<'a>;
+
+    fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
+        // match keyword
+        let (keyword, next) = match parse_token_type(tokens, current_pos, TokenType::MATCH) {
+            Ok(tuple) => tuple,
+            _ => return Err(ParsingError::Unmatched),
+        };
+
+        // the value to match
+        let (subject, next) = match Expression::try_parse(tokens, next) {
+            Ok(tuple) => tuple,
+            Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            _ => {
+                return Err(match_error(
+                    "Expected a value to match after this `match` keyword",
+                    keyword.position,
+                    keyword.get_end_position(),
+                ))
+            }
+        };
+
+        // The arms may be indented
+        let (is_indented, next) = match tokens.get(next) {
+            Some(token) if token.token_type == TokenType::INDENT => (true, next + 1),
+            _ => (false, next),
+        };
+
+        // many arms
+        let mut arms = Vec::new();
+        let mut current_pos = next;
+        while let Ok((case_token, next)) = parse_token_type(tokens, current_pos, TokenType::CASE) {
+            let (arm, next) = parse_arm(tokens, next, case_token)?;
+            arms.push(arm);
+            current_pos = next;
+        }
+
+        if arms.is_empty() {
+            let (error_start, error_end) = subject.get_position();
+            return Err(match_error(
+                "Expected a `case` after the value to match",
+                error_start,
+                error_end,
+            ));
+        }
+
+        // else
+        let (else_block, next) = match parse_token_type(tokens, current_pos, TokenType::ELSE) {
+            Ok((else_token, next)) => {
+                let (block, next) = parse_arm_block(
+                    tokens,
+                    next,
+                    "Expected a block after this `else` keyword",
+                    (else_token.position, else_token.get_end_position()),
+                )?;
+                (Some(block), next)
+            }
+            _ => (None, current_pos),
+        };
+
+        // The dedentation after the last arm
+        let next = match tokens.get(next) {
+            Some(token) if is_indented && token.token_type == TokenType::DEDENT => next + 1,
+            _ => next,
+        };
+
+        let match_expression = Match {
+            keyword,
+            subject: Box::new(subject),
+            arms,
+            else_block,
+            captures: RefCell::new(vec![]),
+            to_float: Cell::new(false),
+        };
+        Ok((match_expression, next))
+    }
+}
+
+/// Parses a match arm, after its `case` keyword
+fn parse_arm<'a>(
+    tokens: &'a Vec<Token>,
+    pos: usize,
+    case_token: &'a Token,
+) -> ParsingResult<'a, MatchArm<'a>> {
+    // first pattern
+    let (first_pattern, mut next) = parse_pattern(
+        tokens,
+        pos,
+        "Expected a value after this `case` keyword",
+        case_token,
+    )?;
+    let mut patterns = vec![first_pattern];
+
+    // many patterns, separated by commas
+    while let Some(comma) = tokens
+        .get(next)
+        .filter(|t| t.token_type == TokenType::Comma)
+    {
+        let (pattern, next_pos) =
+            parse_pattern(tokens, next + 1, "Expected a value after this comma", comma)?;
+        patterns.push(pattern);
+        next = next_pos;
+    }
+
+    // guard
+    let (guard, next) = match tokens.get(next) {
+        Some(if_token) if if_token.token_type == TokenType::IF => {
+            match Expression::try_parse(tokens, next + 1) {
+                Ok((guard, next)) => (Some(guard), next),
+                Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+                _ => {
+                    return Err(match_error(
+                        "Expected a Bool expression after this `if` keyword",
+                        if_token.position,
+                        if_token.get_end_position(),
+                    ))
+                }
+            }
+        }
+        _ => (None, next),
+    };
+
+    // block
+    let block_message = match guard {
+        Some(_) => "Expected a block after this condition",
+        None => "Expected a block after this pattern",
+    };
+    let previous_position = match &guard {
+        Some(guard) => guard.get_position(),
+        None => patterns.last().unwrap().get_position(),
+    };
+    let (body, next) = parse_arm_block(tokens, next, block_message, previous_position)?;
+
+    let arm = MatchArm {
+        patterns,
+        guard,
+        body,
+    };
+    Ok((arm, next))
+}
+
+/// Parses a pattern of a match arm. `message` is the error
+/// reported at `previous_token` if there is no pattern
+fn parse_pattern<'a>(
+    tokens: &'a Vec<Token>,
+    pos: usize,
+    message: &str,
+    previous_token: &Token,
+) -> ParsingResult<'a, Expression<'a>> {
+    let (pattern, next) = match Expression::try_parse(tokens, pos) {
+        Ok(tuple) => tuple,
+        Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+        _ => {
+            return Err(match_error(
+                message,
+                previous_token.position,
+                previous_token.get_end_position(),
+            ))
+        }
+    };
+
+    let is_pattern = match &pattern {
+        Expression::Int(_)
+        | Expression::Float(_)
+        | Expression::String(_)
+        | Expression::Boolean(_)
+        | Expression::Null(_)
+        | Expression::EnumVariant(_, _, _) => true,
+        Expression::UnaryOperator(operator, operand) => {
+            operator.value == "-" && matches!(**operand, Expression::Int(_) | Expression::Float(_))
+        }
+        // The fields of the variant bound to variables, e.g. `Shape::Circle(radius)`
+        Expression::FunctionCall(call)
+            if matches!(*call.function, Expression::EnumVariant(_, _, _)) =>
+        {
+            let binding = call
+                .arguments
+                .arguments
+                .iter()
+                .find(|argument| !matches!(argument, Expression::Identifier(_)));
+            if let Some(binding) = binding {
+                let (error_start, error_end) = binding.get_position();
+                return Err(match_error(
+                    "Expected the name of a field of the variant here",
+                    error_start,
+                    error_end,
+                ));
+            }
+            true
+        }
+        _ => false,
+    };
+    if !is_pattern {
+        let (error_start, error_end) = pattern.get_position();
+        return Err(match_error(
+            "Expected a literal value: a number, a string, a Bool, null or a variant of an enum",
+            error_start,
+            error_end,
+        ));
+    }
+
+    Ok((pattern, next))
+}
+
+/// Parses the block of a match arm. `message` is the error
+/// reported at `previous_position` if there is no block.
+///
+/// The arms of PHP's match are expressions, so for now
+/// the block of an arm may only contain an expression
+fn parse_arm_block<'a>(
+    tokens: &'a Vec<Token>,
+    pos: usize,
+    message: &str,
+    previous_position: (usize, usize),
+) -> ParsingResult<'a, Block<'a>> {
+    match Block::try_parse(tokens, pos) {
+        Ok((block, next)) => match block.members.as_slice() {
+            [] | [BlockMember::Expr(_)] => Ok((block, next)),
+            _ => {
+                let (error_start, error_end) = block.get_position();
+                let label = ErrorLabel {
+                    message: String::from("The arms of a match can only contain an expression"),
+                    start: error_start,
+                    end: error_end + 1,
+                };
+                Err(ParsingError::Err(ErrorContainer {
+                    error_code: COMPILER_TODO,
+                    error_offset: error_start,
+                    labels: vec![label],
+                    note: None,
+                    help: Some(String::from(
+                        "Move the statements to a function, and call it inside the arm",
+                    )),
+                }))
+            }
+        },
+        Err(ParsingError::Err(err)) => Err(ParsingError::Err(err)),
+        // Expressions don't recover from errors,
+        // so only the first error inside the block is reported
+        Err(ParsingError::Recovered(mut errors, _)) => Err(ParsingError::Err(errors.remove(0))),
+        Err(ParsingError::Mismatch(wrong_token)) => Err(match_error(
+            "Expected a block here",
+            wrong_token.position,
+            wrong_token.get_end_position(),
+        )),
+        Err(ParsingError::Unmatched) => Err(match_error(
+            message,
+            previous_position.0,
+            previous_position.1,
+        )),
+    }
+}
+
+fn match_error<'a>(message: &str, start: usize, end: usize) -> ParsingError<'a> {
+    let label = ErrorLabel {
+        message: String::from(message),
+        start,
+        end,
+    };
+    let econtainer = ErrorContainer {
+        error_code: SYNTAX_INVALID_MATCH,
+        error_offset: start,
+        labels: vec![label],
+        note: None,
+        help: None,
+    };
+    ParsingError::Err(econtainer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error_handling::MistiError, lexic::get_tokens};
+
+    fn parse_error(input: &str) -> MistiError {
+        let tokens = get_tokens(&String::from(input)).unwrap();
+        match Match::try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => error,
+            result => panic!("Expected an error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_parse_match() {
+        let tokens = get_tokens(&String::from(
+            "match value\ncase 1, 2 { \"a\" }\ncase 3 if flag {\n    \"b\"\n}\nelse { \"c\" }",
+        ))
+        .unwrap();
+        let (result, next) = Match::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(tokens.len() - 1, next);
+        assert!(matches!(*result.subject, Expression::Identifier(_)));
+        assert_eq!(2, result.arms.len());
+        assert_eq!(2, result.arms[0].patterns.len());
+        assert!(result.arms[0].guard.is_none());
+        assert_eq!(1, result.arms[1].patterns.len());
+        assert!(result.arms[1].guard.is_some());
+        assert!(result.else_block.is_some());
+    }
+
+    #[test]
+    fn should_parse_match_without_else() {
+        let tokens = get_tokens(&String::from("match value\ncase true {}\ncase false {}")).unwrap();
+        let (result, next) = Match::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(tokens.len() - 1, next);
+        assert_eq!(2, result.arms.len());
+        assert!(result.else_block.is_none());
+    }
+
+    #[test]
+    fn should_parse_negative_and_null_patterns() {
+        let tokens =
+            get_tokens(&String::from("match value\ncase -1, null, \"a\", 2.5 {}")).unwrap();
+        let (result, _) = Match::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(4, result.arms[0].patterns.len());
+    }
+
+    #[test]
+    fn should_parse_as_an_expression() {
+        let tokens = get_tokens(&String::from("match a\ncase 1 { 2 }\nelse { 3 }")).unwrap();
+        let (result, _) = Expression::try_parse(&tokens, 0).unwrap();
+
+        assert!(matches!(result, Expression::Match(_)));
+    }
+
+    #[test]
+    fn should_fail_on_missing_parts() {
+        let error = parse_error("match\ncase 1 {}");
+        assert_eq!(SYNTAX_INVALID_MATCH, error.error_code);
+        assert_eq!(
+            "Expected a value to match after this `match` keyword",
+            error.labels[0].message
+        );
+
+        let error = parse_error("match value\nelse {}");
+        assert_eq!(
+            "Expected a `case` after the value to match",
+            error.labels[0].message
+        );
+
+        let error = parse_error("match value\ncase {}");
+        assert_eq!(
+            "Expected a value after this `case` keyword",
+            error.labels[0].message
+        );
+
+        let error = parse_error("match value\ncase 1, {}");
+        assert_eq!("Expected a value after this comma", error.labels[0].message);
+
+        let error = parse_error("match value\ncase 1 if {}");
+        assert_eq!(
+            "Expected a Bool expression after this `if` keyword",
+            error.labels[0].message
+        );
+
+        let error = parse_error("match value\ncase 1");
+        assert_eq!(
+            "Expected a block after this pattern",
+            error.labels[0].message
+        );
+
+        let error = parse_error("match value\ncase 1 {}\nelse");
+        assert_eq!(
+            "Expected a block after this `else` keyword",
+            error.labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_only_allow_literal_patterns() {
+        let error = parse_error("match value\ncase other {}");
+        assert_eq!(SYNTAX_INVALID_MATCH, error.error_code);
+        assert_eq!(17, error.error_offset);
+        assert_eq!(
+            "Expected a literal value: a number, a string, a Bool, null or a variant of an enum",
+            error.labels[0].message
+        );
+
+        parse_error("match value\ncase 1 + 2 {}");
+    }
+
+    #[test]
+    fn should_only_allow_expressions_in_arms() {
+        let error = parse_error("match value\ncase 1 {\n    val x = 1\n    x\n}\nelse { 2 }");
+        assert_eq!(COMPILER_TODO, error.error_code);
+
+        let error = parse_error("match value\ncase 1 { 1 }\nelse {\n    1\n    2\n}");
+        assert_eq!(COMPILER_TODO, error.error_code);
+    }
+
+    #[test]
+    fn should_parse_variant_patterns() {
+        let tokens = get_tokens(&String::from(
+            "match shape\ncase Shape::Circle(radius) { radius }\ncase Shape::Empty { 0 }",
+        ))
+        .unwrap();
+        let (match_expression, _) = Match::try_parse(&tokens, 0).unwrap();
+
+        assert!(matches!(
+            match_expression.arms[0].patterns[0],
+            Expression::FunctionCall(_)
+        ));
+        assert!(matches!(
+            match_expression.arms[1].patterns[0],
+            Expression::EnumVariant(_, _, _)
+        ));
+    }
+
+    #[test]
+    fn should_only_bind_fields_in_variant_patterns() {
+        let error = parse_error("match shape\ncase Shape::Circle(1) {}");
+        assert_eq!(SYNTAX_INVALID_MATCH, error.error_code);
+        assert_eq!(
+            "Expected the name of a field of the variant here",
+            error.labels[0].message
+        );
+    }
+}
@@ -3,12 +3,17 @@ use crate::{lexic::token::Token, syntax::parseable::Parseable};
 
 mod array;
 mod comparison;
-mod dot_access;
 mod equality;
 mod factor;
 pub mod function_call_expr;
+mod lambda;
+mod logic_and;
+mod logic_or;
+mod match_expression;
+mod null_coalescing;
 mod primary;
 mod term;
+mod type_test;
 mod unary;
 mod utils;
 
@@ -16,7 +21,7 @@ impl<'a> Parseable<'a> for Expression<'a> {
     type Item = Expression<'a>;
 
     fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
-        equality::try_parse(tokens, current_pos)
+        null_coalescing::try_parse(tokens, current_pos)
     }
 }
 
@@ -35,4 +40,80 @@ mod tests {
             _ => panic!("Expected a binary operation"),
         }
     }
+
+    /// Returns the operator at the root of a binary expression,
+    /// and the operators at the root of its operands
+    fn operators<'a>(expr: &'a Expression) -> (&'a str, Option<&'a str>, Option<&'a str>) {
+        let root_operator = |expr: &'a Expression| match expr {
+            Expression::BinaryOperator(_, _, op) => Some(op.value.as_str()),
+            Expression::UnaryOperator(op, _) => Some(op.value.as_str()),
+            _ => None,
+        };
+
+        match expr {
+            Expression::BinaryOperator(left, right, op) => {
+                (op.value.as_str(), root_operator(left), root_operator(right))
+            }
+            _ => panic!("Expected a binary operation"),
+        }
+    }
+
+    #[test]
+    fn should_parse_arithmetic_with_precedence() {
+        let tokens = get_tokens(&String::from("a * b + c")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(("+", Some("*"), None), operators(&expr));
+
+        let tokens = get_tokens(&String::from("a + b * c")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(("+", None, Some("*")), operators(&expr));
+
+        let tokens = get_tokens(&String::from("a - b - c")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(("-", Some("-"), None), operators(&expr));
+    }
+
+    #[test]
+    fn should_parse_comparisons_with_precedence() {
+        let tokens = get_tokens(&String::from("a + 1 < b * 2")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(("<", Some("+"), Some("*")), operators(&expr));
+
+        let tokens = get_tokens(&String::from("a < b == c > d")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(("==", Some("<"), Some(">")), operators(&expr));
+    }
+
+    #[test]
+    fn should_parse_unary_operators_with_precedence() {
+        let tokens = get_tokens(&String::from("-a + b")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(("+", Some("-"), None), operators(&expr));
+
+        let tokens = get_tokens(&String::from("!a == b")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(("==", Some("!"), None), operators(&expr));
+
+        let tokens = get_tokens(&String::from("!a.b")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        match expr {
+            Expression::UnaryOperator(op, operand) => {
+                assert_eq!("!", op.value);
+                assert!(matches!(*operand, Expression::BinaryOperator(_, _, _)));
+            }
+            _ => panic!("Expected an unary operation"),
+        }
+    }
+
+    #[test]
+    fn should_parse_logic_operators_with_precedence() {
+        let tokens = get_tokens(&String::from("a == b || c && !d")).unwrap();
+        let (expr, next) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(8, next);
+        assert_eq!(("||", Some("=="), Some("&&")), operators(&expr));
+
+        let tokens = get_tokens(&String::from("a ?? b || c")).unwrap();
+        let (expr, _) = Expression::try_parse(&tokens, 0).unwrap();
+        assert_eq!(("??", None, Some("||")), operators(&expr));
+    }
 }
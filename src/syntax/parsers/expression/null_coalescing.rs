@@ -0,0 +1,95 @@
+use crate::{
+    lexic::token::Token,
+    syntax::{ast::Expression, ParsingError, ParsingResult},
+};
+
+use super::utils::try_binary_op;
+
+/// Parses a null coalescing expression.
+///
+/// The operator is right associative: `a ?? b ?? c` is `a ?? (b ?? c)`
+///
+/// ```ebnf
+/// null_coalescing = logic_or, ("??", null_coalescing)?;
+/// ```
+pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
+    let (logic_or, next_pos) = match super::logic_or::try_parse(tokens, pos) {
+        Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
+        _ => return Err(ParsingError::Unmatched),
+    };
+
+    try_binary_op(
+        tokens,
+        next_pos,
+        logic_or,
+        &vec!["??"],
+        0,
+        |tokens, next_pos, prev_expr, token, _| match try_parse(tokens, next_pos) {
+            Ok((expr, next_pos)) => {
+                let expr = Expression::BinaryOperator(Box::new(prev_expr), Box::new(expr), token);
+                Ok((expr, next_pos))
+            }
+            Err(ParsingError::Err(error)) => Err(ParsingError::Err(error)),
+            _ => Err(ParsingError::Unmatched),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    #[test]
+    fn should_parse_null_coalescing() {
+        let tokens = get_tokens(&String::from("a ?? b")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(3, next);
+        match result {
+            Expression::BinaryOperator(left, right, op) => {
+                assert_eq!("??", op.value);
+                assert!(matches!(*left, Expression::Identifier(_)));
+                assert!(matches!(*right, Expression::Identifier(_)));
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_be_right_associative() {
+        let tokens = get_tokens(&String::from("a ?? b ?? c")).unwrap();
+        let (result, _) = try_parse(&tokens, 0).unwrap();
+
+        match result {
+            Expression::BinaryOperator(left, right, _) => {
+                assert!(matches!(*left, Expression::Identifier(_)));
+                assert!(matches!(*right, Expression::BinaryOperator(_, _, _)));
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_bind_less_tightly_than_equality() {
+        let tokens = get_tokens(&String::from("a == b ?? c")).unwrap();
+        let (result, _) = try_parse(&tokens, 0).unwrap();
+
+        match result {
+            Expression::BinaryOperator(left, _, op) => {
+                assert_eq!("??", op.value);
+                assert!(matches!(*left, Expression::BinaryOperator(_, _, _)));
+            }
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_not_parse_unfinished_null_coalescing() {
+        let tokens = get_tokens(&String::from("a ??")).unwrap();
+        let result = try_parse(&tokens, 0);
+
+        assert!(matches!(result, Err(ParsingError::Unmatched)));
+    }
+}
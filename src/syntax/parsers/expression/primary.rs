@@ -1,7 +1,10 @@
+use std::cell::Cell;
+
 use crate::{
+    error_handling::{error_messages::SYNTAX_INVALID_ENUM_DECLARATION, ErrorContainer, ErrorLabel},
     lexic::token::{Token, TokenType},
     syntax::{
-        ast::{Array, Expression},
+        ast::{functions::Lambda, match_expression::Match, Array, Expression, Identifier},
         parseable::Parseable,
         utils::Tokenizer,
         ParsingError, ParsingResult,
@@ -15,7 +18,11 @@ use crate::{
 ///         | number
 ///         | string
 ///         | boolean
+///         | "null"
 ///         | identifier
+///         | datatype, ("::", datatype)?
+///         | match
+///         | lambda
 ///         | ("(", expression, ")");
 /// ```
 pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
@@ -34,7 +41,30 @@ pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
             TokenType::Identifier if token.value == "true" || token.value == "false" => {
                 Ok((Expression::Boolean(&token), token_pos + 1))
             }
-            TokenType::Identifier => Ok((Expression::Identifier(&token), token_pos + 1)),
+            TokenType::Identifier if token.value == "null" => {
+                Ok((Expression::Null(&token), token_pos + 1))
+            }
+            TokenType::Identifier => Ok((
+                Expression::Identifier(Identifier::new(&token)),
+                token_pos + 1,
+            )),
+            // A variant of an enum, e.g. `Shape::Circle`
+            TokenType::Datatype if is_variant_access(tokens, token_pos + 1) => {
+                parse_enum_variant(tokens, token, token_pos + 1)
+            }
+            // The name of a class, which is called to create an instance
+            TokenType::Datatype => Ok((
+                Expression::Identifier(Identifier::new(&token)),
+                token_pos + 1,
+            )),
+            TokenType::MATCH => match Match::try_parse(tokens, token_pos) {
+                Ok((match_expression, next)) => Ok((Expression::Match(match_expression), next)),
+                Err(err) => Err(err),
+            },
+            TokenType::FN => match Lambda::try_parse(tokens, token_pos) {
+                Ok((lambda, next)) => Ok((Expression::Lambda(lambda), next)),
+                Err(err) => Err(err),
+            },
             TokenType::LeftParen => parse_parenthesized_expression(tokens, token_pos),
             _ => Err(ParsingError::Unmatched),
         },
@@ -42,6 +72,41 @@ pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
     }
 }
 
+/// Checks if the token at `pos` is the `::` operator, right after a datatype
+fn is_variant_access(tokens: &Vec<Token>, pos: usize) -> bool {
+    matches!(tokens.get(pos), Some(t) if t.token_type == TokenType::Operator && t.value == "::")
+}
+
+/// Parses the name of a variant, after the name of its enum & `::`
+fn parse_enum_variant<'a>(
+    tokens: &'a Vec<Token>,
+    enum_name: &'a Token,
+    pos: usize,
+) -> ParsingResult<'a, Expression<'a>> {
+    match tokens.get(pos + 1) {
+        Some(variant) if variant.token_type == TokenType::Datatype => Ok((
+            Expression::EnumVariant(enum_name, variant, Cell::new(false)),
+            pos + 2,
+        )),
+        _ => {
+            let operator = &tokens[pos];
+            let label = ErrorLabel {
+                message: String::from("Expected the name of a variant after this `::`"),
+                start: operator.position,
+                end: operator.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_ENUM_DECLARATION,
+                error_offset: operator.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            Err(ParsingError::Err(econtainer))
+        }
+    }
+}
+
 fn parse_parenthesized_expression(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
     let expression = Expression::try_parse(tokens, pos + 1);
     match expression {
@@ -52,6 +117,7 @@ fn parse_parenthesized_expression(tokens: &Vec<Token>, pos: usize) -> ParsingRes
             },
             None => Err(ParsingError::Unmatched),
         },
+        Err(ParsingError::Err(error)) => Err(ParsingError::Err(error)),
         _ => Err(ParsingError::Unmatched),
     }
 }
@@ -98,6 +164,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_parse_null() {
+        let tokens = get_tokens(&String::from("null")).unwrap();
+        let expression = try_parse(&tokens, 0);
+
+        match expression {
+            Ok((Expression::Null(value), _)) => assert_eq!(value.value, "null"),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn should_parse_an_identifier() {
         let tokens = get_tokens(&String::from("someIdentifier")).unwrap();
@@ -123,4 +200,30 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn should_parse_enum_variant() {
+        let tokens = get_tokens(&String::from("Shape::Circle")).unwrap();
+
+        match try_parse(&tokens, 0) {
+            Ok((Expression::EnumVariant(enum_name, variant, _), 3)) => {
+                assert_eq!("Shape", enum_name.value);
+                assert_eq!("Circle", variant.value);
+            }
+            _ => panic!("Expected an enum variant"),
+        }
+    }
+
+    #[test]
+    fn should_fail_on_variant_access_without_variant() {
+        let tokens = get_tokens(&String::from("Shape::circle")).unwrap();
+
+        match try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => {
+                assert_eq!(SYNTAX_INVALID_ENUM_DECLARATION, error.error_code);
+                assert_eq!(5, error.error_offset);
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
 }
@@ -13,10 +13,18 @@ use super::utils::parse_many;
 pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
     let (factor, next_pos) = match super::factor::try_parse(tokens, pos) {
         Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
         _ => return Err(ParsingError::Unmatched),
     };
 
-    parse_many(tokens, next_pos, factor, 0, &vec!["-", "+", "++"])
+    parse_many(
+        tokens,
+        next_pos,
+        factor,
+        0,
+        &vec!["-", "+", "++"],
+        super::factor::try_parse,
+    )
 }
 
 #[cfg(test)]
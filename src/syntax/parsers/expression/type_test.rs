@@ -0,0 +1,105 @@
+use crate::{
+    error_handling::{error_messages::SYNTAX_INVALID_DATATYPE, ErrorContainer, ErrorLabel},
+    lexic::token::Token,
+    syntax::{
+        ast::{datatype::Datatype, Expression, TypeTest},
+        parseable::Parseable,
+        ParsingError, ParsingResult,
+    },
+};
+
+use super::utils::try_binary_op;
+
+/// Parses a type test expression.
+///
+/// ```ebnf
+/// type_test = term, ("is", datatype)?;
+/// ```
+pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
+    let (term, next_pos) = match super::term::try_parse(tokens, pos) {
+        Ok((expr, next_pos)) => (expr, next_pos),
+        Err(ParsingError::Err(error)) => return Err(ParsingError::Err(error)),
+        _ => return Err(ParsingError::Unmatched),
+    };
+
+    try_binary_op(
+        tokens,
+        next_pos,
+        term,
+        &vec!["is"],
+        0,
+        |tokens, next_pos, prev_expr, token, _| match Datatype::try_parse(tokens, next_pos) {
+            Ok((datatype, next_pos)) => {
+                let expr = Expression::TypeTest(TypeTest {
+                    expression: Box::new(prev_expr),
+                    datatype,
+                });
+                Ok((expr, next_pos))
+            }
+            Err(ParsingError::Err(error)) => Err(ParsingError::Err(error)),
+            _ => {
+                let label = ErrorLabel {
+                    message: String::from("Expected a datatype after `is`"),
+                    start: token.position,
+                    end: token.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_DATATYPE,
+                    error_offset: token.position,
+                    labels: vec![label],
+                    note: None,
+                    help: None,
+                };
+                Err(ParsingError::Err(econtainer))
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    #[test]
+    fn should_parse_type_test() {
+        let tokens = get_tokens(&String::from("value is Int | String")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(5, next);
+        match result {
+            Expression::TypeTest(test) => {
+                assert!(matches!(*test.expression, Expression::Identifier(_)));
+                assert_eq!("Int", test.datatype.name.value);
+                assert_eq!(1, test.datatype.alternatives.len());
+            }
+            _ => panic!("Expected a type test"),
+        }
+    }
+
+    #[test]
+    fn should_bind_more_tightly_than_comparison() {
+        let tokens = get_tokens(&String::from("a + b is Int == c")).unwrap();
+        let (result, next) = try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(5, next);
+        match result {
+            Expression::TypeTest(test) => {
+                assert!(matches!(*test.expression, Expression::BinaryOperator(..)));
+            }
+            _ => panic!("Expected a type test"),
+        }
+    }
+
+    #[test]
+    fn should_fail_on_missing_datatype() {
+        let tokens = get_tokens(&String::from("value is 10")).unwrap();
+        match try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => {
+                assert_eq!(SYNTAX_INVALID_DATATYPE, error.error_code);
+                assert_eq!(6, error.error_offset);
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     lexic::token::Token,
-    syntax::{ast::Expression, parseable::Parseable, ParsingError, ParsingResult},
+    syntax::{ast::Expression, ParsingError, ParsingResult},
 };
 
 use super::function_call_expr;
@@ -8,17 +8,21 @@ use super::function_call_expr;
 /// Parses an unary expression.
 ///
 /// ```ebnf
-/// unary = ("!" | "-"), expression
+/// unary = ("!" | "-"), function call expr
 ///       | function call expr;
 /// ```
+///
+/// Unary operators bind tighter than binary operators:
+/// `-a + b` is `(-a) + b` and `!a == b` is `(!a) == b`
 pub fn try_parse(tokens: &Vec<Token>, pos: usize) -> ParsingResult<Expression> {
     match tokens.get(pos) {
         Some(token) if token.value == "!" || token.value == "-" => {
-            match Expression::try_parse(tokens, pos + 1) {
+            match function_call_expr::try_parse(tokens, pos + 1) {
                 Ok((expression, next_pos)) => Ok((
                     Expression::UnaryOperator(&token, Box::new(expression)),
                     next_pos,
                 )),
+                Err(ParsingError::Err(error)) => Err(ParsingError::Err(error)),
                 _ => Err(ParsingError::Unmatched),
             }
         }
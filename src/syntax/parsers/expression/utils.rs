@@ -88,23 +88,24 @@ where
     Ok((new_expr, next_pos))
 }
 
+/// Parses a chain of left associative binary operators, where
+/// each operand is parsed with `operand`. E.g. in `a + b - c`
+/// the operands are factors, and the result is `(a + b) - c`
 pub fn parse_many<'a>(
     tokens: &'a Vec<Token>,
     pos: usize,
     prev_expr: Expression<'a>,
     indentation_level: u32,
     operators: &Vec<&str>,
+    operand: fn(&'a Vec<Token>, usize) -> ParsingResult<'a, Expression<'a>>,
 ) -> ParsingResult<'a, Expression<'a>> {
-    // comparison = term, ((">" | ">=" | "<" | "<="), term)*;
     try_binary_op(
         tokens,
         pos,
         prev_expr,
         operators,
         indentation_level,
-        |tokens, next_pos, prev_expr, token, indent_count: u32| match super::term::try_parse(
-            tokens, next_pos,
-        ) {
+        |tokens, next_pos, prev_expr, token, indent_count: u32| match operand(tokens, next_pos) {
             Ok((expr, next_pos)) => {
                 let expr = Expression::BinaryOperator(Box::new(prev_expr), Box::new(expr), &token);
 
@@ -114,8 +115,10 @@ pub fn parse_many<'a>(
                     expr,
                     indentation_level + indent_count,
                     operators,
+                    operand,
                 )
             }
+            Err(ParsingError::Err(error)) => Err(ParsingError::Err(error)),
             _ => return Err(ParsingError::Unmatched),
         },
     )
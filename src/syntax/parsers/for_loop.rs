@@ -22,6 +22,9 @@ impl<'a> Parseable<'a> for ForLoop<'a> {
         let (first_id, next) = match parse_token_type(tokens, next, TokenType::Identifier) {
             Ok(t) => t,
             Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(e)) => {
                 let label = ErrorLabel {
                     message: String::from("Expected an identifier here, after the `for` keyword"),
@@ -67,6 +70,9 @@ impl<'a> Parseable<'a> for ForLoop<'a> {
             match parse_token_type(&tokens, next, TokenType::Identifier) {
                 Ok((second_id, next)) => (Some(second_id), next),
                 Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+                Err(ParsingError::Recovered(errors, next)) => {
+                    return Err(ParsingError::Recovered(errors, next))
+                }
                 Err(ParsingError::Mismatch(t)) => {
                     let label = ErrorLabel {
                         message: String::from("Expected an identifier here, after the comma"),
@@ -108,6 +114,9 @@ impl<'a> Parseable<'a> for ForLoop<'a> {
         let (in_keyword, next) = match parse_token_type(tokens, next, TokenType::IN) {
             Ok(tuple) => tuple,
             Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(t)) => {
                 let label = ErrorLabel {
                     message: String::from("Expected the `in` keyword here"),
@@ -170,6 +179,9 @@ impl<'a> Parseable<'a> for ForLoop<'a> {
         let (block, next) = match Block::try_parse(tokens, next) {
             Ok(t) => t,
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(wrong_token)) => {
                 let label = ErrorLabel {
                     message: String::from("Expected a block here"),
@@ -1,10 +1,15 @@
+use std::cell::{Cell, RefCell};
+
 use crate::{
     error_handling::{
         error_messages::SYNTAX_INVALID_FUNCTION_DECLARATION, ErrorContainer, ErrorLabel,
     },
     lexic::token::{Token, TokenType},
     syntax::{
-        ast::{Block, FunctionDeclaration, Positionable},
+        ast::{
+            datatype::Datatype, interface::FunctionSignature, Block, FunctionDeclaration,
+            ParamsList, Positionable,
+        },
         functions::params_list::parse_params_list,
         parseable::{Parseable, ParsingError, ParsingResult},
         utils::{parse_token_type, try_operator, Tokenizer},
@@ -15,70 +20,229 @@ impl<'a> Parseable<'a> for FunctionDeclaration<'a> {
     type Item = FunctionDeclaration<'a>;
 
     fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
-        let mut current_pos = current_pos;
+        let (signature, mut current_pos) = parse_function_signature(tokens, current_pos)?;
+        let FunctionSignature {
+            identifier,
+            type_params,
+            params_list,
+            return_type,
+            ..
+        } = signature;
 
-        // `fun` keyword
-        let (fun_keyword, next_pos) = match parse_token_type(tokens, current_pos, TokenType::FUN) {
-            Ok((t, next)) => (t, next),
-            _ => return Err(ParsingError::Unmatched),
-        };
+        // Function body (block)
+        let (block, next_pos) =
+            parse_function_body(tokens, current_pos, &params_list, &return_type)?;
         current_pos = next_pos;
 
-        // identifier
-        let (identifier, next_pos) =
-            match parse_token_type(tokens, current_pos, TokenType::Identifier) {
-                Ok((id, next)) => (id, next),
-                Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
-                Err(ParsingError::Mismatch(wrong_token)) => {
-                    let label = ErrorLabel {
-                        message: String::from("Expected an identifier here"),
-                        start: wrong_token.position,
-                        end: wrong_token.get_end_position(),
-                    };
-                    let econtainer = ErrorContainer {
-                        error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
-                        error_offset: wrong_token.position,
-                        labels: vec![label],
-                        note: None,
-                        help: None,
-                    };
-                    return Err(ParsingError::Err(econtainer));
-                }
-                Err(ParsingError::Unmatched) => {
-                    let label = ErrorLabel {
-                        message: String::from("Expected an identifier after this `fun` keyword"),
-                        start: fun_keyword.position,
-                        end: fun_keyword.get_end_position(),
-                    };
-                    let econtainer = ErrorContainer {
-                        error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
-                        error_offset: fun_keyword.position,
-                        labels: vec![label],
-                        note: None,
-                        help: None,
-                    };
-                    return Err(ParsingError::Err(econtainer));
-                }
+        // Construct and return the function declaration
+        Ok((
+            FunctionDeclaration {
+                identifier: &identifier,
+                type_params,
+                return_type,
+                params_list: Box::new(params_list),
+                block: Box::new(block),
+                captures: RefCell::new(vec![]),
+                is_nested: Cell::new(false),
+            },
+            current_pos,
+        ))
+    }
+}
+
+/// Parses the declaration of a function up to its body.
+///
+/// ```ebnf
+/// function signature = "fun", identifier, type parameters?, params list, ("->", Datatype)?;
+/// ```
+pub fn parse_function_signature<'a>(
+    tokens: &'a Vec<Token>,
+    current_pos: usize,
+) -> ParsingResult<'a, FunctionSignature<'a>> {
+    let mut current_pos = current_pos;
+
+    // `fun` keyword
+    let (fun_keyword, next_pos) = match parse_token_type(tokens, current_pos, TokenType::FUN) {
+        Ok((t, next)) => (t, next),
+        _ => return Err(ParsingError::Unmatched),
+    };
+    current_pos = next_pos;
+
+    // identifier
+    let (identifier, next_pos) = match parse_token_type(tokens, current_pos, TokenType::Identifier)
+    {
+        Ok((id, next)) => (id, next),
+        Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+        Err(ParsingError::Recovered(errors, next)) => {
+            return Err(ParsingError::Recovered(errors, next))
+        }
+        Err(ParsingError::Mismatch(wrong_token)) => {
+            let label = ErrorLabel {
+                message: String::from("Expected an identifier here"),
+                start: wrong_token.position,
+                end: wrong_token.get_end_position(),
             };
-        current_pos = next_pos;
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
+                error_offset: wrong_token.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            return Err(ParsingError::Err(econtainer));
+        }
+        Err(ParsingError::Unmatched) => {
+            let label = ErrorLabel {
+                message: String::from("Expected an identifier after this `fun` keyword"),
+                start: fun_keyword.position,
+                end: fun_keyword.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
+                error_offset: fun_keyword.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            return Err(ParsingError::Err(econtainer));
+        }
+    };
+    current_pos = next_pos;
+
+    // Type parameters, optional
+    let (type_params, next_pos) = match parse_type_params(tokens, current_pos) {
+        Ok((type_params, next)) => (type_params, next),
+        Err(ParsingError::Unmatched) | Err(ParsingError::Mismatch(_)) => (vec![], current_pos),
+        Err(error) => return Err(error),
+    };
+    current_pos = next_pos;
+
+    // Params list & return type
+    let ((params_list, return_type), next_pos) =
+        parse_params_and_return_type(tokens, current_pos, identifier)?;
+    current_pos = next_pos;
+
+    let signature = FunctionSignature {
+        keyword: fun_keyword,
+        identifier,
+        type_params,
+        params_list,
+        return_type,
+    };
+    Ok((signature, current_pos))
+}
+
+/// Parses the parameters of a function and its return type, if any.
+/// `previous` is the token before the parameters, the name
+/// of the function or the `fn` keyword of a lambda.
+///
+/// ```ebnf
+/// params and return type = params list, ("->", Datatype)?;
+/// ```
+pub fn parse_params_and_return_type<'a>(
+    tokens: &'a Vec<Token>,
+    current_pos: usize,
+    previous: &'a Token,
+) -> ParsingResult<'a, (ParamsList<'a>, Option<Datatype<'a>>)> {
+    let mut current_pos = current_pos;
+    let previous_name = match previous.token_type {
+        TokenType::FN => "`fn` keyword",
+        _ => "identifier",
+    };
+
+    // Params list
+    // TODO: impl Parseable
+    let (params_list, next_pos) = match parse_params_list(tokens, current_pos) {
+        Ok((params, next_pos)) => (params, next_pos),
+        Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+        Err(ParsingError::Recovered(errors, next)) => {
+            return Err(ParsingError::Recovered(errors, next))
+        }
+        Err(ParsingError::Mismatch(wrong_token)) => {
+            let label = ErrorLabel {
+                message: String::from("Expected a parameter list here"),
+                start: wrong_token.position,
+                end: wrong_token.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
+                error_offset: wrong_token.get_end_position(),
+                labels: vec![label],
+                note: Some(String::from(
+                    "If this function doesn't take any parameter, use an empty list `()`",
+                )),
+                help: None,
+            };
+            return Err(ParsingError::Err(econtainer));
+        }
+        Err(ParsingError::Unmatched) => {
+            let label = ErrorLabel {
+                message: format!("Expected a parameter list after this {}", previous_name),
+                start: previous.position,
+                end: previous.get_end_position(),
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
+                error_offset: previous.get_end_position(),
+                labels: vec![label],
+                note: Some(String::from(
+                    "If this function doesn't take any parameter, use an empty list `()`",
+                )),
+                help: None,
+            };
+            return Err(ParsingError::Err(econtainer));
+        }
+    };
+    current_pos = next_pos;
+
+    // Only the parameters of a class constructor declare properties
+    if let Some(modifier) = params_list.parameters.iter().find_map(|p| p.modifier) {
+        let label = ErrorLabel {
+            message: format!(
+                "The parameters of a function can't be declared with `{}`",
+                modifier.value
+            ),
+            start: modifier.position,
+            end: modifier.get_end_position(),
+        };
+        let econtainer = ErrorContainer {
+            error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
+            error_offset: modifier.position,
+            labels: vec![label],
+            note: Some(String::from(
+                "Only the parameters of a class constructor can declare properties",
+            )),
+            help: None,
+        };
+        return Err(ParsingError::Err(econtainer));
+    }
+
+    // Try to parse a return type
+    let (return_type, next_pos) = 'return_label: {
+        let (arrow_op, next_pos) = match try_operator(tokens, current_pos, "->".into()) {
+            Ok((op, next)) => (op, next),
+            _ => break 'return_label (None, current_pos),
+        };
 
-        // Params list
-        // TODO: impl Parseable
-        let (params_list, next_pos) = match parse_params_list(tokens, current_pos) {
-            Ok((params, next_pos)) => (params, next_pos),
+        // At this point the '->' operator was matched, so we expect a datatype
+        match Datatype::try_parse(tokens, next_pos) {
+            Ok((t, next)) => (Some(t), next),
             Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(wrong_token)) => {
                 let label = ErrorLabel {
-                    message: String::from("Expected a parameter list here"),
+                    message: String::from("Expected a Datatype here"),
                     start: wrong_token.position,
                     end: wrong_token.get_end_position(),
                 };
                 let econtainer = ErrorContainer {
                     error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
-                    error_offset: wrong_token.get_end_position(),
+                    error_offset: wrong_token.position,
                     labels: vec![label],
                     note: Some(String::from(
-                        "If this function doesn't take any parameter, use an empty list `()`",
+                        "If you want a function without a return type, omit the arrow as well",
                     )),
                     help: None,
                 };
@@ -86,39 +250,106 @@ impl<'a> Parseable<'a> for FunctionDeclaration<'a> {
             }
             Err(ParsingError::Unmatched) => {
                 let label = ErrorLabel {
-                    message: String::from("Expected a parameter list after this identifier"),
-                    start: identifier.position,
-                    end: identifier.get_end_position(),
+                    message: String::from("Expected a Datatype after this arrow `->` operator"),
+                    start: arrow_op.position,
+                    end: arrow_op.get_end_position(),
                 };
                 let econtainer = ErrorContainer {
                     error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
-                    error_offset: identifier.get_end_position(),
+                    error_offset: arrow_op.position,
                     labels: vec![label],
                     note: Some(String::from(
-                        "If this function doesn't take any parameter, use an empty list `()`",
+                        "If you want a function without a return type, omit the arrow as well",
                     )),
                     help: None,
                 };
                 return Err(ParsingError::Err(econtainer));
             }
-        };
-        current_pos = next_pos;
+        }
+    };
+
+    Ok(((params_list, return_type), next_pos))
+}
 
-        // Try to parse a return type
-        // TODO: abstract parsing of Datatype to parse generics
-        let (return_type, next_pos) = 'return_label: {
-            let (arrow_op, next_pos) = match try_operator(tokens, current_pos, "->".into()) {
-                Ok((op, next)) => (op, next),
-                _ => break 'return_label (None, current_pos),
+/// Parses the body of a function, after its parameters and return type
+pub fn parse_function_body<'a>(
+    tokens: &'a Vec<Token>,
+    current_pos: usize,
+    params_list: &ParamsList,
+    return_type: &Option<Datatype>,
+) -> ParsingResult<'a, Block<'a>> {
+    match Block::try_parse(tokens, current_pos) {
+        Ok(tuple) => Ok(tuple),
+        Err(ParsingError::Err(error)) => Err(ParsingError::Err(error)),
+        Err(ParsingError::Recovered(errors, next)) => Err(ParsingError::Recovered(errors, next)),
+        Err(ParsingError::Mismatch(wrong_token)) => {
+            let label = ErrorLabel {
+                message: String::from("Expected a block here, after the function declaration"),
+                start: wrong_token.position,
+                end: wrong_token.get_end_position(),
             };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
+                error_offset: wrong_token.position,
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            Err(ParsingError::Err(econtainer))
+        }
+        Err(ParsingError::Unmatched) => {
+            let (error_start, error_end) = {
+                if let Some(return_type) = return_type {
+                    return_type.get_position()
+                } else {
+                    params_list.get_position()
+                }
+            };
+            let label = ErrorLabel {
+                message: String::from("Expected a block after this parameter list"),
+                start: error_start,
+                end: error_end,
+            };
+            let econtainer = ErrorContainer {
+                error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
+                error_offset: tokens.code_position_from_idx(current_pos),
+                labels: vec![label],
+                note: None,
+                help: None,
+            };
+            Err(ParsingError::Err(econtainer))
+        }
+    }
+}
 
-            // At this point the '->' operator was matched, so we expect a datatype
-            match parse_token_type(tokens, next_pos, TokenType::Datatype) {
-                Ok((t, next)) => (Some(t), next),
-                Err(ParsingError::Err(err)) => return Err(ParsingError::Err(err)),
+/// Parses the type parameters of a function, e.g. `[T, U]`
+///
+/// ```ebnf
+/// type parameters = "[", Datatype, (",", Datatype)*, ","?, "]";
+/// ```
+fn parse_type_params<'a>(
+    tokens: &'a Vec<Token>,
+    current_pos: usize,
+) -> ParsingResult<'a, Vec<&'a Token>> {
+    let (opening_bracket, next_pos) =
+        parse_token_type(tokens, current_pos, TokenType::LeftBracket)?;
+
+    let mut type_params = Vec::new();
+    let mut current_pos = next_pos;
+    loop {
+        // Closing bracket, after the type parameters or a trailing comma
+        match parse_token_type(tokens, current_pos, TokenType::RightBracket) {
+            Ok((_, next)) if !type_params.is_empty() => return Ok((type_params, next)),
+            _ => {}
+        }
+
+        // Type parameter
+        let (type_param, next_pos) =
+            match parse_token_type(tokens, current_pos, TokenType::Datatype) {
+                Ok(tuple) => tuple,
                 Err(ParsingError::Mismatch(wrong_token)) => {
                     let label = ErrorLabel {
-                        message: String::from("Expected a Datatype here"),
+                        message: String::from("Expected a type parameter here"),
                         start: wrong_token.position,
                         end: wrong_token.get_end_position(),
                     };
@@ -127,89 +358,57 @@ impl<'a> Parseable<'a> for FunctionDeclaration<'a> {
                         error_offset: wrong_token.position,
                         labels: vec![label],
                         note: Some(String::from(
-                            "If you want a function without a return type, omit the arrow as well",
+                            "Type parameters are datatypes, e.g. `fun first[T](Array[T] items)`",
                         )),
                         help: None,
                     };
                     return Err(ParsingError::Err(econtainer));
                 }
-                Err(ParsingError::Unmatched) => {
+                Err(_) => {
                     let label = ErrorLabel {
-                        message: String::from("Expected a Datatype after this arrow `->` operator"),
-                        start: arrow_op.position,
-                        end: arrow_op.get_end_position(),
+                        message: String::from("Expected a type parameter after this bracket"),
+                        start: opening_bracket.position,
+                        end: opening_bracket.get_end_position(),
                     };
                     let econtainer = ErrorContainer {
                         error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
-                        error_offset: arrow_op.position,
+                        error_offset: opening_bracket.position,
                         labels: vec![label],
-                        note: Some(String::from(
-                            "If you want a function without a return type, omit the arrow as well",
-                        )),
+                        note: None,
                         help: None,
                     };
                     return Err(ParsingError::Err(econtainer));
                 }
-            }
-        };
+            };
+        type_params.push(type_param);
         current_pos = next_pos;
 
-        // Function body (block)
-        let (block, next_pos) = match Block::try_parse(tokens, current_pos) {
-            Ok((block, next_pos)) => (block, next_pos),
-            Err(ParsingError::Err(error)) => {
-                return Err(ParsingError::Err(error));
-            }
-            Err(ParsingError::Mismatch(wrong_token)) => {
+        // Comma or closing bracket
+        match parse_token_type(tokens, current_pos, TokenType::Comma) {
+            Ok((_, next)) => current_pos = next,
+            Err(_) if parse_token_type(tokens, current_pos, TokenType::RightBracket).is_ok() => {}
+            Err(_) => {
+                let wrong_token = &tokens[current_pos];
                 let label = ErrorLabel {
-                    message: String::from("Expected a block here, after the function declaration"),
+                    message: String::from("Expected a comma `,` or a closing bracket `]` here"),
                     start: wrong_token.position,
                     end: wrong_token.get_end_position(),
                 };
-                let econtainer = ErrorContainer {
-                    error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
-                    error_offset: wrong_token.position,
-                    labels: vec![label],
-                    note: None,
-                    help: None,
-                };
-                return Err(ParsingError::Err(econtainer));
-            }
-            Err(ParsingError::Unmatched) => {
-                let (error_start, error_end) = {
-                    if let Some(return_type) = return_type {
-                        (return_type.position, return_type.get_end_position())
-                    } else {
-                        params_list.get_position()
-                    }
-                };
-                let label = ErrorLabel {
-                    message: String::from("Expected a block after this parameter list"),
-                    start: error_start,
-                    end: error_end,
+                let label_2 = ErrorLabel {
+                    message: String::from("The type parameters start here"),
+                    start: opening_bracket.position,
+                    end: opening_bracket.get_end_position(),
                 };
                 let econtainer = ErrorContainer {
                     error_code: SYNTAX_INVALID_FUNCTION_DECLARATION,
-                    error_offset: tokens.code_position_from_idx(current_pos),
-                    labels: vec![label],
+                    error_offset: wrong_token.position,
+                    labels: vec![label, label_2],
                     note: None,
                     help: None,
                 };
                 return Err(ParsingError::Err(econtainer));
             }
-        };
-        current_pos = next_pos;
-
-        // Construct and return the function declaration
-        Ok((
-            FunctionDeclaration {
-                identifier: &identifier,
-                return_type,
-                params_list: Box::new(params_list),
-                block: Box::new(block),
-            },
-            current_pos,
-        ))
+        }
     }
 }
 
@@ -414,7 +613,7 @@ mod tests {
         let (function_declaration, _) = FunctionDeclaration::try_parse(&tokens, 0).unwrap();
 
         assert_eq!(function_declaration.identifier.value, String::from("id"));
-        assert_eq!(function_declaration.return_type, None);
+        assert!(function_declaration.return_type.is_none());
     }
 
     #[test]
@@ -424,11 +623,52 @@ mod tests {
 
         assert_eq!(function_declaration.identifier.value, String::from("id"));
         assert_eq!(
-            function_declaration.return_type.unwrap().value,
+            function_declaration.return_type.unwrap().name.value,
             String::from("String")
         );
     }
 
+    #[test]
+    fn should_parse_type_params() {
+        let tokens = get_tokens(&String::from("fun pair[K, V,](K key, V value) {}")).unwrap();
+        let (function_declaration, _) = FunctionDeclaration::try_parse(&tokens, 0).unwrap();
+
+        let type_params: Vec<_> = function_declaration
+            .type_params
+            .iter()
+            .map(|t| t.value.as_str())
+            .collect();
+        assert_eq!(vec!["K", "V"], type_params);
+        assert_eq!(2, function_declaration.params_list.parameters.len());
+    }
+
+    #[test]
+    fn should_fail_on_invalid_type_params() {
+        let tokens = get_tokens(&String::from("fun id[]() {}")).unwrap();
+        let fun_decl = FunctionDeclaration::try_parse(&tokens, 0);
+        match fun_decl {
+            Err(ParsingError::Err(err)) => {
+                assert_eq!(err.error_code, SYNTAX_INVALID_FUNCTION_DECLARATION);
+                assert_eq!(err.error_offset, 7);
+                assert_eq!(err.labels[0].message, "Expected a type parameter here");
+            }
+            _ => panic!("Expected an error: {:?}", fun_decl),
+        }
+
+        let tokens = get_tokens(&String::from("fun id[T U]() {}")).unwrap();
+        let fun_decl = FunctionDeclaration::try_parse(&tokens, 0);
+        match fun_decl {
+            Err(ParsingError::Err(err)) => {
+                assert_eq!(err.error_offset, 9);
+                assert_eq!(
+                    err.labels[0].message,
+                    "Expected a comma `,` or a closing bracket `]` here"
+                );
+            }
+            _ => panic!("Expected an error: {:?}", fun_decl),
+        }
+    }
+
     #[test]
     fn should_throw_error_on_return_type_1() {
         let tokens = get_tokens(&String::from("fun id() -> {}")).unwrap();
@@ -514,4 +754,20 @@ mod whitespace_test {
         let (declaration, _) = FunctionDeclaration::try_parse(&tokens, 0).unwrap();
         assert_eq!(declaration.identifier.value, (String::from("id")));
     }
+
+    #[test]
+    fn should_not_parse_parameters_with_modifiers() {
+        let tokens = get_tokens(&String::from("fun id(val Int x) {}")).unwrap();
+        let error = match FunctionDeclaration::try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => error,
+            _ => panic!("Expected an error"),
+        };
+
+        assert_eq!(SYNTAX_INVALID_FUNCTION_DECLARATION, error.error_code);
+        assert_eq!(7, error.error_offset);
+        assert_eq!(
+            "The parameters of a function can't be declared with `val`",
+            error.labels[0].message
+        );
+    }
 }
@@ -0,0 +1,258 @@
+use crate::{
+    error_handling::{
+        error_messages::SYNTAX_INVALID_INTERFACE_DECLARATION, ErrorContainer, ErrorLabel,
+    },
+    lexic::token::{Token, TokenType},
+    syntax::{
+        ast::interface::InterfaceDeclaration,
+        parseable::{Parseable, ParsingError, ParsingResult},
+        utils::{parse_token_type, Tokenizer},
+    },
+};
+
+use super::function_declaration::parse_function_signature;
+
+/// Parses an interface declaration.
+///
+/// ```ebnf
+/// interface declaration = "interface", datatype, "{", function signature*, "}";
+/// ```
+impl<'a> Parseable<'a> for InterfaceDeclaration<'a> {
+    type Item = InterfaceDeclaration<'a>;
+
+    fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
+        // `interface` keyword
+        let (keyword, next_pos) = match parse_token_type(tokens, current_pos, TokenType::INTERFACE)
+        {
+            Ok(tuple) => tuple,
+            _ => return Err(ParsingError::Unmatched),
+        };
+
+        // The name of the interface
+        let (identifier, next_pos) =
+            match parse_token_type(tokens, next_pos, TokenType::Datatype) {
+                Ok(tuple) => tuple,
+                Err(ParsingError::Mismatch(wrong_token)) => return Err(interface_error(
+                    "Expected the name of the interface here, starting with an uppercase letter",
+                    wrong_token.position,
+                    wrong_token.get_end_position(),
+                )),
+                _ => {
+                    return Err(interface_error(
+                        "Expected the name of the interface after this `interface` keyword",
+                        keyword.position,
+                        keyword.get_end_position(),
+                    ))
+                }
+            };
+
+        // The opening brace
+        let (opening_brace, mut current_pos) =
+            match parse_token_type(tokens, next_pos, TokenType::LeftBrace) {
+                Ok(tuple) => tuple,
+                _ => {
+                    return Err(interface_error(
+                        "Expected the methods of the interface after its name, between braces",
+                        identifier.position,
+                        identifier.get_end_position(),
+                    ))
+                }
+            };
+
+        // The methods
+        let mut methods = Vec::new();
+        loop {
+            let (method, next_pos) = match parse_function_signature(tokens, current_pos) {
+                Ok(tuple) => tuple,
+                Err(ParsingError::Unmatched) | Err(ParsingError::Mismatch(_)) => break,
+                Err(error) => return Err(error),
+            };
+
+            // The classes that implement the interface define the body
+            if let Ok((brace, _)) = parse_token_type(tokens, next_pos, TokenType::LeftBrace) {
+                let label = ErrorLabel {
+                    message: String::from("The methods of an interface don't have a body"),
+                    start: brace.position,
+                    end: brace.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_INTERFACE_DECLARATION,
+                    error_offset: brace.position,
+                    labels: vec![label],
+                    note: Some(String::from(
+                        "The classes that implement the interface define the body of its methods",
+                    )),
+                    help: None,
+                };
+                return Err(ParsingError::Err(econtainer));
+            }
+
+            methods.push(method);
+            current_pos = next_pos;
+        }
+
+        // The closing brace
+        let (closing_brace, next_pos) = match parse_token_type(
+            tokens,
+            current_pos,
+            TokenType::RightBrace,
+        ) {
+            Ok(tuple) => tuple,
+            Err(ParsingError::Mismatch(wrong_token)) => {
+                let label = ErrorLabel {
+                    message: String::from("Expected a method or a closing brace `}` here"),
+                    start: wrong_token.position,
+                    end: wrong_token.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                        error_code: SYNTAX_INVALID_INTERFACE_DECLARATION,
+                        error_offset: wrong_token.position,
+                        labels: vec![label],
+                        note: Some(String::from(
+                            "The body of an interface can only contain methods without a body, e.g. `fun area() -> Float`",
+                        )),
+                        help: None,
+                    };
+                return Err(ParsingError::Err(econtainer));
+            }
+            _ => {
+                let label_1 = ErrorLabel {
+                    message: String::from("The methods of the interface start here"),
+                    start: opening_brace.position,
+                    end: opening_brace.get_end_position(),
+                };
+                let label_2_pos = tokens.code_position_from_idx(current_pos);
+                let label_2 = ErrorLabel {
+                    message: String::from("The code ends here without closing the interface"),
+                    start: label_2_pos,
+                    end: label_2_pos + 1,
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_INTERFACE_DECLARATION,
+                    error_offset: label_2_pos,
+                    labels: vec![label_1, label_2],
+                    note: None,
+                    help: None,
+                };
+                return Err(ParsingError::Err(econtainer));
+            }
+        };
+
+        let interface = InterfaceDeclaration {
+            keyword,
+            identifier,
+            methods,
+            end: closing_brace.position,
+        };
+        Ok((interface, next_pos))
+    }
+}
+
+fn interface_error<'a>(message: &str, start: usize, end: usize) -> ParsingError<'a> {
+    let label = ErrorLabel {
+        message: String::from(message),
+        start,
+        end,
+    };
+    let econtainer = ErrorContainer {
+        error_code: SYNTAX_INVALID_INTERFACE_DECLARATION,
+        error_offset: start,
+        labels: vec![label],
+        note: None,
+        help: None,
+    };
+    ParsingError::Err(econtainer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    fn parse(input: &str) -> Vec<Token> {
+        get_tokens(&String::from(input)).unwrap()
+    }
+
+    #[test]
+    fn should_parse_interface() {
+        let tokens =
+            parse("interface Shape {\n    fun area() -> Float\n    fun scale(Float factor)\n}");
+        let (interface, next) = InterfaceDeclaration::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!("Shape", interface.identifier.value);
+        assert_eq!(2, interface.methods.len());
+        assert_eq!("area", interface.methods[0].identifier.value);
+        assert!(interface.methods[0].return_type.is_some());
+        assert_eq!(1, interface.methods[1].params_list.parameters.len());
+        assert!(interface.methods[1].return_type.is_none());
+        assert_eq!(tokens.len() - 1, next);
+    }
+
+    #[test]
+    fn should_parse_empty_interface() {
+        let tokens = parse("interface Marker {}");
+        let (interface, _) = InterfaceDeclaration::try_parse(&tokens, 0).unwrap();
+
+        assert!(interface.methods.is_empty());
+        assert_eq!(18, interface.end);
+    }
+
+    #[test]
+    fn should_not_parse_without_interface_keyword() {
+        let tokens = parse("class Shape");
+        assert!(matches!(
+            InterfaceDeclaration::try_parse(&tokens, 0),
+            Err(ParsingError::Unmatched)
+        ));
+    }
+
+    #[test]
+    fn should_error_on_lowercase_name() {
+        let tokens = parse("interface shape {}");
+        let Err(ParsingError::Err(error)) = InterfaceDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(SYNTAX_INVALID_INTERFACE_DECLARATION, error.error_code);
+        assert_eq!(10, error.error_offset);
+    }
+
+    #[test]
+    fn should_error_on_methods_with_body() {
+        let tokens = parse("interface Shape {\n    fun area() -> Float {\n        1.0\n    }\n}");
+        let Err(ParsingError::Err(error)) = InterfaceDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "The methods of an interface don't have a body",
+            error.labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_error_on_other_members() {
+        let tokens = parse("interface Shape {\n    val x = 1\n}");
+        let Err(ParsingError::Err(error)) = InterfaceDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "Expected a method or a closing brace `}` here",
+            error.labels[0].message
+        );
+    }
+
+    #[test]
+    fn should_error_on_unclosed_interface() {
+        let tokens = parse("interface Shape {\n    fun area() -> Float");
+        let Err(ParsingError::Err(error)) = InterfaceDeclaration::try_parse(&tokens, 0) else {
+            panic!("Expected an error")
+        };
+
+        assert_eq!(
+            "The code ends here without closing the interface",
+            error.labels[1].message
+        );
+    }
+}
@@ -1,10 +1,15 @@
 pub mod assignment;
 pub mod binding;
 pub mod block;
+pub mod class;
 pub mod conditional;
+pub mod datatype;
+pub mod enum_declaration;
 pub mod expression;
 pub mod for_loop;
 pub mod function_declaration;
+pub mod interface;
 pub mod module;
+pub mod return_statement;
 pub mod statement;
 pub mod while_loop;
@@ -7,7 +7,7 @@ use crate::{
     syntax::{
         ast::{Expression, ModuleAST, ModuleMembers, Statement},
         parseable::{Parseable, ParsingError, ParsingResult},
-        utils::parse_terminator,
+        utils::{parse_terminator, synchronize},
     },
 };
 
@@ -20,8 +20,13 @@ impl<'a> Parseable<'a> for ModuleAST<'a> {
     /// always starts from token 0.
     ///
     /// Its grammar is defined at the spec, at the webpage
+    ///
+    /// When a production fails to parse its error is stored, the tokens
+    /// until the next statement are skipped and parsing continues.
+    /// If any error was found returns `ParsingError::Recovered` with all of them.
     fn try_parse(tokens: &'a Vec<Token>, _current_pos: usize) -> ParsingResult<'a, Self::Item> {
         let mut productions = Vec::<ModuleMembers>::new();
+        let mut errors = Vec::<ErrorContainer>::new();
         let tokens_len = tokens.len();
         let mut current_pos = 0;
 
@@ -35,8 +40,14 @@ impl<'a> Parseable<'a> for ModuleAST<'a> {
                     continue;
                 }
                 Err(ParsingError::Err(error)) => {
-                    // TODO: Better error handling, write a better error message
-                    return Err(ParsingError::Err(error));
+                    errors.push(error);
+                    current_pos = synchronize(tokens, current_pos);
+                    continue;
+                }
+                Err(ParsingError::Recovered(inner_errors, next_pos)) => {
+                    errors.extend(inner_errors);
+                    current_pos = next_pos;
+                    continue;
                 }
                 _ => {}
             };
@@ -65,7 +76,9 @@ impl<'a> Parseable<'a> for ModuleAST<'a> {
                                 )),
                                 help: None,
                             };
-                            return Err(ParsingError::Err(econtainer));
+                            errors.push(econtainer);
+                            current_pos = synchronize(tokens, next_pos);
+                            continue;
                         }
                         _ => unreachable!(),
                     };
@@ -75,8 +88,14 @@ impl<'a> Parseable<'a> for ModuleAST<'a> {
                     continue;
                 }
                 Err(ParsingError::Err(error)) => {
-                    // TODO: Better error handling, write a better error message
-                    return Err(ParsingError::Err(error));
+                    errors.push(error);
+                    current_pos = synchronize(tokens, current_pos);
+                    continue;
+                }
+                Err(ParsingError::Recovered(inner_errors, next_pos)) => {
+                    errors.extend(inner_errors);
+                    current_pos = next_pos;
+                    continue;
                 }
                 _ => {}
             }
@@ -105,7 +124,12 @@ impl<'a> Parseable<'a> for ModuleAST<'a> {
                 note: None,
                 help: None,
             };
-            return Err(ParsingError::Err(econtainer));
+            errors.push(econtainer);
+            current_pos = synchronize(tokens, current_pos);
+        }
+
+        if !errors.is_empty() {
+            return Err(ParsingError::Recovered(errors, current_pos));
         }
 
         Ok((ModuleAST { productions }, current_pos))
@@ -169,10 +193,59 @@ mod test {
 
         match result {
             Ok(_) => panic!("Expected an error"),
-            Err(ParsingError::Err(err)) => {
-                assert_eq!(err.error_code, SYNTAX_INCOMPLETE_STATEMENT)
+            Err(ParsingError::Recovered(errors, _)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].error_code, SYNTAX_INCOMPLETE_STATEMENT)
             }
             _ => panic!("Expected a parsing error"),
         }
     }
+
+    #[test]
+    fn should_report_errors_of_every_statement() {
+        let tokens = get_tokens(&String::from("val x\nval y = 10\nfun (){}\nprint(y)")).unwrap();
+        let result = ModuleAST::try_parse(&tokens, 0);
+
+        match result {
+            Err(ParsingError::Recovered(errors, _)) => {
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors[0].error_offset, 4);
+                assert_eq!(errors[1].error_offset, 21);
+            }
+            _ => panic!("Expected multiple errors, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_report_errors_inside_and_after_blocks() {
+        let tokens = get_tokens(&String::from(
+            "fun f() {\n    val a\n    val b = 1\n    val c\n}\nval d",
+        ))
+        .unwrap();
+        let result = ModuleAST::try_parse(&tokens, 0);
+
+        match result {
+            Err(ParsingError::Recovered(errors, _)) => {
+                assert_eq!(errors.len(), 3);
+                assert_eq!(errors[0].error_offset, 18);
+                assert_eq!(errors[1].error_offset, 44);
+                assert_eq!(errors[2].error_offset, 50);
+            }
+            _ => panic!("Expected multiple errors, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_parse_statements_after_indented_expressions() {
+        let tokens = get_tokens(&String::from("val a = 1\n    + 2\nprint(a)")).unwrap();
+        let (module, _) = ModuleAST::try_parse(&tokens, 0).unwrap();
+        assert_eq!(module.productions.len(), 2);
+
+        let tokens = get_tokens(&String::from(
+            "val a = match b\n    case true { 1 }\n    else { 0 }\nprint(a)",
+        ))
+        .unwrap();
+        let (module, _) = ModuleAST::try_parse(&tokens, 0).unwrap();
+        assert_eq!(module.productions.len(), 2);
+    }
 }
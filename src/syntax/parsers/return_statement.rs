@@ -0,0 +1,140 @@
+use crate::{
+    error_handling::{error_messages::SYNTAX_INVALID_RETURN, ErrorContainer, ErrorLabel},
+    lexic::token::{Token, TokenType},
+    syntax::{
+        ast::{Expression, Return},
+        parseable::{Parseable, ParsingError, ParsingResult},
+        utils::parse_token_type,
+    },
+};
+
+impl<'a> Parseable<'a> for Return<'a> {
+    type Item = Return<'a>;
+
+    fn try_parse(tokens: &'a Vec<Token>, current_pos: usize) -> ParsingResult<'a, Self::Item> {
+        // return keyword
+        let (return_keyword, next) = match parse_token_type(tokens, current_pos, TokenType::RETURN)
+        {
+            Ok(tuple) => tuple,
+            _ => return Err(ParsingError::Unmatched),
+        };
+
+        // A return without a value ends at the end of the line or block
+        match tokens.get(next) {
+            Some(t)
+                if t.token_type == TokenType::NewLine
+                    || t.token_type == TokenType::EOF
+                    || t.token_type == TokenType::DEDENT
+                    || t.token_type == TokenType::RightBrace =>
+            {
+                let return_statement = Return {
+                    keyword: return_keyword,
+                    value: None,
+                };
+                return Ok((return_statement, next));
+            }
+            _ => {}
+        }
+
+        // returned value
+        let (value, next) = match Expression::try_parse(tokens, next) {
+            Ok(t) => t,
+            Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
+            Err(ParsingError::Mismatch(e)) => {
+                let label = ErrorLabel {
+                    message: String::from("Expected an expression here"),
+                    start: e.position,
+                    end: e.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_RETURN,
+                    error_offset: e.position,
+                    labels: vec![label],
+                    note: None,
+                    help: None,
+                };
+                return Err(ParsingError::Err(econtainer));
+            }
+            Err(ParsingError::Unmatched) => {
+                let label = ErrorLabel {
+                    message: String::from("Expected an expression after this `return` keyword"),
+                    start: return_keyword.position,
+                    end: return_keyword.get_end_position(),
+                };
+                let econtainer = ErrorContainer {
+                    error_code: SYNTAX_INVALID_RETURN,
+                    error_offset: return_keyword.position,
+                    labels: vec![label],
+                    note: None,
+                    help: None,
+                };
+                return Err(ParsingError::Err(econtainer));
+            }
+        };
+
+        let return_statement = Return {
+            keyword: return_keyword,
+            value: Some(value),
+        };
+        Ok((return_statement, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexic::get_tokens;
+
+    #[test]
+    fn should_parse_return_with_value() {
+        let tokens = get_tokens(&String::from("return a + b")).unwrap();
+        let (return_statement, next) = Return::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(4, next);
+        assert_eq!("return", return_statement.keyword.value);
+        match return_statement.value {
+            Some(Expression::BinaryOperator(_, _, op)) => assert_eq!("+", op.value),
+            _ => panic!("Expected a binary operator"),
+        }
+    }
+
+    #[test]
+    fn should_parse_return_without_value() {
+        let tokens = get_tokens(&String::from("return\nprint(a)")).unwrap();
+        let (return_statement, next) = Return::try_parse(&tokens, 0).unwrap();
+
+        assert_eq!(1, next);
+        assert!(return_statement.value.is_none());
+
+        let tokens = get_tokens(&String::from("{return}")).unwrap();
+        let (return_statement, next) = Return::try_parse(&tokens, 1).unwrap();
+
+        assert_eq!(2, next);
+        assert!(return_statement.value.is_none());
+    }
+
+    #[test]
+    fn should_not_parse_invalid_value() {
+        let tokens = get_tokens(&String::from("return )")).unwrap();
+
+        match Return::try_parse(&tokens, 0) {
+            Err(ParsingError::Err(error)) => {
+                assert_eq!(SYNTAX_INVALID_RETURN, error.error_code);
+                assert_eq!(0, error.error_offset);
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn should_not_parse_other_statements() {
+        let tokens = get_tokens(&String::from("val x = 10")).unwrap();
+        assert!(matches!(
+            Return::try_parse(&tokens, 0),
+            Err(ParsingError::Unmatched)
+        ));
+    }
+}
@@ -2,9 +2,12 @@ use crate::{
     lexic::token::Token,
     syntax::{
         ast::{
+            class::ClassDeclaration,
+            enum_declaration::EnumDeclaration,
+            interface::InterfaceDeclaration,
             loops::{ForLoop, WhileLoop},
             var_binding::VariableBinding,
-            Assignment, Conditional, FunctionDeclaration, Statement,
+            Assignment, Conditional, FunctionDeclaration, Return, Statement,
         },
         parseable::{Parseable, ParsingError, ParsingResult},
     },
@@ -35,6 +38,36 @@ impl<'a> Parseable<'a> for Statement<'a> {
                 // TODO: Better error handling, write a better error message
                 return Err(ParsingError::Err(error));
             }
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next));
+            }
+            _ => {}
+        }
+
+        // Try to parse a class declaration
+        match ClassDeclaration::try_parse(tokens, current_pos) {
+            Ok((prod, next)) => return Ok((Statement::ClassDecl(prod), next)),
+            Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
+            _ => {}
+        }
+
+        // Try to parse an enum declaration
+        match EnumDeclaration::try_parse(tokens, current_pos) {
+            Ok((prod, next)) => return Ok((Statement::EnumDecl(prod), next)),
+            Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            _ => {}
+        }
+
+        // Try to parse an interface declaration
+        match InterfaceDeclaration::try_parse(tokens, current_pos) {
+            Ok((prod, next)) => return Ok((Statement::InterfaceDecl(prod), next)),
+            Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             _ => {}
         }
 
@@ -42,6 +75,9 @@ impl<'a> Parseable<'a> for Statement<'a> {
         match Conditional::try_parse(tokens, current_pos) {
             Ok((prod, next)) => return Ok((Statement::Conditional(prod), next)),
             Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             _ => {}
         }
 
@@ -49,6 +85,9 @@ impl<'a> Parseable<'a> for Statement<'a> {
         match ForLoop::try_parse(tokens, current_pos) {
             Ok((prod, next)) => return Ok((Statement::ForLoop(prod), next)),
             Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             _ => {}
         }
 
@@ -56,6 +95,19 @@ impl<'a> Parseable<'a> for Statement<'a> {
         match WhileLoop::try_parse(tokens, current_pos) {
             Ok((prod, next)) => return Ok((Statement::WhileLoop(prod), next)),
             Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
+            _ => {}
+        }
+
+        // Try to parse a return statement
+        match Return::try_parse(tokens, current_pos) {
+            Ok((prod, next)) => return Ok((Statement::Return(prod), next)),
+            Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             _ => {}
         }
 
@@ -71,7 +123,7 @@ impl<'a> Parseable<'a> for Statement<'a> {
 
 #[cfg(test)]
 mod test {
-    use crate::lexic::get_tokens;
+    use crate::{lexic::get_tokens, syntax::ast::Expression};
 
     use super::*;
 
@@ -150,4 +202,49 @@ mod test {
             Err(_) => panic!("Expected parsing to be successful"),
         }
     }
+
+    #[test]
+    fn should_parse_class_decl() {
+        let tokens = get_tokens(&String::from("class Point(val Int x)")).unwrap();
+
+        match Statement::try_parse(&tokens, 0) {
+            Ok((Statement::ClassDecl(class), 7)) => {
+                assert_eq!("Point", class.identifier.value)
+            }
+            _ => panic!("Expected a class declaration"),
+        }
+    }
+
+    #[test]
+    fn should_not_parse_class_instantiation() {
+        let tokens = get_tokens(&String::from("Point(1, 2)")).unwrap();
+
+        match Statement::try_parse(&tokens, 0) {
+            Err(ParsingError::Unmatched) => {}
+            _ => panic!("Expected an instantiation not to be a statement"),
+        }
+    }
+
+    #[test]
+    fn should_parse_property_assignment() {
+        let tokens = get_tokens(&String::from("self.position.x = 1")).unwrap();
+
+        match Statement::try_parse(&tokens, 0) {
+            Ok((Statement::Assignment(assignment), _)) => {
+                assert_eq!("x", assignment.identifier.value);
+                match assignment.object.as_deref() {
+                    Some(Expression::BinaryOperator(object, property, _)) => {
+                        assert!(
+                            matches!(**object, Expression::Identifier(ref t) if t.value == "self")
+                        );
+                        assert!(
+                            matches!(**property, Expression::Identifier(ref t) if t.value == "position")
+                        );
+                    }
+                    _ => panic!("Expected the object to be `self.position`"),
+                }
+            }
+            _ => panic!("Expected an assignment"),
+        }
+    }
 }
@@ -22,6 +22,9 @@ impl<'a> Parseable<'a> for WhileLoop<'a> {
         let (condition, next) = match Expression::try_parse(tokens, next) {
             Ok(t) => t,
             Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(e)) => {
                 let label = ErrorLabel {
                     message: String::from("Expected a Bool expression here"),
@@ -58,6 +61,9 @@ impl<'a> Parseable<'a> for WhileLoop<'a> {
         let (body, next) = match Block::try_parse(tokens, next) {
             Ok(t) => t,
             Err(ParsingError::Err(e)) => return Err(ParsingError::Err(e)),
+            Err(ParsingError::Recovered(errors, next)) => {
+                return Err(ParsingError::Recovered(errors, next))
+            }
             Err(ParsingError::Mismatch(e)) => {
                 let label = ErrorLabel {
                     message: String::from("Expected a block here, after the condition"),
@@ -126,11 +126,15 @@ pub fn parse_token_type(
 
 /// Expects the token at `pos` to be a terminator (newline or eof)
 ///
-/// Ignores indentation, newlines and comments.
+/// Ignores indentation, newlines and comments. A DEDENT is also a
+/// terminator, because it replaces the newline before it, e.g. after
+/// an expression that continues in an indented line.
 ///
 /// Only returns: Ok or Unmatched.
 pub fn parse_terminator(tokens: &Vec<Token>, pos: usize) -> ParsingResult<()> {
     let mut current_pos = pos;
+    // The DEDENT may have been consumed by the expression before
+    let mut is_dedented = pos > 0 && tokens[pos - 1].token_type == TokenType::DEDENT;
 
     // Ignore all whitespace, newlines and semicolons
     while let Some(t) = tokens.get(current_pos) {
@@ -139,6 +143,7 @@ pub fn parse_terminator(tokens: &Vec<Token>, pos: usize) -> ParsingResult<()> {
             || t.token_type == TokenType::Comment
             || t.token_type == TokenType::MultilineComment
         {
+            is_dedented = is_dedented || t.token_type == TokenType::DEDENT;
             current_pos += 1;
         } else {
             break;
@@ -149,11 +154,46 @@ pub fn parse_terminator(tokens: &Vec<Token>, pos: usize) -> ParsingResult<()> {
         Some(t) if t.token_type == TokenType::EOF || t.token_type == TokenType::NewLine => {
             Ok(((), current_pos + 1))
         }
+        Some(_) if is_dedented => Ok(((), current_pos)),
         Some(t) => Err(ParsingError::Mismatch(t)),
         None => unreachable!("Stream of tokens finished before getting an EOF"),
     }
 }
 
+/// Skips tokens after a syntax error, to find a position where parsing
+/// can start again.
+///
+/// The token at `pos` (ignoring whitespace) is always skipped. Then
+/// stops after a new line, or before a DEDENT or closing brace `}`
+/// that would close the current block. Nested braces and indentation
+/// are skipped as a whole.
+pub fn synchronize(tokens: &Vec<Token>, pos: usize) -> usize {
+    let mut current_pos = match tokens.get_significant(pos) {
+        Some((t, _)) if t.token_type == TokenType::EOF => return tokens.len() - 1,
+        Some((_, p)) => p,
+        None => return tokens.len() - 1,
+    };
+    let mut depth = match tokens[current_pos].token_type {
+        TokenType::LeftBrace => 1,
+        _ => 0,
+    };
+    current_pos += 1;
+
+    while let Some(t) = tokens.get(current_pos) {
+        match t.token_type {
+            TokenType::EOF => break,
+            TokenType::LeftBrace | TokenType::INDENT => depth += 1,
+            TokenType::RightBrace | TokenType::DEDENT if depth == 0 => break,
+            TokenType::RightBrace | TokenType::DEDENT => depth -= 1,
+            TokenType::NewLine if depth == 0 => return current_pos + 1,
+            _ => {}
+        }
+        current_pos += 1;
+    }
+
+    current_pos
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -164,7 +204,7 @@ mod tests {
         },
     };
 
-    use super::try_operator;
+    use super::{synchronize, try_operator};
 
     #[test]
     fn test_1() {
@@ -217,4 +257,29 @@ mod tests {
             None => {}
         }
     }
+
+    #[test]
+    fn should_synchronize_at_new_line() {
+        let tokens = get_tokens(&String::from("val x\nval y = 1")).unwrap();
+
+        assert_eq!(3, synchronize(&tokens, 0));
+        assert_eq!(TokenType::VAL, tokens[3].token_type);
+    }
+
+    #[test]
+    fn should_synchronize_before_closing_brace() {
+        let tokens = get_tokens(&String::from("{\n    val x\n}")).unwrap();
+
+        // tokens: { INDENT val x DEDENT }
+        assert_eq!(4, synchronize(&tokens, 2));
+        assert_eq!(TokenType::DEDENT, tokens[4].token_type);
+    }
+
+    #[test]
+    fn should_skip_nested_blocks() {
+        let tokens = get_tokens(&String::from("if x {\n    f(\n}\nval y = 1")).unwrap();
+        let next = synchronize(&tokens, 0);
+
+        assert_eq!(TokenType::VAL, tokens[next].token_type);
+    }
 }